@@ -0,0 +1,304 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod deployer {
+    use escrow::EscrowRef;
+    use ink::codegen::TraitCallBuilder;
+    use ink::ToAccountId;
+    use reward_token::RewardtokenRef;
+    use voting::VotingRef;
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // a set of marketplace contracts deployed together. The voting contract resolves disputes on the escrow,
+    // and is the arbiter provider patrons pass to the escrow, the escrow and the voting contract can mint
+    // on the reward token, and the admin administers all three.
+    pub struct Deployment {
+        pub stablecoin: AccountId,
+        pub escrow: AccountId,
+        pub voting: AccountId,
+        pub reward_token: AccountId,
+        pub admin: AccountId,
+        pub deployed_at: Timestamp,
+    }
+
+    //errors that can be encountered while deploying
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        UnAuthorisedCall,
+        EscrowInstantiationFailed,
+        VotingInstantiationFailed,
+        RewardTokenInstantiationFailed,
+        //the escrow or the voting contract didn't accept the contracts deployed with it
+        WiringFailed,
+    }
+
+    // emitted when a set of contracts is deployed and stored in the registry
+    #[ink(event)]
+    pub struct Deployed {
+        id: u32,
+        deployment: Deployment,
+    }
+
+    #[ink(storage)]
+    pub struct Deployer {
+        owner: AccountId,
+        escrow_code_hash: Hash,
+        voting_code_hash: Hash,
+        reward_token_code_hash: Hash,
+        deployment_count: u32,
+        pub deployments: ink::storage::Mapping<u32, Deployment>,
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl Deployer {
+        // the constructor takes the code hashes of the uploaded escrow, voting and reward token contracts,
+        // the caller becomes the owner, the only one who can deploy.
+        #[ink(constructor)]
+        pub fn new(
+            _escrow_code_hash: Hash,
+            _voting_code_hash: Hash,
+            _reward_token_code_hash: Hash,
+        ) -> Self {
+            let owner = Self::env().caller();
+            let escrow_code_hash = _escrow_code_hash;
+            let voting_code_hash = _voting_code_hash;
+            let reward_token_code_hash = _reward_token_code_hash;
+            let deployment_count = u32::default();
+            let deployments = ink::storage::Mapping::default();
+            Self {
+                owner,
+                escrow_code_hash,
+                voting_code_hash,
+                reward_token_code_hash,
+                deployment_count,
+                deployments,
+            }
+        }
+
+        //read function that returns the account allowed to deploy
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        //read function that returns the code hashes of the escrow, voting and reward token contracts, in that order
+        #[ink(message)]
+        pub fn get_code_hashes(&self) -> (Hash, Hash, Hash) {
+            (
+                self.escrow_code_hash,
+                self.voting_code_hash,
+                self.reward_token_code_hash,
+            )
+        }
+
+        //read function that returns how many sets of contracts were deployed, they have IDs from 0
+        #[ink(message)]
+        pub fn get_deployment_count(&self) -> u32 {
+            self.deployment_count
+        }
+
+        //read function that returns the addresses of a deployed set of contracts
+        #[ink(message)]
+        pub fn get_deployment(&self, id: u32) -> Option<Deployment> {
+            self.deployments.get(id)
+        }
+
+        //argument: stablecoin (AccountId) the PSP22 token the escrow holds payments in
+        //argument: admin (AccountId) the admin of the escrow and voting contracts and owner of the reward token
+        // only the owner can deploy. The escrow is instantiated first, then the voting contract for it, then the
+        // reward token with the escrow and voting contract as minters. The escrow is told about the voting contract
+        // and the reward token, and the voting contract about the reward token, all in one transaction, and the set
+        // is stored in the registry with the next ID, which is returned. Emits Deployed.
+        #[ink(message)]
+        pub fn deploy(&mut self, stablecoin: AccountId, admin: AccountId) -> Result<u32> {
+            if self.env().caller() != self.owner {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let id = self.deployment_count;
+            //the deployment ID keeps the addresses of every set apart
+            let salt = id.to_le_bytes();
+            let mut escrow_ref = match EscrowRef::new(stablecoin, admin)
+                .code_hash(self.escrow_code_hash)
+                .endowment(0)
+                .salt_bytes(salt)
+                .try_instantiate()
+            {
+                Ok(Ok(Ok(escrow))) => escrow,
+                _ => return Err(Error::EscrowInstantiationFailed),
+            };
+            let escrow = escrow_ref.to_account_id();
+            let mut voting_ref = match VotingRef::new(escrow, stablecoin, admin)
+                .code_hash(self.voting_code_hash)
+                .endowment(0)
+                .salt_bytes(salt)
+                .try_instantiate()
+            {
+                Ok(Ok(voting)) => voting,
+                _ => return Err(Error::VotingInstantiationFailed),
+            };
+            let voting = voting_ref.to_account_id();
+            let reward_token = match RewardtokenRef::with_minters(admin, [escrow, voting].to_vec())
+                .code_hash(self.reward_token_code_hash)
+                .endowment(0)
+                .salt_bytes(salt)
+                .try_instantiate()
+            {
+                Ok(Ok(Ok(reward_token))) => reward_token.to_account_id(),
+                _ => return Err(Error::RewardTokenInstantiationFailed),
+            };
+            //the deployer instantiated the escrow and the voting contract, so it is the one that can wire them
+            match escrow_ref
+                .call_mut()
+                .set_deployment(voting, reward_token)
+                .try_invoke()
+            {
+                Ok(Ok(Ok(()))) => {}
+                _ => return Err(Error::WiringFailed),
+            }
+            match voting_ref
+                .call_mut()
+                .set_reward_token(Some(reward_token))
+                .try_invoke()
+            {
+                Ok(Ok(Ok(()))) => {}
+                _ => return Err(Error::WiringFailed),
+            }
+            let deployment = Deployment {
+                stablecoin,
+                escrow,
+                voting,
+                reward_token,
+                admin,
+                deployed_at: self.env().block_timestamp(),
+            };
+            self.deployments.insert(id, &deployment);
+            self.deployment_count = id + 1;
+            //the dependencies bring their own EmitEvent impls, so the contract is named
+            ink::codegen::EmitEvent::<Deployer>::emit_event(
+                self.env(),
+                Deployed { id, deployment },
+            );
+            Ok(id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+
+    #[test]
+    fn test_1_deploy_only_by_owner() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let escrow_code_hash = ink::primitives::Hash::from([1; 32]);
+        let voting_code_hash = ink::primitives::Hash::from([2; 32]);
+        let reward_token_code_hash = ink::primitives::Hash::from([3; 32]);
+        let mut contract =
+            deployer::Deployer::new(escrow_code_hash, voting_code_hash, reward_token_code_hash);
+        assert_eq!(contract.get_owner(), accounts.alice);
+        assert_eq!(
+            contract.get_code_hashes(),
+            (escrow_code_hash, voting_code_hash, reward_token_code_hash)
+        );
+        assert_eq!(contract.get_deployment_count(), 0);
+        assert!(contract.get_deployment(0).is_none());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.deploy(accounts.django, accounts.eve),
+            Err(deployer::Error::UnAuthorisedCall)
+        );
+        assert_eq!(contract.get_deployment_count(), 0);
+    }
+}
+
+#[cfg(all(test, feature = "e2e-tests"))]
+mod e2e_tests {
+    use super::deployer::DeployerRef;
+    use erc20::Erc20Ref;
+    use escrow::EscrowRef;
+    use ink_e2e::build_message;
+    use voting::VotingRef;
+    type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[ink_e2e::test]
+    async fn e2e_deploy_wires_escrow_voting_and_reward_token(
+        mut client: ink_e2e::Client<C, E>,
+    ) -> E2EResult<()> {
+        // given
+        let stablecoin = client
+            .instantiate(
+                "erc20",
+                &ink_e2e::alice(),
+                Erc20Ref::new(1_000_000),
+                0,
+                None,
+            )
+            .await
+            .expect("instantiate failed")
+            .account_id;
+        let mut code_hashes = Vec::new();
+        for contract in ["escrow", "voting", "reward_token"] {
+            let code_hash = client
+                .upload(contract, &ink_e2e::alice(), None)
+                .await
+                .expect("upload failed")
+                .code_hash;
+            code_hashes.push(code_hash);
+        }
+        let constructor = DeployerRef::new(code_hashes[0], code_hashes[1], code_hashes[2]);
+        let deployer = client
+            .instantiate("deployer", &ink_e2e::alice(), constructor, 0, None)
+            .await
+            .expect("instantiate failed")
+            .account_id;
+
+        // when
+        let admin = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+        let deploy = build_message::<DeployerRef>(deployer)
+            .call(|deployer| deployer.deploy(stablecoin, admin));
+        let _deploy_res = client
+            .call(&ink_e2e::alice(), deploy, 0, None)
+            .await
+            .expect("deploy failed");
+
+        // then
+        let get_deployment =
+            build_message::<DeployerRef>(deployer).call(|deployer| deployer.get_deployment(0));
+        let deployment = client
+            .call_dry_run(&ink_e2e::alice(), &get_deployment, 0, None)
+            .await
+            .return_value()
+            .expect("deployment not stored");
+        let get_voting_contract = build_message::<EscrowRef>(deployment.escrow)
+            .call(|escrow| escrow.get_voting_contract());
+        let voting_contract = client
+            .call_dry_run(&ink_e2e::alice(), &get_voting_contract, 0, None)
+            .await
+            .return_value();
+        assert_eq!(voting_contract, Some(deployment.voting));
+        let get_escrow_reward_token =
+            build_message::<EscrowRef>(deployment.escrow).call(|escrow| escrow.get_reward_token());
+        let escrow_reward_token = client
+            .call_dry_run(&ink_e2e::alice(), &get_escrow_reward_token, 0, None)
+            .await
+            .return_value();
+        assert_eq!(escrow_reward_token, Some(deployment.reward_token));
+        let get_voting_reward_token =
+            build_message::<VotingRef>(deployment.voting).call(|voting| voting.get_reward_token());
+        let voting_reward_token = client
+            .call_dry_run(&ink_e2e::alice(), &get_voting_reward_token, 0, None)
+            .await
+            .return_value();
+        assert_eq!(voting_reward_token, Some(deployment.reward_token));
+
+        Ok(())
+    }
+}
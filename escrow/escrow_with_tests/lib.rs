@@ -1,7 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+#[path = "../limits.rs"]
+mod limits;
+
 #[ink::contract]
 mod escrow {
+    pub use crate::limits::{MAX_ARBITERS_SHARE, MAX_HAIRCUT, MIN_DEADLINE_EXTENSION};
     use ink::prelude::string::String;
     use ink::storage::Mapping;
 
@@ -703,19 +707,33 @@ mod escrow {
         ) -> Result<()> {
             //checking for the haircut to be lesser than 10% and new deadline to be at least more than 1 day.
             let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-            if haircut <= 90
-                && new_deadline > self.env().block_timestamp() + 86400000
+            if haircut <= MAX_HAIRCUT
+                && new_deadline > self.env().block_timestamp() + MIN_DEADLINE_EXTENSION
                 && self.env().caller() == payment_info.arbiterprovider
-                && arbitersshare <= 10
+                && arbitersshare <= MAX_ARBITERS_SHARE
                 && matches!(
                     payment_info.currentstatus,
                     AuditStatus::AuditAwaitingValidation
                 )
             {
-                let arbitersscut: Balance = payment_info.value * arbitersshare / 100;
-                let haircutvalue: Balance = payment_info.value * haircut / 100;
+                // the caps keep the haircut and the arbiters' share together within the whole value
+                let remaining = 100 - (arbitersshare + haircut);
+                let arbitersscut: Balance = payment_info
+                    .value
+                    .checked_mul(arbitersshare)
+                    .ok_or(Error::InvalidArgument)?
+                    / 100;
+                let haircutvalue: Balance = payment_info
+                    .value
+                    .checked_mul(haircut)
+                    .ok_or(Error::InvalidArgument)?
+                    / 100;
                 // Update the value in storage
-                payment_info.value = payment_info.value * (100 - (arbitersshare + haircut)) / 100;
+                payment_info.value = payment_info
+                    .value
+                    .checked_mul(remaining)
+                    .ok_or(Error::InvalidArgument)?
+                    / 100;
                 // Update the deadline in storage
                 payment_info.deadline = new_deadline;
                 // make the respective transfers to arbitersprovider and
@@ -1150,4 +1168,52 @@ mod test_cases {
         let p = contract.expire_audit(0, true);
         assert!(matches!(p, Err(escrow::Error::UnAuthorisedCall)));
     }
+    #[test]
+    fn test_22_arbiters_extend_deadline_at_the_caps() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.alice);
+        let _x = contract.create_new_payment(100, accounts.bob, 1000000, 12, true);
+        let _y = contract.assign_audit(0, accounts.bob, 100, 200000, true);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let _z = contract.mark_submitted(0, "good work there".to_string());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let _w = contract.assess_audit(0, false, true);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let new_deadline = escrow::MIN_DEADLINE_EXTENSION + 1;
+        let a = contract.arbiters_extend_deadline(
+            0,
+            new_deadline,
+            escrow::MAX_HAIRCUT + 1,
+            escrow::MAX_ARBITERS_SHARE,
+            true,
+        );
+        assert!(matches!(
+            a,
+            Err(escrow::Error::ArbitersExtendDeadlineConditionsNotMet)
+        ));
+        let b = contract.arbiters_extend_deadline(
+            0,
+            new_deadline,
+            escrow::MAX_HAIRCUT,
+            escrow::MAX_ARBITERS_SHARE + 1,
+            true,
+        );
+        assert!(matches!(
+            b,
+            Err(escrow::Error::ArbitersExtendDeadlineConditionsNotMet)
+        ));
+        let c = contract.arbiters_extend_deadline(
+            0,
+            new_deadline,
+            escrow::MAX_HAIRCUT,
+            escrow::MAX_ARBITERS_SHARE,
+            true,
+        );
+        assert!(c.is_ok());
+        let ans = contract.get_paymentinfo(0).unwrap();
+        assert_eq!(ans.value, 0);
+        assert_eq!(ans.deadline, new_deadline);
+    }
 }
@@ -0,0 +1,164 @@
+// the interface of the escrow as other contracts and off-chain tooling see it. PROTOCOL_VERSION is bumped by
+// every release that changes its messages or what they expect, the contracts can't be upgraded in place, so
+// a new version is a new deployment. MESSAGES is the selector of every message, in the order of the source,
+// and a message added to or removed from the contract has to be added to or removed from it too.
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+pub const MESSAGES: [[u8; 4]; 144] = [
+    ink::selector_bytes!("version"),
+    ink::selector_bytes!("interface_hash"),
+    ink::selector_bytes!("get_current_audit_id"),
+    ink::selector_bytes!("know_your_stablecoin"),
+    ink::selector_bytes!("know_your_admin"),
+    ink::selector_bytes!("get_token_decimals"),
+    ink::selector_bytes!("get_min_audit_value"),
+    ink::selector_bytes!("get_operator"),
+    ink::selector_bytes!("get_deadline_warning_window"),
+    ink::selector_bytes!("get_param_change_delay"),
+    ink::selector_bytes!("get_pending_param_change"),
+    ink::selector_bytes!("get_extension_bounds"),
+    ink::selector_bytes!("get_min_deadline_tiers"),
+    ink::selector_bytes!("get_min_deadline"),
+    ink::selector_bytes!("get_treasury"),
+    ink::selector_bytes!("get_rounding_policy"),
+    ink::selector_bytes!("get_price_oracle"),
+    ink::selector_bytes!("get_reward_token"),
+    ink::selector_bytes!("get_fallback_arbiter_provider"),
+    ink::selector_bytes!("get_voting_contract"),
+    ink::selector_bytes!("set_deployment"),
+    ink::selector_bytes!("get_dex_router"),
+    ink::selector_bytes!("is_payout_token"),
+    ink::selector_bytes!("get_governance_address"),
+    ink::selector_bytes!("get_dispute_count"),
+    ink::selector_bytes!("is_escalated_to_governance"),
+    ink::selector_bytes!("get_sponsors"),
+    ink::selector_bytes!("get_contribution"),
+    ink::selector_bytes!("get_refund_split"),
+    ink::selector_bytes!("get_original_value"),
+    ink::selector_bytes!("get_patron_recent_auditors"),
+    ink::selector_bytes!("get_refunded_to_patron"),
+    ink::selector_bytes!("get_refunded_to_sponsors"),
+    ink::selector_bytes!("get_paid_to_auditor"),
+    ink::selector_bytes!("get_expiry_reason"),
+    ink::selector_bytes!("is_auditor_at_fault"),
+    ink::selector_bytes!("get_scorecard"),
+    ink::selector_bytes!("is_blacklisted"),
+    ink::selector_bytes!("get_paymentinfo"),
+    ink::selector_bytes!("get_audit_token"),
+    ink::selector_bytes!("get_archived_audit"),
+    ink::selector_bytes!("multiget_paymentinfo"),
+    ink::selector_bytes!("multiget_status"),
+    ink::selector_bytes!("get_legacy_status"),
+    ink::selector_bytes!("multiget_legacy_status"),
+    ink::selector_bytes!("get_submitted_reports"),
+    ink::selector_bytes!("get_report_hash"),
+    ink::selector_bytes!("get_submission_count"),
+    ink::selector_bytes!("get_submission"),
+    ink::selector_bytes!("get_ids_by_status"),
+    ink::selector_bytes!("get_status_count"),
+    ink::selector_bytes!("get_checkpoints"),
+    ink::selector_bytes!("get_checkpoint_haircut"),
+    ink::selector_bytes!("get_worklog"),
+    ink::selector_bytes!("get_dispute_bond"),
+    ink::selector_bytes!("get_gas_refund_per_call"),
+    ink::selector_bytes!("get_paymaster_pot"),
+    ink::selector_bytes!("get_waiver_vouchers"),
+    ink::selector_bytes!("get_co_approval_threshold"),
+    ink::selector_bytes!("get_co_approval"),
+    ink::selector_bytes!("get_remaining_approvals"),
+    ink::selector_bytes!("get_panel"),
+    ink::selector_bytes!("get_message_anchors"),
+    ink::selector_bytes!("query_timeincreaserequest"),
+    ink::selector_bytes!("get_subscriptions"),
+    ink::selector_bytes!("subscribe"),
+    ink::selector_bytes!("unsubscribe"),
+    ink::selector_bytes!("create_new_payment"),
+    ink::selector_bytes!("finalize_funding"),
+    ink::selector_bytes!("cancel_pending_funding"),
+    ink::selector_bytes!("get_pending_funding"),
+    ink::selector_bytes!("create_draft"),
+    ink::selector_bytes!("update_draft"),
+    ink::selector_bytes!("delete_draft"),
+    ink::selector_bytes!("fund_draft"),
+    ink::selector_bytes!("get_draft"),
+    ink::selector_bytes!("get_drafts"),
+    ink::selector_bytes!("get_metadata_hash"),
+    ink::selector_bytes!("import_audit"),
+    ink::selector_bytes!("get_import_deadline"),
+    ink::selector_bytes!("create_followup"),
+    ink::selector_bytes!("get_parent_audit"),
+    ink::selector_bytes!("contribute"),
+    ink::selector_bytes!("assign_audit"),
+    ink::selector_bytes!("request_additional_time"),
+    ink::selector_bytes!("approve_additional_time"),
+    ink::selector_bytes!("set_retention"),
+    ink::selector_bytes!("release_retention"),
+    ink::selector_bytes!("get_retention_percentage"),
+    ink::selector_bytes!("get_retention"),
+    ink::selector_bytes!("set_payout_preference"),
+    ink::selector_bytes!("get_payout_preference"),
+    ink::selector_bytes!("propose_scope_change"),
+    ink::selector_bytes!("accept_scope_change"),
+    ink::selector_bytes!("get_pending_scope_change"),
+    ink::selector_bytes!("get_obligations"),
+    ink::selector_bytes!("get_solvency_ratio"),
+    ink::selector_bytes!("get_scope_changes"),
+    ink::selector_bytes!("mark_submitted"),
+    ink::selector_bytes!("assess_audit"),
+    ink::selector_bytes!("assess_audit_with_scorecard"),
+    ink::selector_bytes!("set_co_reviewer"),
+    ink::selector_bytes!("co_approve"),
+    ink::selector_bytes!("arbiters_extend_deadline"),
+    ink::selector_bytes!("expire_audit"),
+    ink::selector_bytes!("close_audit"),
+    ink::selector_bytes!("get_settlement"),
+    ink::selector_bytes!("payout_preview"),
+    ink::selector_bytes!("payout_commitment"),
+    ink::selector_bytes!("get_executed_payout_commitment"),
+    ink::selector_bytes!("archive_audit"),
+    ink::selector_bytes!("process_due"),
+    ink::selector_bytes!("emit_deadline_warnings"),
+    ink::selector_bytes!("sync_token_decimals"),
+    ink::selector_bytes!("register_panel"),
+    ink::selector_bytes!("anchor_message"),
+    ink::selector_bytes!("set_silence_policy"),
+    ink::selector_bytes!("decline_arbitration"),
+    ink::selector_bytes!("nominate_fallback_provider"),
+    ink::selector_bytes!("get_declined_arbitration"),
+    ink::selector_bytes!("get_submitted_at"),
+    ink::selector_bytes!("set_checkpoints"),
+    ink::selector_bytes!("submit_checkpoint"),
+    ink::selector_bytes!("assess_checkpoint"),
+    ink::selector_bytes!("miss_checkpoint"),
+    ink::selector_bytes!("commit_worklog"),
+    ink::selector_bytes!("get_call_config"),
+    ink::selector_bytes!("fund_paymaster"),
+    ink::selector_bytes!("claim_gas_refunds"),
+    ink::selector_bytes!("close_paymaster"),
+    ink::selector_bytes!("check_solvency"),
+    ink::selector_bytes!("withdraw"),
+    ink::selector_bytes!("get_credit"),
+    ink::selector_bytes!("get_credit_tokens"),
+    ink::selector_bytes!("mint_waiver_vouchers"),
+    ink::selector_bytes!("blacklist"),
+    ink::selector_bytes!("set_operator"),
+    ink::selector_bytes!("unblacklist"),
+    ink::selector_bytes!("queue_param_change"),
+    ink::selector_bytes!("set_default_stablecoin"),
+    ink::selector_bytes!("execute_param_change"),
+    ink::selector_bytes!("cancel_param_change"),
+    ink::selector_bytes!("execute_governance_ruling"),
+    // before_received, the PSP22Receiver selector
+    [0xfd, 0xa6, 0xf1, 0xa9],
+];
+
+// the blake2x256 hash of the selectors of MESSAGES, sorted and SCALE encoded, so that it doesn't depend on
+// the order of the source.
+pub fn interface_hash() -> [u8; 32] {
+    let mut selectors = MESSAGES;
+    selectors.sort_unstable();
+    let mut hash = [0u8; 32];
+    ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&selectors, &mut hash);
+    hash
+}
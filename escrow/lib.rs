@@ -1,19 +1,78 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use self::escrow::{Escrow, EscrowRef};
+
+mod interface;
+mod limits;
+mod math;
+#[cfg(test)]
+#[path = "../mock/offchain.rs"]
+mod offchain;
+mod reward;
+mod token;
+
 #[ink::contract]
 mod escrow {
+    pub use crate::limits::{MAX_ARBITERS_SHARE, MAX_HAIRCUT, MIN_DEADLINE_EXTENSION};
+    use crate::math::{self, RoundingPolicy};
+    use crate::reward::{MintArgs, RewardToken};
+    use crate::token::{Psp22, Router};
+    use ink::prelude::format;
     use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Lazy;
     use ink::storage::Mapping;
 
-    #[derive(scale::Decode, scale::Encode)]
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     // stores the status of the audit, e.g. whether it
-    // has just been created, assigned, submitted, is awaiting validation,
+    // has just been created, assigned, submitted, disputed,
     // completed, or expired.
+    // AuditDisputed is when the patron rejected the report and arbitration is pending,
+    // AuditAwaitingPatronReview is when the auditor resubmitted after the deadline was extended
+    // on a dispute, and the patron has to assess the report again.
+    // Created -> Assigned -> Submitted -> Completed | Disputed,
+    // Disputed -> Completed | Expired | Assigned (extension) -> AwaitingPatronReview -> Completed | Disputed,
+    // Created | Assigned -> Expired.
     pub enum AuditStatus {
+        AuditCreated,
+        AuditAssigned,
+        AuditSubmitted,
+        AuditDisputed,
+        AuditCompleted,
+        AuditExpired,
+        AuditAwaitingPatronReview,
+    }
+
+    impl AuditStatus {
+        // the status as it was reported before disputes and resubmissions were told apart
+        pub fn to_legacy(self) -> LegacyAuditStatus {
+            match self {
+                AuditStatus::AuditCreated => LegacyAuditStatus::AuditCreated,
+                AuditStatus::AuditAssigned => LegacyAuditStatus::AuditAssigned,
+                AuditStatus::AuditSubmitted | AuditStatus::AuditAwaitingPatronReview => {
+                    LegacyAuditStatus::AuditSubmitted
+                }
+                AuditStatus::AuditDisputed => LegacyAuditStatus::AuditAwaitingValidation,
+                AuditStatus::AuditCompleted => LegacyAuditStatus::AuditCompleted,
+                AuditStatus::AuditExpired => LegacyAuditStatus::AuditExpired,
+            }
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the statuses of the audit before AuditAwaitingValidation was split into AuditDisputed and
+    // AuditAwaitingPatronReview, kept for the consumers that read the old statuses.
+    // the variants keep the names of the old statuses so their SCALE encoding and metadata stay compatible.
+    #[allow(clippy::enum_variant_names)]
+    pub enum LegacyAuditStatus {
         AuditCreated,
         AuditAssigned,
         AuditSubmitted,
@@ -32,7 +91,51 @@ mod escrow {
     // patron’s, auditor’s, and arbiter provider’s account ID. It also stores
     // the value locked, deadline, start time, and the current status of the
     // audit.
+    // duration_ms is how long the auditor has from the assignment, and due_at is the deadline itself,
+    // None until the audit is assigned, extensions move due_at.
+    // value is what is still locked in the escrow, original_value is what was locked
+    // for the job when it was assigned, and haircuts are taken as a percentage of it,
+    // refunded_to_patron and paid_to_auditor add up what has been paid out of it to the patron and the auditor,
+    // the shares of a refund the sponsors of the audit get back are not counted in refunded_to_patron.
+    // fiat_value_at_creation and fiat_value_at_completion are the USD value (6 decimals) of the
+    // locked value when the audit was created and completed, if a price oracle was configured.
+    // silence_policy is what the keeper does once the patron leaves a submitted report unreviewed.
     pub struct PaymentInfo {
+        pub patron: AccountId,
+        pub auditor: AccountId,
+        pub value: Balance,
+        pub arbiterprovider: AccountId,
+        pub duration_ms: Timestamp,
+        pub due_at: Option<Timestamp>,
+        pub starttime: Timestamp,
+        pub currentstatus: AuditStatus,
+        pub original_value: Balance,
+        pub refunded_to_patron: Balance,
+        pub paid_to_auditor: Balance,
+        pub fiat_value_at_creation: Option<Balance>,
+        pub fiat_value_at_completion: Option<Balance>,
+        pub silence_policy: SilencePolicy,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // what process_due does with a submitted report the patron hasn't reviewed within PATRON_REVIEW_WINDOW.
+    // Hold: nothing, the default, AutoApprove: pays it out as if the patron approved it, AutoDispute: sends
+    // it to arbitration as if the patron rejected it.
+    pub enum SilencePolicy {
+        Hold,
+        AutoApprove,
+        AutoDispute,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    // the payment info of earlier deployments, whose deadline was the duration before the assignment and
+    // the deadline itself afterwards.
+    pub struct LegacyPaymentInfo {
         pub patron: AccountId,
         pub auditor: AccountId,
         pub value: Balance,
@@ -40,10 +143,45 @@ mod escrow {
         pub deadline: Timestamp,
         pub starttime: Timestamp,
         pub currentstatus: AuditStatus,
+        pub original_value: Balance,
+        pub refunded_to_patron: Balance,
+        pub paid_to_auditor: Balance,
+        pub fiat_value_at_creation: Option<Balance>,
+        pub fiat_value_at_completion: Option<Balance>,
+    }
+
+    impl LegacyPaymentInfo {
+        // splits the deadline by the status, an assigned audit had as long as from its start to its deadline.
+        pub fn migrate(self) -> PaymentInfo {
+            let (duration_ms, due_at) = if matches!(self.currentstatus, AuditStatus::AuditCreated) {
+                (self.deadline, None)
+            } else {
+                (
+                    self.deadline.saturating_sub(self.starttime),
+                    Some(self.deadline),
+                )
+            };
+            PaymentInfo {
+                patron: self.patron,
+                auditor: self.auditor,
+                value: self.value,
+                arbiterprovider: self.arbiterprovider,
+                duration_ms,
+                due_at,
+                starttime: self.starttime,
+                currentstatus: self.currentstatus,
+                original_value: self.original_value,
+                refunded_to_patron: self.refunded_to_patron,
+                paid_to_auditor: self.paid_to_auditor,
+                fiat_value_at_creation: self.fiat_value_at_creation,
+                fiat_value_at_completion: self.fiat_value_at_completion,
+                silence_policy: SilencePolicy::Hold,
+            }
+        }
     }
 
     //errors that use can encounter in the contract flow
-    #[derive(scale::Decode, scale::Encode)]
+    #[derive(scale::Decode, scale::Encode, Debug)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
@@ -57,6 +195,39 @@ mod escrow {
         ArbitersExtendDeadlineConditionsNotMet,
         WrongState,
         DeadlinePassed,
+        TooManySubscriptions,
+        GovernanceCallFailed,
+        TooManySponsors,
+        ValueBelowMinimum,
+        TokenQueryFailed,
+        TooManyAnchors,
+        NotAParticipant,
+        ReentrantCall,
+        NeedsAdditionalApproval(Balance),
+        CallerBlacklisted,
+        RetentionPeriodNotPassed,
+        NoPendingChange,
+        TimelockNotElapsed,
+        DeadlineTooShort(Timestamp),
+        TooManyWorklogEntries,
+        CoReviewerRequired,
+        CoReviewerAlreadySet,
+        FundingMismatch,
+        DisputeBondFailed,
+        CheckpointsPending,
+        CheckpointOverdue,
+        NoPaymasterPot,
+        TooManyScopeChanges,
+        TokenNotAllowed,
+        SlippageTooHigh,
+        NoDexRouter,
+        AuditAlreadyClosed,
+        TooManyDrafts,
+        ImportWindowClosed,
+        NothingToWithdraw,
+        ProviderDeclined,
+        NoFallbackProvider,
+        ArithmeticOverflow,
     }
 
     #[derive(scale::Decode, scale::Encode)]
@@ -79,6 +250,54 @@ mod escrow {
         id: Option<u32>,
         payment_info: Option<PaymentInfo>,
     }
+    // emitted when the patron creates an audit, which waits for finalize_funding to pull its value
+    #[ink(event)]
+    pub struct FundingPending {
+        id: u32,
+        patron: AccountId,
+        value: Balance,
+    }
+
+    // emitted when the patron creates a follow-up of an audit, it waits for finalize_funding like any other
+    #[ink(event)]
+    pub struct FollowupCreated {
+        id: u32,
+        parent_audit_id: u32,
+    }
+
+    // emitted when the patron cancels an audit they didn't fund
+    #[ink(event)]
+    pub struct PendingFundingCancelled {
+        id: u32,
+    }
+
+    // emitted for every audit the admin imports from a previous deployment, instead of AuditCreated
+    #[ink(event)]
+    pub struct AuditImported {
+        id: u32,
+        payment_info: PaymentInfo,
+    }
+
+    // emitted when the patron creates or edits a draft
+    #[ink(event)]
+    pub struct DraftSaved {
+        draft_id: u32,
+        patron: AccountId,
+    }
+
+    // emitted when the patron deletes a draft
+    #[ink(event)]
+    pub struct DraftDeleted {
+        draft_id: u32,
+    }
+
+    // emitted when a draft is funded and becomes the audit id
+    #[ink(event)]
+    pub struct DraftFunded {
+        draft_id: u32,
+        id: u32,
+    }
+
     //emitted when an audit is created
     #[ink(event)]
     pub struct AuditCreated {
@@ -104,13 +323,23 @@ mod escrow {
         haircut: Balance,
     }
 
-    // emitted when audit is submitted, so that the ipfs
-    // files can be fetched via the backend and the patron/arbiter
-    // provider
+    // emitted when audit is submitted, so that the backend and the patron/arbiter
+    // provider fetch the report through get_report_hash, report_commitment lets them
+    // check it is the one submitted without revealing it, see report_commitment_of
     #[ink(event)]
     pub struct AuditSubmitted {
         id: u32,
-        ipfs_hash: String,
+        report_commitment: [u8; 32],
+    }
+
+    // emitted when the auditor submits the report again after the deadline was
+    // extended on a dispute, the patron has to review it again. version counts
+    // the submissions of the audit, the first one is version 1.
+    #[ink(event)]
+    pub struct AuditResubmitted {
+        id: u32,
+        version: u32,
+        report_commitment: [u8; 32],
     }
 
     //emitted when patron is dissatisfied with audit
@@ -127,693 +356,8336 @@ mod escrow {
         amount: Balance,
     }
 
-    // emitted when tokens are released from the escrow, maybe
+    // emitted when tokens are released from an audit, maybe
     // as haircut, or completion value, or after the expiration of the audit
+    // they are credited to the receiver, who takes them out with withdraw
     #[ink(event)]
-    pub struct TokenOutgoing {
+    pub struct PaymentCredited {
         id: u32,
         receiver: AccountId,
         amount: Balance,
     }
 
-    // emits and informs the retrieval of the audit ID
+    // emitted when tokens leave the escrow, when an account withdraws what was credited to it in a token
     #[ink(event)]
-    pub struct AuditIdRetrieved {
-        id: u32,
+    pub struct TokenOutgoing {
+        account: AccountId,
+        token: AccountId,
+        amount: Balance,
     }
 
-    #[ink(storage)]
-    pub struct Escrow {
-        current_audit_id: u32,
-        stablecoin_address: AccountId,
-        pub audit_id_to_payment_info: Mapping<u32, PaymentInfo>,
-        pub audit_id_to_time_increase_request: ink::storage::Mapping<u32, IncreaseRequest>,
-        pub audit_id_to_ipfs_hash: ink::storage::Mapping<u32, String>,
+    // emitted when the transfer of a withdrawal fails, the amount stays credited to the account
+    #[ink(event)]
+    pub struct WithdrawalFailed {
+        account: AccountId,
+        token: AccountId,
+        amount: Balance,
     }
 
-    pub type Result<T> = core::result::Result<T, Error>;
+    #[derive(scale::Decode, scale::Encode, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the lifecycle transitions that subscribed listener contracts can be
+    // notified of, the bit for each is 1 << (variant index) in the events mask.
+    pub enum AuditLifecycleEvent {
+        Created,
+        Assigned,
+        Submitted,
+        ArbitrationRequested,
+        DeadlineExtended,
+        Completed,
+        Expired,
+    }
 
-    impl Escrow {
-        #[ink(constructor)]
-        pub fn new(_stablecoin_address: AccountId) -> Self {
-            let current_audit_id = u32::default();
-            let stablecoin_address = _stablecoin_address;
-            // let current_request_id = u32::default();
-            let audit_id_to_payment_info = Mapping::default();
-            let audit_id_to_time_increase_request = Mapping::default();
-            let audit_id_to_ipfs_hash = Mapping::default();
-            Self {
-                current_audit_id,
-                stablecoin_address,
-                audit_id_to_payment_info,
-                audit_id_to_time_increase_request,
-                audit_id_to_ipfs_hash,
-            }
-        }
+    impl AuditLifecycleEvent {
+        // the mask of every event, a subscription can't ask for any other bit.
+        pub const ALL_MASK: u32 = (1 << (Self::Expired as u32 + 1)) - 1;
 
-        //read function to see total number of audits in escrow
-        #[ink(message)]
-        pub fn get_current_audit_id(&self) -> u32 {
-            self.current_audit_id
+        pub fn mask(&self) -> u32 {
+            1 << (*self as u32)
         }
+    }
 
-        //read function that returns the stablecoin that is being used
-        #[ink(message)]
-        pub fn know_your_stablecoin(&self) -> AccountId {
-            self.stablecoin_address
-        }
+    #[derive(scale::Decode, scale::Encode, Clone, Debug)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // a listener contract subscribed to the audit lifecycle, the owner is the
+    // account that subscribed it and is the only one who can unsubscribe it.
+    pub struct Subscription {
+        pub listener: AccountId,
+        pub owner: AccountId,
+        pub events_mask: u32,
+    }
 
-        //read function that gives the details of paymentinfo
-        #[ink(message)]
-        pub fn get_paymentinfo(&self, id: u32) -> Option<PaymentInfo> {
-            self.audit_id_to_payment_info.get(&id)
-        }
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the ruling of the governance contract on an escalated dispute, either the
+    // audit is approved or rejected, or the auditor gets a new deadline with a haircut
+    // (in percentage) that goes back to the patron.
+    pub enum GovernanceRuling {
+        Approve,
+        Reject,
+        ExtendDeadline(Timestamp, Balance),
+    }
 
-        //read function that returns the hash/link of the submitted reports on audits
-        #[ink(message)]
-        pub fn get_submitted_reports(&self, id: u32) -> Option<String> {
-            self.audit_id_to_ipfs_hash.get(&id)
-        }
+    #[derive(scale::Decode, scale::Encode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // a reference to an off-chain communication about an audit (scoping call, clarification),
+    // anchored by one of its participants so arbiters can rely on it during disputes.
+    pub struct MessageAnchor {
+        pub author: AccountId,
+        pub ipfs_hash: String,
+        pub anchored_at: Timestamp,
+    }
 
-        //read function that returns time increase request details
-        #[ink(message)]
-        pub fn query_timeincreaserequest(&self, id: u32) -> Option<IncreaseRequest> {
-            self.audit_id_to_time_increase_request.get(&id)
-        }
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // what process_due did for each of the audit IDs it was given.
+    pub enum DueOutcome {
+        Expired,
+        NothingDue,
+        NotFound,
+        RetentionReleased,
+        AutoApproved,
+        AutoDisputed,
+        // the transition that was due failed, the audit is left as it was and the batch moves on
+        Failed(Error),
+    }
 
-        //create new payment function is to be called by the patron by depositing the said sum in the contract, and choosing a rough deadline and balance for the audit job.
-        //argument: value (Balance) that will be locked in the escrow
-        //argument: arbiter_provider (AccountId) the service that will provide with arbiters
-        //argument: deadline, amount of time from the assigning of the auditor for successful audit
-        //argument: salt(u64) a random number to be used by the frontend to verify the post creation
-        //the function will create a new payment, lock in the value amount of payment tokens, and
-        // assign it to current_audit_id, increasing the audit_id afterwards
-        //and emitting the event for AuditInfoUpdated.
-        #[ink(message)]
-        pub fn create_new_payment(
-            &mut self,
-            _value: Balance,
-            _arbiter_provider: AccountId,
-            _deadline: Timestamp,
-            //this deadline is deadline that will be added to current time once the audit is assigned to an auditor.
-            _salt: u64,
-        ) -> Result<()> {
-            let _now = self.env().block_timestamp();
-            let x = PaymentInfo {
-                value: _value,
-                starttime: _now,
-                auditor: self.env().caller(),
-                arbiterprovider: _arbiter_provider,
-                patron: self.env().caller(),
-                deadline: _deadline,
-                currentstatus: AuditStatus::AuditCreated,
-            };
-            assert_ne!(_value, 0);
-            let xyz = ink::env::call::build_call::<Environment>()
-                .call(self.stablecoin_address)
-                .gas_limit(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("transfer_from"),
-                    ))
-                    .push_arg(self.env().caller())
-                    .push_arg(self.env().account_id())
-                    .push_arg(_value),
-                )
-                .returns::<Result<()>>()
-                .try_invoke();
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // why an audit expired. Unassigned: the patron withdrew it after it stayed open for its whole duration
+    // without an auditor, AuditorTimeout: the deadline passed without an accepted report, ArbiterRejection: the
+    // report was rejected in arbitration or by governance, PatronCancelled: the patron withdrew it before its
+    // duration was over. Only the auditor's own failures count against them, see is_auditor_at_fault.
+    pub enum ExpiryReason {
+        Unassigned,
+        AuditorTimeout,
+        ArbiterRejection,
+        PatronCancelled,
+    }
 
-            if matches!(xyz.unwrap().unwrap(), Result::Ok(())) {
-                self.env().emit_event(TokenIncoming {
-                    id: self.current_audit_id,
-                    amount: _value,
-                });
-                self.audit_id_to_payment_info
-                    .insert(&self.current_audit_id, &x);
-                self.env().emit_event(AuditCreated {
-                    id: self.current_audit_id,
-                    payment_info: Some(x),
-                    salt: _salt,
-                });
-                self.current_audit_id = self.current_audit_id + 1;
-                return Ok(());
-            } else {
-                return Err(Error::InsufficientBalance);
-            }
+    impl ExpiryReason {
+        pub fn is_auditor_at_fault(&self) -> bool {
+            matches!(
+                self,
+                ExpiryReason::AuditorTimeout | ExpiryReason::ArbiterRejection
+            )
         }
+    }
 
-        //argument: _id(u32) to access the audit ID.
-        //argument: _auditor(AccountId) the id of auditor being assigned for the audit.
-        //argument: _new_value (Balance) the new value if off-chain patron and auditor decided to have a new value
-        //argument: _new_deadline(Timestamp) new deadline decided by patron and auditor off-chain.
-        // the function verifies if the caller is patron of the audit ID in question,
-        //and then assigns the auditor, resets the start time, and marks a deadline,
-        //emitting the event AuditIdAssigned
-        // if however the new deadline or new value are different than the original ones, it will be reflected
-        // on the audit info, if more value is needed it would require further pre-approved amount, if less, it
-        // will return the subtracted money back to the patron.
-        #[ink(message)]
-        pub fn assign_audit(
-            &mut self,
-            _id: u32,
-            _auditor: AccountId,
-            _new_value: Balance,
-            _new_deadline: Timestamp,
-        ) -> Result<()> {
-            let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-            let _now = self.env().block_timestamp();
-            if payment_info.patron == self.env().caller()
-                && matches!(payment_info.currentstatus, AuditStatus::AuditCreated)
-            {
-                if payment_info.value == _new_value && payment_info.deadline == _new_deadline {
-                    payment_info.auditor = _auditor;
-                    payment_info.starttime = _now;
-                    payment_info.deadline = payment_info.deadline + _now;
-                    payment_info.currentstatus = AuditStatus::AuditAssigned;
-                    self.audit_id_to_payment_info.insert(_id, &payment_info);
-                    self.env().emit_event(AuditIdAssigned {
-                        id: Some(_id),
-                        payment_info: Some(payment_info),
-                    });
-                    return Ok(());
-                } else if payment_info.value == _new_value {
-                    payment_info.auditor = _auditor;
-                    payment_info.starttime = _now;
-                    payment_info.deadline = _new_deadline + _now;
-                    payment_info.currentstatus = AuditStatus::AuditAssigned;
-                    self.audit_id_to_payment_info.insert(_id, &payment_info);
-                    self.env().emit_event(AuditIdAssigned {
-                        id: Some(_id),
-                        payment_info: Some(payment_info),
-                    });
-                    return Ok(());
-                } else {
-                    if _new_value > payment_info.value {
-                        let xyz = ink::env::call::build_call::<Environment>()
-                            .call(self.stablecoin_address)
-                            .gas_limit(0)
-                            .transferred_value(0)
-                            .exec_input(
-                                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                    ink::selector_bytes!("transfer_from"),
-                                ))
-                                .push_arg(self.env().caller())
-                                .push_arg(self.env().account_id())
-                                .push_arg(_new_value - payment_info.value),
-                            )
-                            .returns::<Result<()>>()
-                            .try_invoke();
-                        if matches!(xyz.unwrap().unwrap(), Result::Ok(())) {
-                            payment_info.auditor = _auditor;
-                            payment_info.starttime = _now;
-                            payment_info.value = _new_value;
-                            payment_info.deadline = _new_deadline + _now;
-                            payment_info.currentstatus = AuditStatus::AuditAssigned;
-                            self.audit_id_to_payment_info.insert(_id, &payment_info);
-                            self.env().emit_event(AuditIdAssigned {
-                                id: Some(_id),
-                                payment_info: Some(payment_info),
-                            });
-                            return Ok(());
-                        }
-                        return Err(Error::InsufficientBalance);
-                    } else {
-                        let xyz = ink::env::call::build_call::<Environment>()
-                            .call(self.stablecoin_address)
-                            .gas_limit(0)
-                            .transferred_value(0)
-                            .exec_input(
-                                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                    ink::selector_bytes!("transfer"),
-                                ))
-                                .push_arg(self.env().caller())
-                                .push_arg(payment_info.value - _new_value),
-                            )
-                            .returns::<Result<()>>()
-                            .try_invoke();
-                        if matches!(xyz.unwrap().unwrap(), Result::Ok(())) {
-                            payment_info.auditor = _auditor;
-                            payment_info.starttime = _now;
-                            payment_info.value = _new_value;
-                            payment_info.deadline = _new_deadline + _now;
-                            payment_info.currentstatus = AuditStatus::AuditAssigned;
-                            self.audit_id_to_payment_info.insert(_id, &payment_info);
-                            self.env().emit_event(AuditIdAssigned {
-                                id: Some(_id),
-                                payment_info: Some(payment_info),
-                            });
-                            return Ok(());
-                        }
-                        return Err(Error::TransferFromContractFailed);
-                    }
-                }
-            } else {
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the findings of the report as judged by whoever assessed it, counts per severity and
+    // a quality score from 0 to 100, recorded by the reward token with the minted token.
+    pub struct Scorecard {
+        pub criticals: u32,
+        pub highs: u32,
+        pub mediums: u32,
+        pub quality: u8,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // a commitment of the auditor to the state of their work on an assigned audit, e.g. the hash of their notes,
+    // so that arbiters can weigh documented progress during a dispute.
+    pub struct WorklogEntry {
+        pub hash: Hash,
+        pub committed_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // where an intermediate checkpoint of an audit stands, a submitted one the patron rejects is pending again.
+    pub enum CheckpointStatus {
+        Pending,
+        Submitted,
+        Accepted,
+        Missed,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // an intermediate milestone of an audit, due offset after the audit is assigned. The final report
+    // submitted with mark_submitted is the last checkpoint.
+    pub struct Checkpoint {
+        pub offset: Timestamp,
+        pub ipfs_hash: Option<String>,
+        pub submitted_at: Option<Timestamp>,
+        pub status: CheckpointStatus,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // an audit created by the patron whose value wasn't pulled into the escrow yet, it becomes an audit
+    // once finalize_funding verified the escrow received the full value, with the silence policy the patron
+    // set for it in the meantime.
+    pub struct PendingFunding {
+        pub patron: AccountId,
+        pub value: Balance,
+        pub arbiter_provider: AccountId,
+        pub duration_ms: Timestamp,
+        pub salt: u64,
+        pub client_request_id: Option<u64>,
+        pub created_at: Timestamp,
+        pub silence_policy: SilencePolicy,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    // an audit of a previous deployment of the marketplace, imported with import_audit under its original ID,
+    // in the layout the previous deployment stored it in.
+    // ipfs_hash is the report the auditor submitted, if they did.
+    pub struct LegacyAudit {
+        pub legacy_id: u32,
+        pub payment_info: LegacyPaymentInfo,
+        pub ipfs_hash: Option<String>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // an audit listing the patron is still preparing. It has no audit ID and nothing is checked or locked
+    // until fund_draft, metadata_hash is the hash of the listing's scope document.
+    pub struct DraftAudit {
+        pub patron: AccountId,
+        pub value: Balance,
+        pub arbiter_provider: AccountId,
+        pub duration_ms: Timestamp,
+        pub metadata_hash: String,
+        pub updated_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the pot an arbiter provider opted into by funding it, in the token it was funded with. Every
+    // dispute-resolution message the provider executes moves the per-call gas refund from the balance
+    // to what the provider is owed, which they claim with claim_gas_refunds.
+    pub struct PaymasterPot {
+        pub token: AccountId,
+        pub balance: Balance,
+        pub owed: Balance,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // an addendum to the scope of an assigned audit proposed by the patron, metadata_hash is the hash of the
+    // new scope document, value_delta is added to the locked value (a negative one is refunded) and
+    // deadline_delta to the deadline. accepted_at is set once the auditor accepted it.
+    pub struct ScopeChange {
+        pub metadata_hash: String,
+        pub value_delta: i128,
+        pub deadline_delta: i64,
+        pub proposed_at: Timestamp,
+        pub accepted_at: Option<Timestamp>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the part of the auditor's payout held back under the retention clause of a completed audit,
+    // it is released to the auditor by the patron, or by anyone from releasable_at.
+    pub struct Retention {
+        pub amount: Balance,
+        pub releasable_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the final accounting of a closed audit, where the value locked in it went: paid to the auditor,
+    // refunded to the patron and to the sponsors, the rounding dust sent where the rounding policy said, and the rest
+    // paid as fees to the arbiter provider.
+    pub struct Settlement {
+        pub token: AccountId,
+        pub locked: Balance,
+        pub paid_to_auditor: Balance,
+        pub refunded_to_patron: Balance,
+        pub refunded_to_sponsors: Balance,
+        pub fees: Balance,
+        pub dust: Balance,
+        pub final_status: AuditStatus,
+        pub closed_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    // the payout approving an audit would make under the current parameters: what the auditor is paid and
+    // what is held back from them, the fee of the arbiter provider and the dust sent to the dust receiver,
+    // and the payout preference and DEX router the auditor's payout would be swapped with.
+    pub struct PayoutPreview {
+        pub id: u32,
+        pub token: AccountId,
+        pub auditor: AccountId,
+        pub to_auditor: Balance,
+        pub retained: Balance,
+        pub arbiter_provider: AccountId,
+        pub to_arbiter_provider: Balance,
+        pub dust_receiver: AccountId,
+        pub dust: Balance,
+        pub payout_preference: Option<PayoutPreference>,
+        pub dex_router: Option<AccountId>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the token the auditor of an audit wants to be paid in instead of the audit's own, the payout is swapped
+    // through the DEX router for at least the router's quote less max_slippage_bps (in basis points).
+    pub struct PayoutPreference {
+        pub token: AccountId,
+        pub max_slippage_bps: u16,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the second account the patron designated to sign off the payout of an audit at or above the
+    // co-approval threshold, and which of the two approvals of the current submission were given.
+    pub struct CoApproval {
+        pub reviewer: AccountId,
+        pub patron_approved: bool,
+        pub reviewer_approved: bool,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // what the patron paid to open the current dispute of an audit, so that it can be given back if the
+    // arbiter provider declines it. Bond: the dispute bond, Voucher: one of their waiver vouchers.
+    pub enum DisputeCharge {
+        Bond(Balance),
+        Voucher,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // the arbiter provider who declined the dispute of an audit, e.g. as outside their expertise, with the hash
+    // of their off-chain reason. The audit can't be disputed to them again until the patron nominates the
+    // fallback provider.
+    pub struct DeclinedArbitration {
+        pub provider: AccountId,
+        pub reason_hash: [u8; 32],
+        pub declined_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // what is kept of an audit once it is archived, the parties, the money flow and the outcome,
+    // while the report hash, anchors, panel, sponsors and requests are cleared.
+    pub struct ArchivedAudit {
+        pub patron: AccountId,
+        pub auditor: AccountId,
+        pub arbiterprovider: AccountId,
+        pub original_value: Balance,
+        pub paid_to_auditor: Balance,
+        pub refunded_to_patron: Balance,
+        pub final_status: AuditStatus,
+        pub expiry_reason: Option<ExpiryReason>,
+        pub scorecard: Option<Scorecard>,
+        pub closed_at: Timestamp,
+        pub archived_at: Timestamp,
+        pub token: AccountId,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // a change of an admin parameter, applied through the timelock of queue_param_change and execute_param_change.
+    // Treasury: the account that receives the rounding dust of payout splits under RoundingPolicy::Treasury.
+    // RoundingPolicy: where the remainder of the floored shares of a payout split goes, see math.
+    // PriceOracle: the contract answering `get_price(token) -> Option<Balance>` with the USD price of one whole
+    // token with 6 decimals, None to stop taking snapshots. Snapshots are best effort, an oracle that fails leaves them empty.
+    // GovernanceAddress: the governance contract disputes escalate to, None to disable it, audits already
+    // escalated stay with the old address's ruling.
+    // GasLimit: the gas forwarded on every call to a contract, None to go back to the default.
+    // DeadlineWarningWindow: how long before the deadline of an assigned audit it is warned about.
+    // TimelockDelay: how long later changes wait before they can be executed.
+    // MinDeadlineTiers: the minimum deadline of audits per value tier, as (minimum value, minimum deadline) pairs.
+    // DefaultStablecoin: the PSP22 token new audits are created with, audits already created keep their token.
+    // RewardToken: the reward token closed audits are reported to with `record_patron_audit`, for the history of
+    // their patron. The escrow has to be one of its minters. None stops reporting them.
+    // FallbackArbiterProvider: the arbiter provider a patron can nominate for an audit whose provider declined
+    // its dispute, None if there is none.
+    pub enum ParamChange {
+        Treasury(AccountId),
+        PriceOracle(Option<AccountId>),
+        GovernanceAddress(Option<AccountId>),
+        GasLimit(AccountId, Option<u64>),
+        DeadlineWarningWindow(Timestamp),
+        TimelockDelay(Timestamp),
+        MinDeadlineTiers(Vec<(Balance, Timestamp)>),
+        DefaultStablecoin(AccountId),
+        CoApprovalThreshold(Option<Balance>),
+        DisputeBond(Balance),
+        CheckpointHaircut(Balance),
+        GasRefundPerCall(Balance),
+        DexRouter(Option<AccountId>),
+        PayoutToken(AccountId, bool),
+        RoundingPolicy(RoundingPolicy),
+        RewardToken(Option<AccountId>),
+        FallbackArbiterProvider(Option<AccountId>),
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    // the gas limit the escrow uses when calling a contract, and whether it is the default one
+    // or was configured by the admin.
+    pub struct CallConfig {
+        pub callee: AccountId,
+        pub gas_limit: u64,
+        pub is_default: bool,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    // the error of the PSP22Receiver interface, returned to a token whose transfer hook
+    // the escrow refuses.
+    pub enum PSP22ReceiverError {
+        TransferRejected(String),
+    }
+
+    // emits and informs the retrieval of the audit ID
+    #[ink(event)]
+    pub struct AuditIdRetrieved {
+        id: u32,
+    }
+
+    // emitted when a listener contract subscribes or changes its events mask
+    #[ink(event)]
+    pub struct ListenerSubscribed {
+        listener: AccountId,
+        owner: AccountId,
+        events_mask: u32,
+    }
+
+    // emitted when a listener contract is removed from the registry
+    #[ink(event)]
+    pub struct ListenerUnsubscribed {
+        listener: AccountId,
+    }
+
+    // emitted when a dispute that was already appealed twice is forwarded
+    // to the governance contract instead of the arbiter provider
+    #[ink(event)]
+    pub struct DisputeEscalatedToGovernance {
+        id: u32,
+        governance: AccountId,
+    }
+
+    // emitted when an audit expires, with the reason it did
+    #[ink(event)]
+    pub struct AuditExpired {
+        id: u32,
+        reason: ExpiryReason,
+    }
+
+    // emitted when the closing of an audit is reported to the reward token for the patron's history, recorded
+    // is false if the reward token refused it
+    #[ink(event)]
+    pub struct PatronHistoryRecorded {
+        id: u32,
+        patron: AccountId,
+        disputed: bool,
+        dispute_won: bool,
+        recorded: bool,
+    }
+
+    // emitted when the assessment of an audit comes with a scorecard, token_id is the reward token minted for
+    // the audit with the scorecard attached, None if the assessment didn't close the audit or the reward token
+    // refused the mint or the scorecard
+    #[ink(event)]
+    pub struct ScorecardRecorded {
+        id: u32,
+        scorecard: Scorecard,
+        token_id: Option<u32>,
+    }
+
+    // emitted when the admin excludes an account from the marketplace
+    #[ink(event)]
+    pub struct AccountBlacklisted {
+        account: AccountId,
+    }
+
+    // emitted when the admin lets a blacklisted account back in
+    #[ink(event)]
+    pub struct AccountUnblacklisted {
+        account: AccountId,
+    }
+
+    // emitted when the admin changes the OPERATOR, None makes housekeeping permissionless again
+    #[ink(event)]
+    pub struct OperatorChanged {
+        operator: Option<AccountId>,
+    }
+
+    // emitted when the escrow executes the ruling of the governance contract
+    #[ink(event)]
+    pub struct GovernanceRulingExecuted {
+        id: u32,
+        ruling: GovernanceRuling,
+    }
+
+    // emitted when the admin or governance queues a parameter change
+    #[ink(event)]
+    pub struct ParamChangeQueued {
+        change_id: u32,
+        change: ParamChange,
+        executable_at: Timestamp,
+    }
+
+    // emitted when a queued parameter change takes effect
+    #[ink(event)]
+    pub struct ParamChangeExecuted {
+        change_id: u32,
+        change: ParamChange,
+    }
+
+    // emitted when a queued parameter change is dropped
+    #[ink(event)]
+    pub struct ParamChangeCancelled {
+        change_id: u32,
+    }
+
+    // emitted once for an assigned audit whose deadline is within the warning window
+    #[ink(event)]
+    pub struct DeadlineApproaching {
+        id: u32,
+        remaining_ms: Timestamp,
+    }
+
+    // emitted when a terminal audit is closed, with its final accounting
+    #[ink(event)]
+    pub struct AuditClosed {
+        id: u32,
+        settlement: Settlement,
+    }
+
+    // emitted when a terminal audit is archived, with the summary that is kept of it
+    #[ink(event)]
+    pub struct AuditArchived {
+        id: u32,
+        summary: ArchivedAudit,
+    }
+
+    // emitted when an account other than the patron adds funds to an audit
+    #[ink(event)]
+    pub struct SponsorContributed {
+        id: u32,
+        sponsor: AccountId,
+        amount: Balance,
+    }
+
+    // emitted when a participant anchors an off-chain message to an audit
+    #[ink(event)]
+    pub struct MessageAnchored {
+        id: u32,
+        author: AccountId,
+        ipfs_hash: String,
+    }
+
+    // emitted when the patron sets the intermediate checkpoints of an audit
+    #[ink(event)]
+    pub struct CheckpointsSet {
+        id: u32,
+        offsets: Vec<Timestamp>,
+    }
+
+    // emitted when the arbiter provider declines the dispute of an audit, refunded is the dispute bond given
+    // back to the patron
+    #[ink(event)]
+    pub struct ArbitrationDeclined {
+        id: u32,
+        provider: AccountId,
+        reason_hash: [u8; 32],
+        refunded: Balance,
+    }
+
+    // emitted when the patron nominates the fallback arbiter provider for an audit whose provider declined
+    #[ink(event)]
+    pub struct FallbackProviderNominated {
+        id: u32,
+        provider: AccountId,
+    }
+
+    // emitted when the patron chooses what happens to a report they leave unreviewed
+    #[ink(event)]
+    pub struct SilencePolicySet {
+        id: u32,
+        policy: SilencePolicy,
+    }
+
+    // emitted when the auditor submits the report of an intermediate checkpoint, committed to like the
+    // final report
+    #[ink(event)]
+    pub struct CheckpointSubmitted {
+        id: u32,
+        index: u32,
+        report_commitment: [u8; 32],
+    }
+
+    // emitted when the patron accepts or rejects the report of an intermediate checkpoint
+    #[ink(event)]
+    pub struct CheckpointAssessed {
+        id: u32,
+        index: u32,
+        accepted: bool,
+    }
+
+    // emitted when an intermediate checkpoint passed its due time without a report, haircut is refunded
+    #[ink(event)]
+    pub struct CheckpointMissed {
+        id: u32,
+        index: u32,
+        haircut: Balance,
+    }
+
+    // emitted when the auditor commits to the progress of their work
+    #[ink(event)]
+    pub struct WorklogCommitted {
+        id: u32,
+        hash: Hash,
+    }
+
+    // emitted when the patron designates the reviewer who co-approves the payout of an audit
+    #[ink(event)]
+    pub struct CoReviewerSet {
+        id: u32,
+        reviewer: AccountId,
+    }
+
+    // emitted when the patron or the reviewer approves a submission that needs both approvals,
+    // the payout follows in the same call once the second one is given
+    #[ink(event)]
+    pub struct CoApprovalRecorded {
+        id: u32,
+        approver: AccountId,
+    }
+
+    // emitted when the admin credits waiver vouchers to an account
+    #[ink(event)]
+    pub struct WaiverVouchersMinted {
+        account: AccountId,
+        count: u32,
+    }
+
+    // emitted when a patron opens a dispute with a voucher instead of the dispute bond
+    #[ink(event)]
+    pub struct WaiverVoucherUsed {
+        id: u32,
+        patron: AccountId,
+        remaining: u32,
+    }
+
+    // emitted when an arbiter provider adds to their paymaster pot
+    #[ink(event)]
+    pub struct PaymasterFunded {
+        provider: AccountId,
+        amount: Balance,
+    }
+
+    // emitted when the gas of a dispute-resolution message is refunded to the arbiter provider from their pot
+    #[ink(event)]
+    pub struct GasRefunded {
+        id: u32,
+        provider: AccountId,
+        amount: Balance,
+    }
+
+    // emitted when the patron proposes a change of the scope of an assigned audit
+    #[ink(event)]
+    pub struct ScopeChangeProposed {
+        id: u32,
+        metadata_hash: String,
+        value_delta: i128,
+        deadline_delta: i64,
+    }
+
+    // emitted when the auditor accepts the proposed scope change, with the resulting value and deadline
+    #[ink(event)]
+    pub struct ScopeChangeAccepted {
+        id: u32,
+        value: Balance,
+        deadline: Timestamp,
+    }
+
+    // emitted when the patron sets the retention percentage of an audit
+    #[ink(event)]
+    pub struct RetentionSet {
+        id: u32,
+        percentage: Balance,
+    }
+
+    // emitted when part of the auditor's payout is held back under the retention clause of the audit
+    #[ink(event)]
+    pub struct RetentionWithheld {
+        id: u32,
+        amount: Balance,
+        releasable_at: Timestamp,
+    }
+
+    // emitted when the retention of an audit is paid to the auditor
+    #[ink(event)]
+    pub struct RetentionReleased {
+        id: u32,
+        amount: Balance,
+    }
+
+    // emitted when an approved audit is paid out, commitment is the hash of the PayoutPreview it was paid out by
+    #[ink(event)]
+    pub struct PayoutCommitted {
+        id: u32,
+        commitment: [u8; 32],
+    }
+
+    // emitted when the auditor sets or removes the payout preference of an audit
+    #[ink(event)]
+    pub struct PayoutPreferenceSet {
+        id: u32,
+        preference: Option<PayoutPreference>,
+    }
+
+    // emitted when a payout to the auditor is swapped into the token of their payout preference
+    #[ink(event)]
+    pub struct PayoutSwapped {
+        id: u32,
+        token: AccountId,
+        amount_in: Balance,
+        amount_out: Balance,
+    }
+
+    // emitted when the swap of a payout fails and the auditor is paid in the audit's token instead
+    #[ink(event)]
+    pub struct PayoutSwapFailed {
+        id: u32,
+        token: AccountId,
+    }
+
+    // emitted by check_solvency when the escrow holds less of a token than it owes in it
+    #[ink(event)]
+    pub struct SolvencyWarning {
+        token: AccountId,
+        shortfall: Balance,
+    }
+
+    // emitted when a patron pays the dispute bond to the arbiter provider to open a dispute
+    #[ink(event)]
+    pub struct DisputeBondPaid {
+        id: u32,
+        amount: Balance,
+    }
+
+    // the first dispute of an audit goes to the arbiter provider, every later one is an appeal.
+    // once an audit has been appealed this many times, the next dispute goes to governance.
+    pub const APPEALS_BEFORE_GOVERNANCE: u8 = 2;
+
+    // the most listener contracts that can subscribe at once, every subscription costs
+    // a cross-contract call on each transition it listens to.
+    pub const MAX_SUBSCRIPTIONS: u32 = 10;
+    // the gas each listener is allowed to use to handle a notification, enough to record the event. A transition
+    // notifies up to MAX_SUBSCRIPTIONS listeners, so this bounds what the notifications add to its cost.
+    pub const LISTENER_GAS_LIMIT: u64 = 500_000_000;
+    // the smallest value an audit can be created or assigned with, in whole tokens of the stablecoin.
+    pub const MIN_AUDIT_VALUE_TOKENS: Balance = 1;
+
+    // how long after deployment the admin can import audits of a previous deployment, 30 days.
+    pub const IMPORT_WINDOW: Timestamp = 2592000000;
+    // the most audits import_audit accepts in one call.
+    pub const MAX_IMPORT_BATCH: u32 = 50;
+    // how long the patron has to review a submitted report before its silence policy applies, 14 days
+    pub const PATRON_REVIEW_WINDOW: Timestamp = 1209600000;
+    // the most drafts a patron can keep at once.
+    pub const MAX_DRAFTS_PER_PATRON: u32 = 20;
+    // the most sponsors an audit can have, a refund makes one transfer per sponsor.
+    pub const MAX_SPONSORS_PER_AUDIT: u32 = 20;
+    // the most messages each of the patron, auditor and arbiter provider can anchor to a single audit, so
+    // that none of them can use up the trail of the others.
+    pub const MAX_ANCHORS_PER_PARTY: u32 = 20;
+    // the longest the hash of an anchored message can be, in bytes.
+    pub const MAX_ANCHOR_HASH_LENGTH: u32 = 128;
+    // the most work-log commitments the auditor can make on a single audit.
+    pub const MAX_WORKLOG_ENTRIES: u32 = 50;
+    // the most intermediate checkpoints an audit can have.
+    pub const MAX_CHECKPOINTS: u32 = 10;
+    // the most scope changes that can be accepted on a single audit.
+    pub const MAX_SCOPE_CHANGES: u32 = 10;
+    // the most arbiters the arbiter provider can register on the panel of a dispute.
+    pub const MAX_PANEL_SIZE: u32 = 15;
+    // the most audit IDs process_due accepts in one call.
+    pub const MAX_PROCESS_BATCH: u32 = 50;
+    // the most audit IDs get_ids_by_status returns in one page.
+    pub const MAX_PAGE_SIZE: u32 = 100;
+    // how many of the latest auditors of a patron are remembered, for arbiters' conflict of interest checks.
+    pub const MAX_RECENT_AUDITORS: u32 = 20;
+    // the most value tiers the minimum deadline table can have.
+    pub const MAX_DEADLINE_TIERS: u32 = 10;
+    // gas limit of calls to PSP22 tokens without a configured one, enough for a transfer or a query.
+    pub const TOKEN_GAS_LIMIT: u64 = 5_000_000_000;
+    // gas limit of calls to other contracts without a configured one, e.g. governance, the reward token or the
+    // router, which update several records of their own.
+    pub const DEFAULT_GAS_LIMIT: u64 = 20_000_000_000;
+    // how long a completed or expired audit is kept in full before it can be archived, 90 days.
+    pub const ARCHIVE_RETENTION_PERIOD: Timestamp = 7776000000;
+    // the largest part of the auditor's payout, in percent, a patron can hold back under a retention clause.
+    pub const MAX_RETENTION_PERCENTAGE: Balance = 50;
+    // how long after completion the retention of an audit is held before anyone can release it, 30 days.
+    pub const RETENTION_PERIOD: Timestamp = 2592000000;
+    // the most slippage an auditor can accept on the swap of their payout, 10%
+    pub const MAX_PAYOUT_SLIPPAGE_BPS: u16 = 1000;
+
+    #[ink(storage)]
+    pub struct Escrow {
+        current_audit_id: u32,
+        stablecoin_address: AccountId,
+        pub audit_id_to_payment_info: Mapping<u32, PaymentInfo>,
+        pub audit_id_to_time_increase_request: ink::storage::Mapping<u32, IncreaseRequest>,
+        pub audit_id_to_ipfs_hash: ink::storage::Mapping<u32, String>,
+        pub subscriptions: Vec<Subscription>,
+        admin: AccountId,
+        governance_address: Option<AccountId>,
+        pub audit_id_to_dispute_count: Mapping<u32, u8>,
+        pub audit_id_to_governance_escalation: Mapping<u32, bool>,
+        pub audit_id_to_sponsors: Mapping<u32, Vec<AccountId>>,
+        pub audit_id_to_contributions: Mapping<(u32, AccountId), Balance>,
+        token_decimals: u8,
+        treasury: AccountId,
+        price_oracle: Option<AccountId>,
+        pub audit_id_to_message_anchors: Mapping<u32, Vec<MessageAnchor>>,
+        audit_id_to_panel: Mapping<u32, Vec<AccountId>>,
+        pub receiving_transfer: Lazy<bool>,
+        callee_gas_limits: Mapping<AccountId, u64>,
+        pub audit_id_to_expiry_reason: Mapping<u32, ExpiryReason>,
+        pub audit_id_to_scorecard: Mapping<u32, Scorecard>,
+        blacklisted: Mapping<AccountId, ()>,
+        pub client_request_to_audit_id: Mapping<(AccountId, u64), u32>,
+        pub audit_id_to_closed_at: Mapping<u32, Timestamp>,
+        pub archived: Mapping<u32, ArchivedAudit>,
+        deadline_warning_window: Timestamp,
+        pub audit_id_to_deadline_warned: Mapping<u32, bool>,
+        param_change_delay: Timestamp,
+        pub pending_param_changes: Mapping<u32, (ParamChange, Timestamp)>,
+        next_param_change_id: u32,
+        min_deadline_tiers: Vec<(Balance, Timestamp)>,
+        pub audit_id_to_token: Mapping<u32, (AccountId, u8)>,
+        pub receiving_token: Lazy<AccountId>,
+        pub audit_id_to_worklog: Mapping<u32, Vec<WorklogEntry>>,
+        co_approval_threshold: Option<Balance>,
+        pub audit_id_to_co_approval: Mapping<u32, CoApproval>,
+        pub audit_id_to_pending_funding: Mapping<u32, PendingFunding>,
+        dispute_bond: Balance,
+        pub waiver_vouchers: Mapping<AccountId, u32>,
+        pub audit_id_to_checkpoints: Mapping<u32, Vec<Checkpoint>>,
+        checkpoint_haircut: Balance,
+        gas_refund_per_call: Balance,
+        pub paymaster_pots: Mapping<AccountId, PaymasterPot>,
+        pub credits: Mapping<(AccountId, AccountId), Balance>,
+        pub account_to_credit_tokens: Mapping<AccountId, Vec<AccountId>>,
+        pub status_heads: Mapping<AuditStatus, u32>,
+        pub status_tails: Mapping<AuditStatus, u32>,
+        pub status_counts: Mapping<AuditStatus, u32>,
+        pub audit_id_to_next_in_status: Mapping<u32, u32>,
+        pub audit_id_to_previous_in_status: Mapping<u32, u32>,
+        pub audit_id_to_submission_count: Mapping<u32, u32>,
+        pub submissions: Mapping<(u32, u32), String>,
+        pub patron_to_recent_auditors: Mapping<AccountId, Vec<AccountId>>,
+        pub audit_id_to_parent: Mapping<u32, u32>,
+        pub audit_id_to_pending_scope_change: Mapping<u32, ScopeChange>,
+        pub audit_id_to_scope_changes: Mapping<u32, Vec<ScopeChange>>,
+        pub obligations: Mapping<AccountId, Balance>,
+        pub audit_id_to_retention_percentage: Mapping<u32, Balance>,
+        pub audit_id_to_retention: Mapping<u32, Retention>,
+        pub dex_router: Option<AccountId>,
+        pub payout_tokens: Mapping<AccountId, bool>,
+        pub audit_id_to_payout_preference: Mapping<u32, PayoutPreference>,
+        pub audit_id_to_dust: Mapping<u32, Balance>,
+        pub audit_id_to_refunded_to_sponsors: Mapping<u32, Balance>,
+        pub audit_id_to_settlement: Mapping<u32, Settlement>,
+        operator: Option<AccountId>,
+        pub audit_id_to_payout_commitment: Mapping<u32, [u8; 32]>,
+        rounding_policy: RoundingPolicy,
+        next_draft_id: u32,
+        pub drafts: Mapping<u32, DraftAudit>,
+        pub patron_to_drafts: Mapping<AccountId, Vec<u32>>,
+        pub audit_id_to_metadata_hash: Mapping<u32, String>,
+        import_deadline: Timestamp,
+        pub audit_id_to_submitted_at: Mapping<u32, Timestamp>,
+        reward_token: Option<AccountId>,
+        pub audit_id_to_dispute_charge: Mapping<u32, DisputeCharge>,
+        pub audit_id_to_declined_arbitration: Mapping<u32, DeclinedArbitration>,
+        fallback_arbiter_provider: Option<AccountId>,
+        instantiator: AccountId,
+        voting_contract: Option<AccountId>,
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl Escrow {
+        // the constructor queries the stablecoin's total_supply and decimals, and fails if it doesn't
+        // answer like a PSP22 token, so a wrong address is caught at deployment instead of the first payment.
+        #[ink(constructor)]
+        pub fn new(_stablecoin_address: AccountId, _admin: AccountId) -> Result<Self> {
+            let token_decimals = Self::probe_stablecoin(_stablecoin_address)?;
+            let current_audit_id = u32::default();
+            let stablecoin_address = _stablecoin_address;
+            // let current_request_id = u32::default();
+            let audit_id_to_payment_info = Mapping::default();
+            let audit_id_to_time_increase_request = Mapping::default();
+            let audit_id_to_ipfs_hash = Mapping::default();
+            let subscriptions = Vec::new();
+            let admin = _admin;
+            //no governance tier until the admin configures one
+            let governance_address = None;
+            let audit_id_to_dispute_count = Mapping::default();
+            let audit_id_to_governance_escalation = Mapping::default();
+            let audit_id_to_sponsors = Mapping::default();
+            let audit_id_to_contributions = Mapping::default();
+            //rounding dust of the payout splits goes to the admin until a treasury is set
+            let treasury = _admin;
+            //no fiat snapshots until the admin configures a price oracle
+            let price_oracle = None;
+            let audit_id_to_message_anchors = Mapping::default();
+            let audit_id_to_panel = Mapping::default();
+            let receiving_transfer = Lazy::default();
+            let callee_gas_limits = Mapping::default();
+            let audit_id_to_expiry_reason = Mapping::default();
+            let audit_id_to_scorecard = Mapping::default();
+            let blacklisted = Mapping::default();
+            let client_request_to_audit_id = Mapping::default();
+            let audit_id_to_closed_at = Mapping::default();
+            let archived = Mapping::default();
+            //auditors are warned a day before their deadline
+            let deadline_warning_window = 86400000;
+            let audit_id_to_deadline_warned = Mapping::default();
+            //parameter changes wait 2 days before they can be executed
+            let param_change_delay = 172800000;
+            let pending_param_changes = Mapping::default();
+            let next_param_change_id = u32::default();
+            //no minimum deadline until the admin configures the value tiers
+            let min_deadline_tiers = Vec::new();
+            let audit_id_to_token = Mapping::default();
+            let receiving_token = Lazy::default();
+            let audit_id_to_worklog = Mapping::default();
+            //every payout needs the patron only until the admin configures a co-approval threshold
+            let co_approval_threshold = None;
+            let audit_id_to_co_approval = Mapping::default();
+            let audit_id_to_pending_funding = Mapping::default();
+            //patrons open disputes for free until the admin configures a bond
+            let dispute_bond = 0;
+            let waiver_vouchers = Mapping::default();
+            let audit_id_to_checkpoints = Mapping::default();
+            //a missed checkpoint costs the auditor 10% of the original value
+            let checkpoint_haircut = 10;
+            //nothing is refunded from the paymaster pots until the admin sets the gas cost of a call
+            let gas_refund_per_call = 0;
+            let paymaster_pots = Mapping::default();
+            let credits = Mapping::default();
+            let account_to_credit_tokens = Mapping::default();
+            let status_heads = Mapping::default();
+            let status_tails = Mapping::default();
+            let status_counts = Mapping::default();
+            let audit_id_to_next_in_status = Mapping::default();
+            let audit_id_to_previous_in_status = Mapping::default();
+            let audit_id_to_submission_count = Mapping::default();
+            let submissions = Mapping::default();
+            let patron_to_recent_auditors = Mapping::default();
+            let audit_id_to_parent = Mapping::default();
+            let audit_id_to_pending_scope_change = Mapping::default();
+            let audit_id_to_scope_changes = Mapping::default();
+            let obligations = Mapping::default();
+            let audit_id_to_retention_percentage = Mapping::default();
+            let audit_id_to_retention = Mapping::default();
+            let dex_router = None;
+            let payout_tokens = Mapping::default();
+            let audit_id_to_payout_preference = Mapping::default();
+            let audit_id_to_dust = Mapping::default();
+            let audit_id_to_refunded_to_sponsors = Mapping::default();
+            let audit_id_to_settlement = Mapping::default();
+            //housekeeping is permissionless until the admin configures an operator
+            let operator = None;
+            let audit_id_to_payout_commitment = Mapping::default();
+            //the rounding dust goes to the treasury until the admin changes the policy
+            let rounding_policy = RoundingPolicy::Treasury;
+            let next_draft_id = u32::default();
+            let drafts = Mapping::default();
+            let patron_to_drafts = Mapping::default();
+            let audit_id_to_metadata_hash = Mapping::default();
+            let import_deadline = Self::env().block_timestamp().saturating_add(IMPORT_WINDOW);
+            let audit_id_to_submitted_at = Mapping::default();
+            //the history of patrons isn't recorded until the admin configures a reward token
+            let reward_token = None;
+            let audit_id_to_dispute_charge = Mapping::default();
+            let audit_id_to_declined_arbitration = Mapping::default();
+            //a declined dispute waits for the patron until the admin configures a fallback provider
+            let fallback_arbiter_provider = None;
+            //the deployer that instantiated the escrow records the voting contract it deployed for it
+            let instantiator = Self::env().caller();
+            let voting_contract = None;
+            Ok(Self {
+                current_audit_id,
+                stablecoin_address,
+                audit_id_to_payment_info,
+                audit_id_to_time_increase_request,
+                audit_id_to_ipfs_hash,
+                subscriptions,
+                admin,
+                governance_address,
+                audit_id_to_dispute_count,
+                audit_id_to_governance_escalation,
+                audit_id_to_sponsors,
+                audit_id_to_contributions,
+                token_decimals,
+                treasury,
+                price_oracle,
+                audit_id_to_message_anchors,
+                audit_id_to_panel,
+                receiving_transfer,
+                callee_gas_limits,
+                audit_id_to_expiry_reason,
+                audit_id_to_scorecard,
+                blacklisted,
+                client_request_to_audit_id,
+                audit_id_to_closed_at,
+                archived,
+                deadline_warning_window,
+                audit_id_to_deadline_warned,
+                param_change_delay,
+                pending_param_changes,
+                next_param_change_id,
+                min_deadline_tiers,
+                audit_id_to_token,
+                receiving_token,
+                audit_id_to_worklog,
+                co_approval_threshold,
+                audit_id_to_co_approval,
+                audit_id_to_pending_funding,
+                dispute_bond,
+                waiver_vouchers,
+                audit_id_to_checkpoints,
+                checkpoint_haircut,
+                gas_refund_per_call,
+                paymaster_pots,
+                credits,
+                account_to_credit_tokens,
+                status_heads,
+                status_tails,
+                status_counts,
+                audit_id_to_next_in_status,
+                audit_id_to_previous_in_status,
+                audit_id_to_submission_count,
+                submissions,
+                patron_to_recent_auditors,
+                audit_id_to_parent,
+                audit_id_to_pending_scope_change,
+                audit_id_to_scope_changes,
+                obligations,
+                audit_id_to_retention_percentage,
+                audit_id_to_retention,
+                dex_router,
+                payout_tokens,
+                audit_id_to_payout_preference,
+                audit_id_to_dust,
+                audit_id_to_refunded_to_sponsors,
+                audit_id_to_settlement,
+                operator,
+                audit_id_to_payout_commitment,
+                rounding_policy,
+                next_draft_id,
+                drafts,
+                patron_to_drafts,
+                audit_id_to_metadata_hash,
+                import_deadline,
+                audit_id_to_submitted_at,
+                reward_token,
+                audit_id_to_dispute_charge,
+                audit_id_to_declined_arbitration,
+                fallback_arbiter_provider,
+                instantiator,
+                voting_contract,
+            })
+        }
+
+        //read function that returns the protocol version of the escrow, see interface.rs
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            crate::interface::PROTOCOL_VERSION
+        }
+
+        //read function that returns the blake2x256 hash of the sorted selectors of every message of the
+        //escrow, two deployments with the same hash accept the same calls
+        #[ink(message)]
+        pub fn interface_hash(&self) -> [u8; 32] {
+            crate::interface::interface_hash()
+        }
+
+        //read function to see total number of audits in escrow
+        #[ink(message)]
+        pub fn get_current_audit_id(&self) -> u32 {
+            self.current_audit_id
+        }
+
+        //read function that returns the stablecoin that is being used
+        #[ink(message)]
+        pub fn know_your_stablecoin(&self) -> AccountId {
+            self.stablecoin_address
+        }
+
+        //read function to know the admin of the escrow contract
+        #[ink(message)]
+        pub fn know_your_admin(&self) -> AccountId {
+            self.admin
+        }
+
+        //read function that returns the decimals of the stablecoin, as last synced from it
+        #[ink(message)]
+        pub fn get_token_decimals(&self) -> u8 {
+            self.token_decimals
+        }
+
+        //read function that returns the minimum value of an audit in the smallest unit of the stablecoin
+        #[ink(message)]
+        pub fn get_min_audit_value(&self) -> Balance {
+            Self::min_audit_value_for(self.token_decimals)
+        }
+
+        //read function that returns the OPERATOR, the only account next to the admin that can do the housekeeping
+        #[ink(message)]
+        pub fn get_operator(&self) -> Option<AccountId> {
+            self.operator
+        }
+
+        //read function that returns how long before the deadline of an assigned audit emit_deadline_warnings warns about it
+        #[ink(message)]
+        pub fn get_deadline_warning_window(&self) -> Timestamp {
+            self.deadline_warning_window
+        }
+
+        //read function that returns how long a queued parameter change waits before it can be executed
+        #[ink(message)]
+        pub fn get_param_change_delay(&self) -> Timestamp {
+            self.param_change_delay
+        }
+
+        //read function that returns a queued parameter change and from when it can be executed
+        #[ink(message)]
+        pub fn get_pending_param_change(&self, change_id: u32) -> Option<(ParamChange, Timestamp)> {
+            self.pending_param_changes.get(change_id)
+        }
+
+        //read function that returns the bounds arbiters_extend_deadline enforces, as (largest haircut, largest
+        //arbiters' share, least extension of the deadline), so the arbiter provider can check its ruling first
+        #[ink(message)]
+        pub fn get_extension_bounds(&self) -> (Balance, Balance, Timestamp) {
+            (MAX_HAIRCUT, MAX_ARBITERS_SHARE, MIN_DEADLINE_EXTENSION)
+        }
+
+        //read function that returns the minimum deadline table, as (minimum value, minimum deadline) pairs
+        #[ink(message)]
+        pub fn get_min_deadline_tiers(&self) -> Vec<(Balance, Timestamp)> {
+            self.min_deadline_tiers.clone()
+        }
+
+        //read function that returns the shortest deadline an audit of the given value can have,
+        //the one of the highest tier the value reaches, 0 if it reaches none
+        #[ink(message)]
+        pub fn get_min_deadline(&self, value: Balance) -> Timestamp {
+            self.min_deadline_tiers
+                .iter()
+                .filter(|(min_value, _)| value >= *min_value)
+                .map(|(_, min_deadline)| *min_deadline)
+                .max()
+                .unwrap_or_default()
+        }
+
+        //read function that returns the account the rounding dust of payouts is sent to
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        //read function that returns where the rounding remainder of payout splits goes
+        #[ink(message)]
+        pub fn get_rounding_policy(&self) -> RoundingPolicy {
+            self.rounding_policy
+        }
+
+        //read function that returns the price oracle used for the fiat snapshots of audits, if any
+        #[ink(message)]
+        pub fn get_price_oracle(&self) -> Option<AccountId> {
+            self.price_oracle
+        }
+
+        //read function that returns the reward token the history of patrons is recorded on, if any
+        #[ink(message)]
+        pub fn get_reward_token(&self) -> Option<AccountId> {
+            self.reward_token
+        }
+
+        //read function that returns the arbiter provider patrons can nominate when theirs declines, if any
+        #[ink(message)]
+        pub fn get_fallback_arbiter_provider(&self) -> Option<AccountId> {
+            self.fallback_arbiter_provider
+        }
+
+        //read function that returns the voting contract deployed to resolve the disputes of the escrow, if any
+        #[ink(message)]
+        pub fn get_voting_contract(&self) -> Option<AccountId> {
+            self.voting_contract
+        }
+
+        //argument: voting (AccountId) the voting contract deployed for the escrow
+        //argument: reward_token (AccountId) the reward token deployed with the escrow, with the escrow as a minter
+        // only the account that instantiated the escrow, e.g. the deployer, can record the contracts deployed with it,
+        // and only once, so that the contracts of a deployment are wired in the same transaction. The reward token
+        // can later be changed like any other parameter.
+        #[ink(message)]
+        pub fn set_deployment(&mut self, voting: AccountId, reward_token: AccountId) -> Result<()> {
+            if self.env().caller() != self.instantiator {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.voting_contract.is_some() {
+                return Err(Error::InvalidArgument);
+            }
+            self.voting_contract = Some(voting);
+            self.reward_token = Some(reward_token);
+            Ok(())
+        }
+
+        //read function that returns the DEX router payouts are swapped through, if any
+        #[ink(message)]
+        pub fn get_dex_router(&self) -> Option<AccountId> {
+            self.dex_router
+        }
+
+        //read function that tells if auditors can choose to be paid in the token
+        #[ink(message)]
+        pub fn is_payout_token(&self, token: AccountId) -> bool {
+            self.payout_tokens.get(token).unwrap_or_default()
+        }
+
+        //read function that returns the governance contract disputes escalate to, if any
+        #[ink(message)]
+        pub fn get_governance_address(&self) -> Option<AccountId> {
+            self.governance_address
+        }
+
+        //read function that returns how many times the patron has disputed an audit
+        #[ink(message)]
+        pub fn get_dispute_count(&self, id: u32) -> u8 {
+            self.audit_id_to_dispute_count.get(id).unwrap_or_default()
+        }
+
+        //read function that tells if the dispute of an audit is currently with governance
+        #[ink(message)]
+        pub fn is_escalated_to_governance(&self, id: u32) -> bool {
+            self.audit_id_to_governance_escalation
+                .get(id)
+                .unwrap_or_default()
+        }
+
+        //read function that returns the accounts other than the patron that funded an audit
+        #[ink(message)]
+        pub fn get_sponsors(&self, id: u32) -> Vec<AccountId> {
+            self.audit_id_to_sponsors.get(id).unwrap_or_default()
+        }
+
+        //read function that returns how much a sponsor has contributed to an audit
+        #[ink(message)]
+        pub fn get_contribution(&self, id: u32, sponsor: AccountId) -> Balance {
+            self.audit_id_to_contributions
+                .get((id, sponsor))
+                .unwrap_or_default()
+        }
+
+        //read function that returns how the value currently locked for an audit would be
+        //refunded if it expired now, the sponsors pro-rata to their contributions and the
+        //patron gets the rest. Empty if there is no such audit or the split overflows.
+        #[ink(message)]
+        pub fn get_refund_split(&self, id: u32) -> Vec<(AccountId, Balance)> {
+            match self.audit_id_to_payment_info.get(id) {
+                Some(payment_info) => self
+                    .refund_shares(id, payment_info.patron, payment_info.value)
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            }
+        }
+
+        //read function that returns the value originally locked for the audit job,
+        //that haircuts are taken as a percentage of
+        #[ink(message)]
+        pub fn get_original_value(&self, id: u32) -> Balance {
+            self.audit_id_to_payment_info
+                .get(id)
+                .map(|payment_info| payment_info.original_value)
+                .unwrap_or_default()
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: count(u32) how many of the latest assignments to look at, at most MAX_RECENT_AUDITORS
+        // read function that returns the auditors the patron of the audit assigned their latest audits to, the
+        // latest first, so the voting contract can keep them off the panel of a dispute with that patron.
+        #[ink(message)]
+        pub fn get_patron_recent_auditors(&self, id: u32, count: u32) -> Vec<AccountId> {
+            let Some(payment_info) = self.audit_id_to_payment_info.get(id) else {
+                return Vec::new();
+            };
+            let recent = self
+                .patron_to_recent_auditors
+                .get(payment_info.patron)
+                .unwrap_or_default();
+            recent.into_iter().rev().take(count as usize).collect()
+        }
+
+        //read function that returns the total refunded from the audit's value to the patron, as haircuts or after expiry,
+        //the shares of the sponsors refunded with it are not counted
+        #[ink(message)]
+        pub fn get_refunded_to_patron(&self, id: u32) -> Balance {
+            self.audit_id_to_payment_info
+                .get(id)
+                .map(|payment_info| payment_info.refunded_to_patron)
+                .unwrap_or_default()
+        }
+
+        //read function that returns the total refunded from the audit's value to its sponsors, their shares
+        //of the haircuts and of the refund after expiry
+        #[ink(message)]
+        pub fn get_refunded_to_sponsors(&self, id: u32) -> Balance {
+            self.audit_id_to_refunded_to_sponsors
+                .get(id)
+                .unwrap_or_default()
+        }
+
+        //read function that returns the total paid to the auditor from the audit's value
+        #[ink(message)]
+        pub fn get_paid_to_auditor(&self, id: u32) -> Balance {
+            self.audit_id_to_payment_info
+                .get(id)
+                .map(|payment_info| payment_info.paid_to_auditor)
+                .unwrap_or_default()
+        }
+
+        //read function that returns why an audit expired, None if it didn't
+        #[ink(message)]
+        pub fn get_expiry_reason(&self, id: u32) -> Option<ExpiryReason> {
+            self.audit_id_to_expiry_reason.get(id)
+        }
+
+        //read function for the reward contract (or whoever mints for it), tells if an expired audit
+        // should count as unsuccessful for its auditor, None if the audit didn't expire
+        #[ink(message)]
+        pub fn is_auditor_at_fault(&self, id: u32) -> Option<bool> {
+            self.get_expiry_reason(id)
+                .map(|reason| reason.is_auditor_at_fault())
+        }
+
+        //read function that returns the scorecard of the latest assessment of an audit, if it had one
+        #[ink(message)]
+        pub fn get_scorecard(&self, id: u32) -> Option<Scorecard> {
+            self.audit_id_to_scorecard.get(id)
+        }
+
+        //read function that tells if the admin has excluded the account from the marketplace
+        #[ink(message)]
+        pub fn is_blacklisted(&self, account: AccountId) -> bool {
+            self.blacklisted.contains(account)
+        }
+
+        //read function that gives the details of paymentinfo
+        #[ink(message)]
+        pub fn get_paymentinfo(&self, id: u32) -> Option<PaymentInfo> {
+            self.audit_id_to_payment_info.get(&id)
+        }
+
+        //read function that returns the token an audit pays out in, the default stablecoin when it was created
+        #[ink(message)]
+        pub fn get_audit_token(&self, id: u32) -> Option<AccountId> {
+            self.audit_id_to_payment_info
+                .contains(id)
+                .then(|| self.token_of(id))
+        }
+
+        //read function that returns the summary of an archived audit
+        #[ink(message)]
+        pub fn get_archived_audit(&self, id: u32) -> Option<ArchivedAudit> {
+            self.archived.get(id)
+        }
+
+        //read function that gives the details of paymentinfo for a batch of audit ids,
+        //in the same order as the ids passed, with None for ids that don't exist
+        #[ink(message)]
+        pub fn multiget_paymentinfo(&self, ids: Vec<u32>) -> Vec<Option<PaymentInfo>> {
+            ids.iter()
+                .map(|id| self.audit_id_to_payment_info.get(id))
+                .collect()
+        }
+
+        //read function that gives only the current status for a batch of audit ids
+        #[ink(message)]
+        pub fn multiget_status(&self, ids: Vec<u32>) -> Vec<Option<AuditStatus>> {
+            ids.iter()
+                .map(|id| {
+                    self.audit_id_to_payment_info
+                        .get(id)
+                        .map(|payment_info| payment_info.currentstatus)
+                })
+                .collect()
+        }
+
+        //read function that returns the status of an audit as reported before AuditAwaitingValidation was split,
+        //AuditDisputed is AuditAwaitingValidation and AuditAwaitingPatronReview is AuditSubmitted
+        #[ink(message)]
+        pub fn get_legacy_status(&self, id: u32) -> Option<LegacyAuditStatus> {
+            self.audit_id_to_payment_info
+                .get(id)
+                .map(|payment_info| payment_info.currentstatus.to_legacy())
+        }
+
+        //read function that gives the legacy status, see get_legacy_status, for a batch of audit ids
+        #[ink(message)]
+        pub fn multiget_legacy_status(&self, ids: Vec<u32>) -> Vec<Option<LegacyAuditStatus>> {
+            ids.iter().map(|id| self.get_legacy_status(*id)).collect()
+        }
+
+        //read function that returns the hash/link of the submitted reports on audits,
+        //None if there is none or the caller isn't allowed to see it, see get_report_hash
+        #[ink(message)]
+        pub fn get_submitted_reports(&self, id: u32) -> Option<String> {
+            self.get_report_hash(id).ok().flatten()
+        }
+
+        //read function that returns the hash of the submitted report only to the patron, auditor and
+        //arbiter provider of the audit, or to the arbiters on its panel while it is disputed
+        #[ink(message)]
+        pub fn get_report_hash(&self, id: u32) -> Result<Option<String>> {
+            self.ensure_can_read_report(id)?;
+            Ok(self.audit_id_to_ipfs_hash.get(id))
+        }
+
+        //read function that returns how many times the report of the audit was submitted
+        #[ink(message)]
+        pub fn get_submission_count(&self, id: u32) -> u32 {
+            self.audit_id_to_submission_count.get(id).unwrap_or(0)
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: version(u32) the submission, from 1 to get_submission_count
+        // read function that returns the hash of an earlier version of the report, so the versions can be
+        // compared across arbitration rounds. Visible to the same accounts as get_report_hash.
+        #[ink(message)]
+        pub fn get_submission(&self, id: u32, version: u32) -> Result<Option<String>> {
+            self.ensure_can_read_report(id)?;
+            Ok(self.submissions.get((id, version)))
+        }
+
+        // the report of an audit is only visible to its patron, auditor and arbiter provider, and to the
+        // arbiters on its panel while it is disputed
+        fn ensure_can_read_report(&self, id: u32) -> Result<()> {
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            let caller = self.env().caller();
+            let is_party = caller == payment_info.patron
+                || caller == payment_info.auditor
+                || caller == payment_info.arbiterprovider;
+            let is_panel_arbiter = matches!(payment_info.currentstatus, AuditStatus::AuditDisputed)
+                && self.get_panel(id).contains(&caller);
+            if !is_party && !is_panel_arbiter {
+                return Err(Error::NotAParticipant);
+            }
+            Ok(())
+        }
+
+        //argument: status (AuditStatus) the status of the audits, e.g. AuditCreated for open jobs
+        //argument: after (Option<u32>) the last ID of the previous page, None for the first page
+        //argument: limit (u32) how many IDs to return at most, capped at MAX_PAGE_SIZE
+        // read function that returns the IDs of the audits currently in the status, in the order they entered it,
+        // archived audits aren't listed anymore. Audits entering or leaving the status between two pages don't move
+        // the others, the page is empty if the audit after which it starts has left the status.
+        #[ink(message)]
+        pub fn get_ids_by_status(
+            &self,
+            status: AuditStatus,
+            after: Option<u32>,
+            limit: u32,
+        ) -> Vec<u32> {
+            let mut next = match after {
+                None => self.status_heads.get(status),
+                Some(id) if self.is_in_status(id, status) => {
+                    self.audit_id_to_next_in_status.get(id)
+                }
+                Some(_) => None,
+            };
+            let mut ids = Vec::new();
+            while let Some(id) = next {
+                if ids.len() as u32 >= limit.min(MAX_PAGE_SIZE) {
+                    break;
+                }
+                ids.push(id);
+                next = self.audit_id_to_next_in_status.get(id);
+            }
+            ids
+        }
+
+        //read function that returns how many audits are currently in the status
+        #[ink(message)]
+        pub fn get_status_count(&self, status: AuditStatus) -> u32 {
+            self.status_counts.get(status).unwrap_or_default()
+        }
+
+        //read function that returns the intermediate checkpoints of an audit, in the order they are due,
+        //visible to the same accounts as get_report_hash since they hold the reports of the checkpoints
+        #[ink(message)]
+        pub fn get_checkpoints(&self, id: u32) -> Result<Vec<Checkpoint>> {
+            self.ensure_can_read_report(id)?;
+            Ok(self.checkpoints_of(id))
+        }
+
+        //read function that returns the percentage of the original value refunded when a checkpoint is missed
+        #[ink(message)]
+        pub fn get_checkpoint_haircut(&self) -> Balance {
+            self.checkpoint_haircut
+        }
+
+        //read function that returns the work-log commitments of the auditor on an audit, oldest first
+        #[ink(message)]
+        pub fn get_worklog(&self, id: u32) -> Vec<WorklogEntry> {
+            self.audit_id_to_worklog.get(id).unwrap_or_default()
+        }
+
+        //read function that returns the bond a patron pays the arbiter provider to dispute a submission,
+        //in the smallest unit of the audit's token
+        #[ink(message)]
+        pub fn get_dispute_bond(&self) -> Balance {
+            self.dispute_bond
+        }
+
+        //read function that returns the approximate gas cost of a dispute-resolution message, refunded to the
+        //arbiter provider from their paymaster pot, in the smallest unit of the token of the pot
+        #[ink(message)]
+        pub fn get_gas_refund_per_call(&self) -> Balance {
+            self.gas_refund_per_call
+        }
+
+        //read function that returns the paymaster pot of the arbiter provider, None if they didn't opt in
+        #[ink(message)]
+        pub fn get_paymaster_pot(&self, provider: AccountId) -> Option<PaymasterPot> {
+            self.paymaster_pots.get(provider)
+        }
+
+        //read function that returns how many disputes the account can open without paying the bond
+        #[ink(message)]
+        pub fn get_waiver_vouchers(&self, account: AccountId) -> u32 {
+            self.waiver_vouchers.get(account).unwrap_or_default()
+        }
+
+        //read function that returns the original value from which a payout needs a co-approval, if any
+        #[ink(message)]
+        pub fn get_co_approval_threshold(&self) -> Option<Balance> {
+            self.co_approval_threshold
+        }
+
+        //read function that returns the reviewer of an audit and the approvals given to its current submission
+        #[ink(message)]
+        pub fn get_co_approval(&self, id: u32) -> Option<CoApproval> {
+            self.audit_id_to_co_approval.get(id)
+        }
+
+        //read function that returns who still has to approve the submission of an audit before it is paid out,
+        //empty if the audit doesn't need a co-approval. The reviewer is missing until the patron designates one.
+        #[ink(message)]
+        pub fn get_remaining_approvals(&self, id: u32) -> Vec<AccountId> {
+            let payment_info = match self.audit_id_to_payment_info.get(id) {
+                Some(payment_info) => payment_info,
+                None => return Vec::new(),
+            };
+            if !self.requires_co_approval(&payment_info) {
+                return Vec::new();
+            }
+            let approval = self.audit_id_to_co_approval.get(id);
+            let mut remaining = Vec::new();
+            if !approval.is_some_and(|approval| approval.patron_approved) {
+                remaining.push(payment_info.patron);
+            }
+            if let Some(approval) = approval.filter(|approval| !approval.reviewer_approved) {
+                remaining.push(approval.reviewer);
+            }
+            remaining
+        }
+
+        //read function that returns the arbiters registered on the panel of a disputed audit
+        #[ink(message)]
+        pub fn get_panel(&self, id: u32) -> Vec<AccountId> {
+            self.audit_id_to_panel.get(id).unwrap_or_default()
+        }
+
+        //read function that returns the off-chain messages anchored to an audit, oldest first
+        #[ink(message)]
+        pub fn get_message_anchors(&self, id: u32) -> Vec<MessageAnchor> {
+            self.audit_id_to_message_anchors.get(id).unwrap_or_default()
+        }
+
+        //read function that returns time increase request details
+        #[ink(message)]
+        pub fn query_timeincreaserequest(&self, id: u32) -> Option<IncreaseRequest> {
+            self.audit_id_to_time_increase_request.get(&id)
+        }
+
+        //read function that returns all the listener contracts subscribed to the audit lifecycle
+        #[ink(message)]
+        pub fn get_subscriptions(&self) -> Vec<Subscription> {
+            self.subscriptions.clone()
+        }
+
+        //argument: listener (AccountId) the contract that will receive `on_audit_event(id, event)` calls
+        //argument: events_mask (u32) the AuditLifecycleEvent bits the listener wants to be notified of
+        // only the admin can subscribe a listener, every notification is paid for by the callers of the
+        // transitions, so listeners can't take the MAX_SUBSCRIPTIONS slots themselves. The admin becomes the
+        // owner of the subscription and calling it again updates the mask, which must name at least one event
+        // and no bit outside AuditLifecycleEvent::ALL_MASK.
+        // Notifications are best effort, a failing listener never blocks the audit flow.
+        #[ink(message)]
+        pub fn subscribe(&mut self, listener: AccountId, events_mask: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let caller = self.env().caller();
+            if caller != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if events_mask == 0 || events_mask & !AuditLifecycleEvent::ALL_MASK != 0 {
+                return Err(Error::InvalidArgument);
+            }
+            if let Some(subscription) = self
+                .subscriptions
+                .iter_mut()
+                .find(|subscription| subscription.listener == listener)
+            {
+                subscription.events_mask = events_mask;
+            } else {
+                if self.subscriptions.len() as u32 >= MAX_SUBSCRIPTIONS {
+                    return Err(Error::TooManySubscriptions);
+                }
+                self.subscriptions.push(Subscription {
+                    listener,
+                    owner: caller,
+                    events_mask,
+                });
+            }
+            self.env().emit_event(ListenerSubscribed {
+                listener,
+                owner: caller,
+                events_mask,
+            });
+            Ok(())
+        }
+
+        //argument: listener (AccountId) the contract to remove from the registry
+        // only the owner of the subscription, the admin who subscribed the listener, or the current admin can
+        // remove it.
+        #[ink(message)]
+        pub fn unsubscribe(&mut self, listener: AccountId) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let caller = self.env().caller();
+            match self
+                .subscriptions
+                .iter()
+                .position(|subscription| subscription.listener == listener)
+            {
+                Some(index)
+                    if self.subscriptions[index].owner == caller || caller == self.admin =>
+                {
+                    self.subscriptions.swap_remove(index);
+                    self.env().emit_event(ListenerUnsubscribed { listener });
+                    Ok(())
+                }
+                Some(_) => Err(Error::UnAuthorisedCall),
+                None => Err(Error::InvalidArgument),
+            }
+        }
+
+        //create new payment function is to be called by the patron, choosing a rough deadline and balance for the audit job.
+        //argument: value (Balance) that will be locked in the escrow
+        //argument: arbiter_provider (AccountId) the service that will provide with arbiters
+        //argument: deadline, amount of time from the assigning of the auditor for successful audit,
+        //at least the minimum deadline of the value's tier
+        //argument: salt(u64) a random number to be used by the frontend to verify the post creation
+        //argument: client_request_id(Option<u64>) an id chosen by the frontend to make retries safe
+        //the function will record the payment as pending funding under current_audit_id, increasing the audit_id
+        //afterwards and emitting the event for FundingPending. It returns the audit ID. Nothing is transferred
+        //until the patron calls finalize_funding, which locks in the value amount of payment tokens.
+        // if the caller already created a payment with the same client_request_id, nothing is created
+        // and the ID of that audit is returned, so a retried transaction can't double-pay.
+        #[ink(message)]
+        pub fn create_new_payment(
+            &mut self,
+            _value: Balance,
+            _arbiter_provider: AccountId,
+            _deadline: Timestamp,
+            //this deadline is deadline that will be added to current time once the audit is assigned to an auditor.
+            _salt: u64,
+            client_request_id: Option<u64>,
+        ) -> Result<u32> {
+            self.ensure_not_receiving()?;
+            self.ensure_not_blacklisted(self.env().caller())?;
+            if let Some(request_id) = client_request_id {
+                if let Some(id) = self
+                    .client_request_to_audit_id
+                    .get((self.env().caller(), request_id))
+                {
+                    return Ok(id);
+                }
+            }
+            if _value < Self::min_audit_value_for(self.token_decimals) {
+                return Err(Error::ValueBelowMinimum);
+            }
+            self.ensure_min_deadline(_value, _deadline)?;
+            //the audit pays out in the token it was funded with, even if the default changes later
+            self.audit_id_to_token.insert(
+                self.current_audit_id,
+                &(self.stablecoin_address, self.token_decimals),
+            );
+            let pending = PendingFunding {
+                patron: self.env().caller(),
+                value: _value,
+                arbiter_provider: _arbiter_provider,
+                duration_ms: _deadline,
+                salt: _salt,
+                client_request_id,
+                created_at: self.env().block_timestamp(),
+                silence_policy: SilencePolicy::Hold,
+            };
+            self.audit_id_to_pending_funding
+                .insert(self.current_audit_id, &pending);
+            self.env().emit_event(FundingPending {
+                id: self.current_audit_id,
+                patron: pending.patron,
+                value: _value,
+            });
+            if let Some(request_id) = client_request_id {
+                self.client_request_to_audit_id
+                    .insert((self.env().caller(), request_id), &self.current_audit_id);
+            }
+            let id = self.current_audit_id;
+            self.current_audit_id += 1;
+            Ok(id)
+        }
+
+        //argument: id(u32) the audit ID returned by create_new_payment
+        // only the patron can call it. The value of the audit is pulled from the patron and the escrow's balance
+        // of the token is compared before and after, if it grew by less than the value, e.g. because the token
+        // takes a fee on transfers, the whole call fails with FundingMismatch and nothing is pulled. Otherwise the
+        // audit is created with AuditCreated, and its deadline only starts once it is assigned.
+        #[ink(message)]
+        pub fn finalize_funding(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let pending = self
+                .audit_id_to_pending_funding
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != pending.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_not_blacklisted(pending.patron)?;
+            self.fund_pending(id, &pending)
+        }
+
+        //argument: id(u32) the audit ID returned by create_new_payment
+        // only the patron can call it, for an audit they haven't funded yet. The audit is dropped, and its
+        // client_request_id can be used again.
+        #[ink(message)]
+        pub fn cancel_pending_funding(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let pending = self
+                .audit_id_to_pending_funding
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != pending.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.audit_id_to_pending_funding.remove(id);
+            self.audit_id_to_token.remove(id);
+            if self.audit_id_to_parent.take(id).is_some() {
+                self.audit_id_to_co_approval.remove(id);
+            }
+            if let Some(request_id) = pending.client_request_id {
+                self.client_request_to_audit_id
+                    .remove((pending.patron, request_id));
+            }
+            self.env().emit_event(PendingFundingCancelled { id });
+            Ok(())
+        }
+
+        //read function that returns an audit created by its patron and still waiting for finalize_funding
+        #[ink(message)]
+        pub fn get_pending_funding(&self, id: u32) -> Option<PendingFunding> {
+            self.audit_id_to_pending_funding.get(id)
+        }
+
+        //argument: value (Balance) that will be locked in the escrow once the draft is funded
+        //argument: arbiter_provider (AccountId) the service that will provide with arbiters
+        //argument: duration_ms, amount of time from the assigning of the auditor for successful audit
+        //argument: metadata_hash(String) the hash of the scope document of the listing
+        // any patron can keep up to MAX_DRAFTS_PER_PATRON drafts. A draft doesn't take an audit ID and
+        // nothing is checked or pulled until fund_draft, so it can be edited and deleted freely.
+        // Emits DraftSaved and returns the ID of the draft.
+        #[ink(message)]
+        pub fn create_draft(
+            &mut self,
+            value: Balance,
+            arbiter_provider: AccountId,
+            duration_ms: Timestamp,
+            metadata_hash: String,
+        ) -> Result<u32> {
+            self.ensure_not_receiving()?;
+            let patron = self.env().caller();
+            self.ensure_not_blacklisted(patron)?;
+            let mut draft_ids = self.get_drafts(patron);
+            if draft_ids.len() as u32 >= MAX_DRAFTS_PER_PATRON {
+                return Err(Error::TooManyDrafts);
+            }
+            let draft_id = self.next_draft_id;
+            let draft = DraftAudit {
+                patron,
+                value,
+                arbiter_provider,
+                duration_ms,
+                metadata_hash,
+                updated_at: self.env().block_timestamp(),
+            };
+            self.drafts.insert(draft_id, &draft);
+            draft_ids.push(draft_id);
+            self.patron_to_drafts.insert(patron, &draft_ids);
+            self.next_draft_id += 1;
+            self.env().emit_event(DraftSaved { draft_id, patron });
+            Ok(draft_id)
+        }
+
+        //argument: draft_id(u32) the draft to edit
+        //the other arguments replace the ones of the draft, see create_draft
+        // only the patron of the draft can call it. Emits DraftSaved.
+        #[ink(message)]
+        pub fn update_draft(
+            &mut self,
+            draft_id: u32,
+            value: Balance,
+            arbiter_provider: AccountId,
+            duration_ms: Timestamp,
+            metadata_hash: String,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut draft = self.drafts.get(draft_id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != draft.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            draft.value = value;
+            draft.arbiter_provider = arbiter_provider;
+            draft.duration_ms = duration_ms;
+            draft.metadata_hash = metadata_hash;
+            draft.updated_at = self.env().block_timestamp();
+            self.drafts.insert(draft_id, &draft);
+            self.env().emit_event(DraftSaved {
+                draft_id,
+                patron: draft.patron,
+            });
+            Ok(())
+        }
+
+        //argument: draft_id(u32) the draft to delete
+        // only the patron of the draft can call it. Emits DraftDeleted.
+        #[ink(message)]
+        pub fn delete_draft(&mut self, draft_id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let draft = self.drafts.get(draft_id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != draft.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.remove_draft(draft_id, draft.patron);
+            self.env().emit_event(DraftDeleted { draft_id });
+            Ok(())
+        }
+
+        //argument: draft_id(u32) the draft to fund
+        //argument: salt(u64) a random number to be used by the frontend to verify the post creation
+        // only the patron of the draft can call it. The draft is checked like create_new_payment checks a
+        // payment, takes the next audit ID and its value is pulled like finalize_funding pulls it, the
+        // metadata hash is kept with the audit, see get_metadata_hash. The draft is removed, emits
+        // AuditCreated and DraftFunded and returns the audit ID.
+        #[ink(message)]
+        pub fn fund_draft(&mut self, draft_id: u32, salt: u64) -> Result<u32> {
+            self.ensure_not_receiving()?;
+            let draft = self.drafts.get(draft_id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != draft.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_not_blacklisted(draft.patron)?;
+            if draft.value < Self::min_audit_value_for(self.token_decimals) {
+                return Err(Error::ValueBelowMinimum);
+            }
+            self.ensure_min_deadline(draft.value, draft.duration_ms)?;
+            let id = self.current_audit_id;
+            self.audit_id_to_token
+                .insert(id, &(self.stablecoin_address, self.token_decimals));
+            let pending = PendingFunding {
+                patron: draft.patron,
+                value: draft.value,
+                arbiter_provider: draft.arbiter_provider,
+                duration_ms: draft.duration_ms,
+                salt,
+                client_request_id: None,
+                created_at: self.env().block_timestamp(),
+                silence_policy: SilencePolicy::Hold,
+            };
+            self.fund_pending(id, &pending)?;
+            self.current_audit_id += 1;
+            self.audit_id_to_metadata_hash
+                .insert(id, &draft.metadata_hash);
+            self.remove_draft(draft_id, draft.patron);
+            self.env().emit_event(DraftFunded { draft_id, id });
+            Ok(id)
+        }
+
+        //read function that returns a draft, None once it was funded or deleted
+        #[ink(message)]
+        pub fn get_draft(&self, draft_id: u32) -> Option<DraftAudit> {
+            self.drafts.get(draft_id)
+        }
+
+        //read function that returns the IDs of the drafts of a patron
+        #[ink(message)]
+        pub fn get_drafts(&self, patron: AccountId) -> Vec<u32> {
+            self.patron_to_drafts.get(patron).unwrap_or_default()
+        }
+
+        //read function that returns the metadata hash of an audit funded from a draft
+        #[ink(message)]
+        pub fn get_metadata_hash(&self, id: u32) -> Option<String> {
+            self.audit_id_to_metadata_hash.get(id)
+        }
+
+        //argument: audits(Vec<LegacyAudit>) up to MAX_IMPORT_BATCH audits of a previous deployment
+        // only the admin can call it, until IMPORT_WINDOW has passed since the escrow was deployed. Every audit
+        // is stored under its original ID, which can't be taken yet, in the default stablecoin, with its deadline
+        // split into duration_ms and due_at, see LegacyPaymentInfo::migrate, and its report
+        // as its first submission. The next new audit gets an ID after the highest imported one, so u32::MAX
+        // can't be imported. The value still locked in every audit is pulled from the admin, who must have
+        // approved it after moving it over from the previous deployment. Emits AuditImported for every audit.
+        #[ink(message)]
+        pub fn import_audit(&mut self, audits: Vec<LegacyAudit>) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.env().block_timestamp() > self.import_deadline {
+                return Err(Error::ImportWindowClosed);
+            }
+            if audits.len() as u32 > MAX_IMPORT_BATCH {
+                return Err(Error::InvalidArgument);
+            }
+            for audit in audits {
+                let LegacyAudit {
+                    legacy_id: id,
+                    payment_info,
+                    ipfs_hash,
+                } = audit;
+                let payment_info = payment_info.migrate();
+                if id == u32::MAX
+                    || self.audit_id_to_payment_info.contains(id)
+                    || self.audit_id_to_pending_funding.contains(id)
+                    || self.archived.contains(id)
+                    || payment_info.value > payment_info.original_value
+                {
+                    return Err(Error::InvalidArgument);
+                }
+                self.audit_id_to_token
+                    .insert(id, &(self.stablecoin_address, self.token_decimals));
+                let locked = Self::locked_value_of(&payment_info);
+                if locked > 0 {
+                    self.receive_tokens(id, self.admin, locked)?;
+                }
+                self.store_payment_info(id, &payment_info);
+                if let Some(ipfs_hash) = ipfs_hash {
+                    self.audit_id_to_ipfs_hash.insert(id, &ipfs_hash);
+                    self.audit_id_to_submission_count.insert(id, &1);
+                    self.submissions.insert((id, 1), &ipfs_hash);
+                }
+                //the deployment the audit comes from already reported it to its reward token
+                if matches!(
+                    payment_info.currentstatus,
+                    AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+                ) {
+                    self.audit_id_to_closed_at
+                        .insert(id, &self.env().block_timestamp());
+                }
+                self.current_audit_id = self.current_audit_id.max(id + 1);
+                self.env().emit_event(AuditImported { id, payment_info });
+            }
+            Ok(())
+        }
+
+        //read function that returns until when the admin can import audits of a previous deployment
+        #[ink(message)]
+        pub fn get_import_deadline(&self) -> Timestamp {
+            self.import_deadline
+        }
+
+        //argument: id(u32) the completed audit to follow up on
+        // only the patron of the audit can call it, once it is completed, e.g. for a re-audit after the findings
+        // were fixed. A new audit is recorded as pending funding with the original value, deadline, arbiter
+        // provider and token of the audit, and its co-reviewer if it had one, and is linked to it, see
+        // get_parent_audit. Emits FundingPending and FollowupCreated, and returns the ID of the new audit,
+        // which is funded with finalize_funding like any other.
+        #[ink(message)]
+        pub fn create_followup(&mut self, id: u32) -> Result<u32> {
+            self.ensure_not_receiving()?;
+            self.ensure_not_blacklisted(self.env().caller())?;
+            let parent = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != parent.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if parent.currentstatus != AuditStatus::AuditCompleted {
+                return Err(Error::WrongState);
+            }
+            //the deadline of an assigned audit is a point in time, the follow-up gets as long as the original had
+            let duration_ms = parent.duration_ms;
+            self.ensure_min_deadline(parent.original_value, duration_ms)?;
+            let followup_id = self.current_audit_id;
+            self.audit_id_to_token.insert(
+                followup_id,
+                &(self.token_of(id), self.token_decimals_of(id)),
+            );
+            let pending = PendingFunding {
+                patron: parent.patron,
+                value: parent.original_value,
+                arbiter_provider: parent.arbiterprovider,
+                duration_ms,
+                salt: 0,
+                client_request_id: None,
+                created_at: self.env().block_timestamp(),
+                silence_policy: parent.silence_policy,
+            };
+            self.audit_id_to_pending_funding
+                .insert(followup_id, &pending);
+            if let Some(co_approval) = self.audit_id_to_co_approval.get(id) {
+                self.audit_id_to_co_approval.insert(
+                    followup_id,
+                    &CoApproval {
+                        reviewer: co_approval.reviewer,
+                        patron_approved: false,
+                        reviewer_approved: false,
+                    },
+                );
+            }
+            self.audit_id_to_parent.insert(followup_id, &id);
+            self.env().emit_event(FundingPending {
+                id: followup_id,
+                patron: pending.patron,
+                value: pending.value,
+            });
+            self.env().emit_event(FollowupCreated {
+                id: followup_id,
+                parent_audit_id: id,
+            });
+            self.current_audit_id += 1;
+            Ok(followup_id)
+        }
+
+        //read function that returns the audit the audit is a follow-up of, so the reward token and frontends
+        //can trace the lineage of re-audits, it is kept after archiving
+        #[ink(message)]
+        pub fn get_parent_audit(&self, id: u32) -> Option<u32> {
+            self.audit_id_to_parent.get(id)
+        }
+
+        //argument: id(u32) the audit ID to fund
+        //argument: amount(Balance) the amount of stablecoin to add to the audit's value
+        // anyone but the patron can fund an audit while it is still waiting for an auditor, the
+        // amount has to be pre-approved for the escrow. The contribution is tracked per sponsor,
+        // so that if the audit expires, the refund is split pro-rata between the patron and sponsors.
+        #[ink(message)]
+        pub fn contribute(&mut self, id: u32, amount: Balance) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            let sponsor = self.env().caller();
+            if amount == 0 || sponsor == payment_info.patron {
+                return Err(Error::InvalidArgument);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditCreated) {
+                return Err(Error::WrongState);
+            }
+            let mut sponsors = self.get_sponsors(id);
+            if !sponsors.contains(&sponsor) {
+                if sponsors.len() as u32 >= MAX_SPONSORS_PER_AUDIT {
+                    return Err(Error::TooManySponsors);
+                }
+                sponsors.push(sponsor);
+            }
+            self.receive_tokens(id, sponsor, amount)?;
+            let contribution = self.get_contribution(id, sponsor) + amount;
+            self.audit_id_to_contributions
+                .insert((id, sponsor), &contribution);
+            self.audit_id_to_sponsors.insert(id, &sponsors);
+            payment_info.value += amount;
+            payment_info.original_value = payment_info.value;
+            self.store_payment_info(id, &payment_info);
+            self.env().emit_event(SponsorContributed {
+                id,
+                sponsor,
+                amount,
+            });
+            self.env().emit_event(AuditInfoUpdated {
+                id: Some(id),
+                payment_info: Some(payment_info),
+                updated_by: Some(sponsor),
+            });
+            Ok(())
+        }
+
+        //argument: _id(u32) to access the audit ID.
+        //argument: _auditor(AccountId) the id of auditor being assigned for the audit.
+        //argument: _new_value (Balance) the new value if off-chain patron and auditor decided to have a new value
+        //argument: _new_deadline(Timestamp) new deadline decided by patron and auditor off-chain.
+        // the function verifies if the caller is patron of the audit ID in question,
+        //and then assigns the auditor, resets the start time, and marks a deadline,
+        //emitting the event AuditIdAssigned
+        // if however the new deadline or new value are different than the original ones, it will be reflected
+        // on the audit info, if more value is needed it would require further pre-approved amount, if less, it
+        // will return the subtracted money back to the patron.
+        // if the allowance doesn't cover the difference, NeedsAdditionalApproval carries how much is missing.
+        // the new deadline has to be at least the minimum deadline of the new value's tier.
+        #[ink(message)]
+        pub fn assign_audit(
+            &mut self,
+            _id: u32,
+            _auditor: AccountId,
+            _new_value: Balance,
+            _new_deadline: Timestamp,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            self.ensure_not_blacklisted(self.env().caller())?;
+            self.ensure_not_blacklisted(_auditor)?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(_id)
+                .ok_or(Error::InvalidArgument)?;
+            let token = self.token_of(_id);
+            let _now = self.env().block_timestamp();
+            if _new_value < Self::min_audit_value_for(self.token_decimals_of(_id)) {
+                return Err(Error::ValueBelowMinimum);
+            }
+            self.ensure_min_deadline(_new_value, _new_deadline)?;
+            if payment_info.patron == self.env().caller()
+                && matches!(payment_info.currentstatus, AuditStatus::AuditCreated)
+            {
+                if payment_info.value == _new_value && payment_info.duration_ms == _new_deadline {
+                    payment_info.auditor = _auditor;
+                    payment_info.starttime = _now;
+                    payment_info.due_at =
+                        Some(Self::deadline_from(_now, payment_info.duration_ms)?);
+                    payment_info.currentstatus = AuditStatus::AuditAssigned;
+                    self.store_payment_info(_id, &payment_info);
+                    self.record_recent_auditor(payment_info.patron, _auditor);
+                    self.env().emit_event(AuditIdAssigned {
+                        id: Some(_id),
+                        payment_info: Some(payment_info),
+                    });
+                    self.notify_listeners(_id, AuditLifecycleEvent::Assigned);
+                    return Ok(());
+                } else if payment_info.value == _new_value {
+                    payment_info.auditor = _auditor;
+                    payment_info.starttime = _now;
+                    payment_info.duration_ms = _new_deadline;
+                    payment_info.due_at = Some(Self::deadline_from(_now, _new_deadline)?);
+                    payment_info.currentstatus = AuditStatus::AuditAssigned;
+                    self.store_payment_info(_id, &payment_info);
+                    self.record_recent_auditor(payment_info.patron, _auditor);
+                    self.env().emit_event(AuditIdAssigned {
+                        id: Some(_id),
+                        payment_info: Some(payment_info),
+                    });
+                    self.notify_listeners(_id, AuditLifecycleEvent::Assigned);
+                    return Ok(());
+                } else {
+                    if _new_value > payment_info.value {
+                        //only the difference is pulled, the wallet learns how much more it has to approve
+                        let top_up = _new_value - payment_info.value;
+                        let allowance = self.allowance_of(token, self.env().caller())?;
+                        if allowance < top_up {
+                            return Err(Error::NeedsAdditionalApproval(top_up - allowance));
+                        }
+                        self.receive_tokens(_id, self.env().caller(), top_up)?;
+                        payment_info.auditor = _auditor;
+                        payment_info.starttime = _now;
+                        payment_info.value = _new_value;
+                        payment_info.original_value = _new_value;
+                        payment_info.duration_ms = _new_deadline;
+                        payment_info.due_at = Some(Self::deadline_from(_now, _new_deadline)?);
+                        payment_info.currentstatus = AuditStatus::AuditAssigned;
+                        self.store_payment_info(_id, &payment_info);
+                        self.record_recent_auditor(payment_info.patron, _auditor);
+                        self.env().emit_event(AuditIdAssigned {
+                            id: Some(_id),
+                            payment_info: Some(payment_info),
+                        });
+                        self.notify_listeners(_id, AuditLifecycleEvent::Assigned);
+                        Ok(())
+                    } else {
+                        //the patron can only take back their own part of the value, not the sponsors'
+                        if _new_value < self.sponsored_total(_id) {
+                            return Err(Error::InvalidArgument);
+                        }
+                        let xyz = self.credit_tokens(
+                            token,
+                            self.env().caller(),
+                            payment_info.value - _new_value,
+                        );
+                        if xyz.is_ok() {
+                            payment_info.auditor = _auditor;
+                            payment_info.starttime = _now;
+                            payment_info.value = _new_value;
+                            payment_info.original_value = _new_value;
+                            payment_info.duration_ms = _new_deadline;
+                            payment_info.due_at = Some(Self::deadline_from(_now, _new_deadline)?);
+                            payment_info.currentstatus = AuditStatus::AuditAssigned;
+                            self.store_payment_info(_id, &payment_info);
+                            self.record_recent_auditor(payment_info.patron, _auditor);
+                            self.env().emit_event(AuditIdAssigned {
+                                id: Some(_id),
+                                payment_info: Some(payment_info),
+                            });
+                            self.notify_listeners(_id, AuditLifecycleEvent::Assigned);
+                            return Ok(());
+                        }
+                        return Err(Error::TransferFromContractFailed);
+                    }
+                }
+            } else {
+                return Err(Error::UnAuthorisedCall);
+            }
+        }
+
+        //argument: _id (u32) audit Id
+        //argument: _time (Timestamp) the new deadline
+        //argument: haircut_percentage(Balance) the part of value that will be sent back to the patron for delay
+        // the function verifies that the auditor of an assigned audit is calling the function, that the haircut is
+        // at most MAX_HAIRCUT and the new deadline is in the future, then the request is made,
+        //mapping of IncreaseRequest updated, and event is emitted for DeadlineExtendRequest
+        #[ink(message)]
+        pub fn request_additional_time(
+            &mut self,
+            _id: u32,
+            _time: Timestamp,
+            _haircut_percentage: Balance,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self.get_paymentinfo(_id).ok_or(Error::InvalidArgument)?;
+            //an audit is stored with the patron as its auditor until one is assigned
+            if payment_info.auditor == self.env().caller()
+                && payment_info.patron != self.env().caller()
+            {
+                if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                    return Err(Error::WrongState);
+                }
+                if _haircut_percentage > MAX_HAIRCUT || _time <= self.env().block_timestamp() {
+                    return Err(Error::InvalidArgument);
+                }
+                let x = IncreaseRequest {
+                    haircut_percentage: _haircut_percentage,
+                    new_deadline: _time,
+                };
+                self.audit_id_to_time_increase_request.insert(_id, &x);
+                self.env().emit_event(DeadlineExtendRequest {
+                    id: _id,
+                    newtime: _time,
+                    haircut: _haircut_percentage,
+                });
+                return Ok(());
+            }
+            return Err(Error::UnAuthorisedCall);
+        }
+
+        //argument: _id(u32) audit Id for which the additional time will be approved
+        // the function verifies that only patron is calling it, while the audit is assigned, that haircut is at most
+        // MAX_HAIRCUT and the new deadline is still in the future. The request is used up by the approval,
+        // the function assumes the consent for approving the time, transfers the haircut percentage
+        // of the original value to the patron's address, and changes the time in payment_info along with the new amount
+        //  events are emitted for tokenOutgoing and AuditInfoUpdated.
+        #[ink(message)]
+        pub fn approve_additional_time(&mut self, _id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self.get_paymentinfo(_id).ok_or(Error::InvalidArgument)?;
+            if payment_info.patron == self.env().caller() {
+                if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                    return Err(Error::WrongState);
+                }
+                //there has to be a pending request from the auditor to approve, it can't be approved twice
+                let request = self
+                    .audit_id_to_time_increase_request
+                    .take(_id)
+                    .ok_or(Error::InvalidArgument)?;
+                let haircut = request.haircut_percentage;
+                if haircut <= MAX_HAIRCUT && request.new_deadline > self.env().block_timestamp() {
+                    let new_deadline = request.new_deadline;
+                    let value0 = Self::haircut_of(&payment_info, haircut);
+                    let token = self.token_of(_id);
+                    let xyz = self.credit_tokens(token, payment_info.patron, value0);
+                    if xyz.is_ok() {
+                        self.env().emit_event(PaymentCredited {
+                            id: _id,
+                            receiver: payment_info.patron,
+                            amount: value0,
+                        });
+                        payment_info.value -= value0;
+                        payment_info.refunded_to_patron += value0;
+                        payment_info.due_at = Some(new_deadline);
+                        self.store_payment_info(_id, &payment_info);
+
+                        self.env().emit_event(AuditInfoUpdated {
+                            id: Some(_id),
+                            payment_info: self.audit_id_to_payment_info.get(_id),
+                            updated_by: Some(payment_info.patron),
+                        });
+                        self.notify_listeners(_id, AuditLifecycleEvent::DeadlineExtended);
+                        return Ok(());
+                    }
+                    return Err(Error::TransferFromContractFailed);
+                }
+                return Err(Error::InvalidArgument);
+            }
+            Err(Error::UnAuthorisedCall)
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: percentage(Balance) the part of the auditor's payout to hold back, in percent
+        // only the patron can call it, while the audit isn't assigned, so that the auditor knows the retention
+        // clause before taking the job. At most MAX_RETENTION_PERCENTAGE, 0 removes the clause.
+        #[ink(message)]
+        pub fn set_retention(&mut self, id: u32, percentage: Balance) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self.get_paymentinfo(id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditCreated) {
+                return Err(Error::WrongState);
+            }
+            if percentage > MAX_RETENTION_PERCENTAGE {
+                return Err(Error::InvalidArgument);
+            }
+            if percentage == 0 {
+                self.audit_id_to_retention_percentage.remove(id);
+            } else {
+                self.audit_id_to_retention_percentage
+                    .insert(id, &percentage);
+            }
+            self.env().emit_event(RetentionSet { id, percentage });
+            Ok(())
+        }
+
+        //argument: id(u32) the completed audit
+        // pays the retention held back from the auditor's payout to the auditor. The patron can release it
+        // at any time, anyone else once RETENTION_PERIOD has passed since completion, process_due releases
+        // it too. Emits RetentionReleased.
+        #[ink(message)]
+        pub fn release_retention(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self.get_paymentinfo(id).ok_or(Error::InvalidArgument)?;
+            let retention = self
+                .audit_id_to_retention
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron
+                && self.env().block_timestamp() < retention.releasable_at
+            {
+                return Err(Error::RetentionPeriodNotPassed);
+            }
+            self.pay_retention(id, payment_info, retention)
+        }
+
+        //read function that returns the retention percentage of an audit, 0 without a retention clause
+        #[ink(message)]
+        pub fn get_retention_percentage(&self, id: u32) -> Balance {
+            self.audit_id_to_retention_percentage
+                .get(id)
+                .unwrap_or_default()
+        }
+
+        //read function that returns what is held back from the auditor of a completed audit and until when
+        #[ink(message)]
+        pub fn get_retention(&self, id: u32) -> Option<Retention> {
+            self.audit_id_to_retention.get(id)
+        }
+
+        //argument: id(u32) the assigned audit
+        //argument: preference(Option<PayoutPreference>) the token to be paid in and the slippage accepted, None
+        //to be paid in the audit's token
+        // only the auditor can call it, once they accepted the audit and until they submit. The token has to be
+        // allow-listed by the admin and a DEX router configured, the swap happens when the auditor is paid and
+        // falls back to the audit's token if it fails. Emits PayoutPreferenceSet.
+        #[ink(message)]
+        pub fn set_payout_preference(
+            &mut self,
+            id: u32,
+            preference: Option<PayoutPreference>,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self.get_paymentinfo(id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.auditor {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                return Err(Error::WrongState);
+            }
+            match preference {
+                Some(preference) => {
+                    if self.dex_router.is_none() {
+                        return Err(Error::NoDexRouter);
+                    }
+                    if preference.token == self.token_of(id)
+                        || !self.is_payout_token(preference.token)
+                    {
+                        return Err(Error::TokenNotAllowed);
+                    }
+                    if preference.max_slippage_bps > MAX_PAYOUT_SLIPPAGE_BPS {
+                        return Err(Error::SlippageTooHigh);
+                    }
+                    self.audit_id_to_payout_preference.insert(id, &preference);
+                }
+                None => self.audit_id_to_payout_preference.remove(id),
+            }
+            self.env()
+                .emit_event(PayoutPreferenceSet { id, preference });
+            Ok(())
+        }
+
+        //read function that returns the token the auditor of an audit wants to be paid in, if not the audit's
+        #[ink(message)]
+        pub fn get_payout_preference(&self, id: u32) -> Option<PayoutPreference> {
+            self.audit_id_to_payout_preference.get(id)
+        }
+
+        //argument: id(u32) the assigned audit
+        //argument: metadata_hash(String) the hash of the new scope document
+        //argument: value_delta(i128) what is added to the locked value, negative to refund part of it
+        //argument: deadline_delta(i64) what is added to the deadline, negative to shorten it
+        // only the patron can propose a scope change, while the audit is assigned. A new proposal replaces the
+        // pending one, nothing is applied until the auditor accepts it with accept_scope_change.
+        #[ink(message)]
+        pub fn propose_scope_change(
+            &mut self,
+            id: u32,
+            metadata_hash: String,
+            value_delta: i128,
+            deadline_delta: i64,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self.get_paymentinfo(id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if payment_info.currentstatus != AuditStatus::AuditAssigned {
+                return Err(Error::WrongState);
+            }
+            let change = ScopeChange {
+                metadata_hash: metadata_hash.clone(),
+                value_delta,
+                deadline_delta,
+                proposed_at: self.env().block_timestamp(),
+                accepted_at: None,
+            };
+            self.audit_id_to_pending_scope_change.insert(id, &change);
+            self.env().emit_event(ScopeChangeProposed {
+                id,
+                metadata_hash,
+                value_delta,
+                deadline_delta,
+            });
+            Ok(())
+        }
+
+        //argument: id(u32) the assigned audit
+        // only the auditor can accept the pending scope change, while the audit is assigned. The value and
+        // deadline are adjusted at once, an increase of the value is pulled from the patron (who has to approve
+        // it first), a decrease is refunded to the patron and the sponsors like any other refund. The value
+        // can't go below the minimum audit value nor the deadline into the past. The change is added to the
+        // scope history of the audit, emits ScopeChangeAccepted and AuditInfoUpdated.
+        #[ink(message)]
+        pub fn accept_scope_change(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self.get_paymentinfo(id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.auditor {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if payment_info.currentstatus != AuditStatus::AuditAssigned {
+                return Err(Error::WrongState);
+            }
+            let mut change = self
+                .audit_id_to_pending_scope_change
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            let mut history = self.get_scope_changes(id);
+            if history.len() as u32 >= MAX_SCOPE_CHANGES {
+                return Err(Error::TooManyScopeChanges);
+            }
+            let new_value = payment_info
+                .value
+                .checked_add_signed(change.value_delta)
+                .ok_or(Error::InvalidArgument)?;
+            if new_value < Self::min_audit_value_for(self.token_decimals_of(id)) {
+                return Err(Error::ValueBelowMinimum);
+            }
+            let new_deadline = payment_info
+                .due_at
+                .ok_or(Error::WrongState)?
+                .checked_add_signed(change.deadline_delta)
+                .ok_or(Error::InvalidArgument)?;
+            if new_deadline <= self.env().block_timestamp() {
+                return Err(Error::DeadlinePassed);
+            }
+            let delta = change.value_delta.unsigned_abs();
+            if change.value_delta > 0 {
+                self.receive_tokens(id, payment_info.patron, delta)?;
+                payment_info.original_value += delta;
+            } else if delta > 0 {
+                //the sponsors' contributions shrink by what they get back, so later refunds stay pro-rata
+                for (receiver, share) in self.refund_shares(id, payment_info.patron, delta)? {
+                    if share == 0 {
+                        continue;
+                    }
+                    self.credit_payout(id, receiver, share)?;
+                    if receiver != payment_info.patron {
+                        let contribution = self.get_contribution(id, receiver) - share;
+                        self.audit_id_to_contributions
+                            .insert((id, receiver), &contribution);
+                    }
+                }
+                payment_info.original_value = payment_info.original_value.saturating_sub(delta);
+            }
+            payment_info.value = new_value;
+            payment_info.due_at = Some(new_deadline);
+            self.store_payment_info(id, &payment_info);
+            change.accepted_at = Some(self.env().block_timestamp());
+            history.push(change);
+            self.audit_id_to_scope_changes.insert(id, &history);
+            self.audit_id_to_pending_scope_change.remove(id);
+            self.env().emit_event(ScopeChangeAccepted {
+                id,
+                value: new_value,
+                deadline: new_deadline,
+            });
+            self.env().emit_event(AuditInfoUpdated {
+                id: Some(id),
+                payment_info: Some(payment_info),
+                updated_by: Some(self.env().caller()),
+            });
+            Ok(())
+        }
+
+        //read function that returns the scope change of an audit waiting for the auditor's acceptance
+        #[ink(message)]
+        pub fn get_pending_scope_change(&self, id: u32) -> Option<ScopeChange> {
+            self.audit_id_to_pending_scope_change.get(id)
+        }
+
+        //read function that returns what the escrow owes in a token, the value locked in audits that
+        //aren't completed or expired, the balance of the paymaster pots and what they owe, and what is
+        //credited to accounts and not withdrawn yet
+        #[ink(message)]
+        pub fn get_obligations(&self, token: AccountId) -> Balance {
+            self.obligations.get(token).unwrap_or_default()
+        }
+
+        //read function that returns the balance of the escrow in a token over what it owes in it, in basis
+        //points (10000 is exactly covered), None when it owes nothing in the token
+        #[ink(message)]
+        pub fn get_solvency_ratio(&self, token: AccountId) -> Result<Option<Balance>> {
+            let obligations = self.get_obligations(token);
+            if obligations == 0 {
+                return Ok(None);
+            }
+            let holdings = self.own_balance_of(token)?;
+            Ok(Some(holdings.saturating_mul(10000) / obligations))
+        }
+
+        //read function that returns the accepted scope changes of an audit, oldest first
+        #[ink(message)]
+        pub fn get_scope_changes(&self, id: u32) -> Vec<ScopeChange> {
+            self.audit_id_to_scope_changes.get(id).unwrap_or_default()
+        }
+
+        //argument: _id (u32) The audit Id for which ipfs hash will be submitted,
+        //argument: _ipfs_hash (String) the hash for the audit reports
+        // the function changes the state of payment_info's audit status, and inserts the ipfs hash for the corresponding id.
+        //event is emitted for AuditSubmitted, or AuditResubmitted for every later version of the report, every
+        //version is kept for get_submission. After a dispute the audit awaits the patron's review instead.
+        #[ink(message)]
+        pub fn mark_submitted(&mut self, _id: u32, _ipfs_hash: String) -> Result<()> {
+            self.ensure_not_receiving()?;
+            self.ensure_not_blacklisted(self.env().caller())?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(_id)
+                .ok_or(Error::InvalidArgument)?;
+            // matches!(payment_info.currentstatus, AuditStatus::AuditAssigned)
+            // && payment_info.deadline > self.env().block_timestamp()
+            if payment_info.auditor == self.env().caller() {
+                if matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                    if payment_info
+                        .due_at
+                        .is_some_and(|due_at| due_at > self.env().block_timestamp())
+                    {
+                        //the final report completes the audit once every intermediate checkpoint is settled
+                        if self
+                            .checkpoints_of(_id)
+                            .iter()
+                            .any(|checkpoint| checkpoint.status == CheckpointStatus::Pending)
+                        {
+                            return Err(Error::CheckpointsPending);
+                        }
+                        self.audit_id_to_ipfs_hash.insert(_id, &_ipfs_hash);
+                        let version = self.get_submission_count(_id) + 1;
+                        self.audit_id_to_submission_count.insert(_id, &version);
+                        self.submissions.insert((_id, version), &_ipfs_hash);
+                        self.audit_id_to_submitted_at
+                            .insert(_id, &self.env().block_timestamp());
+                        //the only way back to assigned after a dispute is an extension
+                        if self.get_dispute_count(_id) > 0 {
+                            payment_info.currentstatus = AuditStatus::AuditAwaitingPatronReview;
+                        } else {
+                            payment_info.currentstatus = AuditStatus::AuditSubmitted;
+                        }
+                        self.store_payment_info(_id, &payment_info);
+                        if version > 1 {
+                            self.env().emit_event(AuditResubmitted {
+                                id: _id,
+                                version,
+                                report_commitment: Self::report_commitment_of(_id, &_ipfs_hash),
+                            });
+                        } else {
+                            self.env().emit_event(AuditSubmitted {
+                                id: _id,
+                                report_commitment: Self::report_commitment_of(_id, &_ipfs_hash),
+                            });
+                        }
+                        self.notify_listeners(_id, AuditLifecycleEvent::Submitted);
+                        return Ok(());
+                    } else {
+                        return Err(Error::DeadlinePassed);
+                    }
+                } else {
+                    return Err(Error::WrongState);
+                }
+            }
+            Err(Error::UnAuthorisedCall)
+        }
+
+        //argument: id(u32) the audit id for assessment
+        //argument: answer (bool) if the caller is satisfied with audit report or not.
+        //broken down into three cases,
+        //C1: when patron calls,
+        //C2: when arbiterprovider calls,
+        //C3: when anything else happens
+        //C1 has two parts further, patron can only assess the audit if it is in submitted or awaiting patron review
+        //state, if patron says yes, then transfers happen, if no, then state is changed to disputed.
+        //C2 could have had two parts, and state should be disputed
+        // if true, transfer happens, if false, function sets the audit status to expired, and returns the tokens to patron.
+        //only then will the transfers happen.
+        #[ink(message)]
+        pub fn assess_audit(&mut self, _id: u32, answer: bool) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(_id)
+                .ok_or(Error::InvalidArgument)?;
+            let token = self.token_of(_id);
+            //C1
+            if self.env().caller() == payment_info.patron
+                && matches!(
+                    payment_info.currentstatus,
+                    AuditStatus::AuditSubmitted | AuditStatus::AuditAwaitingPatronReview
+                )
+            {
+                if answer {
+                    if self.requires_co_approval(&payment_info) {
+                        let mut approval = self
+                            .audit_id_to_co_approval
+                            .get(_id)
+                            .ok_or(Error::CoReviewerRequired)?;
+                        approval.patron_approved = true;
+                        self.audit_id_to_co_approval.insert(_id, &approval);
+                        if !approval.reviewer_approved {
+                            self.env().emit_event(CoApprovalRecorded {
+                                id: _id,
+                                approver: payment_info.patron,
+                            });
+                            return Ok(());
+                        }
+                    }
+                    return self.pay_out_approved(_id, payment_info);
+                } else {
+                    return self.open_dispute(_id, payment_info);
+                }
+            }
+            //C2
+            else if self.env().caller() == payment_info.arbiterprovider
+                && matches!(payment_info.currentstatus, AuditStatus::AuditDisputed)
+                && !self.is_escalated_to_governance(_id)
+            {
+                if answer {
+                    let split = math::split(payment_info.value, 95, 5);
+                    let retained = self.retention_of(_id, split.payee);
+                    let xyz = self.pay_auditor(_id, payment_info.auditor, split.payee - retained);
+
+                    let zyx =
+                        self.credit_tokens(token, payment_info.arbiterprovider, split.provider);
+                    let swept = self.sweep_dust(_id, split.remainder);
+
+                    if xyz.is_ok() && zyx.is_ok() && swept.is_ok() {
+                        self.env().emit_event(PaymentCredited {
+                            id: _id,
+                            receiver: payment_info.auditor,
+                            amount: split.payee - retained,
+                        });
+
+                        self.env().emit_event(PaymentCredited {
+                            id: _id,
+                            receiver: payment_info.arbiterprovider,
+                            amount: split.provider,
+                        });
+                        payment_info.value = split.payee;
+                        payment_info.paid_to_auditor += payment_info.value - retained;
+                        payment_info.currentstatus = AuditStatus::AuditCompleted;
+                        payment_info.fiat_value_at_completion =
+                            self.fiat_value_of(_id, payment_info.original_value);
+                        self.record_closing(_id);
+                        self.store_payment_info(_id, &payment_info);
+                        self.hold_retention(_id, retained);
+                        self.env().emit_event(AuditInfoUpdated {
+                            id: Some(_id),
+                            payment_info: self.audit_id_to_payment_info.get(_id),
+                            updated_by: Some(self.env().caller()),
+                        });
+                        self.refund_provider_gas(_id, payment_info.arbiterprovider);
+                        self.notify_listeners(_id, AuditLifecycleEvent::Completed);
+                        return Ok(());
+                    }
+                    return Err(Error::TransferFromContractFailed);
+                }
+                //if arbitersprovider is finally dissatisfied.
+                else {
+                    let split = math::split(payment_info.value, 95, 5);
+                    let xyz = self.refund_to_funders(_id, payment_info.patron, split.payee);
+                    let zyx =
+                        self.credit_tokens(token, payment_info.arbiterprovider, split.provider);
+                    let swept = self.sweep_dust(_id, split.remainder);
+                    if let (Ok(to_patron), Ok(()), Ok(())) = (xyz, zyx, swept) {
+                        self.env().emit_event(PaymentCredited {
+                            id: _id,
+                            receiver: payment_info.arbiterprovider,
+                            amount: split.provider,
+                        });
+                        payment_info.value = split.payee;
+                        payment_info.refunded_to_patron += to_patron;
+                        payment_info.currentstatus = AuditStatus::AuditExpired;
+                        self.store_payment_info(_id, &payment_info);
+                        self.env().emit_event(AuditInfoUpdated {
+                            id: Some(_id),
+                            payment_info: self.audit_id_to_payment_info.get(_id),
+                            updated_by: Some(self.env().caller()),
+                        });
+                        self.record_expiry(_id, ExpiryReason::ArbiterRejection);
+                        self.refund_provider_gas(_id, payment_info.arbiterprovider);
+                        self.notify_listeners(_id, AuditLifecycleEvent::Expired);
+                        return Ok(());
+                    }
+                    return Err(Error::TransferFromContractFailed);
+                }
+            }
+            //C3
+            Err(Error::UnAuthorisedCall)
+        }
+
+        //argument: id(u32) the audit id for assessment
+        //argument: answer (bool) if the caller is satisfied with audit report or not.
+        //argument: scorecard (Option<Scorecard>) the severity counts and quality score of the report
+        // same as assess_audit, for the patron and for the arbiter provider. The scorecard is only recorded
+        // by an assessment that closes the audit, completed or expired, not by one that opens a dispute or
+        // leaves the payout waiting for the co-reviewer. It is stored, and if a reward token is configured the
+        // token of the audit is minted to its auditor with the scorecard attached, a refused mint doesn't undo
+        // the assessment.
+        #[ink(message)]
+        pub fn assess_audit_with_scorecard(
+            &mut self,
+            id: u32,
+            answer: bool,
+            scorecard: Option<Scorecard>,
+        ) -> Result<()> {
+            if matches!(scorecard, Some(scorecard) if scorecard.quality > 100) {
+                return Err(Error::InvalidArgument);
+            }
+            self.assess_audit(id, answer)?;
+            let closed = self
+                .audit_id_to_payment_info
+                .get(id)
+                .is_some_and(|payment_info| {
+                    matches!(
+                        payment_info.currentstatus,
+                        AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+                    )
+                });
+            if let Some(scorecard) = scorecard.filter(|_| closed) {
+                self.audit_id_to_scorecard.insert(id, &scorecard);
+                let token_id = self.mint_with_scorecard(id, scorecard);
+                self.env().emit_event(ScorecardRecorded {
+                    id,
+                    scorecard,
+                    token_id,
+                });
+            }
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: reviewer(AccountId) the second account that signs off the payout
+        // only the patron can call it, once per audit and before it is closed, so a compromised patron key
+        // can't swap the reviewer. Audits whose original value reaches the co-approval threshold are only paid
+        // out once both the patron (in assess_audit) and the reviewer (in co_approve) approved the submission.
+        #[ink(message)]
+        pub fn set_co_reviewer(&mut self, id: u32, reviewer: AccountId) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if reviewer == payment_info.patron {
+                return Err(Error::InvalidArgument);
+            }
+            if matches!(
+                payment_info.currentstatus,
+                AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+            ) {
+                return Err(Error::WrongState);
+            }
+            if self.audit_id_to_co_approval.contains(id) {
+                return Err(Error::CoReviewerAlreadySet);
+            }
+            self.audit_id_to_co_approval.insert(
+                id,
+                &CoApproval {
+                    reviewer,
+                    patron_approved: false,
+                    reviewer_approved: false,
+                },
+            );
+            self.env().emit_event(CoReviewerSet { id, reviewer });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        // only the reviewer designated by the patron can call it, while the audit is submitted. If the patron
+        // already approved the submission it is paid out like in assess_audit, otherwise the approval is
+        // recorded and the payout happens when the patron approves.
+        #[ink(message)]
+        pub fn co_approve(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            let mut approval = self
+                .audit_id_to_co_approval
+                .get(id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if self.env().caller() != approval.reviewer {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if approval.reviewer_approved
+                || !matches!(
+                    payment_info.currentstatus,
+                    AuditStatus::AuditSubmitted | AuditStatus::AuditAwaitingPatronReview
+                )
+            {
+                return Err(Error::WrongState);
+            }
+            approval.reviewer_approved = true;
+            self.audit_id_to_co_approval.insert(id, &approval);
+            self.env().emit_event(CoApprovalRecorded {
+                id,
+                approver: approval.reviewer,
+            });
+            if approval.patron_approved {
+                return self.pay_out_approved(id, payment_info);
+            }
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID for extending deadline
+        //argument: new_deadline(Timestamp) the new deadline
+        //argument: haircut(Balance) the decided haircut for the auditor
+        //argument: arbitersshare(Balance) decided off-chain by the arbitersproivder and the arbiters according to their inputs
+        //and work put in for the audit ID.
+        // the function is only to be called by the assigned arbitersprovider that too when the auditStatus is disputed
+        // the haircut and arbitersshare should be less than 10%, and the deadline should be extended by at least 1 day.
+        // then the changes take place, haircut is given to patron, arbitersshare to the arbitersprovider, and payment_info is modified.
+        //events for PaymentCredited and AuditInfoUpdated are emitted.
+        #[ink(message)]
+        pub fn arbiters_extend_deadline(
+            &mut self,
+            _id: u32,
+            new_deadline: Timestamp,
+            haircut: Balance,
+            arbitersshare: Balance,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            //checking for the haircut to be lesser than 10% and new deadline to be at least more than 1 day.
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(_id)
+                .ok_or(Error::InvalidArgument)?;
+            let token = self.token_of(_id);
+            if haircut <= MAX_HAIRCUT
+                && new_deadline >= self.env().block_timestamp() + MIN_DEADLINE_EXTENSION
+                && self.env().caller() == payment_info.arbiterprovider
+                && arbitersshare <= MAX_ARBITERS_SHARE
+                && matches!(payment_info.currentstatus, AuditStatus::AuditDisputed)
+                && !self.is_escalated_to_governance(_id)
+            {
+                let arbitersscut: Balance = math::percent_of(payment_info.value, arbitersshare);
+                payment_info.value -= arbitersscut;
+                // the haircut is a percentage of the original value, so it doesn't compound with earlier ones
+                let haircutvalue: Balance = Self::haircut_of(&payment_info, haircut);
+                // Update the value in storage
+                payment_info.value -= haircutvalue;
+                payment_info.refunded_to_patron += haircutvalue;
+                // Update the deadline in storage
+                payment_info.due_at = Some(new_deadline);
+                payment_info.currentstatus = AuditStatus::AuditAssigned;
+
+                let xyz = self.credit_tokens(token, payment_info.arbiterprovider, arbitersscut);
+
+                let zyx = self.credit_tokens(token, payment_info.patron, haircutvalue);
+
+                //xyz.is_ok()
+                //removed from condition.
+                if zyx.is_ok() && xyz.is_ok() {
+                    self.env().emit_event(PaymentCredited {
+                        id: _id,
+                        receiver: payment_info.arbiterprovider,
+                        amount: arbitersscut,
+                    });
+                    self.env().emit_event(PaymentCredited {
+                        id: _id,
+                        receiver: payment_info.patron,
+                        amount: haircutvalue,
+                    });
+                    self.store_payment_info(_id, &payment_info);
+                    self.env().emit_event(AuditInfoUpdated {
+                        id: Some(_id),
+                        payment_info: self.audit_id_to_payment_info.get(_id),
+                        updated_by: Some(payment_info.patron),
+                    });
+                    self.refund_provider_gas(_id, payment_info.arbiterprovider);
+                    self.notify_listeners(_id, AuditLifecycleEvent::DeadlineExtended);
+                    return Ok(());
+                }
+            }
+            Err(Error::ArbitersExtendDeadlineConditionsNotMet)
+        }
+
+        //argument: id(u32) the audit ID to be retrieved
+        // the function can only be called by the patron, and only when the state is created or deadline has passed,
+        // WrongState otherwise. this updates the status of the audit, fires the event of PaymentCredited, returns the
+        // value to the patron, and to the sponsors pro-rata to what they contributed, if the audit had any. A refund
+        // that fails is returned as its own error.
+        #[ink(message)]
+        pub fn expire_audit(&mut self, _id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(_id)
+                .ok_or(Error::InvalidArgument)?;
+            let reason = if !matches!(payment_info.currentstatus, AuditStatus::AuditCreated) {
+                ExpiryReason::AuditorTimeout
+            } else if self.env().block_timestamp()
+                >= payment_info
+                    .starttime
+                    .saturating_add(payment_info.duration_ms)
+            {
+                ExpiryReason::Unassigned
+            } else {
+                ExpiryReason::PatronCancelled
+            };
+            if payment_info.patron != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !(matches!(payment_info.currentstatus, AuditStatus::AuditCreated)
+                || (matches!(payment_info.currentstatus, AuditStatus::AuditAssigned)
+                    && payment_info
+                        .due_at
+                        .is_some_and(|due_at| due_at <= self.env().block_timestamp())))
+            {
+                return Err(Error::WrongState);
+            }
+            self.settle_expiry(_id, payment_info, reason)
+        }
+
+        //argument: id(u32) the completed or expired audit
+        // only the patron or the admin can call it, once nothing is held back from the auditor anymore.
+        // the final accounting of the audit is stored and emitted in AuditClosed, it is the settlement record
+        // of the audit and is never changed again, archiving the audit keeps it too.
+        #[ink(message)]
+        pub fn close_audit(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self.get_paymentinfo(id).ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron && self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(
+                payment_info.currentstatus,
+                AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+            ) || self.audit_id_to_retention.contains(id)
+            {
+                return Err(Error::WrongState);
+            }
+            if self.audit_id_to_settlement.contains(id) {
+                return Err(Error::AuditAlreadyClosed);
+            }
+            let dust = self.audit_id_to_dust.get(id).unwrap_or_default();
+            let refunded_to_sponsors = self.get_refunded_to_sponsors(id);
+            let settlement = Settlement {
+                token: self.token_of(id),
+                locked: payment_info.original_value,
+                paid_to_auditor: payment_info.paid_to_auditor,
+                refunded_to_patron: payment_info.refunded_to_patron,
+                refunded_to_sponsors,
+                fees: payment_info
+                    .original_value
+                    .saturating_sub(payment_info.paid_to_auditor)
+                    .saturating_sub(payment_info.refunded_to_patron)
+                    .saturating_sub(refunded_to_sponsors)
+                    .saturating_sub(dust),
+                dust,
+                final_status: payment_info.currentstatus,
+                closed_at: self.env().block_timestamp(),
+            };
+            self.audit_id_to_settlement.insert(id, &settlement);
+            self.env().emit_event(AuditClosed { id, settlement });
+            Ok(())
+        }
+
+        //read function that returns the final accounting of a closed audit, None if it wasn't closed
+        #[ink(message)]
+        pub fn get_settlement(&self, id: u32) -> Option<Settlement> {
+            self.audit_id_to_settlement.get(id)
+        }
+
+        //argument: id(u32) the audit
+        // read function that returns the payout approving the audit would make right now, None once the audit
+        // is completed or expired. It changes with the parameters, e.g. the retention or the payout preference.
+        #[ink(message)]
+        pub fn payout_preview(&self, id: u32) -> Option<PayoutPreview> {
+            let payment_info = self.get_paymentinfo(id)?;
+            if matches!(
+                payment_info.currentstatus,
+                AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+            ) {
+                return None;
+            }
+            Some(self.preview_payout(id, &payment_info))
+        }
+
+        //argument: id(u32) the audit
+        // read function that returns the blake2x256 hash of the SCALE encoded payout_preview of the audit.
+        // Arbiters can reference it in their ruling, and once the audit is paid out anyone can compare it with
+        // get_executed_payout_commitment to prove the payout matched the previewed terms.
+        #[ink(message)]
+        pub fn payout_commitment(&self, id: u32) -> Option<[u8; 32]> {
+            self.payout_preview(id)
+                .map(|preview| Self::commitment_of(&preview))
+        }
+
+        //read function that returns the hash of the PayoutPreview an approved audit was paid out by
+        #[ink(message)]
+        pub fn get_executed_payout_commitment(&self, id: u32) -> Option<[u8; 32]> {
+            self.audit_id_to_payout_commitment.get(id)
+        }
+
+        //argument: id(u32) the audit ID to archive
+        // anyone, or only the admin and the OPERATOR once one is configured, can archive an audit that was
+        // completed or expired more than ARCHIVE_RETENTION_PERIOD ago, its summary is moved to the archived
+        // mapping and every other entry of the audit is removed, which gives back their storage deposit.
+        // Emits AuditArchived.
+        #[ink(message)]
+        pub fn archive_audit(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            self.ensure_housekeeper()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if !matches!(
+                payment_info.currentstatus,
+                AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+            ) {
+                return Err(Error::WrongState);
+            }
+            let closed_at = self
+                .audit_id_to_closed_at
+                .get(id)
+                .ok_or(Error::WrongState)?;
+            let now = self.env().block_timestamp();
+            if now < closed_at.saturating_add(ARCHIVE_RETENTION_PERIOD) {
+                return Err(Error::RetentionPeriodNotPassed);
+            }
+            //the auditor is still owed what was held back
+            if self.audit_id_to_retention.contains(id) {
+                return Err(Error::WrongState);
+            }
+            let summary = ArchivedAudit {
+                patron: payment_info.patron,
+                auditor: payment_info.auditor,
+                arbiterprovider: payment_info.arbiterprovider,
+                original_value: payment_info.original_value,
+                paid_to_auditor: payment_info.paid_to_auditor,
+                refunded_to_patron: payment_info.refunded_to_patron,
+                final_status: payment_info.currentstatus,
+                expiry_reason: self.audit_id_to_expiry_reason.get(id),
+                scorecard: self.audit_id_to_scorecard.get(id),
+                closed_at,
+                archived_at: now,
+                token: self.token_of(id),
+            };
+            for sponsor in self.get_sponsors(id) {
+                self.audit_id_to_contributions.remove((id, sponsor));
+            }
+            self.audit_id_to_sponsors.remove(id);
+            self.unindex_status(id, payment_info.currentstatus);
+            self.audit_id_to_payment_info.remove(id);
+            self.audit_id_to_time_increase_request.remove(id);
+            self.audit_id_to_ipfs_hash.remove(id);
+            for version in 1..=self.get_submission_count(id) {
+                self.submissions.remove((id, version));
+            }
+            self.audit_id_to_submission_count.remove(id);
+            self.audit_id_to_dispute_count.remove(id);
+            self.audit_id_to_governance_escalation.remove(id);
+            self.audit_id_to_message_anchors.remove(id);
+            self.audit_id_to_panel.remove(id);
+            self.audit_id_to_expiry_reason.remove(id);
+            self.audit_id_to_scorecard.remove(id);
+            self.audit_id_to_closed_at.remove(id);
+            self.audit_id_to_deadline_warned.remove(id);
+            self.audit_id_to_token.remove(id);
+            self.audit_id_to_worklog.remove(id);
+            self.audit_id_to_co_approval.remove(id);
+            self.audit_id_to_checkpoints.remove(id);
+            self.audit_id_to_submitted_at.remove(id);
+            self.audit_id_to_dispute_charge.remove(id);
+            self.audit_id_to_declined_arbitration.remove(id);
+            self.audit_id_to_pending_scope_change.remove(id);
+            self.audit_id_to_scope_changes.remove(id);
+            self.audit_id_to_retention_percentage.remove(id);
+            self.audit_id_to_payout_preference.remove(id);
+            self.audit_id_to_dust.remove(id);
+            self.audit_id_to_refunded_to_sponsors.remove(id);
+            self.archived.insert(id, &summary);
+            self.env().emit_event(AuditArchived { id, summary });
+            Ok(())
+        }
+
+        //argument: ids(Vec<u32>) the audit IDs to check
+        // heartbeat for keeper bots, permissionless unless an OPERATOR is configured, it performs the time based
+        // transitions that are due for the given audits, i.e. expires assigned audits whose deadline has passed
+        // and refunds them, applies the silence policy of reports the patron left unreviewed for
+        // PATRON_REVIEW_WINDOW, releases due retentions, and returns what was done for each ID in the same order.
+        // A transition that fails, e.g. an auto-dispute whose bond can't be taken, is reported as Failed for its
+        // ID and leaves the audit unchanged, the other IDs of the batch are still processed.
+        #[ink(message)]
+        pub fn process_due(&mut self, ids: Vec<u32>) -> Result<Vec<DueOutcome>> {
+            self.ensure_not_receiving()?;
+            self.ensure_housekeeper()?;
+            if ids.len() as u32 > MAX_PROCESS_BATCH {
+                return Err(Error::InvalidArgument);
+            }
+            let now = self.env().block_timestamp();
+            let mut outcomes = Vec::new();
+            for id in ids {
+                let outcome = match self.audit_id_to_payment_info.get(id) {
+                    None => DueOutcome::NotFound,
+                    Some(payment_info)
+                        if matches!(payment_info.currentstatus, AuditStatus::AuditAssigned)
+                            && payment_info.due_at.is_some_and(|due_at| due_at <= now) =>
+                    {
+                        match self.settle_expiry(id, payment_info, ExpiryReason::AuditorTimeout) {
+                            Ok(()) => DueOutcome::Expired,
+                            Err(error) => DueOutcome::Failed(error),
+                        }
+                    }
+                    Some(payment_info) if self.is_review_overdue(id, &payment_info, now) => {
+                        match payment_info.silence_policy {
+                            //a co-reviewer still has to approve for themselves
+                            SilencePolicy::AutoApprove
+                                if !self.awaits_co_reviewer(id, &payment_info) =>
+                            {
+                                match self.pay_out_approved(id, payment_info) {
+                                    Ok(()) => DueOutcome::AutoApproved,
+                                    Err(error) => DueOutcome::Failed(error),
+                                }
+                            }
+                            SilencePolicy::AutoDispute => {
+                                match self.open_dispute(id, payment_info) {
+                                    Ok(()) => DueOutcome::AutoDisputed,
+                                    Err(error) => DueOutcome::Failed(error),
+                                }
+                            }
+                            _ => DueOutcome::NothingDue,
+                        }
+                    }
+                    Some(payment_info) => match self.audit_id_to_retention.get(id) {
+                        Some(retention) if retention.releasable_at <= now => {
+                            match self.pay_retention(id, payment_info, retention) {
+                                Ok(()) => DueOutcome::RetentionReleased,
+                                Err(error) => DueOutcome::Failed(error),
+                            }
+                        }
+                        _ => DueOutcome::NothingDue,
+                    },
+                };
+                outcomes.push(outcome);
+            }
+            Ok(outcomes)
+        }
+
+        //argument: ids(Vec<u32>) the audit IDs to check
+        // for keeper bots like process_due and restricted the same way. Emits DeadlineApproaching for every
+        // assigned audit whose deadline is within the deadline warning window and hasn't passed yet, once per
+        // audit, and returns the IDs that were warned about.
+        #[ink(message)]
+        pub fn emit_deadline_warnings(&mut self, ids: Vec<u32>) -> Result<Vec<u32>> {
+            self.ensure_not_receiving()?;
+            self.ensure_housekeeper()?;
+            if ids.len() as u32 > MAX_PROCESS_BATCH {
+                return Err(Error::InvalidArgument);
+            }
+            let now = self.env().block_timestamp();
+            let mut warned = Vec::new();
+            for id in ids {
+                let Some(payment_info) = self.audit_id_to_payment_info.get(id) else {
+                    continue;
+                };
+                let due_at = payment_info.due_at.unwrap_or_default();
+                if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned)
+                    || due_at <= now
+                    || self.audit_id_to_deadline_warned.get(id).unwrap_or_default()
+                {
+                    continue;
+                }
+                let remaining_ms = due_at - now;
+                if remaining_ms > self.deadline_warning_window {
+                    continue;
+                }
+                self.audit_id_to_deadline_warned.insert(id, &true);
+                self.env()
+                    .emit_event(DeadlineApproaching { id, remaining_ms });
+                warned.push(id);
+            }
+            Ok(warned)
+        }
+
+        // reads the decimals of the stablecoin again and stores them, so that the minimum audit value is one
+        // whole token. Anyone can call it since it only mirrors the token's own metadata.
+        #[ink(message)]
+        pub fn sync_token_decimals(&mut self) -> Result<u8> {
+            self.ensure_not_receiving()?;
+            let decimals = Self::decimals_of(self.stablecoin_address)?;
+            self.token_decimals = decimals;
+            Ok(decimals)
+        }
+
+        //argument: id(u32) the disputed audit ID
+        //argument: arbiters(Vec<AccountId>) the arbiters voting on the dispute
+        // only the arbiter provider of the audit can register the panel, and only while the audit is
+        // disputed. The panel can read the report hash until the dispute is resolved.
+        #[ink(message)]
+        pub fn register_panel(&mut self, id: u32, arbiters: Vec<AccountId>) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.arbiterprovider {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditDisputed) {
+                return Err(Error::WrongState);
+            }
+            if arbiters.len() as u32 > MAX_PANEL_SIZE {
+                return Err(Error::InvalidArgument);
+            }
+            self.audit_id_to_panel.insert(id, &arbiters);
+            self.refund_provider_gas(id, payment_info.arbiterprovider);
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID the message is about
+        //argument: ipfs_hash(String) the hash of the off-chain message
+        // only the patron, auditor and arbiter provider of an audit can anchor messages to it, up to
+        // MAX_ANCHORS_PER_PARTY each, and only until it is completed or expired. The hash can't be longer than
+        // MAX_ANCHOR_HASH_LENGTH. Anchors are append only, giving a tamper-evident trail.
+        #[ink(message)]
+        pub fn anchor_message(&mut self, id: u32, ipfs_hash: String) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            let author = self.env().caller();
+            if author != payment_info.patron
+                && author != payment_info.auditor
+                && author != payment_info.arbiterprovider
+            {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if matches!(
+                payment_info.currentstatus,
+                AuditStatus::AuditCompleted | AuditStatus::AuditExpired
+            ) {
+                return Err(Error::WrongState);
+            }
+            if ipfs_hash.is_empty() || ipfs_hash.len() as u32 > MAX_ANCHOR_HASH_LENGTH {
+                return Err(Error::InvalidArgument);
+            }
+            let mut anchors = self.get_message_anchors(id);
+            let anchored_by_author = anchors
+                .iter()
+                .filter(|anchor| anchor.author == author)
+                .count() as u32;
+            if anchored_by_author >= MAX_ANCHORS_PER_PARTY {
+                return Err(Error::TooManyAnchors);
+            }
+            anchors.push(MessageAnchor {
+                author,
+                ipfs_hash: ipfs_hash.clone(),
+                anchored_at: self.env().block_timestamp(),
+            });
+            self.audit_id_to_message_anchors.insert(id, &anchors);
+            self.env().emit_event(MessageAnchored {
+                id,
+                author,
+                ipfs_hash,
+            });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: policy(SilencePolicy) what process_due does with a report the patron leaves unreviewed
+        // only the patron can call it, while the audit is pending funding or isn't assigned, so the auditor knows
+        // the policy when accepting the job. Emits SilencePolicySet.
+        #[ink(message)]
+        pub fn set_silence_policy(&mut self, id: u32, policy: SilencePolicy) -> Result<()> {
+            self.ensure_not_receiving()?;
+            //the audit is created with the policy once its funding is finalized
+            if let Some(mut pending) = self.audit_id_to_pending_funding.get(id) {
+                if self.env().caller() != pending.patron {
+                    return Err(Error::UnAuthorisedCall);
+                }
+                pending.silence_policy = policy;
+                self.audit_id_to_pending_funding.insert(id, &pending);
+                self.env().emit_event(SilencePolicySet { id, policy });
+                return Ok(());
+            }
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditCreated) {
+                return Err(Error::WrongState);
+            }
+            payment_info.silence_policy = policy;
+            self.store_payment_info(id, &payment_info);
+            self.env().emit_event(SilencePolicySet { id, policy });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: reason_hash([u8; 32]) the hash of why the arbiter provider declines the case
+        // only the arbiter provider can call it, while the audit is disputed and not escalated to governance, e.g.
+        // for a case outside their expertise. What the patron paid to open the dispute is given back, the bond is
+        // taken from what the escrow credits the provider first, only the shortfall is pulled from them (who has
+        // to approve it), and credited to the patron. The audit goes back to
+        // AuditSubmitted with a new review window, and can't be disputed to the same provider again, the patron
+        // can nominate the fallback provider instead. Emits ArbitrationDeclined.
+        #[ink(message)]
+        pub fn decline_arbitration(&mut self, id: u32, reason_hash: [u8; 32]) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.arbiterprovider {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if payment_info.currentstatus != AuditStatus::AuditDisputed
+                || self.is_escalated_to_governance(id)
+            {
+                return Err(Error::WrongState);
+            }
+            let refunded = match self.audit_id_to_dispute_charge.take(id) {
+                Some(DisputeCharge::Bond(bond)) => {
+                    let token = self.token_of(id);
+                    let debited = self.debit_credit(token, payment_info.arbiterprovider, bond);
+                    if debited < bond {
+                        self.pull_tokens(token, payment_info.arbiterprovider, bond - debited)
+                            .map_err(|_| Error::DisputeBondFailed)?;
+                    }
+                    self.credit_tokens(token, payment_info.patron, bond)?;
+                    bond
+                }
+                Some(DisputeCharge::Voucher) => {
+                    let vouchers = self.get_waiver_vouchers(payment_info.patron);
+                    self.waiver_vouchers
+                        .insert(payment_info.patron, &(vouchers + 1));
+                    0
+                }
+                None => 0,
+            };
+            //the declined dispute doesn't count toward the appeals before governance
+            let disputes = self.get_dispute_count(id).saturating_sub(1);
+            self.audit_id_to_dispute_count.insert(id, &disputes);
+            self.audit_id_to_declined_arbitration.insert(
+                id,
+                &DeclinedArbitration {
+                    provider: payment_info.arbiterprovider,
+                    reason_hash,
+                    declined_at: self.env().block_timestamp(),
+                },
+            );
+            self.audit_id_to_submitted_at
+                .insert(id, &self.env().block_timestamp());
+            payment_info.currentstatus = AuditStatus::AuditSubmitted;
+            self.store_payment_info(id, &payment_info);
+            self.env().emit_event(ArbitrationDeclined {
+                id,
+                provider: payment_info.arbiterprovider,
+                reason_hash,
+                refunded,
+            });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        // only the patron can call it, for an audit whose arbiter provider declined its dispute. The fallback
+        // arbiter provider becomes the provider of the audit, which can be disputed again.
+        // Emits FallbackProviderNominated.
+        #[ink(message)]
+        pub fn nominate_fallback_provider(&mut self, id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let declined = self
+                .audit_id_to_declined_arbitration
+                .get(id)
+                .ok_or(Error::WrongState)?;
+            let provider = self
+                .fallback_arbiter_provider
+                .filter(|provider| *provider != declined.provider)
+                .ok_or(Error::NoFallbackProvider)?;
+            self.audit_id_to_declined_arbitration.remove(id);
+            payment_info.arbiterprovider = provider;
+            self.store_payment_info(id, &payment_info);
+            self.env()
+                .emit_event(FallbackProviderNominated { id, provider });
+            Ok(())
+        }
+
+        //read function that returns who declined the dispute of the audit and why, until the patron nominates
+        //the fallback provider
+        #[ink(message)]
+        pub fn get_declined_arbitration(&self, id: u32) -> Option<DeclinedArbitration> {
+            self.audit_id_to_declined_arbitration.get(id)
+        }
+
+        //read function that returns when the latest report of the audit was submitted
+        #[ink(message)]
+        pub fn get_submitted_at(&self, id: u32) -> Option<Timestamp> {
+            self.audit_id_to_submitted_at.get(id)
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: offsets(Vec<Timestamp>) when each intermediate checkpoint is due, counted from the assignment
+        // only the patron can call it, while the audit isn't assigned. The offsets have to be increasing, shorter
+        // than the deadline of the audit and at most MAX_CHECKPOINTS, an empty list removes the checkpoints.
+        #[ink(message)]
+        pub fn set_checkpoints(&mut self, id: u32, offsets: Vec<Timestamp>) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditCreated) {
+                return Err(Error::WrongState);
+            }
+            if offsets.len() as u32 > MAX_CHECKPOINTS
+                || offsets.first() == Some(&0)
+                || offsets.windows(2).any(|pair| pair[0] >= pair[1])
+                || offsets
+                    .last()
+                    .is_some_and(|last| *last >= payment_info.duration_ms)
+            {
+                return Err(Error::InvalidArgument);
+            }
+            let checkpoints: Vec<Checkpoint> = offsets
+                .iter()
+                .map(|offset| Checkpoint {
+                    offset: *offset,
+                    ipfs_hash: None,
+                    submitted_at: None,
+                    status: CheckpointStatus::Pending,
+                })
+                .collect();
+            if checkpoints.is_empty() {
+                self.audit_id_to_checkpoints.remove(id);
+            } else {
+                self.audit_id_to_checkpoints.insert(id, &checkpoints);
+            }
+            self.env().emit_event(CheckpointsSet { id, offsets });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: index(u32) the position of the checkpoint
+        //argument: ipfs_hash(String) the report of the checkpoint
+        // only the auditor can call it, while the audit is assigned and before the checkpoint is due.
+        // A checkpoint whose report the patron rejected can be submitted again until it is due.
+        #[ink(message)]
+        pub fn submit_checkpoint(&mut self, id: u32, index: u32, ipfs_hash: String) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.auditor {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                return Err(Error::WrongState);
+            }
+            let mut checkpoints = self.checkpoints_of(id);
+            let now = self.env().block_timestamp();
+            let checkpoint = checkpoints
+                .get_mut(index as usize)
+                .ok_or(Error::InvalidArgument)?;
+            if checkpoint.status != CheckpointStatus::Pending {
+                return Err(Error::WrongState);
+            }
+            if now > payment_info.starttime.saturating_add(checkpoint.offset) {
+                return Err(Error::CheckpointOverdue);
+            }
+            let report_commitment = Self::report_commitment_of(id, &ipfs_hash);
+            checkpoint.ipfs_hash = Some(ipfs_hash);
+            checkpoint.submitted_at = Some(now);
+            checkpoint.status = CheckpointStatus::Submitted;
+            self.audit_id_to_checkpoints.insert(id, &checkpoints);
+            self.env().emit_event(CheckpointSubmitted {
+                id,
+                index,
+                report_commitment,
+            });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: index(u32) the position of the checkpoint
+        //argument: answer(bool) if the patron is satisfied with the report of the checkpoint or not
+        // only the patron can call it, for a submitted checkpoint. A rejected one is pending again.
+        #[ink(message)]
+        pub fn assess_checkpoint(&mut self, id: u32, index: u32, answer: bool) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.patron {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let mut checkpoints = self.checkpoints_of(id);
+            let checkpoint = checkpoints
+                .get_mut(index as usize)
+                .ok_or(Error::InvalidArgument)?;
+            if checkpoint.status != CheckpointStatus::Submitted {
+                return Err(Error::WrongState);
+            }
+            checkpoint.status = if answer {
+                CheckpointStatus::Accepted
+            } else {
+                CheckpointStatus::Pending
+            };
+            self.audit_id_to_checkpoints.insert(id, &checkpoints);
+            self.env().emit_event(CheckpointAssessed {
+                id,
+                index,
+                accepted: answer,
+            });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID
+        //argument: index(u32) the position of the checkpoint
+        // anyone can call it once a pending checkpoint of an assigned audit is past due. Instead of expiring the
+        // audit, the checkpoint haircut of the original value is refunded to the funders and the audit goes on.
+        #[ink(message)]
+        pub fn miss_checkpoint(&mut self, id: u32, index: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                return Err(Error::WrongState);
+            }
+            let mut checkpoints = self.checkpoints_of(id);
+            let checkpoint = checkpoints
+                .get_mut(index as usize)
+                .ok_or(Error::InvalidArgument)?;
+            if checkpoint.status != CheckpointStatus::Pending
+                || self.env().block_timestamp()
+                    <= payment_info.starttime.saturating_add(checkpoint.offset)
+            {
+                return Err(Error::WrongState);
+            }
+            checkpoint.status = CheckpointStatus::Missed;
+            let haircut = Self::haircut_of(&payment_info, self.checkpoint_haircut);
+            payment_info.refunded_to_patron +=
+                self.refund_to_funders(id, payment_info.patron, haircut)?;
+            payment_info.value -= haircut;
+            self.store_payment_info(id, &payment_info);
+            self.audit_id_to_checkpoints.insert(id, &checkpoints);
+            self.env()
+                .emit_event(CheckpointMissed { id, index, haircut });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID the work is for
+        //argument: hash(Hash) the hash of the auditor's off-chain record of their progress
+        // only the auditor can commit to their work, and only while the audit is assigned to them,
+        // up to MAX_WORKLOG_ENTRIES times. Every commitment is timestamped and the list is append only.
+        #[ink(message)]
+        pub fn commit_worklog(&mut self, id: u32, hash: Hash) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if self.env().caller() != payment_info.auditor {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
+                return Err(Error::WrongState);
+            }
+            let mut worklog = self.get_worklog(id);
+            if worklog.len() as u32 >= MAX_WORKLOG_ENTRIES {
+                return Err(Error::TooManyWorklogEntries);
+            }
+            worklog.push(WorklogEntry {
+                hash,
+                committed_at: self.env().block_timestamp(),
+            });
+            self.audit_id_to_worklog.insert(id, &worklog);
+            self.env().emit_event(WorklogCommitted { id, hash });
+            Ok(())
+        }
+
+        //argument: callee (AccountId) the contract the escrow calls, e.g. the stablecoin or governance
+        #[ink(message)]
+        pub fn get_call_config(&self, callee: AccountId) -> CallConfig {
+            let configured = self.callee_gas_limits.get(callee);
+            CallConfig {
+                callee,
+                gas_limit: configured.unwrap_or(self.default_gas_limit_for(callee)),
+                is_default: configured.is_none(),
+            }
+        }
+
+        //argument: amount (Balance) how much to add to the pot, pre-approved for the escrow
+        // an arbiter provider opts into the paymaster by funding their pot, the first funding fixes its token
+        // to the default stablecoin. Emits PaymasterFunded.
+        #[ink(message)]
+        pub fn fund_paymaster(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let provider = self.env().caller();
+            let mut pot = self.paymaster_pots.get(provider).unwrap_or(PaymasterPot {
+                token: self.stablecoin_address,
+                balance: 0,
+                owed: 0,
+            });
+            pot.balance = pot
+                .balance
+                .checked_add(amount)
+                .ok_or(Error::InvalidArgument)?;
+            self.pull_tokens(pot.token, provider, amount)?;
+            self.paymaster_pots.insert(provider, &pot);
+            self.add_obligation(pot.token, amount);
+            self.env().emit_event(PaymasterFunded { provider, amount });
+            Ok(())
+        }
+
+        // the arbiter provider is paid what they are owed from their pot.
+        #[ink(message)]
+        pub fn claim_gas_refunds(&mut self) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let provider = self.env().caller();
+            let mut pot = self
+                .paymaster_pots
+                .get(provider)
+                .ok_or(Error::NoPaymasterPot)?;
+            if pot.owed == 0 {
+                return Err(Error::InsufficientBalance);
+            }
+            let owed = pot.owed;
+            pot.owed = 0;
+            self.paymaster_pots.insert(provider, &pot);
+            self.remove_obligation(pot.token, owed);
+            self.credit_tokens(pot.token, provider, owed)
+        }
+
+        // the arbiter provider opts out of the paymaster, what is left in the pot and what they are owed
+        // is paid back to them and the pot is closed.
+        #[ink(message)]
+        pub fn close_paymaster(&mut self) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let provider = self.env().caller();
+            let pot = self
+                .paymaster_pots
+                .take(provider)
+                .ok_or(Error::NoPaymasterPot)?;
+            let total = pot.balance + pot.owed;
+            if total > 0 {
+                self.remove_obligation(pot.token, total);
+                self.credit_tokens(pot.token, provider, total)?;
+            }
+            Ok(())
+        }
+
+        //argument: token(AccountId) the token to check
+        // anyone can call it, it compares what the escrow owes in the token, the value locked in open audits and
+        // the paymaster pots, with its balance of the token. If it holds less, e.g. after an admin flush, it emits
+        // SolvencyWarning. Returns the shortfall, 0 when the escrow is solvent.
+        #[ink(message)]
+        pub fn check_solvency(&mut self, token: AccountId) -> Result<Balance> {
+            let obligations = self.get_obligations(token);
+            if obligations == 0 {
+                return Ok(0);
+            }
+            let shortfall = obligations.saturating_sub(self.own_balance_of(token)?);
+            if shortfall > 0 {
+                self.env().emit_event(SolvencyWarning { token, shortfall });
+            }
+            Ok(shortfall)
+        }
+
+        // transfers everything credited to the caller to them, token by token, and returns what was
+        // withdrawn in each token. A token whose transfer fails keeps its credit and emits WithdrawalFailed,
+        // so one token can't hold up the others. Emits TokenOutgoing for every token withdrawn.
+        #[ink(message)]
+        pub fn withdraw(&mut self) -> Result<Vec<(AccountId, Balance)>> {
+            self.ensure_not_receiving()?;
+            let account = self.env().caller();
+            let tokens = self
+                .account_to_credit_tokens
+                .take(account)
+                .ok_or(Error::NothingToWithdraw)?;
+            let mut withdrawn = Vec::new();
+            let mut kept = Vec::new();
+            for token in tokens {
+                //the credit is cleared before the transfer, so that it can't be withdrawn twice
+                let amount = self.credits.take((account, token)).unwrap_or_default();
+                self.remove_obligation(token, amount);
+                if self.psp22(token).transfer(account, amount).is_ok() {
+                    self.env().emit_event(TokenOutgoing {
+                        account,
+                        token,
+                        amount,
+                    });
+                    withdrawn.push((token, amount));
+                } else {
+                    self.credits.insert((account, token), &amount);
+                    self.add_obligation(token, amount);
+                    self.env().emit_event(WithdrawalFailed {
+                        account,
+                        token,
+                        amount,
+                    });
+                    kept.push(token);
+                }
+            }
+            if !kept.is_empty() {
+                self.account_to_credit_tokens.insert(account, &kept);
+            }
+            Ok(withdrawn)
+        }
+
+        //read function that returns what is credited to the account in the token and not withdrawn yet
+        #[ink(message)]
+        pub fn get_credit(&self, account: AccountId, token: AccountId) -> Balance {
+            self.credits.get((account, token)).unwrap_or_default()
+        }
+
+        //read function that returns the tokens the account has credits in
+        #[ink(message)]
+        pub fn get_credit_tokens(&self, account: AccountId) -> Vec<AccountId> {
+            self.account_to_credit_tokens
+                .get(account)
+                .unwrap_or_default()
+        }
+
+        //argument: account (AccountId) the patron the vouchers are for
+        //argument: count (u32) how many vouchers to add to the ones the account has
+        // only the admin can mint vouchers, e.g. for plan-based pricing or goodwill credits. Each one lets the
+        // patron dispute a submission without paying the dispute bond, and is used up by assess_audit.
+        #[ink(message)]
+        pub fn mint_waiver_vouchers(&mut self, account: AccountId, count: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let vouchers = self
+                .get_waiver_vouchers(account)
+                .checked_add(count)
+                .ok_or(Error::InvalidArgument)?;
+            self.waiver_vouchers.insert(account, &vouchers);
+            self.env()
+                .emit_event(WaiverVouchersMinted { account, count });
+            Ok(())
+        }
+
+        //argument: account (AccountId) the account found to be fraudulent, e.g. for plagiarized reports
+        // only the admin can blacklist, a blacklisted account can't create audits, be assigned one, assign one
+        // or submit a report. Audits it is already part of can still be settled.
+        #[ink(message)]
+        pub fn blacklist(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.blacklisted.insert(account, &());
+            self.env().emit_event(AccountBlacklisted { account });
+            Ok(())
+        }
+
+        //argument: operator (Option<AccountId>) the hot key of the platform backend, None to remove it
+        // only the admin can call it, the change is immediate so that a leaked key can be revoked right away.
+        // The OPERATOR can only emit deadline warnings, process due audits and archive audits, and once it is
+        // configured nobody else but the admin can. It can't touch payouts or parameters. Emits OperatorChanged.
+        #[ink(message)]
+        pub fn set_operator(&mut self, operator: Option<AccountId>) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.operator = operator;
+            self.env().emit_event(OperatorChanged { operator });
+            Ok(())
+        }
+
+        //argument: account (AccountId) the account to let back in
+        #[ink(message)]
+        pub fn unblacklist(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !self.is_blacklisted(account) {
+                return Err(Error::InvalidArgument);
+            }
+            self.blacklisted.remove(account);
+            self.env().emit_event(AccountUnblacklisted { account });
+            Ok(())
+        }
+
+        //argument: change (ParamChange) the parameter and its new value
+        // only the admin or the governance contract can queue a change, it can be executed once the param change
+        // delay has passed, so that users can see it coming. Emits ParamChangeQueued and returns the change ID.
+        #[ink(message)]
+        pub fn queue_param_change(&mut self, change: ParamChange) -> Result<u32> {
+            self.ensure_not_receiving()?;
+            if !self.can_change_params(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if matches!(&change, ParamChange::MinDeadlineTiers(tiers) if tiers.len() as u32 > MAX_DEADLINE_TIERS)
+                || matches!(change, ParamChange::CheckpointHaircut(haircut) if haircut > 90)
+            {
+                return Err(Error::InvalidArgument);
+            }
+            let change_id = self.next_param_change_id;
+            let executable_at = self
+                .env()
+                .block_timestamp()
+                .saturating_add(self.param_change_delay);
+            self.pending_param_changes
+                .insert(change_id, &(change.clone(), executable_at));
+            self.next_param_change_id = change_id + 1;
+            self.env().emit_event(ParamChangeQueued {
+                change_id,
+                change,
+                executable_at,
+            });
+            Ok(change_id)
+        }
+
+        //argument: token (AccountId) the PSP22 token new audits are created with
+        // queues ParamChange::DefaultStablecoin, its decimals are read when it is executed. Audits already created
+        // keep paying out in the token they were funded with. Returns the change ID.
+        #[ink(message)]
+        pub fn set_default_stablecoin(&mut self, token: AccountId) -> Result<u32> {
+            self.queue_param_change(ParamChange::DefaultStablecoin(token))
+        }
+
+        //argument: change_id (u32) the queued change
+        // anyone can execute a queued change once its delay has passed, it was authorized when it was queued.
+        #[ink(message)]
+        pub fn execute_param_change(&mut self, change_id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            let (change, executable_at) = self
+                .pending_param_changes
+                .get(change_id)
+                .ok_or(Error::NoPendingChange)?;
+            if self.env().block_timestamp() < executable_at {
+                return Err(Error::TimelockNotElapsed);
+            }
+            self.pending_param_changes.remove(change_id);
+            match change.clone() {
+                ParamChange::Treasury(treasury) => self.treasury = treasury,
+                ParamChange::PriceOracle(oracle) => self.price_oracle = oracle,
+                ParamChange::GovernanceAddress(governance) => self.governance_address = governance,
+                ParamChange::GasLimit(callee, Some(gas_limit)) => {
+                    self.callee_gas_limits.insert(callee, &gas_limit);
+                }
+                ParamChange::GasLimit(callee, None) => self.callee_gas_limits.remove(callee),
+                ParamChange::DeadlineWarningWindow(window) => self.deadline_warning_window = window,
+                ParamChange::TimelockDelay(delay) => self.param_change_delay = delay,
+                ParamChange::MinDeadlineTiers(tiers) => self.min_deadline_tiers = tiers,
+                ParamChange::DefaultStablecoin(token) => {
+                    self.token_decimals = Self::probe_stablecoin(token)?;
+                    self.stablecoin_address = token;
+                }
+                ParamChange::CoApprovalThreshold(threshold) => {
+                    self.co_approval_threshold = threshold
+                }
+                ParamChange::DisputeBond(bond) => self.dispute_bond = bond,
+                ParamChange::CheckpointHaircut(haircut) => self.checkpoint_haircut = haircut,
+                ParamChange::GasRefundPerCall(amount) => self.gas_refund_per_call = amount,
+                ParamChange::DexRouter(router) => self.dex_router = router,
+                ParamChange::PayoutToken(token, true) => {
+                    self.payout_tokens.insert(token, &true);
+                }
+                ParamChange::PayoutToken(token, false) => self.payout_tokens.remove(token),
+                ParamChange::RoundingPolicy(policy) => self.rounding_policy = policy,
+                ParamChange::RewardToken(reward_token) => self.reward_token = reward_token,
+                ParamChange::FallbackArbiterProvider(provider) => {
+                    self.fallback_arbiter_provider = provider
+                }
+            }
+            self.env()
+                .emit_event(ParamChangeExecuted { change_id, change });
+            Ok(())
+        }
+
+        //argument: change_id (u32) the queued change
+        // the admin or the governance contract can drop a queued change before it is executed.
+        #[ink(message)]
+        pub fn cancel_param_change(&mut self, change_id: u32) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if !self.can_change_params(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.pending_param_changes.get(change_id).is_none() {
+                return Err(Error::NoPendingChange);
+            }
+            self.pending_param_changes.remove(change_id);
+            self.env().emit_event(ParamChangeCancelled { change_id });
+            Ok(())
+        }
+
+        //argument: id(u32) the audit ID that was escalated to governance
+        //argument: ruling(GovernanceRuling) the final decision of governance on the dispute
+        // only the governance contract can call it, and only for audits that were escalated to it.
+        // Approve and Reject split the value the same way an arbiter provider's decision does,
+        // ExtendDeadline sends the haircut back to the patron and reassigns the audit with the new deadline.
+        #[ink(message)]
+        pub fn execute_governance_ruling(
+            &mut self,
+            id: u32,
+            ruling: GovernanceRuling,
+        ) -> Result<()> {
+            self.ensure_not_receiving()?;
+            if self.governance_address != Some(self.env().caller()) {
                 return Err(Error::UnAuthorisedCall);
             }
+            let mut payment_info = self
+                .audit_id_to_payment_info
+                .get(id)
+                .ok_or(Error::InvalidArgument)?;
+            if !self.is_escalated_to_governance(id)
+                || !matches!(payment_info.currentstatus, AuditStatus::AuditDisputed)
+            {
+                return Err(Error::WrongState);
+            }
+            match ruling {
+                GovernanceRuling::Approve => {
+                    let split = math::split(payment_info.value, 95, 5);
+                    let retained = self.retention_of(id, split.payee);
+                    self.pay_auditor(id, payment_info.auditor, split.payee - retained)?;
+                    self.env().emit_event(PaymentCredited {
+                        id,
+                        receiver: payment_info.auditor,
+                        amount: split.payee - retained,
+                    });
+                    self.credit_payout(id, payment_info.arbiterprovider, split.provider)?;
+                    self.sweep_dust(id, split.remainder)?;
+                    self.hold_retention(id, retained);
+                    payment_info.value = split.payee;
+                    payment_info.paid_to_auditor += split.payee - retained;
+                    payment_info.currentstatus = AuditStatus::AuditCompleted;
+                    payment_info.fiat_value_at_completion =
+                        self.fiat_value_of(id, payment_info.original_value);
+                    self.record_closing(id);
+                }
+                GovernanceRuling::Reject => {
+                    let split = math::split(payment_info.value, 95, 5);
+                    payment_info.refunded_to_patron +=
+                        self.refund_to_funders(id, payment_info.patron, split.payee)?;
+                    self.credit_payout(id, payment_info.arbiterprovider, split.provider)?;
+                    self.sweep_dust(id, split.remainder)?;
+                    payment_info.value = split.payee;
+                    payment_info.currentstatus = AuditStatus::AuditExpired;
+                    self.record_expiry(id, ExpiryReason::ArbiterRejection);
+                }
+                GovernanceRuling::ExtendDeadline(new_deadline, haircut) => {
+                    if haircut > MAX_HAIRCUT
+                        || new_deadline < self.env().block_timestamp() + MIN_DEADLINE_EXTENSION
+                    {
+                        return Err(Error::InvalidArgument);
+                    }
+                    let haircutvalue = Self::haircut_of(&payment_info, haircut);
+                    self.credit_payout(id, payment_info.patron, haircutvalue)?;
+                    payment_info.value -= haircutvalue;
+                    payment_info.refunded_to_patron += haircutvalue;
+                    payment_info.due_at = Some(new_deadline);
+                    payment_info.currentstatus = AuditStatus::AuditAssigned;
+                }
+            }
+            self.audit_id_to_governance_escalation.remove(id);
+            self.store_payment_info(id, &payment_info);
+            let event = match payment_info.currentstatus {
+                AuditStatus::AuditCompleted => AuditLifecycleEvent::Completed,
+                AuditStatus::AuditExpired => AuditLifecycleEvent::Expired,
+                _ => AuditLifecycleEvent::DeadlineExtended,
+            };
+            self.env().emit_event(AuditInfoUpdated {
+                id: Some(id),
+                payment_info: Some(payment_info),
+                updated_by: Some(self.env().caller()),
+            });
+            self.env()
+                .emit_event(GovernanceRulingExecuted { id, ruling });
+            self.notify_listeners(id, event);
+            Ok(())
+        }
+
+        //argument: operator(AccountId) the account that started the transfer
+        //argument: from(AccountId) the account the tokens come from
+        //argument: value(Balance) the amount being transferred
+        //argument: data(Vec<u8>) the data attached to the transfer
+        // the PSP22Receiver hook, called by tokens with transfer hooks before they credit the escrow.
+        // Only the stablecoin is accepted, and only while the escrow itself is pulling funds, so tokens
+        // sent directly to the escrow (which no audit would account for) are refused.
+        #[ink(message, selector = 0xfda6f1a9)]
+        pub fn before_received(
+            &self,
+            operator: AccountId,
+            _from: AccountId,
+            _value: Balance,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22ReceiverError> {
+            if self.env().caller() != self.stablecoin_address
+                && Some(self.env().caller()) != self.receiving_token.get()
+            {
+                return Err(PSP22ReceiverError::TransferRejected(String::from(
+                    "only the stablecoin is accepted",
+                )));
+            }
+            if operator != self.env().account_id()
+                || !self.receiving_transfer.get().unwrap_or_default()
+            {
+                return Err(PSP22ReceiverError::TransferRejected(String::from(
+                    "tokens are only accepted through the escrow's own transfers",
+                )));
+            }
+            Ok(())
+        }
+
+        // marks the audit expired and refunds its value to the patron and sponsors.
+        fn settle_expiry(
+            &mut self,
+            id: u32,
+            mut payment_info: PaymentInfo,
+            reason: ExpiryReason,
+        ) -> Result<()> {
+            payment_info.currentstatus = AuditStatus::AuditExpired;
+            payment_info.refunded_to_patron +=
+                self.refund_to_funders(id, payment_info.patron, payment_info.value)?;
+            self.store_payment_info(id, &payment_info);
+            self.env().emit_event(AuditInfoUpdated {
+                id: Some(id),
+                payment_info: Some(payment_info),
+                updated_by: Some(self.env().caller()),
+            });
+            self.record_expiry(id, reason);
+            self.notify_listeners(id, AuditLifecycleEvent::Expired);
+            Ok(())
+        }
+
+        // parameter changes can be queued and cancelled by the admin and by the governance contract.
+        fn can_change_params(&self, account: AccountId) -> bool {
+            account == self.admin || self.governance_address == Some(account)
+        }
+
+        // housekeeping is open to anyone until an OPERATOR is configured, then only to it and the admin.
+        fn ensure_housekeeper(&self) -> Result<()> {
+            match self.operator {
+                Some(operator)
+                    if self.env().caller() != operator && self.env().caller() != self.admin =>
+                {
+                    Err(Error::UnAuthorisedCall)
+                }
+                _ => Ok(()),
+            }
+        }
+
+        // stores why the audit expired and emits AuditExpired.
+        fn record_expiry(&mut self, id: u32, reason: ExpiryReason) {
+            self.audit_id_to_expiry_reason.insert(id, &reason);
+            self.record_closing(id);
+            self.env().emit_event(AuditExpired { id, reason });
+        }
+
+        // stores when the audit reached a terminal state, the retention period of archive_audit starts then,
+        // and reports it to the reward token for the patron's history.
+        fn record_closing(&mut self, id: u32) {
+            self.audit_id_to_closed_at
+                .insert(id, &self.env().block_timestamp());
+            self.record_patron_history(id);
+        }
+
+        // tells the reward token, if there is one, that an audit the patron funded closed, whether it was
+        // disputed and whether the patron won the dispute, i.e. the report was rejected in arbitration or by
+        // governance. A refused record doesn't undo the closing, it is only reported in PatronHistoryRecorded.
+        fn record_patron_history(&self, id: u32) {
+            let Some(reward_token) = self.reward_token else {
+                return;
+            };
+            let Some(payment_info) = self.audit_id_to_payment_info.get(id) else {
+                return;
+            };
+            let disputed = self.get_dispute_count(id) > 0;
+            let dispute_won = disputed
+                && self.audit_id_to_expiry_reason.get(id) == Some(ExpiryReason::ArbiterRejection);
+            let recorded = self.record_patron_audit_on(
+                reward_token,
+                payment_info.patron,
+                id,
+                disputed,
+                dispute_won,
+            );
+            self.env().emit_event(PatronHistoryRecorded {
+                id,
+                patron: payment_info.patron,
+                disputed,
+                dispute_won,
+                recorded,
+            });
+        }
+
+        // asks the reward token to record the closed audit in the history of the patron, returns whether it did
+        fn record_patron_audit_on(
+            &self,
+            reward_token: AccountId,
+            patron: AccountId,
+            id: u32,
+            disputed: bool,
+            dispute_won: bool,
+        ) -> bool {
+            RewardToken::new(reward_token, self.gas_limit_for(reward_token))
+                .record_patron_audit(patron, id, disputed, dispute_won)
+                .is_ok()
+        }
+
+        // mints the reward token of a closed audit to its auditor, positive if it completed, and attaches the
+        // scorecard it was assessed with, returns the ID of the token. Every dispute of the audit but a
+        // rejecting one ended in a deadline extension.
+        fn mint_with_scorecard(&self, id: u32, scorecard: Scorecard) -> Option<u32> {
+            let reward_token = self.reward_token?;
+            let payment_info = self.audit_id_to_payment_info.get(id)?;
+            let is_positive = match payment_info.currentstatus {
+                AuditStatus::AuditCompleted => true,
+                AuditStatus::AuditExpired => false,
+                _ => return None,
+            };
+            let disputes = self.get_dispute_count(id);
+            let extensions = if is_positive {
+                disputes
+            } else {
+                disputes.saturating_sub(1)
+            };
+            let taken = self
+                .get_submitted_at(id)
+                .unwrap_or(self.env().block_timestamp())
+                .saturating_sub(payment_info.starttime);
+            let completion_time = (u128::from(taken) * 100)
+                .checked_div(u128::from(payment_info.duration_ms))
+                .unwrap_or_default()
+                .min(u128::from(u8::MAX)) as u8;
+            let reward_token = RewardToken::new(reward_token, self.gas_limit_for(reward_token));
+            let token_id = reward_token
+                .mint(MintArgs {
+                    recipient: payment_info.auditor,
+                    audit_id: id,
+                    completion_time,
+                    extensions,
+                    amount: payment_info.original_value,
+                    //the token is public, it only carries the commitment to the report
+                    ipfs_hash: self
+                        .audit_id_to_ipfs_hash
+                        .get(id)
+                        .map(|ipfs_hash| {
+                            Self::report_commitment_of(id, &ipfs_hash)
+                                .iter()
+                                .map(|byte| format!("{:02x}", byte))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    is_positive,
+                })
+                .ok()?;
+            reward_token.record_scorecard(token_id, scorecard).ok()?;
+            Some(token_id)
+        }
+
+        // the percentage splits of a payout round down, whatever is left of the value over them is sent
+        // where the rounding policy says instead of staying stranded in the escrow.
+        fn sweep_dust(&mut self, id: u32, dust: Balance) -> Result<()> {
+            if dust == 0 {
+                return Ok(());
+            }
+            let patron = self
+                .audit_id_to_payment_info
+                .get(id)
+                .map_or(self.treasury, |payment_info| payment_info.patron);
+            self.credit_payout(id, self.dust_receiver_of(patron), dust)?;
+            let swept = self.audit_id_to_dust.get(id).unwrap_or_default();
+            self.audit_id_to_dust.insert(id, &(swept + dust));
+            Ok(())
+        }
+
+        // the account the rounding dust of an audit of the patron is sent to under the rounding policy
+        fn dust_receiver_of(&self, patron: AccountId) -> AccountId {
+            match self.rounding_policy {
+                RoundingPolicy::Treasury => self.treasury,
+                RoundingPolicy::Patron => patron,
+            }
         }
 
-        //argument: _id (u32) audit Id
-        //argument: _time (Timestamp) the new deadline
-        //argument: haircut_percentage(Balance) the part of value that will be sent back to the patron for delay
-        // the function verifies that the auditor is calling the function, then the request is made,
-        //mapping of IncreaseRequest updated, and event is emitted for DeadlineExtendRequest
-        #[ink(message)]
-        pub fn request_additional_time(
-            &mut self,
-            _id: u32,
-            _time: Timestamp,
-            _haircut_percentage: Balance,
-        ) -> Result<()> {
-            if self.get_paymentinfo(_id).unwrap().auditor == self.env().caller() {
-                let x = IncreaseRequest {
-                    haircut_percentage: _haircut_percentage,
-                    new_deadline: _time,
-                };
-                self.audit_id_to_time_increase_request.insert(_id, &x);
-                self.env().emit_event(DeadlineExtendRequest {
+        // checks that the token responds to `total_supply` and `decimals` like a PSP22, returning its decimals.
+        // it runs in the constructor, before any gas limit can be configured, so it uses TOKEN_GAS_LIMIT.
+        fn probe_stablecoin(token: AccountId) -> Result<u8> {
+            Psp22::new(token, TOKEN_GAS_LIMIT)
+                .total_supply()
+                .map_err(|_| Error::TokenQueryFailed)?;
+            Self::decimals_of(token)
+        }
+
+        // queries `decimals` of the token.
+        fn decimals_of(token: AccountId) -> Result<u8> {
+            Psp22::new(token, TOKEN_GAS_LIMIT)
+                .decimals()
+                .map_err(|_| Error::TokenQueryFailed)
+        }
+
+        // queries the escrow's own balance of the token.
+        fn own_balance_of(&self, token: AccountId) -> Result<Balance> {
+            self.psp22(token)
+                .balance_of(self.env().account_id())
+                .map_err(|_| Error::TokenQueryFailed)
+        }
+
+        // queries how much of owner's token the escrow is still allowed to pull.
+        fn allowance_of(&self, token: AccountId, owner: AccountId) -> Result<Balance> {
+            self.psp22(token)
+                .allowance(owner, self.env().account_id())
+                .map_err(|_| Error::TokenQueryFailed)
+        }
+
+        // the token called with the gas limit configured for it.
+        fn psp22(&self, token: AccountId) -> Psp22 {
+            Psp22::new(token, self.gas_limit_for(token))
+        }
+
+        // pays out a submission the patron approved, 98% to the auditor and 2% to the arbiter provider.
+        fn pay_out_approved(&mut self, _id: u32, mut payment_info: PaymentInfo) -> Result<()> {
+            let token = self.token_of(_id);
+            //the payout is made by its preview, so that its commitment describes exactly what was paid
+            let preview = self.preview_payout(_id, &payment_info);
+            let xyz = self.pay_auditor(_id, payment_info.auditor, preview.to_auditor);
+            let zyx = self.credit_tokens(
+                token,
+                payment_info.arbiterprovider,
+                preview.to_arbiter_provider,
+            );
+            let swept = self.sweep_dust(_id, preview.dust);
+
+            if xyz.is_ok() && zyx.is_ok() && swept.is_ok() {
+                self.env().emit_event(PaymentCredited {
                     id: _id,
-                    newtime: _time,
-                    haircut: _haircut_percentage,
+                    receiver: payment_info.auditor,
+                    amount: preview.to_auditor,
                 });
+
+                self.env().emit_event(PaymentCredited {
+                    id: _id,
+                    receiver: payment_info.arbiterprovider,
+                    amount: preview.to_arbiter_provider,
+                });
+                payment_info.currentstatus = AuditStatus::AuditCompleted;
+                payment_info.fiat_value_at_completion =
+                    self.fiat_value_of(_id, payment_info.original_value);
+                self.record_closing(_id);
+                payment_info.value = preview.to_auditor + preview.retained;
+                payment_info.paid_to_auditor += preview.to_auditor;
+                self.store_payment_info(_id, &payment_info);
+                self.hold_retention(_id, preview.retained);
+                let commitment = Self::commitment_of(&preview);
+                self.audit_id_to_payout_commitment.insert(_id, &commitment);
+                self.env().emit_event(PayoutCommitted {
+                    id: _id,
+                    commitment,
+                });
+                self.env().emit_event(AuditInfoUpdated {
+                    id: Some(_id),
+                    payment_info: self.audit_id_to_payment_info.get(_id),
+                    updated_by: Some(self.env().caller()),
+                });
+                self.notify_listeners(_id, AuditLifecycleEvent::Completed);
                 return Ok(());
             }
-            return Err(Error::UnAuthorisedCall);
+            Err(Error::TransferFromContractFailed)
         }
 
-        //argument: _id(u32) audit Id for which the additional time will be approved
-        // the function verifies that only patron is calling it, and haircut is lesser than 100%,
-        // the function assumes the consent for approving the time, transfers the haircut percentage
-        //to the patron's address, and changes the time in payment_info along with the new amount
-        //  events are emitted for tokenOutgoing and AuditInfoUpdated.
-        #[ink(message)]
-        pub fn approve_additional_time(&mut self, _id: u32) -> Result<()> {
-            if self.get_paymentinfo(_id).unwrap().patron == self.env().caller() {
-                let haircut = self
-                    .query_timeincreaserequest(_id)
-                    .unwrap()
-                    .haircut_percentage;
-                if haircut < 100 {
-                    let new_deadline = self.query_timeincreaserequest(_id).unwrap().new_deadline;
-
-                    let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-                    let value0 = payment_info.value * haircut / 100;
-                    let xyz = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.patron)
-                            .push_arg(value0), // .push_arg(&[0x10u8; 32]),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-                    if matches!(xyz.unwrap().unwrap(), Result::Ok(())) {
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.patron,
-                            amount: value0,
-                        });
-                        payment_info.value = payment_info.value * (100 - haircut) / 100;
-                        payment_info.deadline = new_deadline;
-                        self.audit_id_to_payment_info.insert(_id, &payment_info);
+        // the payout approving the audit makes: 98% of its value to the auditor, less the retention held back
+        // from them, 2% to the arbiter provider and the rounding dust to the dust receiver.
+        fn preview_payout(&self, id: u32, payment_info: &PaymentInfo) -> PayoutPreview {
+            let split = math::split(payment_info.value, 98, 2);
+            let retained = self.retention_of(id, split.payee);
+            PayoutPreview {
+                id,
+                token: self.token_of(id),
+                auditor: payment_info.auditor,
+                to_auditor: split.payee - retained,
+                retained,
+                arbiter_provider: payment_info.arbiterprovider,
+                to_arbiter_provider: split.provider,
+                dust_receiver: self.dust_receiver_of(payment_info.patron),
+                dust: split.remainder,
+                payout_preference: self.get_payout_preference(id),
+                dex_router: self.dex_router,
+            }
+        }
 
-                        self.env().emit_event(AuditInfoUpdated {
-                            id: Some(_id),
-                            payment_info: Some(self.audit_id_to_payment_info.get(_id).unwrap()),
-                            updated_by: Some(self.get_paymentinfo(_id).unwrap().patron),
-                        });
-                        return Ok(());
+        // the blake2x256 hash of the SCALE encoding of the audit ID and the report, announced instead of the
+        // report so that only the accounts get_report_hash lets see it can tell which one it is.
+        fn report_commitment_of(id: u32, ipfs_hash: &str) -> [u8; 32] {
+            let mut commitment = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(id, ipfs_hash),
+                &mut commitment,
+            );
+            commitment
+        }
+
+        // the intermediate checkpoints of an audit, see get_checkpoints
+        fn checkpoints_of(&self, id: u32) -> Vec<Checkpoint> {
+            self.audit_id_to_checkpoints.get(id).unwrap_or_default()
+        }
+
+        // the blake2x256 hash of the SCALE encoding of the preview
+        fn commitment_of(preview: &PayoutPreview) -> [u8; 32] {
+            let mut commitment = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(preview, &mut commitment);
+            commitment
+        }
+
+        // remembers the auditor the patron assigned an audit to, forgetting the oldest past MAX_RECENT_AUDITORS.
+        fn record_recent_auditor(&mut self, patron: AccountId, auditor: AccountId) {
+            let mut recent = self
+                .patron_to_recent_auditors
+                .get(patron)
+                .unwrap_or_default();
+            if recent.len() >= MAX_RECENT_AUDITORS as usize {
+                recent.remove(0);
+            }
+            recent.push(auditor);
+            self.patron_to_recent_auditors.insert(patron, &recent);
+        }
+
+        // stores the payment info of an audit, and moves the audit to the index of its new status if it changed.
+        fn store_payment_info(&mut self, id: u32, payment_info: &PaymentInfo) {
+            let stored = self.audit_id_to_payment_info.get(id);
+            //the obligations follow the value locked in the audit
+            let token = self.token_of(id);
+            let owed_before = stored.as_ref().map_or(0, Self::locked_value_of);
+            let owed_after = Self::locked_value_of(payment_info);
+            if owed_after > owed_before {
+                self.add_obligation(token, owed_after - owed_before);
+            } else {
+                self.remove_obligation(token, owed_before - owed_after);
+            }
+            let previous = stored.map(|previous| previous.currentstatus);
+            if previous != Some(payment_info.currentstatus) {
+                if let Some(previous) = previous {
+                    self.unindex_status(id, previous);
+                }
+                //every status has its own list, linked through the audits in it, so that no transition has to
+                //read or write more than a few cells however many audits ever reached the status
+                let status = payment_info.currentstatus;
+                match self.status_tails.get(status) {
+                    Some(tail) => {
+                        self.audit_id_to_next_in_status.insert(tail, &id);
+                        self.audit_id_to_previous_in_status.insert(id, &tail);
+                    }
+                    None => {
+                        self.status_heads.insert(status, &id);
                     }
-                    return Err(Error::TransferFromContractFailed);
                 }
-                return Err(Error::InvalidArgument);
+                self.status_tails.insert(status, &id);
+                self.status_counts
+                    .insert(status, &(self.get_status_count(status) + 1));
             }
-            Err(Error::UnAuthorisedCall)
+            self.audit_id_to_payment_info.insert(id, payment_info);
         }
 
-        //argument: _id (u32) The audit Id for which ipfs hash will be submitted,
-        //argument: _ipfs_hash (String) the hash for the audit reports
-        // the function changes the state of payment_info's audit status, and inserts the ipfs hash for the corresponding id.
-        //event is emitted for AuditSubmitted.
-        #[ink(message)]
-        pub fn mark_submitted(&mut self, _id: u32, _ipfs_hash: String) -> Result<()> {
-            let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-            // matches!(payment_info.currentstatus, AuditStatus::AuditAssigned)
-            // && payment_info.deadline > self.env().block_timestamp()
-            if payment_info.auditor == self.env().caller() {
-                if matches!(payment_info.currentstatus, AuditStatus::AuditAssigned) {
-                    if payment_info.deadline > self.env().block_timestamp() {
-                        self.audit_id_to_ipfs_hash.insert(_id, &_ipfs_hash);
-                        payment_info.currentstatus = AuditStatus::AuditSubmitted;
-                        self.audit_id_to_payment_info.insert(_id, &payment_info);
-                        self.env().emit_event(AuditSubmitted {
-                            id: _id,
-                            ipfs_hash: _ipfs_hash,
-                        });
-                        return Ok(());
-                    } else {
-                        return Err(Error::DeadlinePassed);
-                    }
-                } else {
-                    return Err(Error::WrongState);
+        // whether the audit is in the list of the status, an audit is in the list of its current status only,
+        // and its links are those of that list.
+        fn is_in_status(&self, id: u32, status: AuditStatus) -> bool {
+            self.audit_id_to_payment_info
+                .get(id)
+                .is_some_and(|payment_info| payment_info.currentstatus == status)
+                && (self.audit_id_to_previous_in_status.contains(id)
+                    || self.status_heads.get(status) == Some(id))
+        }
+
+        // removes the audit from the list of the status, linking the audits before and after it.
+        fn unindex_status(&mut self, id: u32, status: AuditStatus) {
+            if !self.is_in_status(id, status) {
+                return;
+            }
+            let previous = self.audit_id_to_previous_in_status.take(id);
+            let next = self.audit_id_to_next_in_status.take(id);
+            match (previous, next) {
+                (Some(previous), Some(next)) => {
+                    self.audit_id_to_next_in_status.insert(previous, &next);
+                    self.audit_id_to_previous_in_status.insert(next, &previous);
+                }
+                (Some(previous), None) => {
+                    self.audit_id_to_next_in_status.remove(previous);
+                    self.status_tails.insert(status, &previous);
+                }
+                (None, Some(next)) => {
+                    self.audit_id_to_previous_in_status.remove(next);
+                    self.status_heads.insert(status, &next);
+                }
+                (None, None) => {
+                    self.status_heads.remove(status);
+                    self.status_tails.remove(status);
                 }
             }
-            Err(Error::UnAuthorisedCall)
+            self.status_counts
+                .insert(status, &self.get_status_count(status).saturating_sub(1));
         }
 
-        //argument: id(u32) the audit id for assessment
-        //argument: answer (bool) if the caller is satisfied with audit report or not.
-        //broken down into three cases,
-        //C1: when patron calls,
-        //C2: when arbiterprovider calls,
-        //C3: when anything else happens
-        //C1 has two parts further, patron can only assess the audit if it is in submitted state, if patron
-        //says yes, then transfers happen, if no, then state is changed to awaitingValidation.
-        //C2 could have had two parts, and state should be awaitingValidation
-        // if true, transfer happens, if false, function sets the audit status to expired, and returns the tokens to patron.
-        //only then will the transfers happen.
-        #[ink(message)]
-        pub fn assess_audit(&mut self, _id: u32, answer: bool) -> Result<()> {
-            let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-            //C1
-            if self.env().caller() == payment_info.patron
-                && matches!(payment_info.currentstatus, AuditStatus::AuditSubmitted)
+        // the patron rejects the submitted report, the dispute bond is charged and the audit goes to the arbiter
+        // provider, or to governance once it was appealed APPEALS_BEFORE_GOVERNANCE times.
+        fn open_dispute(&mut self, id: u32, mut payment_info: PaymentInfo) -> Result<()> {
+            if self
+                .get_declined_arbitration(id)
+                .is_some_and(|declined| declined.provider == payment_info.arbiterprovider)
             {
-                if answer {
-                    let xyz = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.auditor)
-                            .push_arg(payment_info.value * 98 / 100), // .push_arg(&[0x10u8; 32]),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-                    let zyx = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.arbiterprovider)
-                            .push_arg(payment_info.value * 2 / 100),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-
-                    if matches!(xyz.unwrap().unwrap(), Result::Ok(()))
-                        && matches!(zyx.unwrap().unwrap(), Result::Ok(()))
-                    {
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.auditor,
-                            amount: payment_info.value * 98 / 100,
-                        });
-
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.arbiterprovider,
-                            amount: payment_info.value * 2 / 100,
-                        });
-                        payment_info.currentstatus = AuditStatus::AuditCompleted;
-                        payment_info.value = payment_info.value * 98 / 100;
-                        self.audit_id_to_payment_info.insert(_id, &payment_info);
-                        self.env().emit_event(AuditInfoUpdated {
-                            id: Some(_id),
-                            payment_info: Some(self.audit_id_to_payment_info.get(_id).unwrap()),
-                            updated_by: Some(self.env().caller()),
-                        });
-                        return Ok(());
-                    }
-                    return Err(Error::TransferFromContractFailed);
-                } else {
-                    payment_info.currentstatus = AuditStatus::AuditAwaitingValidation;
-                    self.audit_id_to_payment_info.insert(_id, &payment_info);
-                    self.env().emit_event(AuditRequestsArbitration { id: _id });
-                    return Ok(());
+                return Err(Error::ProviderDeclined);
+            }
+            let disputes = self.get_dispute_count(id) + 1;
+            let governance = self
+                .governance_address
+                .filter(|_| disputes > APPEALS_BEFORE_GOVERNANCE);
+            //the calls that can fail come before any write, process_due moves on from a failed dispute and
+            //its audit must be left as it was
+            if let Some(governance) = governance {
+                let forwarded = ink::env::call::build_call::<Environment>()
+                    .call(governance)
+                    .gas_limit(self.gas_limit_for(governance))
+                    .transferred_value(0)
+                    .exec_input(
+                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                            ink::selector_bytes!("on_dispute_escalated"),
+                        ))
+                        .push_arg(id),
+                    )
+                    .returns::<()>()
+                    .try_invoke();
+                if !matches!(forwarded, Ok(Ok(()))) {
+                    return Err(Error::GovernanceCallFailed);
                 }
             }
-            //C2
-            else if self.env().caller() == payment_info.arbiterprovider
-                && matches!(
-                    payment_info.currentstatus,
-                    AuditStatus::AuditAwaitingValidation
+            self.charge_dispute_bond(id, &payment_info)?;
+            //approvals are given to a submission, a rejected one needs them again
+            if let Some(mut approval) = self.audit_id_to_co_approval.get(id) {
+                approval.patron_approved = false;
+                approval.reviewer_approved = false;
+                self.audit_id_to_co_approval.insert(id, &approval);
+            }
+            self.audit_id_to_dispute_count.insert(id, &disputes);
+            payment_info.currentstatus = AuditStatus::AuditDisputed;
+            self.store_payment_info(id, &payment_info);
+            match governance {
+                Some(governance) => {
+                    self.audit_id_to_governance_escalation.insert(id, &true);
+                    self.env()
+                        .emit_event(DisputeEscalatedToGovernance { id, governance });
+                }
+                None => self.env().emit_event(AuditRequestsArbitration { id }),
+            }
+            self.notify_listeners(id, AuditLifecycleEvent::ArbitrationRequested);
+            Ok(())
+        }
+
+        // whether the report of the audit awaits the patron's review for longer than PATRON_REVIEW_WINDOW and the
+        // patron chose a policy other than Hold for it.
+        fn is_review_overdue(&self, id: u32, payment_info: &PaymentInfo, now: Timestamp) -> bool {
+            matches!(
+                payment_info.currentstatus,
+                AuditStatus::AuditSubmitted | AuditStatus::AuditAwaitingPatronReview
+            ) && payment_info.silence_policy != SilencePolicy::Hold
+                && self
+                    .audit_id_to_submitted_at
+                    .get(id)
+                    .is_some_and(|submitted_at| {
+                        submitted_at.saturating_add(PATRON_REVIEW_WINDOW) <= now
+                    })
+        }
+
+        // whether the payout of the audit still waits for the approval of its co-reviewer.
+        fn awaits_co_reviewer(&self, id: u32, payment_info: &PaymentInfo) -> bool {
+            self.requires_co_approval(payment_info)
+                && !self
+                    .audit_id_to_co_approval
+                    .get(id)
+                    .is_some_and(|approval| approval.reviewer_approved)
+        }
+
+        // takes the dispute bond from the patron for the arbiter provider, or one of the patron's waiver
+        // vouchers if they have any, nothing if no bond is configured.
+        fn charge_dispute_bond(&mut self, id: u32, payment_info: &PaymentInfo) -> Result<()> {
+            if self.dispute_bond == 0 {
+                self.audit_id_to_dispute_charge.remove(id);
+                return Ok(());
+            }
+            let vouchers = self.get_waiver_vouchers(payment_info.patron);
+            if vouchers > 0 {
+                self.waiver_vouchers
+                    .insert(payment_info.patron, &(vouchers - 1));
+                self.env().emit_event(WaiverVoucherUsed {
+                    id,
+                    patron: payment_info.patron,
+                    remaining: vouchers - 1,
+                });
+                self.audit_id_to_dispute_charge
+                    .insert(id, &DisputeCharge::Voucher);
+                return Ok(());
+            }
+            let token = self.token_of(id);
+            self.psp22(token)
+                .transfer_from(
+                    payment_info.patron,
+                    payment_info.arbiterprovider,
+                    self.dispute_bond,
                 )
-            {
-                if answer {
-                    let xyz = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.auditor)
-                            .push_arg(payment_info.value * 95 / 100), // .push_arg(&[0x10u8; 32]),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-
-                    let zyx = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.arbiterprovider)
-                            .push_arg(payment_info.value * 5 / 100), // .push_arg(&[0x10u8; 32]),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-
-                    if matches!(xyz.unwrap().unwrap(), Result::Ok(()))
-                        && matches!(zyx.unwrap().unwrap(), Result::Ok(()))
-                    {
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.auditor,
-                            amount: payment_info.value * 95 / 100,
-                        });
+                .map_err(|_| Error::DisputeBondFailed)?;
+            self.audit_id_to_dispute_charge
+                .insert(id, &DisputeCharge::Bond(self.dispute_bond));
+            self.env().emit_event(DisputeBondPaid {
+                id,
+                amount: self.dispute_bond,
+            });
+            Ok(())
+        }
 
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.arbiterprovider,
-                            amount: payment_info.value * 5 / 100,
-                        });
-                        payment_info.value = payment_info.value * 95 / 100;
-                        payment_info.currentstatus = AuditStatus::AuditCompleted;
-                        self.audit_id_to_payment_info.insert(_id, &payment_info);
-                        self.env().emit_event(AuditInfoUpdated {
-                            id: Some(_id),
-                            payment_info: Some(self.audit_id_to_payment_info.get(_id).unwrap()),
-                            updated_by: Some(self.env().caller()),
-                        });
-                        return Ok(());
+        // whether the payout of the audit needs the approval of the reviewer on top of the patron's.
+        fn requires_co_approval(&self, payment_info: &PaymentInfo) -> bool {
+            self.co_approval_threshold
+                .is_some_and(|threshold| payment_info.original_value >= threshold)
+        }
+
+        // the USD value (6 decimals) of amount of the token of the audit according to the price oracle,
+        // None if there is no oracle or it has no price for the token.
+        fn fiat_value_of(&self, id: u32, amount: Balance) -> Option<Balance> {
+            let oracle = self.price_oracle?;
+            let result = ink::env::call::build_call::<Environment>()
+                .call(oracle)
+                .gas_limit(self.gas_limit_for(oracle))
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("get_price"),
+                    ))
+                    .push_arg(self.token_of(id)),
+                )
+                .returns::<Option<Balance>>()
+                .try_invoke();
+            let price = match result {
+                Ok(Ok(Some(price))) => price,
+                _ => return None,
+            };
+            amount
+                .checked_mul(price)?
+                .checked_div(10u128.checked_pow(self.token_decimals_of(id) as u32)?)
+        }
+
+        // the part of the auditor's share of an audit held back under its retention clause.
+        fn retention_of(&self, id: u32, auditors_share: Balance) -> Balance {
+            math::percent_of(auditors_share, self.get_retention_percentage(id))
+        }
+
+        // holds amount back from the auditor of a completed audit until RETENTION_PERIOD from now.
+        // Emits RetentionWithheld.
+        fn hold_retention(&mut self, id: u32, amount: Balance) {
+            if amount == 0 {
+                return;
+            }
+            let releasable_at = self
+                .env()
+                .block_timestamp()
+                .saturating_add(RETENTION_PERIOD);
+            self.audit_id_to_retention.insert(
+                id,
+                &Retention {
+                    amount,
+                    releasable_at,
+                },
+            );
+            self.add_obligation(self.token_of(id), amount);
+            self.env().emit_event(RetentionWithheld {
+                id,
+                amount,
+                releasable_at,
+            });
+        }
+
+        // pays the retention of an audit to its auditor. Emits RetentionReleased.
+        fn pay_retention(
+            &mut self,
+            id: u32,
+            mut payment_info: PaymentInfo,
+            retention: Retention,
+        ) -> Result<()> {
+            self.pay_auditor(id, payment_info.auditor, retention.amount)?;
+            self.audit_id_to_retention.remove(id);
+            self.remove_obligation(self.token_of(id), retention.amount);
+            self.env().emit_event(PaymentCredited {
+                id,
+                receiver: payment_info.auditor,
+                amount: retention.amount,
+            });
+            payment_info.paid_to_auditor += retention.amount;
+            self.store_payment_info(id, &payment_info);
+            self.env().emit_event(RetentionReleased {
+                id,
+                amount: retention.amount,
+            });
+            Ok(())
+        }
+
+        // the value an audit still locks in the escrow, a completed or expired audit was paid out or refunded.
+        fn locked_value_of(payment_info: &PaymentInfo) -> Balance {
+            match payment_info.currentstatus {
+                AuditStatus::AuditCompleted | AuditStatus::AuditExpired => 0,
+                _ => payment_info.value,
+            }
+        }
+
+        fn add_obligation(&mut self, token: AccountId, amount: Balance) {
+            let obligations = self.get_obligations(token).saturating_add(amount);
+            self.obligations.insert(token, &obligations);
+        }
+
+        fn remove_obligation(&mut self, token: AccountId, amount: Balance) {
+            let obligations = self.get_obligations(token).saturating_sub(amount);
+            self.obligations.insert(token, &obligations);
+        }
+
+        // the haircut percentage of the original value of the audit, capped at the value still locked.
+        fn haircut_of(payment_info: &PaymentInfo, percentage: Balance) -> Balance {
+            math::percent_of(payment_info.original_value, percentage).min(payment_info.value)
+        }
+
+        // moves the gas refund of a call from the paymaster pot of the arbiter provider, if they have one,
+        // to what they are owed, as much as is left in the pot. Emits GasRefunded.
+        fn refund_provider_gas(&mut self, id: u32, provider: AccountId) {
+            let Some(mut pot) = self.paymaster_pots.get(provider) else {
+                return;
+            };
+            let amount = self.gas_refund_per_call.min(pot.balance);
+            if amount == 0 {
+                return;
+            }
+            pot.balance -= amount;
+            pot.owed += amount;
+            self.paymaster_pots.insert(provider, &pot);
+            self.env().emit_event(GasRefunded {
+                id,
+                provider,
+                amount,
+            });
+        }
+
+        // credits amount of the audit's token to the receiver, who takes it out with withdraw, and emits
+        // PaymentCredited.
+        fn credit_payout(&mut self, id: u32, receiver: AccountId, amount: Balance) -> Result<()> {
+            self.credit_tokens(self.token_of(id), receiver, amount)?;
+            self.env().emit_event(PaymentCredited {
+                id,
+                receiver,
+                amount,
+            });
+            Ok(())
+        }
+
+        // pays amount of the audit's token to its auditor. If the auditor has a payout preference the amount
+        // is swapped into their token first, and credited as is if the swap fails.
+        fn pay_auditor(&mut self, id: u32, auditor: AccountId, amount: Balance) -> Result<()> {
+            if let (Some(preference), Some(router)) =
+                (self.audit_id_to_payout_preference.get(id), self.dex_router)
+            {
+                if amount > 0 && self.is_payout_token(preference.token) {
+                    match self.swap_payout(id, router, preference, amount) {
+                        Ok(amount_out) => {
+                            self.credit_tokens(preference.token, auditor, amount_out)?;
+                            self.env().emit_event(PayoutSwapped {
+                                id,
+                                token: preference.token,
+                                amount_in: amount,
+                                amount_out,
+                            });
+                            return Ok(());
+                        }
+                        Err(_) => self.env().emit_event(PayoutSwapFailed {
+                            id,
+                            token: preference.token,
+                        }),
                     }
-                    return Err(Error::TransferFromContractFailed);
                 }
-                //if arbitersprovider is finally dissatisfied.
-                else {
-                    let xyz = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.patron)
-                            .push_arg(payment_info.value * 95 / 100),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-                    let zyx = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(payment_info.arbiterprovider)
-                            .push_arg(payment_info.value * 5 / 100),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-                    if matches!(xyz.unwrap().unwrap(), Result::Ok(()))
-                        && matches!(zyx.unwrap().unwrap(), Result::Ok(()))
-                    {
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.patron,
-                            amount: payment_info.value * 95 / 100,
-                        });
-                        self.env().emit_event(TokenOutgoing {
-                            id: _id,
-                            receiver: payment_info.arbiterprovider,
-                            amount: payment_info.value * 5 / 100,
-                        });
-                        self.env().emit_event(AuditInfoUpdated {
-                            id: Some(_id),
-                            payment_info: Some(self.audit_id_to_payment_info.get(_id).unwrap()),
-                            updated_by: Some(self.env().caller()),
-                        });
-                        payment_info.value = payment_info.value * 95 / 100;
-                        payment_info.currentstatus = AuditStatus::AuditExpired;
-                        self.audit_id_to_payment_info.insert(_id, &payment_info);
-                        return Ok(());
-                    }
-                    return Err(Error::TransferFromContractFailed);
+            }
+            self.credit_tokens(self.token_of(id), auditor, amount)
+        }
+
+        // swaps amount of the audit's token into the preferred one through the router, the proceeds stay
+        // in the escrow to be credited to the auditor. The router's allowance is reset if the swap fails.
+        fn swap_payout(
+            &self,
+            id: u32,
+            router: AccountId,
+            preference: PayoutPreference,
+            amount: Balance,
+        ) -> crate::token::Result<Balance> {
+            let token = self.token_of(id);
+            let dex = Router::new(router, self.gas_limit_for(router));
+            let path = [token, preference.token].to_vec();
+            let quote = dex.get_amount_out(amount, path.clone())?;
+            let min_amount_out =
+                quote - math::bps_of(quote, Balance::from(preference.max_slippage_bps));
+            self.psp22(token).approve(router, amount)?;
+            let swapped = dex.swap(amount, min_amount_out, path, self.env().account_id());
+            if swapped.is_err() {
+                let _ = self.psp22(token).approve(router, 0);
+            }
+            swapped
+        }
+
+        // credits amount of the token to the receiver, who takes it out with withdraw. Nothing is
+        // transferred, so a receiver that can't take tokens doesn't hold up the transition paying it.
+        fn credit_tokens(
+            &mut self,
+            token: AccountId,
+            receiver: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            if amount == 0 {
+                return Ok(());
+            }
+            let credit = self.get_credit(receiver, token);
+            if credit == 0 {
+                let mut tokens = self.get_credit_tokens(receiver);
+                tokens.push(token);
+                self.account_to_credit_tokens.insert(receiver, &tokens);
+            }
+            self.credits
+                .insert((receiver, token), &credit.saturating_add(amount));
+            self.add_obligation(token, amount);
+            Ok(())
+        }
+
+        // takes up to amount of the token from what is credited to the account, returns how much was taken.
+        fn debit_credit(
+            &mut self,
+            token: AccountId,
+            account: AccountId,
+            amount: Balance,
+        ) -> Balance {
+            let credit = self.get_credit(account, token);
+            let debited = credit.min(amount);
+            if debited == 0 {
+                return 0;
+            }
+            if credit == debited {
+                self.credits.remove((account, token));
+                let mut tokens = self.get_credit_tokens(account);
+                tokens.retain(|credited| *credited != token);
+                if tokens.is_empty() {
+                    self.account_to_credit_tokens.remove(account);
+                } else {
+                    self.account_to_credit_tokens.insert(account, &tokens);
                 }
+            } else {
+                self.credits.insert((account, token), &(credit - debited));
+            }
+            self.remove_obligation(token, debited);
+            debited
+        }
+
+        // pulls pre-approved amount of the stablecoin from the sender into the escrow and emits TokenIncoming.
+        fn receive_tokens(&mut self, id: u32, sender: AccountId, amount: Balance) -> Result<()> {
+            self.pull_tokens(self.token_of(id), sender, amount)?;
+            self.env().emit_event(TokenIncoming { id, amount });
+            Ok(())
+        }
+
+        // pulls pre-approved amount of the token from the sender into the escrow.
+        // the token is allowed to call back into the escrow during the transfer, so that a token with
+        // transfer hooks can reach before_received, while every other message refuses to run until
+        // the transfer has returned.
+        fn pull_tokens(
+            &mut self,
+            token: AccountId,
+            sender: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            self.receiving_transfer.set(&true);
+            self.receiving_token.set(&token);
+            let result = self.psp22(token).pull(sender, amount);
+            self.receiving_transfer.set(&false);
+            result.map_err(|_| Error::InsufficientBalance)
+        }
+
+        // the token an audit was funded with, audits created before it was recorded use the default one
+        fn token_of(&self, id: u32) -> AccountId {
+            self.audit_id_to_token
+                .get(id)
+                .map_or(self.stablecoin_address, |(token, _)| token)
+        }
+
+        // the decimals of the token an audit was funded with
+        fn token_decimals_of(&self, id: u32) -> u8 {
+            self.audit_id_to_token
+                .get(id)
+                .map_or(self.token_decimals, |(_, decimals)| decimals)
+        }
+
+        // MIN_AUDIT_VALUE_TOKENS in the smallest unit of a token with the given decimals
+        fn min_audit_value_for(decimals: u8) -> Balance {
+            MIN_AUDIT_VALUE_TOKENS * 10u128.saturating_pow(decimals as u32)
+        }
+
+        // audits of higher value tiers need more time, DeadlineTooShort carries the minimum for the value.
+        fn ensure_min_deadline(&self, value: Balance, deadline: Timestamp) -> Result<()> {
+            let min_deadline = self.get_min_deadline(value);
+            if deadline < min_deadline {
+                return Err(Error::DeadlineTooShort(min_deadline));
+            }
+            Ok(())
+        }
+
+        //a relative deadline that would overflow the timestamp is rejected instead of trapping
+        fn deadline_from(now: Timestamp, duration: Timestamp) -> Result<Timestamp> {
+            now.checked_add(duration).ok_or(Error::InvalidArgument)
+        }
+
+        // pulls the value of a pending audit from its patron and creates the audit, FundingMismatch if the
+        // escrow received less than the value.
+        fn fund_pending(&mut self, id: u32, pending: &PendingFunding) -> Result<()> {
+            let token = self.token_of(id);
+            let balance_before = self.own_balance_of(token)?;
+            self.receive_tokens(id, pending.patron, pending.value)?;
+            let balance_after = self.own_balance_of(token)?;
+            if balance_after.saturating_sub(balance_before) != pending.value {
+                return Err(Error::FundingMismatch);
             }
-            //C3
-            Err(Error::UnAuthorisedCall)
+            self.audit_id_to_pending_funding.remove(id);
+            let x = PaymentInfo {
+                value: pending.value,
+                starttime: self.env().block_timestamp(),
+                auditor: pending.patron,
+                arbiterprovider: pending.arbiter_provider,
+                patron: pending.patron,
+                duration_ms: pending.duration_ms,
+                due_at: None,
+                currentstatus: AuditStatus::AuditCreated,
+                original_value: pending.value,
+                refunded_to_patron: 0,
+                paid_to_auditor: 0,
+                fiat_value_at_creation: self.fiat_value_of(id, pending.value),
+                fiat_value_at_completion: None,
+                silence_policy: pending.silence_policy,
+            };
+            self.store_payment_info(id, &x);
+            self.env().emit_event(AuditCreated {
+                id,
+                payment_info: Some(x),
+                salt: pending.salt,
+            });
+            self.notify_listeners(id, AuditLifecycleEvent::Created);
+            Ok(())
         }
 
-        //argument: id(u32) the audit ID for extending deadline
-        //argument: new_deadline(Timestamp) the new deadline
-        //argument: haircut(Balance) the decided haircut for the auditor
-        //argument: arbitersshare(Balance) decided off-chain by the arbitersproivder and the arbiters according to their inputs
-        //and work put in for the audit ID.
-        // the function is only to be called by the assigned arbitersprovider that too when the auditStatus is awaiting validation
-        // the haircut and arbitersshare should be less than 10%, and the deadline should be extended by at least 1 day.
-        // then the changes take place, haircut is given to patron, arbitersshare to the arbitersprovider, and payment_info is modified.
-        //events for TokenOutgoing and AuditInfoUpdated are emitted.
-        #[ink(message)]
-        pub fn arbiters_extend_deadline(
-            &mut self,
-            _id: u32,
-            new_deadline: Timestamp,
-            haircut: Balance,
-            arbitersshare: Balance,
-        ) -> Result<()> {
-            //checking for the haircut to be lesser than 10% and new deadline to be at least more than 1 day.
-            let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-            if haircut <= 90
-                && new_deadline >= self.env().block_timestamp() + 86400000
-                && self.env().caller() == payment_info.arbiterprovider
-                && arbitersshare <= 10
-                && matches!(
-                    payment_info.currentstatus,
-                    AuditStatus::AuditAwaitingValidation
-                )
-            {
-                let arbitersscut: Balance = payment_info.value * arbitersshare / 100;
-                let haircutvalue: Balance = payment_info.value * haircut / 100;
-                // Update the value in storage
-                payment_info.value = payment_info.value * (100 - (arbitersshare + haircut)) / 100;
-                // Update the deadline in storage
-                payment_info.deadline = new_deadline;
-                payment_info.currentstatus = AuditStatus::AuditAssigned;
+        fn remove_draft(&mut self, draft_id: u32, patron: AccountId) {
+            self.drafts.remove(draft_id);
+            let mut draft_ids = self.get_drafts(patron);
+            draft_ids.retain(|other| *other != draft_id);
+            self.patron_to_drafts.insert(patron, &draft_ids);
+        }
 
-                let xyz = ink::env::call::build_call::<Environment>()
-                    .call(self.stablecoin_address)
-                    .gas_limit(0)
-                    .transferred_value(0)
-                    .exec_input(
-                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("transfer"),
-                        ))
-                        .push_arg(payment_info.arbiterprovider)
-                        .push_arg(arbitersscut), // .push_arg(&[0x10u8; 32]),
-                    )
-                    .returns::<Result<()>>()
-                    .try_invoke();
+        // the receiving_transfer flag lives in its own storage cell so a reentrant call sees it set,
+        // messages that change state are refused while a transfer into the escrow is in flight.
+        fn ensure_not_receiving(&self) -> Result<()> {
+            if self.receiving_transfer.get().unwrap_or_default() {
+                return Err(Error::ReentrantCall);
+            }
+            Ok(())
+        }
 
-                let zyx = ink::env::call::build_call::<Environment>()
-                    .call(self.stablecoin_address)
-                    .gas_limit(0)
-                    .transferred_value(0)
-                    .exec_input(
-                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("transfer"),
-                        ))
-                        .push_arg(payment_info.patron)
-                        .push_arg(haircutvalue), // .push_arg(&[0x10u8; 32]),
-                    )
-                    .returns::<Result<()>>()
-                    .try_invoke();
+        // the gas limit of calls to callee, the default for the callee unless the admin configured one.
+        fn gas_limit_for(&self, callee: AccountId) -> u64 {
+            self.callee_gas_limits
+                .get(callee)
+                .unwrap_or(self.default_gas_limit_for(callee))
+        }
 
-                //matches!(xyz.unwrap().unwrap(), Result::Ok(()))
-                //removed from condition.
-                if matches!(zyx.unwrap().unwrap(), Result::Ok(()))
-                    && matches!(xyz.unwrap().unwrap(), Result::Ok(()))
-                {
-                    self.env().emit_event(TokenOutgoing {
-                        id: _id,
-                        receiver: payment_info.arbiterprovider,
-                        amount: arbitersscut,
-                    });
-                    self.env().emit_event(TokenOutgoing {
-                        id: _id,
-                        receiver: payment_info.patron,
-                        amount: haircutvalue,
-                    });
-                    self.audit_id_to_payment_info.insert(_id, &payment_info);
-                    self.env().emit_event(AuditInfoUpdated {
-                        id: Some(_id),
-                        payment_info: Some(self.audit_id_to_payment_info.get(_id).unwrap()),
-                        updated_by: Some(self.get_paymentinfo(_id).unwrap().patron),
-                    });
-                    return Ok(());
+        // TOKEN_GAS_LIMIT for the stablecoin and the payout tokens, DEFAULT_GAS_LIMIT for every other contract,
+        // including a former stablecoin audits still pay out in, which can only get more gas than it needs.
+        fn default_gas_limit_for(&self, callee: AccountId) -> u64 {
+            if callee == self.stablecoin_address || self.is_payout_token(callee) {
+                TOKEN_GAS_LIMIT
+            } else {
+                DEFAULT_GAS_LIMIT
+            }
+        }
+
+        fn ensure_not_blacklisted(&self, account: AccountId) -> Result<()> {
+            if self.is_blacklisted(account) {
+                return Err(Error::CallerBlacklisted);
+            }
+            Ok(())
+        }
+
+        // the total that sponsors have contributed to an audit.
+        fn sponsored_total(&self, id: u32) -> Balance {
+            self.get_sponsors(id)
+                .iter()
+                .map(|sponsor| self.get_contribution(id, *sponsor))
+                .sum()
+        }
+
+        // splits a refund of amount between the sponsors, pro-rata to their share of the original value
+        // the audit was funded with, the patron gets the remainder including the rounding dust. ArithmeticOverflow
+        // if a share doesn't fit in a Balance, which contributions of at most the original value can't cause.
+        fn refund_shares(
+            &self,
+            id: u32,
+            patron: AccountId,
+            amount: Balance,
+        ) -> Result<Vec<(AccountId, Balance)>> {
+            let sponsors = self.get_sponsors(id);
+            let funded = self
+                .audit_id_to_payment_info
+                .get(id)
+                .map(|payment_info| payment_info.original_value)
+                .unwrap_or(amount);
+            let mut shares = Vec::new();
+            let mut refunded: Balance = 0;
+            for sponsor in sponsors {
+                let share = if funded == 0 {
+                    0
+                } else {
+                    math::mul_div(amount, self.get_contribution(id, sponsor), funded)
+                        .ok_or(Error::ArithmeticOverflow)?
+                };
+                refunded = refunded
+                    .checked_add(share)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                shares.push((sponsor, share));
+            }
+            let to_patron = amount
+                .checked_sub(refunded)
+                .ok_or(Error::ArithmeticOverflow)?;
+            shares.push((patron, to_patron));
+            Ok(shares)
+        }
+
+        // refunds amount to the patron and the sponsors of the audit as split by refund_shares, returns the
+        // part of it refunded to the patron, the sponsors' part is added up in audit_id_to_refunded_to_sponsors.
+        fn refund_to_funders(
+            &mut self,
+            id: u32,
+            patron: AccountId,
+            amount: Balance,
+        ) -> Result<Balance> {
+            let mut to_patron = 0;
+            let mut to_sponsors: Balance = 0;
+            for (receiver, share) in self.refund_shares(id, patron, amount)? {
+                if share > 0 {
+                    self.credit_payout(id, receiver, share)?;
+                }
+                if receiver == patron {
+                    to_patron = share;
+                } else {
+                    to_sponsors += share;
                 }
             }
-            Err(Error::ArbitersExtendDeadlineConditionsNotMet)
+            if to_sponsors > 0 {
+                let refunded = self.get_refunded_to_sponsors(id) + to_sponsors;
+                self.audit_id_to_refunded_to_sponsors.insert(id, &refunded);
+            }
+            Ok(to_patron)
         }
 
-        //argument: id(u32) the audit ID to be retrieved
-        // the function can only be called by the patron, and only when the state is created or deadline has passed.
-        // this updates the status of the audit, fires the event of TokenOutgoing, returns the value to the patron,
-        #[ink(message)]
-        pub fn expire_audit(&mut self, _id: u32) -> Result<()> {
-            let mut payment_info = self.audit_id_to_payment_info.get(_id).unwrap();
-            if payment_info.patron == self.env().caller()
-                && (matches!(payment_info.currentstatus, AuditStatus::AuditCreated)
-                    || (matches!(payment_info.currentstatus, AuditStatus::AuditAssigned)
-                        && payment_info.deadline <= self.env().block_timestamp()))
-            {
-                payment_info.currentstatus = AuditStatus::AuditExpired;
-                let xyz = ink::env::call::build_call::<Environment>()
-                    .call(self.stablecoin_address)
-                    .gas_limit(0)
+        // makes a gas capped `on_audit_event(id, event)` call to every listener subscribed to the event,
+        // the result is ignored so that a failing or misbehaving listener can't block the audit flow.
+        fn notify_listeners(&self, id: u32, event: AuditLifecycleEvent) {
+            for subscription in &self.subscriptions {
+                if subscription.events_mask & event.mask() == 0 {
+                    continue;
+                }
+                let _ = ink::env::call::build_call::<Environment>()
+                    .call(subscription.listener)
+                    .gas_limit(LISTENER_GAS_LIMIT)
                     .transferred_value(0)
                     .exec_input(
                         ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("transfer"),
+                            ink::selector_bytes!("on_audit_event"),
                         ))
-                        .push_arg(payment_info.patron)
-                        .push_arg(payment_info.value),
+                        .push_arg(id)
+                        .push_arg(event),
                     )
-                    .returns::<Result<()>>()
+                    .returns::<()>()
                     .try_invoke();
-                if matches!(xyz.unwrap().unwrap(), Result::Ok(())) {
-                    self.env().emit_event(TokenOutgoing {
-                        id: _id,
-                        receiver: payment_info.patron,
-                        amount: payment_info.value,
-                    });
-                    self.env().emit_event(AuditInfoUpdated {
-                        id: Some(_id),
-                        payment_info: Some(self.audit_id_to_payment_info.get(_id).unwrap()),
-                        updated_by: Some(self.env().caller()),
-                    });
-                    self.audit_id_to_payment_info.insert(_id, &payment_info);
-                    return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+
+    fn seed_audit(
+        contract: &mut escrow::Escrow,
+        id: u32,
+        patron: ink::primitives::AccountId,
+        status: escrow::AuditStatus,
+    ) {
+        let payment_info = escrow::PaymentInfo {
+            patron,
+            auditor: patron,
+            value: 100,
+            arbiterprovider: patron,
+            duration_ms: 1000000,
+            due_at: (status != escrow::AuditStatus::AuditCreated).then_some(1000000),
+            starttime: 0,
+            currentstatus: status,
+            original_value: 100,
+            refunded_to_patron: 0,
+            paid_to_auditor: 0,
+            fiat_value_at_creation: None,
+            fiat_value_at_completion: None,
+            silence_policy: escrow::SilencePolicy::Hold,
+        };
+        contract.audit_id_to_payment_info.insert(id, &payment_info);
+    }
+
+    // queues the change and executes it once its delay has passed
+    fn apply_param_change(
+        contract: &mut escrow::Escrow,
+        change: escrow::ParamChange,
+    ) -> escrow::Result<()> {
+        let change_id = contract.queue_param_change(change)?;
+        let (_, executable_at) = contract.get_pending_param_change(change_id).unwrap();
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(executable_at);
+        contract.execute_param_change(change_id)
+    }
+
+    #[test]
+    fn test_1_multiget_paymentinfo() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.charlie,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        let ans = contract.multiget_paymentinfo([1, 5, 0].to_vec());
+        assert_eq!(ans.len(), 3);
+        assert_eq!(ans[0].as_ref().unwrap().patron, accounts.charlie);
+        assert!(ans[1].is_none());
+        assert_eq!(ans[2].as_ref().unwrap().patron, accounts.alice);
+    }
+
+    #[test]
+    fn test_2_multiget_status() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let ans = contract.multiget_status([0, 1, 2].to_vec());
+        assert!(matches!(ans[0], Some(escrow::AuditStatus::AuditCreated)));
+        assert!(matches!(ans[1], Some(escrow::AuditStatus::AuditSubmitted)));
+        assert!(ans[2].is_none());
+    }
+
+    #[test]
+    fn test_3_subscribe_and_update_mask() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let created = escrow::AuditLifecycleEvent::Created.mask();
+        let expired = escrow::AuditLifecycleEvent::Expired.mask();
+        assert!(contract.subscribe(accounts.eve, created).is_ok());
+        assert!(contract.subscribe(accounts.eve, created | expired).is_ok());
+        let ans = contract.get_subscriptions();
+        assert_eq!(ans.len(), 1);
+        assert_eq!(ans[0].owner, accounts.alice);
+        assert_eq!(ans[0].events_mask, created | expired);
+        let x = contract.subscribe(accounts.frank, 0);
+        assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+        //a bit past the last event
+        let y = contract.subscribe(accounts.frank, escrow::AuditLifecycleEvent::ALL_MASK + 1);
+        assert!(matches!(y, Err(escrow::Error::InvalidArgument)));
+        assert!(contract
+            .subscribe(accounts.frank, escrow::AuditLifecycleEvent::ALL_MASK)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_4_failed_unsubscribe_by_non_owner() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let _x = contract.subscribe(accounts.eve, 1);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let y = contract.unsubscribe(accounts.eve);
+        assert!(matches!(y, Err(escrow::Error::UnAuthorisedCall)));
+        let z = contract.subscribe(accounts.eve, 2);
+        assert!(matches!(z, Err(escrow::Error::UnAuthorisedCall)));
+        //only the admin subscribes listeners, a listener can't subscribe itself
+        let w = contract.subscribe(accounts.frank, 2);
+        assert!(matches!(w, Err(escrow::Error::UnAuthorisedCall)));
+        let v = contract.subscribe(accounts.charlie, 2);
+        assert!(matches!(v, Err(escrow::Error::UnAuthorisedCall)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.unsubscribe(accounts.eve).is_ok());
+        assert_eq!(contract.get_subscriptions().len(), 0);
+    }
+
+    #[test]
+    fn test_5_failed_subscribe_over_the_cap() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        for i in 0..escrow::MAX_SUBSCRIPTIONS {
+            let listener = ink::primitives::AccountId::from([i as u8 + 100; 32]);
+            assert!(contract.subscribe(listener, 1).is_ok());
+        }
+        let x = contract.subscribe(accounts.eve, 1);
+        assert!(matches!(x, Err(escrow::Error::TooManySubscriptions)));
+    }
+
+    #[test]
+    fn test_6_set_governance_address_only_by_admin() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.know_your_admin(), accounts.alice);
+        assert!(contract.get_governance_address().is_none());
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::GovernanceAddress(Some(accounts.frank))
+        )
+        .is_ok());
+        assert_eq!(contract.get_governance_address(), Some(accounts.frank));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let x = apply_param_change(&mut contract, escrow::ParamChange::GovernanceAddress(None));
+        assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    }
+
+    #[test]
+    fn test_7_patron_dispute_is_counted() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert_eq!(contract.get_dispute_count(0), 1);
+        assert!(!contract.is_escalated_to_governance(0));
+    }
+
+    #[test]
+    fn test_8_governance_ruling_gated_to_governance() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let _x = apply_param_change(
+            &mut contract,
+            escrow::ParamChange::GovernanceAddress(Some(accounts.frank)),
+        );
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        let y = contract.execute_governance_ruling(0, escrow::GovernanceRuling::Approve);
+        assert!(matches!(y, Err(escrow::Error::UnAuthorisedCall)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        let z = contract.execute_governance_ruling(0, escrow::GovernanceRuling::Approve);
+        assert!(matches!(z, Err(escrow::Error::WrongState)));
+    }
+
+    #[test]
+    fn test_9_arbiter_provider_locked_out_of_escalated_dispute() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        contract.audit_id_to_governance_escalation.insert(0, &true);
+        let x = contract.assess_audit(0, true);
+        assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    }
+
+    #[test]
+    fn test_10_failed_contribute_by_patron_or_after_assignment() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        let x = contract.contribute(0, 50);
+        assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let y = contract.contribute(0, 0);
+        assert!(matches!(y, Err(escrow::Error::InvalidArgument)));
+        let z = contract.contribute(1, 50);
+        assert!(matches!(z, Err(escrow::Error::WrongState)));
+    }
+
+    #[test]
+    fn test_11_refund_split_is_pro_rata() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        //the patron locked 50 and two sponsors 30 and 20 of the 100
+        contract
+            .audit_id_to_sponsors
+            .insert(0, &[accounts.charlie, accounts.eve].to_vec());
+        contract
+            .audit_id_to_contributions
+            .insert((0, accounts.charlie), &30);
+        contract
+            .audit_id_to_contributions
+            .insert((0, accounts.eve), &20);
+        let ans = contract.get_refund_split(0);
+        assert_eq!(
+            ans,
+            [
+                (accounts.charlie, 30),
+                (accounts.eve, 20),
+                (accounts.alice, 50)
+            ]
+            .to_vec()
+        );
+        assert!(contract.get_refund_split(1).is_empty());
+    }
+
+    #[test]
+    fn test_12_patron_cannot_take_back_sponsored_value() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        contract
+            .audit_id_to_sponsors
+            .insert(0, &[accounts.charlie].to_vec());
+        contract
+            .audit_id_to_contributions
+            .insert((0, accounts.charlie), &60);
+        let x = contract.assign_audit(0, accounts.frank, 59, 1000);
+        assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    }
+
+    #[test]
+    fn test_13_accounting_getters() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.alice, 200);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 200);
+        mock::set_balance(accounts.django, accounts.charlie, 100);
+        mock::approve(accounts.django, accounts.charlie, accounts.bob, 100);
+        //a sponsored audit the patron withdraws, the sponsor gets their share of the refund back
+        let sponsored = contract
+            .create_new_payment(100, accounts.frank, 1000, 1, None)
+            .unwrap();
+        assert!(contract.finalize_funding(sponsored).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.contribute(sponsored, 100).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.expire_audit(sponsored).is_ok());
+        assert_eq!(contract.get_original_value(sponsored), 200);
+        assert_eq!(contract.get_refunded_to_patron(sponsored), 100);
+        assert_eq!(contract.get_credit(accounts.charlie, accounts.django), 100);
+        assert_eq!(contract.get_paid_to_auditor(sponsored), 0);
+        //an audit approved by the patron, the auditor is paid 98% of it
+        let approved = contract
+            .create_new_payment(100, accounts.frank, 1000, 2, None)
+            .unwrap();
+        assert!(contract.finalize_funding(approved).is_ok());
+        assert!(contract
+            .assign_audit(approved, accounts.eve, 100, 2000)
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract
+            .mark_submitted(approved, String::from("report"))
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.assess_audit(approved, true).is_ok());
+        assert_eq!(contract.get_original_value(approved), 100);
+        assert_eq!(contract.get_refunded_to_patron(approved), 0);
+        assert_eq!(contract.get_paid_to_auditor(approved), 98);
+        assert_eq!(contract.get_original_value(approved + 1), 0);
+    }
+
+    #[test]
+    fn test_14_failed_creation_below_minimum_value() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_min_audit_value(), 1);
+        let x = contract.create_new_payment(0, accounts.bob, 1000, 7, None);
+        assert!(matches!(x, Err(escrow::Error::ValueBelowMinimum)));
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        let y = contract.assign_audit(0, accounts.frank, 0, 1000);
+        assert!(matches!(y, Err(escrow::Error::ValueBelowMinimum)));
+    }
+
+    #[test]
+    fn test_15_set_treasury_only_by_admin() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_treasury(), accounts.alice);
+        assert!(
+            apply_param_change(&mut contract, escrow::ParamChange::Treasury(accounts.eve)).is_ok()
+        );
+        assert_eq!(contract.get_treasury(), accounts.eve);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let x = apply_param_change(&mut contract, escrow::ParamChange::Treasury(accounts.bob));
+        assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    }
+
+    #[test]
+    fn test_16_anchor_message_by_participants_only() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        assert!(contract.anchor_message(0, "hash1".to_string()).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let x = contract.anchor_message(0, "hash2".to_string());
+        assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+        let ans = contract.get_message_anchors(0);
+        assert_eq!(ans.len(), 1);
+        assert_eq!(ans[0].author, accounts.alice);
+        assert_eq!(ans[0].ipfs_hash, "hash1");
+    }
+
+    #[test]
+    fn test_17_failed_anchor_over_the_cap_or_after_completion() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditCompleted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        for _ in 0..escrow::MAX_ANCHORS_PER_PARTY {
+            assert!(contract.anchor_message(0, "hash".to_string()).is_ok());
+        }
+        let x = contract.anchor_message(0, "hash".to_string());
+        assert!(matches!(x, Err(escrow::Error::TooManyAnchors)));
+        //the patron's anchors don't use up the auditor's
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.anchor_message(0, "hash".to_string()).is_ok());
+        let long = "h".repeat(escrow::MAX_ANCHOR_HASH_LENGTH as usize + 1);
+        assert!(matches!(
+            contract.anchor_message(0, long),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            contract.anchor_message(0, String::new()),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert_eq!(
+            contract.get_message_anchors(0).len() as u32,
+            escrow::MAX_ANCHORS_PER_PARTY + 1
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let y = contract.anchor_message(1, "hash".to_string());
+        assert!(matches!(y, Err(escrow::Error::WrongState)));
+    }
+
+    #[test]
+    fn test_18_report_hash_only_for_participants() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        contract
+            .audit_id_to_ipfs_hash
+            .insert(0, &"report".to_string());
+        assert_eq!(
+            contract.get_report_hash(0).unwrap(),
+            Some("report".to_string())
+        );
+        assert!(contract
+            .register_panel(0, [accounts.charlie].to_vec())
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.get_report_hash(0).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        let x = contract.get_report_hash(0);
+        assert!(matches!(x, Err(escrow::Error::NotAParticipant)));
+        assert!(contract.get_submitted_reports(0).is_none());
+        let y = contract.register_panel(0, [accounts.eve].to_vec());
+        assert!(matches!(y, Err(escrow::Error::UnAuthorisedCall)));
+    }
+
+    #[test]
+    fn test_19_panel_loses_access_after_dispute() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        let _x = contract.register_panel(0, [accounts.charlie].to_vec());
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCompleted,
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let y = contract.get_report_hash(0);
+        assert!(matches!(y, Err(escrow::Error::NotAParticipant)));
+    }
+
+    #[test]
+    fn test_20_process_due_expires_only_overdue_audits() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        //nothing left to refund, so that no transfer is needed
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.value = 0;
+        payment_info.due_at = Some(10);
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        let ans = contract.process_due([0, 1, 2, 0].to_vec()).unwrap();
+        assert!(matches!(ans[0], escrow::DueOutcome::Expired));
+        assert!(matches!(ans[1], escrow::DueOutcome::NothingDue));
+        assert!(matches!(ans[2], escrow::DueOutcome::NotFound));
+        assert!(matches!(ans[3], escrow::DueOutcome::NothingDue));
+        assert!(matches!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditExpired
+        ));
+    }
+
+    #[test]
+    fn test_21_failed_process_due_over_batch_limit() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let ids = (0..escrow::MAX_PROCESS_BATCH + 1).collect();
+        let x = contract.process_due(ids);
+        assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    }
+
+    #[test]
+    fn test_22_before_received_selector_matches_psp22_receiver() {
+        assert_eq!(
+            ink::selector_bytes!("PSP22Receiver::before_received"),
+            [0xfd, 0xa6, 0xf1, 0xa9]
+        );
+    }
+
+    #[test]
+    fn test_23_before_received_only_during_own_transfers() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        //a direct transfer by the stablecoin is refused
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert!(matches!(
+            contract.before_received(accounts.charlie, accounts.charlie, 10, Vec::new()),
+            Err(escrow::PSP22ReceiverError::TransferRejected(_))
+        ));
+        //while the escrow pulls funds, only the stablecoin's hook is accepted
+        contract.receiving_transfer.set(&true);
+        assert!(contract
+            .before_received(accounts.bob, accounts.charlie, 10, Vec::new())
+            .is_ok());
+        assert!(matches!(
+            contract.before_received(accounts.charlie, accounts.charlie, 10, Vec::new()),
+            Err(escrow::PSP22ReceiverError::TransferRejected(_))
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.before_received(accounts.bob, accounts.charlie, 10, Vec::new()),
+            Err(escrow::PSP22ReceiverError::TransferRejected(_))
+        ));
+    }
+
+    #[test]
+    fn test_24_reentrant_calls_refused_during_transfer() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        contract.receiving_transfer.set(&true);
+        assert!(matches!(
+            apply_param_change(&mut contract, escrow::ParamChange::Treasury(accounts.eve)),
+            Err(escrow::Error::ReentrantCall)
+        ));
+        assert!(matches!(
+            contract.expire_audit(0),
+            Err(escrow::Error::ReentrantCall)
+        ));
+        contract.receiving_transfer.set(&false);
+        assert!(
+            apply_param_change(&mut contract, escrow::ParamChange::Treasury(accounts.eve)).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_25_gas_limits_configured_by_admin_only() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        //the stablecoin gets the limit of a token, any other contract the default
+        let config = contract.get_call_config(accounts.django);
+        assert!(config.is_default);
+        assert_eq!(config.gas_limit, escrow::TOKEN_GAS_LIMIT);
+        assert_eq!(
+            contract.get_call_config(accounts.frank).gas_limit,
+            escrow::DEFAULT_GAS_LIMIT
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            apply_param_change(
+                &mut contract,
+                escrow::ParamChange::GasLimit(accounts.django, Some(1_000_000))
+            ),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::GasLimit(accounts.django, Some(1_000_000))
+        )
+        .is_ok());
+        let config = contract.get_call_config(accounts.django);
+        assert!(!config.is_default);
+        assert_eq!(config.gas_limit, 1_000_000);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::GasLimit(accounts.django, None)
+        )
+        .is_ok());
+        assert!(contract.get_call_config(accounts.django).is_default);
+    }
+
+    #[test]
+    fn test_26_expiry_reason_recorded() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        for (id, status) in [
+            (0, escrow::AuditStatus::AuditCreated),
+            (1, escrow::AuditStatus::AuditAssigned),
+            (2, escrow::AuditStatus::AuditCreated),
+        ] {
+            seed_audit(&mut contract, id, accounts.alice, status);
+            //nothing left to refund, so that no transfer is needed
+            let mut payment_info = contract.get_paymentinfo(id).unwrap();
+            payment_info.value = 0;
+            payment_info.due_at = Some(10);
+            contract.audit_id_to_payment_info.insert(id, &payment_info);
+        }
+        assert_eq!(contract.get_expiry_reason(0), None);
+        assert_eq!(contract.is_auditor_at_fault(0), None);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10);
+        assert!(contract.expire_audit(0).is_ok());
+        assert!(contract.expire_audit(1).is_ok());
+        //withdrawn by the patron before its duration was over
+        assert_eq!(
+            contract.get_expiry_reason(0),
+            Some(escrow::ExpiryReason::PatronCancelled)
+        );
+        assert_eq!(contract.is_auditor_at_fault(0), Some(false));
+        assert_eq!(
+            contract.get_expiry_reason(1),
+            Some(escrow::ExpiryReason::AuditorTimeout)
+        );
+        assert_eq!(contract.is_auditor_at_fault(1), Some(true));
+        //open for its whole duration without an auditor
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000000);
+        assert!(contract.expire_audit(2).is_ok());
+        assert_eq!(
+            contract.get_expiry_reason(2),
+            Some(escrow::ExpiryReason::Unassigned)
+        );
+        assert_eq!(contract.is_auditor_at_fault(2), Some(false));
+    }
+
+    #[test]
+    fn test_27_scorecard_stored_with_assessment() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut scorecard = escrow::Scorecard {
+            criticals: 1,
+            highs: 2,
+            mediums: 3,
+            quality: 101,
+        };
+        assert!(matches!(
+            contract.assess_audit_with_scorecard(0, false, Some(scorecard)),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        scorecard.quality = 60;
+        //a failed assessment stores nothing
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract
+            .assess_audit_with_scorecard(0, false, Some(scorecard))
+            .is_err());
+        assert_eq!(contract.get_scorecard(0), None);
+        //a rejection only opens a dispute, the audit isn't closed
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .assess_audit_with_scorecard(0, false, Some(scorecard))
+            .is_ok());
+        assert_eq!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditDisputed
+        );
+        assert_eq!(contract.get_scorecard(0), None);
+        //an assessment that closes the audit mints its reward token with the scorecard attached
+        use crate::reward::mock;
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::RewardToken(Some(accounts.frank))
+        )
+        .is_ok());
+        for id in [1, 2] {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.alice,
+                escrow::AuditStatus::AuditSubmitted,
+            );
+            let mut payment_info = contract.get_paymentinfo(id).unwrap();
+            payment_info.auditor = accounts.eve;
+            contract.audit_id_to_payment_info.insert(id, &payment_info);
+            contract.audit_id_to_submitted_at.insert(id, &250000);
+            contract
+                .audit_id_to_ipfs_hash
+                .insert(id, &String::from("report"));
+        }
+        assert!(contract
+            .assess_audit_with_scorecard(1, true, Some(scorecard))
+            .is_ok());
+        //the token only carries the commitment to the report
+        let mut commitment = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(1u32, "report"), &mut commitment);
+        let minted = crate::reward::MintArgs {
+            recipient: accounts.eve,
+            audit_id: 1,
+            completion_time: 25,
+            extensions: 0,
+            amount: 100,
+            ipfs_hash: commitment
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+            is_positive: true,
+        };
+        assert_eq!(
+            mock::records_of(accounts.frank)[1..],
+            [
+                mock::Record::Minted(0, minted),
+                mock::Record::Scorecard(0, scorecard)
+            ]
+        );
+        //a refused mint doesn't undo the assessment
+        mock::set_rejecting(accounts.frank, 3);
+        assert!(contract
+            .assess_audit_with_scorecard(2, true, Some(scorecard))
+            .is_ok());
+        assert_eq!(
+            contract.get_paymentinfo(2).unwrap().currentstatus,
+            escrow::AuditStatus::AuditCompleted
+        );
+        assert_eq!(mock::records_of(accounts.frank).len(), 3);
+        assert_eq!(contract.get_scorecard(1), Some(scorecard));
+    }
+
+    #[test]
+    fn test_28_blacklisted_accounts_excluded() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.blacklist(accounts.eve),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.blacklist(accounts.eve).is_ok());
+        assert!(contract.is_blacklisted(accounts.eve));
+        assert!(matches!(
+            contract.assign_audit(0, accounts.eve, 100, 1000000),
+            Err(escrow::Error::CallerBlacklisted)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.create_new_payment(100, accounts.frank, 1000, 1, None),
+            Err(escrow::Error::CallerBlacklisted)
+        ));
+        assert!(matches!(
+            contract.mark_submitted(0, String::from("QmHash")),
+            Err(escrow::Error::CallerBlacklisted)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.unblacklist(accounts.eve).is_ok());
+        assert!(!contract.is_blacklisted(accounts.eve));
+        assert!(matches!(
+            contract.unblacklist(accounts.eve),
+            Err(escrow::Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn test_29_retried_creation_returns_existing_audit() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        //as left by an earlier create_new_payment with client_request_id 42
+        contract
+            .client_request_to_audit_id
+            .insert((accounts.alice, 42), &0);
+        assert_eq!(
+            contract
+                .create_new_payment(100, accounts.frank, 1000, 1, Some(42))
+                .unwrap(),
+            0
+        );
+        assert_eq!(contract.get_current_audit_id(), 0);
+        //the request id is per caller
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.create_new_payment(0, accounts.frank, 1000, 1, Some(42)),
+            Err(escrow::Error::ValueBelowMinimum)
+        ));
+    }
+
+    #[test]
+    fn test_30_price_oracle_set_by_admin_only() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_price_oracle(), None);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            apply_param_change(
+                &mut contract,
+                escrow::ParamChange::PriceOracle(Some(accounts.frank))
+            ),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::PriceOracle(Some(accounts.frank))
+        )
+        .is_ok());
+        assert_eq!(contract.get_price_oracle(), Some(accounts.frank));
+    }
+
+    #[test]
+    fn test_31_malformed_input_returns_errors() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        //audit IDs that were never created
+        let invalid = |x: escrow::Result<()>| matches!(x, Err(escrow::Error::InvalidArgument));
+        assert!(invalid(contract.contribute(7, 100)));
+        assert!(invalid(contract.assign_audit(7, accounts.bob, 100, 1000)));
+        assert!(invalid(contract.request_additional_time(7, 1000, 10)));
+        assert!(invalid(contract.approve_additional_time(7)));
+        assert!(invalid(contract.mark_submitted(7, String::from("hash"))));
+        assert!(invalid(contract.assess_audit(7, true)));
+        assert!(invalid(contract.assess_audit_with_scorecard(7, true, None)));
+        assert!(invalid(contract.arbiters_extend_deadline(
+            7,
+            u64::MAX,
+            10,
+            10
+        )));
+        assert!(invalid(contract.expire_audit(7)));
+        assert!(invalid(contract.register_panel(7, Vec::new())));
+        assert!(invalid(contract.anchor_message(7, String::from("hash"))));
+        assert!(contract.get_report_hash(7).is_err());
+        assert!(matches!(
+            contract.process_due([7, u32::MAX].to_vec()).unwrap()[..],
+            [escrow::DueOutcome::NotFound, escrow::DueOutcome::NotFound]
+        ));
+        //nothing to approve without a request from the auditor
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        assert!(invalid(contract.approve_additional_time(1)));
+        //a deadline that overflows the timestamp
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1);
+        assert!(invalid(contract.assign_audit(
+            0,
+            accounts.bob,
+            100,
+            u64::MAX
+        )));
+        assert!(matches!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditCreated
+        ));
+    }
+
+    #[test]
+    fn test_32_archive_audit_after_retention() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCompleted,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        contract.audit_id_to_closed_at.insert(0, &1000);
+        contract
+            .audit_id_to_ipfs_hash
+            .insert(0, &String::from("hash"));
+        assert!(matches!(
+            contract.archive_audit(1),
+            Err(escrow::Error::WrongState)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            1000 + escrow::ARCHIVE_RETENTION_PERIOD - 1,
+        );
+        assert!(matches!(
+            contract.archive_audit(0),
+            Err(escrow::Error::RetentionPeriodNotPassed)
+        ));
+        //anyone can archive once the retention period is over
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            1000 + escrow::ARCHIVE_RETENTION_PERIOD,
+        );
+        assert!(contract.archive_audit(0).is_ok());
+        assert!(contract.get_paymentinfo(0).is_none());
+        assert!(contract.audit_id_to_ipfs_hash.get(0).is_none());
+        let summary = contract.get_archived_audit(0).unwrap();
+        assert_eq!(summary.patron, accounts.alice);
+        assert_eq!(summary.original_value, 100);
+        assert_eq!(summary.closed_at, 1000);
+        assert!(matches!(
+            summary.final_status,
+            escrow::AuditStatus::AuditCompleted
+        ));
+        assert!(matches!(
+            contract.archive_audit(0),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(contract.get_archived_audit(1).is_none());
+    }
+
+    #[test]
+    fn test_33_deadline_warnings_emitted_once() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::DeadlineWarningWindow(10000)
+        )
+        .is_ok());
+        //the deadline of audit 0 is 1000000
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(989999);
+        assert!(contract
+            .emit_deadline_warnings([0, 1, 7].to_vec())
+            .unwrap()
+            .is_empty());
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(990000);
+        let events_before = ink::env::test::recorded_events().count();
+        assert_eq!(
+            contract.emit_deadline_warnings([0, 1, 7].to_vec()).unwrap(),
+            [0].to_vec()
+        );
+        assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+        assert!(contract
+            .emit_deadline_warnings([0].to_vec())
+            .unwrap()
+            .is_empty());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            apply_param_change(&mut contract, escrow::ParamChange::DeadlineWarningWindow(0)),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+    }
+
+    #[test]
+    fn test_34_param_changes_are_timelocked() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let delay = contract.get_param_change_delay();
+        let x = contract
+            .queue_param_change(escrow::ParamChange::Treasury(accounts.eve))
+            .unwrap();
+        let y = contract
+            .queue_param_change(escrow::ParamChange::DeadlineWarningWindow(0))
+            .unwrap();
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(delay - 1);
+        assert!(matches!(
+            contract.execute_param_change(x),
+            Err(escrow::Error::TimelockNotElapsed)
+        ));
+        assert!(contract.cancel_param_change(y).is_ok());
+        assert!(contract.get_pending_param_change(y).is_none());
+        //executing a queued change is permissionless
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.cancel_param_change(x),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(delay);
+        assert!(contract.execute_param_change(x).is_ok());
+        assert_eq!(contract.get_treasury(), accounts.eve);
+        assert!(matches!(
+            contract.execute_param_change(x),
+            Err(escrow::Error::NoPendingChange)
+        ));
+        assert!(matches!(
+            contract.execute_param_change(y),
+            Err(escrow::Error::NoPendingChange)
+        ));
+        assert_eq!(contract.get_deadline_warning_window(), 86400000);
+    }
+
+    #[test]
+    fn test_35_min_deadline_per_value_tier() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        assert_eq!(contract.get_min_deadline(100), 0);
+        let tiers = [(1, 86400000), (100, 1209600000)].to_vec();
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::MinDeadlineTiers(tiers.clone())
+        )
+        .is_ok());
+        assert_eq!(contract.get_min_deadline_tiers(), tiers);
+        assert_eq!(contract.get_min_deadline(99), 86400000);
+        assert_eq!(contract.get_min_deadline(100), 1209600000);
+        assert!(matches!(
+            contract.create_new_payment(100, accounts.frank, 86400000, 1, None),
+            Err(escrow::Error::DeadlineTooShort(1209600000))
+        ));
+        assert!(matches!(
+            contract.assign_audit(0, accounts.bob, 100, 1000000),
+            Err(escrow::Error::DeadlineTooShort(1209600000))
+        ));
+        assert!(matches!(
+            contract
+                .queue_param_change(escrow::ParamChange::MinDeadlineTiers([(1, 1); 11].to_vec())),
+            Err(escrow::Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn test_36_resubmission_after_dispute_awaits_patron_review() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(matches!(
+            contract.get_legacy_status(0),
+            Some(escrow::LegacyAuditStatus::AuditSubmitted)
+        ));
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert!(matches!(
+            contract.multiget_status([0].to_vec())[0],
+            Some(escrow::AuditStatus::AuditDisputed)
+        ));
+        assert!(matches!(
+            contract.get_legacy_status(0),
+            Some(escrow::LegacyAuditStatus::AuditAwaitingValidation)
+        ));
+        //the deadline was extended by the arbiters
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        assert!(contract.mark_submitted(0, String::from("ipfs")).is_ok());
+        assert!(matches!(
+            contract.multiget_status([0].to_vec())[0],
+            Some(escrow::AuditStatus::AuditAwaitingPatronReview)
+        ));
+        assert!(matches!(
+            contract.multiget_legacy_status([0, 1].to_vec())[..],
+            [Some(escrow::LegacyAuditStatus::AuditSubmitted), None]
+        ));
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert_eq!(contract.get_dispute_count(0), 2);
+    }
+
+    #[test]
+    fn test_38_audits_keep_their_token_after_default_changes() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        contract.audit_id_to_token.insert(1, &(accounts.django, 0));
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_default_stablecoin(accounts.frank),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let change_id = contract.set_default_stablecoin(accounts.frank).unwrap();
+        let (_, executable_at) = contract.get_pending_param_change(change_id).unwrap();
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(executable_at);
+        assert!(contract.execute_param_change(change_id).is_ok());
+        assert_eq!(contract.know_your_stablecoin(), accounts.frank);
+        //the audit funded before the change keeps its token, an audit without one recorded uses the default
+        assert_eq!(contract.get_audit_token(1), Some(accounts.django));
+        assert_eq!(contract.get_audit_token(0), Some(accounts.frank));
+        assert_eq!(contract.get_audit_token(2), None);
+    }
+
+    #[test]
+    fn test_39_worklog_committed_by_assigned_auditor() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let hash = ink::primitives::Hash::from([7; 32]);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5);
+        assert!(contract.commit_worklog(0, hash).is_ok());
+        assert_eq!(
+            contract.get_worklog(0),
+            [escrow::WorklogEntry {
+                hash,
+                committed_at: 5
+            }]
+            .to_vec()
+        );
+        assert!(matches!(
+            contract.commit_worklog(1, hash),
+            Err(escrow::Error::WrongState)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.commit_worklog(0, hash),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        for _ in 1..escrow::MAX_WORKLOG_ENTRIES {
+            assert!(contract.commit_worklog(0, hash).is_ok());
+        }
+        assert!(matches!(
+            contract.commit_worklog(0, hash),
+            Err(escrow::Error::TooManyWorklogEntries)
+        ));
+        //the work log is pruned with the rest of the audit once it is archived
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCompleted,
+        );
+        contract.audit_id_to_closed_at.insert(0, &5);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            5 + escrow::ARCHIVE_RETENTION_PERIOD,
+        );
+        assert!(contract.archive_audit(0).is_ok());
+        assert!(contract.get_worklog(0).is_empty());
+    }
+    #[test]
+    fn test_40_co_approval_over_threshold() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.get_remaining_approvals(0).is_empty());
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::CoApprovalThreshold(Some(100))
+        )
+        .is_ok());
+        assert_eq!(contract.get_co_approval_threshold(), Some(100));
+        //the payout of an audit at the threshold needs a reviewer
+        assert!(matches!(
+            contract.assess_audit(0, true),
+            Err(escrow::Error::CoReviewerRequired)
+        ));
+        assert!(matches!(
+            contract.set_co_reviewer(0, accounts.alice),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(contract.set_co_reviewer(0, accounts.eve).is_ok());
+        assert!(matches!(
+            contract.set_co_reviewer(0, accounts.frank),
+            Err(escrow::Error::CoReviewerAlreadySet)
+        ));
+        assert_eq!(
+            contract.get_remaining_approvals(0),
+            vec![accounts.alice, accounts.eve]
+        );
+        //the patron's approval alone doesn't pay out
+        assert!(contract.assess_audit(0, true).is_ok());
+        assert_eq!(contract.get_remaining_approvals(0), vec![accounts.eve]);
+        assert!(matches!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditSubmitted
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.co_approve(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        //a rejection clears the approvals of the submission
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert_eq!(
+            contract.get_co_approval(0),
+            Some(escrow::CoApproval {
+                reviewer: accounts.eve,
+                patron_approved: false,
+                reviewer_approved: false,
+            })
+        );
+    }
+    #[test]
+    fn test_41_creation_waits_for_funding() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(
+            contract
+                .create_new_payment(100, accounts.frank, 1000, 1, Some(7))
+                .unwrap(),
+            0
+        );
+        //nothing is locked until the patron funds the audit
+        assert!(contract.get_paymentinfo(0).is_none());
+        let pending = contract.get_pending_funding(0).unwrap();
+        assert_eq!(pending.patron, accounts.alice);
+        assert_eq!(pending.value, 100);
+        assert_eq!(contract.get_current_audit_id(), 1);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.finalize_funding(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.cancel_pending_funding(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.cancel_pending_funding(0).is_ok());
+        assert!(contract.get_pending_funding(0).is_none());
+        assert!(matches!(
+            contract.finalize_funding(0),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        //the request id is free again once the audit is cancelled
+        assert_eq!(
+            contract
+                .create_new_payment(100, accounts.frank, 1000, 1, Some(7))
+                .unwrap(),
+            1
+        );
+    }
+    #[test]
+    fn test_42_waiver_voucher_replaces_dispute_bond() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert_eq!(contract.get_dispute_bond(), 0);
+        assert!(apply_param_change(&mut contract, escrow::ParamChange::DisputeBond(10)).is_ok());
+        assert_eq!(contract.get_dispute_bond(), 10);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.mint_waiver_vouchers(accounts.alice, 2),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.mint_waiver_vouchers(accounts.alice, 2).is_ok());
+        assert_eq!(contract.get_waiver_vouchers(accounts.alice), 2);
+        //the voucher is used instead of the bond
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert_eq!(contract.get_waiver_vouchers(accounts.alice), 1);
+        assert_eq!(contract.get_dispute_count(0), 1);
+    }
+    #[test]
+    fn test_43_checkpoints_submitted_before_final_report() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        assert!(matches!(
+            contract.set_checkpoints(0, [2000, 1000].to_vec()),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(contract.set_checkpoints(0, [1000, 2000].to_vec()).is_ok());
+        assert_eq!(contract.get_checkpoints(0).unwrap().len(), 2);
+        //as left by assign_audit
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.currentstatus = escrow::AuditStatus::AuditAssigned;
+        payment_info.due_at = Some(1000000);
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        assert!(contract
+            .submit_checkpoint(0, 0, String::from("QmFirst"))
+            .is_ok());
+        assert!(contract.assess_checkpoint(0, 0, false).is_ok());
+        assert_eq!(
+            contract.get_checkpoints(0).unwrap()[0].status,
+            escrow::CheckpointStatus::Pending
+        );
+        assert!(contract
+            .submit_checkpoint(0, 0, String::from("QmFirst"))
+            .is_ok());
+        assert!(contract.assess_checkpoint(0, 0, true).is_ok());
+        //the second checkpoint isn't due yet, so it can't be missed and blocks the final report
+        assert!(matches!(
+            contract.miss_checkpoint(0, 1),
+            Err(escrow::Error::WrongState)
+        ));
+        assert!(matches!(
+            contract.mark_submitted(0, String::from("QmFinal")),
+            Err(escrow::Error::CheckpointsPending)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            payment_info.starttime + 2001,
+        );
+        assert!(matches!(
+            contract.submit_checkpoint(0, 1, String::from("QmLate")),
+            Err(escrow::Error::CheckpointOverdue)
+        ));
+        //the reports of the checkpoints are only visible to the participants, the events only commit to them
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(matches!(
+            contract.get_checkpoints(0),
+            Err(escrow::Error::NotAParticipant)
+        ));
+        let report = scale::Encode::encode(&String::from("QmFirst"));
+        assert!(ink::env::test::recorded_events()
+            .all(|event| !event.data.windows(report.len()).any(|data| data == report)));
+    }
+    #[test]
+    fn test_44_ids_by_status_follow_transitions() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        for id in [2, 0, 1] {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.alice,
+                escrow::AuditStatus::AuditCreated,
+            );
+            assert!(contract
+                .assign_audit(id, accounts.alice, 100, 1000000)
+                .is_ok());
+        }
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, None, 10),
+            vec![2, 0, 1]
+        );
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, Some(2), 1),
+            vec![0]
+        );
+        //the audits after the one leaving the status keep their order
+        assert!(contract.mark_submitted(2, String::from("QmHash")).is_ok());
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, None, 10),
+            vec![0, 1]
+        );
+        assert!(contract.mark_submitted(0, String::from("QmHash")).is_ok());
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, None, 10),
+            vec![1]
+        );
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditSubmitted, None, 10),
+            vec![2, 0]
+        );
+        assert_eq!(
+            contract.get_status_count(escrow::AuditStatus::AuditAssigned),
+            1
+        );
+        assert_eq!(
+            contract.get_status_count(escrow::AuditStatus::AuditDisputed),
+            0
+        );
+    }
+    #[test]
+    fn test_45_arbiters_extend_deadline_bounds() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        let new_deadline = escrow::MIN_DEADLINE_EXTENSION;
+        for (haircut, arbitersshare) in [
+            (escrow::MAX_HAIRCUT + 1, 0),
+            (0, escrow::MAX_ARBITERS_SHARE + 1),
+            (escrow::MAX_HAIRCUT + 1, escrow::MAX_ARBITERS_SHARE),
+            (u128::MAX, 1),
+        ] {
+            assert!(matches!(
+                contract.arbiters_extend_deadline(0, new_deadline, haircut, arbitersshare),
+                Err(escrow::Error::ArbitersExtendDeadlineConditionsNotMet)
+            ));
+        }
+        assert!(matches!(
+            contract.arbiters_extend_deadline(
+                0,
+                new_deadline - 1,
+                escrow::MAX_HAIRCUT,
+                escrow::MAX_ARBITERS_SHARE
+            ),
+            Err(escrow::Error::ArbitersExtendDeadlineConditionsNotMet)
+        ));
+        let payment_info = contract.get_paymentinfo(0).unwrap();
+        assert_eq!(payment_info.value, 100);
+        assert_eq!(payment_info.due_at, Some(1000000));
+        //a ruling exactly at the caps and the shortest extension is accepted
+        assert!(contract
+            .arbiters_extend_deadline(
+                0,
+                new_deadline,
+                escrow::MAX_HAIRCUT,
+                escrow::MAX_ARBITERS_SHARE
+            )
+            .is_ok());
+        let payment_info = contract.get_paymentinfo(0).unwrap();
+        assert_eq!(payment_info.due_at, Some(new_deadline));
+        assert!(matches!(
+            payment_info.currentstatus,
+            escrow::AuditStatus::AuditAssigned
+        ));
+    }
+    #[test]
+    fn test_46_report_versions_kept_across_disputes() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        assert_eq!(contract.get_submission_count(0), 0);
+        assert!(contract.mark_submitted(0, String::from("hash1")).is_ok());
+        assert!(contract.assess_audit(0, false).is_ok());
+        //the deadline was extended by the arbiters
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        let events_before = ink::env::test::recorded_events().count();
+        assert!(contract.mark_submitted(0, String::from("hash2")).is_ok());
+        assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+        assert_eq!(contract.get_submission_count(0), 2);
+        assert_eq!(
+            contract.get_submission(0, 1).unwrap(),
+            Some(String::from("hash1"))
+        );
+        assert_eq!(
+            contract.get_submission(0, 2).unwrap(),
+            Some(String::from("hash2"))
+        );
+        assert_eq!(contract.get_submission(0, 3).unwrap(), None);
+        assert_eq!(
+            contract.get_report_hash(0).unwrap(),
+            Some(String::from("hash2"))
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.get_submission(0, 1),
+            Err(escrow::Error::NotAParticipant)
+        ));
+    }
+    #[test]
+    fn test_47_recent_auditors_of_the_patron() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        for (id, auditor) in [(0, accounts.bob), (1, accounts.charlie), (2, accounts.eve)] {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.alice,
+                escrow::AuditStatus::AuditCreated,
+            );
+            assert!(contract.assign_audit(id, auditor, 100, 1000000).is_ok());
+        }
+        assert_eq!(
+            contract.get_patron_recent_auditors(0, 2),
+            vec![accounts.eve, accounts.charlie]
+        );
+        assert_eq!(
+            contract.get_patron_recent_auditors(2, 10),
+            vec![accounts.eve, accounts.charlie, accounts.bob]
+        );
+        assert!(contract.get_patron_recent_auditors(3, 10).is_empty());
+        //only the latest MAX_RECENT_AUDITORS are remembered
+        for id in 3..3 + escrow::MAX_RECENT_AUDITORS {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.alice,
+                escrow::AuditStatus::AuditCreated,
+            );
+            assert!(contract
+                .assign_audit(id, accounts.frank, 100, 1000000)
+                .is_ok());
+        }
+        let recent = contract.get_patron_recent_auditors(0, u32::MAX);
+        assert_eq!(recent.len(), escrow::MAX_RECENT_AUDITORS as usize);
+        assert!(!recent.contains(&accounts.bob));
+    }
+    #[test]
+    fn test_48_followup_links_to_completed_audit() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert!(contract
+            .create_new_payment(100, accounts.alice, 1000000, 1, None)
+            .is_ok());
+        assert!(contract.cancel_pending_funding(0).is_ok());
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        assert!(contract.set_co_reviewer(0, accounts.eve).is_ok());
+        assert!(matches!(
+            contract.create_followup(0),
+            Err(escrow::Error::WrongState)
+        ));
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCompleted,
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.create_followup(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.create_followup(0).unwrap(), 1);
+        let pending = contract.get_pending_funding(1).unwrap();
+        assert_eq!(pending.patron, accounts.alice);
+        assert_eq!(pending.value, 100);
+        assert_eq!(pending.arbiter_provider, accounts.alice);
+        assert_eq!(pending.duration_ms, 1000000);
+        assert_eq!(contract.get_parent_audit(1), Some(0));
+        assert_eq!(contract.get_parent_audit(0), None);
+        let co_approval = contract.get_co_approval(1).unwrap();
+        assert_eq!(co_approval.reviewer, accounts.eve);
+        assert!(!co_approval.patron_approved);
+        //a cancelled follow-up leaves no lineage behind
+        assert!(contract.cancel_pending_funding(1).is_ok());
+        assert_eq!(contract.get_parent_audit(1), None);
+        assert!(contract.get_co_approval(1).is_none());
+    }
+    #[test]
+    fn test_49_paymaster_refunds_provider_gas() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_gas_refund_per_call(), 0);
+        assert!(
+            apply_param_change(&mut contract, escrow::ParamChange::GasRefundPerCall(10)).is_ok()
+        );
+        assert_eq!(contract.get_gas_refund_per_call(), 10);
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        //a provider who didn't opt in isn't refunded
+        assert!(contract.register_panel(0, [accounts.eve].to_vec()).is_ok());
+        assert!(contract.get_paymaster_pot(accounts.alice).is_none());
+        //a pot as fund_paymaster would have opened it
+        contract.paymaster_pots.insert(
+            accounts.alice,
+            &escrow::PaymasterPot {
+                token: accounts.django,
+                balance: 15,
+                owed: 0,
+            },
+        );
+        assert!(contract.register_panel(0, [accounts.eve].to_vec()).is_ok());
+        let pot = contract.get_paymaster_pot(accounts.alice).unwrap();
+        assert_eq!((pot.balance, pot.owed), (5, 10));
+        //only what is left in the pot is refunded
+        assert!(contract.register_panel(0, [accounts.eve].to_vec()).is_ok());
+        assert!(contract.register_panel(0, [accounts.eve].to_vec()).is_ok());
+        let pot = contract.get_paymaster_pot(accounts.alice).unwrap();
+        assert_eq!((pot.balance, pot.owed), (0, 15));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.claim_gas_refunds(),
+            Err(escrow::Error::NoPaymasterPot)
+        ));
+        assert!(matches!(
+            contract.close_paymaster(),
+            Err(escrow::Error::NoPaymasterPot)
+        ));
+    }
+
+    #[test]
+    fn test_50_scope_change() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.propose_scope_change(0, String::from("scope-v2"), 0, 500000),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .propose_scope_change(0, String::from("scope-v2"), 0, -1000000)
+            .is_ok());
+        assert!(matches!(
+            contract.accept_scope_change(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.accept_scope_change(0),
+            Err(escrow::Error::DeadlinePassed)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .propose_scope_change(0, String::from("scope-v2"), 0, 500000)
+            .is_ok());
+        assert_eq!(
+            contract.get_pending_scope_change(0).unwrap().deadline_delta,
+            500000
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.accept_scope_change(0).is_ok());
+        assert_eq!(contract.get_paymentinfo(0).unwrap().due_at, Some(1500000));
+        assert_eq!(contract.get_paymentinfo(0).unwrap().value, 100);
+        assert!(contract.get_pending_scope_change(0).is_none());
+        let history = contract.get_scope_changes(0);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].metadata_hash, String::from("scope-v2"));
+        assert!(history[0].accepted_at.is_some());
+        assert!(matches!(
+            contract.accept_scope_change(0),
+            Err(escrow::Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn test_51_obligations_follow_locked_value() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_obligations(accounts.django), 0);
+        //nothing is owed, so the token isn't queried
+        assert!(matches!(contract.check_solvency(accounts.django), Ok(0)));
+        assert!(matches!(
+            contract.get_solvency_ratio(accounts.django),
+            Ok(None)
+        ));
+        //a scope change that keeps the value keeps what is owed
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        contract.obligations.insert(accounts.django, &100);
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        assert!(contract
+            .propose_scope_change(0, String::from("scope-v2"), 0, 1000)
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.accept_scope_change(0).is_ok());
+        assert_eq!(contract.get_obligations(accounts.django), 100);
+    }
+
+    #[test]
+    fn test_52_retention_clause() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        assert!(matches!(
+            contract.set_retention(0, escrow::MAX_RETENTION_PERCENTAGE + 1),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(contract.set_retention(0, 10).is_ok());
+        assert_eq!(contract.get_retention_percentage(0), 10);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_retention(0, 20),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        //a completed audit whose payout held back 9 of the auditor's 95
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.currentstatus = escrow::AuditStatus::AuditCompleted;
+        payment_info.auditor = accounts.frank;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        let retention = escrow::Retention {
+            amount: 9,
+            releasable_at: escrow::RETENTION_PERIOD,
+        };
+        contract.audit_id_to_retention.insert(0, &retention);
+        assert!(matches!(
+            contract.release_retention(0),
+            Err(escrow::Error::RetentionPeriodNotPassed)
+        ));
+        let ans = contract.process_due([0].to_vec()).unwrap();
+        assert!(matches!(ans[0], escrow::DueOutcome::NothingDue));
+        assert_eq!(contract.get_retention(0), Some(retention));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.set_retention(0, 20),
+            Err(escrow::Error::WrongState)
+        ));
+    }
+
+    #[test]
+    fn test_53_token_transfers_through_psp22() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        //pulling needs both the balance and the allowance
+        mock::set_balance(accounts.django, accounts.charlie, 50);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(matches!(
+            contract.fund_paymaster(50),
+            Err(escrow::Error::InsufficientBalance)
+        ));
+        mock::approve(accounts.django, accounts.charlie, accounts.bob, 50);
+        assert!(contract.fund_paymaster(50).is_ok());
+        assert_eq!(mock::balance_of(accounts.django, accounts.bob), 50);
+        assert_eq!(
+            mock::allowance_of(accounts.django, accounts.charlie, accounts.bob),
+            0
+        );
+        assert!(matches!(contract.check_solvency(accounts.django), Ok(0)));
+        assert!(contract.close_paymaster().is_ok());
+        assert_eq!(contract.get_credit(accounts.charlie, accounts.django), 50);
+        assert_eq!(contract.get_obligations(accounts.django), 50);
+        assert_eq!(
+            contract.withdraw().ok(),
+            Some([(accounts.django, 50)].to_vec())
+        );
+        assert_eq!(mock::balance_of(accounts.django, accounts.charlie), 50);
+        assert!(matches!(
+            contract.withdraw(),
+            Err(escrow::Error::NothingToWithdraw)
+        ));
+        //an approved submission is credited, and withdrawn out of the escrow's balance
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        payment_info.arbiterprovider = accounts.frank;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.assess_audit(0, true).is_ok());
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 98);
+        assert_eq!(contract.get_credit(accounts.frank, accounts.django), 2);
+        //a transfer the escrow can't make leaves the credit for a later withdrawal
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(contract.withdraw().ok(), Some(Vec::new()));
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 98);
+        mock::set_balance(accounts.django, accounts.bob, 100);
+        assert!(contract.withdraw().is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.withdraw().is_ok());
+        assert_eq!(mock::balance_of(accounts.django, accounts.eve), 98);
+        assert_eq!(mock::balance_of(accounts.django, accounts.frank), 2);
+        assert_eq!(mock::balance_of(accounts.django, accounts.bob), 0);
+        assert_eq!(contract.get_credit_tokens(accounts.eve), Vec::new());
+        assert!(matches!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditCompleted
+        ));
+        //a token that can't be called isn't accepted as the stablecoin
+        mock::set_unreachable(accounts.django);
+        assert!(matches!(
+            escrow::Escrow::new(accounts.django, accounts.alice),
+            Err(escrow::Error::TokenQueryFailed)
+        ));
+    }
+
+    #[test]
+    fn test_54_payout_swapped_into_preferred_token() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        let preference = escrow::PayoutPreference {
+            token: accounts.charlie,
+            max_slippage_bps: 100,
+        };
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.set_payout_preference(0, Some(preference)),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_payout_preference(0, Some(preference)),
+            Err(escrow::Error::NoDexRouter)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::DexRouter(Some(accounts.frank))
+        )
+        .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_payout_preference(0, Some(preference)),
+            Err(escrow::Error::TokenNotAllowed)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::PayoutToken(accounts.charlie, true)
+        )
+        .is_ok());
+        assert!(contract.is_payout_token(accounts.charlie));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_payout_preference(
+                0,
+                Some(escrow::PayoutPreference {
+                    token: accounts.charlie,
+                    max_slippage_bps: escrow::MAX_PAYOUT_SLIPPAGE_BPS + 1,
+                })
+            ),
+            Err(escrow::Error::SlippageTooHigh)
+        ));
+        assert!(contract.set_payout_preference(0, Some(preference)).is_ok());
+        assert_eq!(contract.get_payout_preference(0), Some(preference));
+        //the router fills at 2 of the other token for 1, within 1% of its quote
+        mock::set_balance(accounts.django, accounts.bob, 100);
+        mock::set_balance(accounts.charlie, accounts.frank, 1000);
+        mock::set_rates(accounts.frank, accounts.charlie, 20000, 19900);
+        payment_info.currentstatus = escrow::AuditStatus::AuditSubmitted;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.assess_audit(0, true).is_ok());
+        assert_eq!(contract.get_credit(accounts.eve, accounts.charlie), 195);
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 0);
+        assert_eq!(mock::balance_of(accounts.django, accounts.frank), 98);
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), 2);
+        //past the slippage bound the auditor is paid in the audit's token
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        payment_info.currentstatus = escrow::AuditStatus::AuditAssigned;
+        contract.audit_id_to_payment_info.insert(1, &payment_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.set_payout_preference(1, Some(preference)).is_ok());
+        mock::set_balance(accounts.django, accounts.bob, 100);
+        mock::set_rates(accounts.frank, accounts.charlie, 20000, 19000);
+        payment_info.currentstatus = escrow::AuditStatus::AuditSubmitted;
+        contract.audit_id_to_payment_info.insert(1, &payment_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.assess_audit(1, true).is_ok());
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 98);
+        assert_eq!(contract.get_credit(accounts.eve, accounts.charlie), 195);
+        assert_eq!(
+            mock::allowance_of(accounts.django, accounts.bob, accounts.frank),
+            0
+        );
+    }
+
+    #[test]
+    fn test_55_close_audit_with_final_accounting() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.charlie,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        payment_info.arbiterprovider = accounts.frank;
+        payment_info.value = 101;
+        payment_info.original_value = 101;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(matches!(
+            contract.close_audit(0),
+            Err(escrow::Error::WrongState)
+        ));
+        mock::set_balance(accounts.django, accounts.bob, 101);
+        assert!(contract.assess_audit(0, true).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.close_audit(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.close_audit(0).is_ok());
+        let settlement = contract.get_settlement(0).unwrap();
+        assert_eq!(settlement.locked, 101);
+        assert_eq!(settlement.paid_to_auditor, 98);
+        assert_eq!(settlement.refunded_to_patron, 0);
+        assert_eq!(settlement.fees, 2);
+        assert_eq!(settlement.dust, 1);
+        assert_eq!(settlement.final_status, escrow::AuditStatus::AuditCompleted);
+        assert_eq!(contract.get_credit(accounts.frank, accounts.django), 2);
+        //the treasury defaults to the admin
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), 1);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.close_audit(0),
+            Err(escrow::Error::AuditAlreadyClosed)
+        ));
+        assert!(contract.get_settlement(1).is_none());
+    }
+
+    #[test]
+    fn test_56_operator_limited_to_housekeeping() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.charlie,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        assert!(contract.get_operator().is_none());
+        //without an operator, anyone can do the housekeeping
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.emit_deadline_warnings([0].to_vec()).is_ok());
+        assert!(matches!(
+            contract.set_operator(Some(accounts.eve)),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_operator(Some(accounts.frank)).is_ok());
+        assert_eq!(contract.get_operator(), Some(accounts.frank));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.emit_deadline_warnings([0].to_vec()),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.process_due([0].to_vec()),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.archive_audit(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.emit_deadline_warnings([0].to_vec()).is_ok());
+        assert!(matches!(
+            contract.process_due([0].to_vec()).unwrap()[..],
+            [escrow::DueOutcome::NothingDue]
+        ));
+        assert!(matches!(
+            contract.archive_audit(0),
+            Err(escrow::Error::WrongState)
+        ));
+        //the operator can't touch parameters or accounts
+        assert!(matches!(
+            contract.queue_param_change(escrow::ParamChange::Treasury(accounts.frank)),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.blacklist(accounts.charlie),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.set_operator(None),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.emit_deadline_warnings([0].to_vec()).is_ok());
+        assert!(contract.set_operator(None).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.process_due([0].to_vec()).is_ok());
+    }
+
+    #[test]
+    fn test_57_payout_commitment_matches_executed_payout() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.charlie,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        payment_info.arbiterprovider = accounts.frank;
+        payment_info.value = 101;
+        payment_info.original_value = 101;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        let preview = contract.payout_preview(0).unwrap();
+        assert_eq!(
+            (
+                preview.to_auditor,
+                preview.retained,
+                preview.to_arbiter_provider,
+                preview.dust
+            ),
+            (98, 0, 2, 1)
+        );
+        assert_eq!(preview.dust_receiver, accounts.alice);
+        let commitment = contract.payout_commitment(0).unwrap();
+        assert!(contract.payout_preview(1).is_none());
+        //a parameter change between the ruling and the payout changes the commitment
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::Treasury(accounts.django)
+        )
+        .is_ok());
+        let changed = contract.payout_commitment(0).unwrap();
+        assert_ne!(changed, commitment);
+        assert!(contract.get_executed_payout_commitment(0).is_none());
+        mock::set_balance(accounts.django, accounts.bob, 101);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.assess_audit(0, true).is_ok());
+        assert_eq!(contract.get_executed_payout_commitment(0), Some(changed));
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 98);
+        assert!(contract.payout_preview(0).is_none());
+    }
+
+    #[test]
+    fn test_58_rounding_parts_add_up_to_the_whole() {
+        use crate::math;
+        //every share is floored, and the parts of a split always add up to the value
+        let values = (0..=1000).chain([
+            9_999,
+            10_001,
+            123_456_789,
+            u64::MAX as u128,
+            u128::MAX - 1,
+            u128::MAX,
+        ]);
+        for value in values {
+            for (payee, provider) in [(98, 2), (95, 5), (90, 10), (0, 0), (100, 0), (33, 33)] {
+                let split = math::split(value, payee, provider);
+                assert_eq!(split.payee + split.provider + split.remainder, value);
+                //each floor loses less than one unit of the shares that add up to the whole value
+                assert!(payee + provider != 100 || split.remainder < 2);
+                if value <= u64::MAX as u128 {
+                    assert_eq!(split.payee, value * payee / 100);
+                    assert_eq!(split.provider, value * provider / 100);
                 }
             }
-            Err(Error::UnAuthorisedCall)
+            for bps in [0, 1, 50, 1000, 9999, 10000] {
+                let part = math::bps_of(value, bps);
+                assert!(part <= value);
+                if value <= u64::MAX as u128 {
+                    assert_eq!(part, value * bps / 10000);
+                }
+            }
+        }
+        //under the patron policy the dust of an approval is refunded to the patron
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(
+            contract.get_rounding_policy(),
+            math::RoundingPolicy::Treasury
+        );
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::RoundingPolicy(math::RoundingPolicy::Patron)
+        )
+        .is_ok());
+        assert_eq!(contract.get_rounding_policy(), math::RoundingPolicy::Patron);
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.charlie,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.auditor = accounts.eve;
+        payment_info.arbiterprovider = accounts.frank;
+        payment_info.value = 101;
+        payment_info.original_value = 101;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        assert_eq!(
+            contract.payout_preview(0).unwrap().dust_receiver,
+            accounts.charlie
+        );
+        mock::set_balance(accounts.django, accounts.bob, 101);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.assess_audit(0, true).is_ok());
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 98);
+        assert_eq!(contract.get_credit(accounts.frank, accounts.django), 2);
+        assert_eq!(contract.get_credit(accounts.charlie, accounts.django), 1);
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), 0);
+    }
+    #[test]
+    fn test_59_drafts_take_an_audit_id_once_funded() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let draft_id = contract
+            .create_draft(100, accounts.frank, 1000, String::from("scope-v1"))
+            .unwrap();
+        let deleted = contract
+            .create_draft(0, accounts.frank, 0, String::new())
+            .unwrap();
+        //drafts don't take audit IDs
+        assert_eq!(contract.get_current_audit_id(), 0);
+        assert_eq!(
+            contract.get_drafts(accounts.alice),
+            [draft_id, deleted].to_vec()
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.update_draft(draft_id, 200, accounts.frank, 1000, String::new()),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.delete_draft(draft_id),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.fund_draft(draft_id, 1),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.delete_draft(deleted).is_ok());
+        assert!(contract.get_draft(deleted).is_none());
+        assert!(matches!(
+            contract.fund_draft(deleted, 1),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(contract
+            .update_draft(
+                draft_id,
+                150,
+                accounts.frank,
+                1000,
+                String::from("scope-v2")
+            )
+            .is_ok());
+        assert_eq!(contract.get_draft(draft_id).unwrap().value, 150);
+        //nothing is pulled without an allowance, and the draft is kept
+        assert!(matches!(
+            contract.fund_draft(draft_id, 1),
+            Err(escrow::Error::InsufficientBalance)
+        ));
+        assert!(contract.get_draft(draft_id).is_some());
+        mock::set_balance(accounts.django, accounts.alice, 150);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 150);
+        assert_eq!(contract.fund_draft(draft_id, 1).unwrap(), 0);
+        assert_eq!(contract.get_current_audit_id(), 1);
+        assert_eq!(mock::balance_of(accounts.django, accounts.bob), 150);
+        let payment_info = contract.get_paymentinfo(0).unwrap();
+        assert_eq!(payment_info.value, 150);
+        assert_eq!(payment_info.arbiterprovider, accounts.frank);
+        assert_eq!(
+            payment_info.currentstatus,
+            escrow::AuditStatus::AuditCreated
+        );
+        assert_eq!(
+            contract.get_metadata_hash(0),
+            Some(String::from("scope-v2"))
+        );
+        assert!(contract.get_draft(draft_id).is_none());
+        assert!(contract.get_drafts(accounts.alice).is_empty());
+    }
+    #[test]
+    fn test_60_import_legacy_audits_under_their_ids() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        let legacy =
+            |legacy_id: u32, value: u128, status: escrow::AuditStatus| escrow::LegacyAudit {
+                legacy_id,
+                payment_info: escrow::LegacyPaymentInfo {
+                    patron: accounts.charlie,
+                    auditor: accounts.eve,
+                    value,
+                    arbiterprovider: accounts.frank,
+                    deadline: 1000000,
+                    starttime: 0,
+                    currentstatus: status,
+                    original_value: 100,
+                    refunded_to_patron: 0,
+                    paid_to_auditor: 100 - value,
+                    fiat_value_at_creation: None,
+                    fiat_value_at_completion: None,
+                },
+                ipfs_hash: None,
+            };
+        let mut completed = legacy(2, 0, escrow::AuditStatus::AuditCompleted);
+        completed.ipfs_hash = Some(String::from("report"));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.import_audit(vec![legacy(5, 100, escrow::AuditStatus::AuditAssigned)]),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        //the value locked in the imported audits comes from the admin
+        assert!(matches!(
+            contract.import_audit(vec![legacy(5, 100, escrow::AuditStatus::AuditAssigned)]),
+            Err(escrow::Error::InsufficientBalance)
+        ));
+        mock::set_balance(accounts.django, accounts.alice, 100);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 100);
+        assert!(matches!(
+            contract.import_audit(vec![legacy(
+                u32::MAX,
+                0,
+                escrow::AuditStatus::AuditCompleted
+            )]),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert!(contract
+            .import_audit(vec![
+                legacy(5, 100, escrow::AuditStatus::AuditAssigned),
+                completed
+            ])
+            .is_ok());
+        assert_eq!(mock::balance_of(accounts.django, accounts.bob), 100);
+        assert_eq!(mock::balance_of(accounts.django, accounts.alice), 0);
+        assert_eq!(
+            contract.get_paymentinfo(5).unwrap().currentstatus,
+            escrow::AuditStatus::AuditAssigned
+        );
+        //the deadline of the assigned audit is split into its duration and due date
+        let payment_info = contract.get_paymentinfo(5).unwrap();
+        assert_eq!(payment_info.due_at, Some(1000000));
+        assert_eq!(payment_info.duration_ms, 1000000);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(
+            contract.get_report_hash(2).ok().flatten(),
+            Some(String::from("report"))
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.get_submission_count(2), 1);
+        //new audits are numbered after the imported ones
+        assert_eq!(contract.get_current_audit_id(), 6);
+        assert_eq!(contract.get_obligations(accounts.django), 100);
+        assert!(matches!(
+            contract.import_audit(vec![legacy(5, 100, escrow::AuditStatus::AuditAssigned)]),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            contract.get_import_deadline() + 1,
+        );
+        assert!(matches!(
+            contract.import_audit(vec![legacy(7, 100, escrow::AuditStatus::AuditCreated)]),
+            Err(escrow::Error::ImportWindowClosed)
+        ));
+    }
+    #[test]
+    fn test_61_due_at_is_only_set_at_assignment() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.alice, 100);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 100);
+        assert_eq!(
+            contract
+                .create_new_payment(100, accounts.frank, 1000, 1, None)
+                .unwrap(),
+            0
+        );
+        assert!(contract.finalize_funding(0).is_ok());
+        let payment_info = contract.get_paymentinfo(0).unwrap();
+        assert_eq!(payment_info.duration_ms, 1000);
+        assert_eq!(payment_info.due_at, None);
+        //an unassigned audit can't be warned about or expire for its duration
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+        assert_eq!(
+            contract.emit_deadline_warnings([0].to_vec()).ok(),
+            Some(Vec::new())
+        );
+        assert!(matches!(
+            contract.process_due([0].to_vec()).ok().as_deref(),
+            Some([escrow::DueOutcome::NothingDue])
+        ));
+        assert!(contract.assign_audit(0, accounts.eve, 100, 2000).is_ok());
+        let payment_info = contract.get_paymentinfo(0).unwrap();
+        assert_eq!(payment_info.duration_ms, 2000);
+        assert_eq!(payment_info.due_at, Some(7000));
+        assert_eq!(payment_info.starttime, 5000);
+    }
+
+    #[test]
+    fn test_62_silence_policy_applied_by_keeper() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.alice, 100);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 100);
+        contract
+            .create_new_payment(100, accounts.frank, 1000, 1, None)
+            .unwrap();
+        assert!(contract.finalize_funding(0).is_ok());
+        assert_eq!(
+            contract.get_paymentinfo(0).unwrap().silence_policy,
+            escrow::SilencePolicy::Hold
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_silence_policy(0, escrow::SilencePolicy::AutoApprove),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_silence_policy(0, escrow::SilencePolicy::AutoApprove)
+            .is_ok());
+        assert!(contract.assign_audit(0, accounts.eve, 100, 2000).is_ok());
+        //the policy is fixed once the auditor took the job
+        assert!(matches!(
+            contract.set_silence_policy(0, escrow::SilencePolicy::Hold),
+            Err(escrow::Error::WrongState)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        assert!(contract.mark_submitted(0, String::from("report")).is_ok());
+        assert_eq!(contract.get_submitted_at(0), Some(1000));
+        let review_ends = 1000 + escrow::PATRON_REVIEW_WINDOW;
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(review_ends - 1);
+        assert!(matches!(
+            contract.process_due([0].to_vec()).ok().as_deref(),
+            Some([escrow::DueOutcome::NothingDue])
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(review_ends);
+        assert!(matches!(
+            contract.process_due([0].to_vec()).ok().as_deref(),
+            Some([escrow::DueOutcome::AutoApproved])
+        ));
+        assert_eq!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditCompleted
+        );
+        assert!(contract.get_credit(accounts.eve, accounts.django) > 0);
+        //a patron who chose to dispute a silent review, and one who holds it
+        for (id, policy) in [
+            (1, escrow::SilencePolicy::AutoDispute),
+            (2, escrow::SilencePolicy::Hold),
+        ] {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.charlie,
+                escrow::AuditStatus::AuditSubmitted,
+            );
+            let mut payment_info = contract.get_paymentinfo(id).unwrap();
+            payment_info.silence_policy = policy;
+            contract.audit_id_to_payment_info.insert(id, &payment_info);
+            contract.audit_id_to_submitted_at.insert(id, &1000);
+        }
+        assert!(matches!(
+            contract.process_due([1, 2].to_vec()).ok().as_deref(),
+            Some([
+                escrow::DueOutcome::AutoDisputed,
+                escrow::DueOutcome::NothingDue
+            ])
+        ));
+        assert_eq!(
+            contract.get_paymentinfo(1).unwrap().currentstatus,
+            escrow::AuditStatus::AuditDisputed
+        );
+        assert_eq!(
+            contract.get_paymentinfo(2).unwrap().currentstatus,
+            escrow::AuditStatus::AuditSubmitted
+        );
+    }
+
+    #[test]
+    fn test_63_version_and_interface_hash() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.version(), 1);
+        //every message of the contract is listed in the interface, and nothing else
+        extern "Rust" {
+            fn __ink_generate_metadata() -> ink::metadata::InkProject;
+        }
+        let metadata = unsafe { __ink_generate_metadata() };
+        let mut selectors: Vec<[u8; 4]> = metadata
+            .spec()
+            .messages()
+            .iter()
+            .map(|message| message.selector().to_bytes().try_into().unwrap())
+            .collect();
+        selectors.sort_unstable();
+        let mut interface = crate::interface::MESSAGES.to_vec();
+        interface.sort_unstable();
+        assert_eq!(interface, selectors);
+        let mut hash = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+            &<[[u8; 4]; crate::interface::MESSAGES.len()]>::try_from(selectors).unwrap(),
+            &mut hash,
+        );
+        assert_eq!(contract.interface_hash(), hash);
+    }
+
+    #[test]
+    fn test_64_closed_audits_reported_for_patron_history() {
+        use crate::reward::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_reward_token(), None);
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::RewardToken(Some(accounts.frank))
+        )
+        .is_ok());
+        assert_eq!(contract.get_reward_token(), Some(accounts.frank));
+        let reported = |id: u32, disputed: bool, dispute_won: bool| {
+            let data = scale::Encode::encode(&(id, accounts.alice, disputed, dispute_won, true));
+            ink::env::test::recorded_events().any(|event| event.data.ends_with(&data))
+        };
+        //alice is the patron, the auditor and the arbiter provider of the seeded audits
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.assess_audit(0, true).is_ok());
+        assert!(reported(0, false, false));
+        //the patron wins the dispute when the arbiters reject the report
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.assess_audit(1, false).is_ok());
+        assert!(!reported(1, true, true));
+        assert!(contract.assess_audit(1, false).is_ok());
+        assert!(reported(1, true, true));
+        //and loses it when they approve it
+        seed_audit(
+            &mut contract,
+            2,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.assess_audit(2, false).is_ok());
+        assert!(contract.assess_audit(2, true).is_ok());
+        assert!(reported(2, true, false));
+        assert_eq!(
+            mock::records_of(accounts.frank),
+            [
+                mock::Record::PatronAudit(accounts.alice, 0, false, false),
+                mock::Record::PatronAudit(accounts.alice, 1, true, true),
+                mock::Record::PatronAudit(accounts.alice, 2, true, false),
+            ]
+            .to_vec()
+        );
+        //a reward token that refuses the record doesn't hold up the closing, the event reports it
+        mock::set_rejecting(accounts.frank, 0);
+        seed_audit(
+            &mut contract,
+            3,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.assess_audit(3, true).is_ok());
+        let data = scale::Encode::encode(&(3u32, accounts.alice, false, false, false));
+        assert!(ink::env::test::recorded_events().any(|event| event.data.ends_with(&data)));
+        assert_eq!(mock::records_of(accounts.frank).len(), 3);
+    }
+
+    #[test]
+    fn test_65_arbiter_provider_declines_dispute() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.arbiterprovider = accounts.frank;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        assert!(apply_param_change(&mut contract, escrow::ParamChange::DisputeBond(10)).is_ok());
+        mock::set_balance(accounts.django, accounts.alice, 10);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 10);
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert_eq!(mock::balance_of(accounts.django, accounts.frank), 10);
+        assert!(matches!(
+            contract.decline_arbitration(0, [1; 32]),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        //the bond is pulled back from the provider and credited to the patron
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        mock::approve(accounts.django, accounts.frank, accounts.bob, 10);
+        assert!(contract.decline_arbitration(0, [1; 32]).is_ok());
+        assert!(matches!(
+            contract.decline_arbitration(0, [1; 32]),
+            Err(escrow::Error::WrongState)
+        ));
+        let payment_info = contract.get_paymentinfo(0).unwrap();
+        assert!(payment_info.currentstatus == escrow::AuditStatus::AuditSubmitted);
+        assert_eq!(contract.get_dispute_count(0), 0);
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), 10);
+        assert_eq!(
+            contract
+                .get_declined_arbitration(0)
+                .map(|declined| declined.provider),
+            Some(accounts.frank)
+        );
+        //the audit can't go back to the provider who declined it
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.assess_audit(0, false),
+            Err(escrow::Error::ProviderDeclined)
+        ));
+        assert!(matches!(
+            contract.nominate_fallback_provider(0),
+            Err(escrow::Error::NoFallbackProvider)
+        ));
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::FallbackArbiterProvider(Some(accounts.eve))
+        )
+        .is_ok());
+        assert_eq!(contract.get_fallback_arbiter_provider(), Some(accounts.eve));
+        assert!(contract.nominate_fallback_provider(0).is_ok());
+        assert_eq!(
+            contract.get_paymentinfo(0).unwrap().arbiterprovider,
+            accounts.eve
+        );
+        assert_eq!(contract.get_declined_arbitration(0), None);
+        //the fallback provider declines as well, the bond comes out of their unwithdrawn credit first
+        mock::set_balance(accounts.django, accounts.alice, 10);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 10);
+        assert!(contract.assess_audit(0, false).is_ok());
+        assert_eq!(mock::balance_of(accounts.django, accounts.eve), 10);
+        contract.credits.insert((accounts.eve, accounts.django), &4);
+        contract
+            .account_to_credit_tokens
+            .insert(accounts.eve, &[accounts.django].to_vec());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        mock::approve(accounts.django, accounts.eve, accounts.bob, 6);
+        assert!(contract.decline_arbitration(0, [2; 32]).is_ok());
+        assert_eq!(contract.get_credit(accounts.eve, accounts.django), 0);
+        assert!(contract.get_credit_tokens(accounts.eve).is_empty());
+        assert_eq!(mock::balance_of(accounts.django, accounts.eve), 4);
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), 20);
+    }
+
+    #[test]
+    fn test_66_process_due_continues_past_a_failed_audit() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert!(apply_param_change(&mut contract, escrow::ParamChange::DisputeBond(10)).is_ok());
+        //a silent review the patron chose to dispute, but the bond can't be taken from them
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.charlie,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        let mut payment_info = contract.get_paymentinfo(0).unwrap();
+        payment_info.silence_policy = escrow::SilencePolicy::AutoDispute;
+        payment_info.arbiterprovider = accounts.frank;
+        contract.audit_id_to_payment_info.insert(0, &payment_info);
+        contract.audit_id_to_submitted_at.insert(0, &0);
+        //an assigned audit past its deadline
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.charlie,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        let now = 1000000 + escrow::PATRON_REVIEW_WINDOW;
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+        assert!(matches!(
+            contract.process_due([0, 1, 2].to_vec()).ok().as_deref(),
+            Some([
+                escrow::DueOutcome::Failed(escrow::Error::DisputeBondFailed),
+                escrow::DueOutcome::Expired,
+                escrow::DueOutcome::NotFound
+            ])
+        ));
+        //the failed dispute left its audit as it was
+        assert_eq!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditSubmitted
+        );
+        assert_eq!(contract.get_dispute_count(0), 0);
+        assert_eq!(
+            contract.get_paymentinfo(1).unwrap().currentstatus,
+            escrow::AuditStatus::AuditExpired
+        );
+        //once the patron can pay the bond the next run disputes the audit
+        mock::set_balance(accounts.django, accounts.charlie, 10);
+        mock::approve(accounts.django, accounts.charlie, accounts.bob, 10);
+        assert!(matches!(
+            contract.process_due([0].to_vec()).ok().as_deref(),
+            Some([escrow::DueOutcome::AutoDisputed])
+        ));
+        assert_eq!(
+            contract.get_paymentinfo(0).unwrap().currentstatus,
+            escrow::AuditStatus::AuditDisputed
+        );
+        assert_eq!(contract.get_dispute_count(0), 1);
+        assert_eq!(mock::balance_of(accounts.django, accounts.frank), 10);
+    }
+    #[test]
+    fn test_67_deployment_set_once_by_instantiator() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert_eq!(contract.get_voting_contract(), None);
+        //not even the admin can wire the deployment
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.set_deployment(accounts.eve, accounts.charlie),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract
+            .set_deployment(accounts.eve, accounts.charlie)
+            .is_ok());
+        assert_eq!(contract.get_voting_contract(), Some(accounts.eve));
+        assert_eq!(contract.get_reward_token(), Some(accounts.charlie));
+        assert!(matches!(
+            contract.set_deployment(accounts.charlie, accounts.eve),
+            Err(escrow::Error::InvalidArgument)
+        ));
+        assert_eq!(contract.get_voting_contract(), Some(accounts.eve));
+        assert_eq!(contract.get_reward_token(), Some(accounts.charlie));
+    }
+    #[test]
+    fn test_68_silence_policy_set_while_pending_funding() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.alice, 100);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 100);
+        let id = contract
+            .create_new_payment(100, accounts.frank, 1000, 1, None)
+            .unwrap();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_silence_policy(id, escrow::SilencePolicy::AutoApprove),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_silence_policy(id, escrow::SilencePolicy::AutoApprove)
+            .is_ok());
+        assert!(contract.get_paymentinfo(id).is_none());
+        assert!(contract.finalize_funding(id).is_ok());
+        assert_eq!(
+            contract.get_paymentinfo(id).unwrap().silence_policy,
+            escrow::SilencePolicy::AutoApprove
+        );
+    }
+    #[test]
+    fn test_69_additional_time_approved_once_while_assigned() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.alice, 100);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 100);
+        let id = contract
+            .create_new_payment(100, accounts.frank, 1000, 1, None)
+            .unwrap();
+        assert!(contract.finalize_funding(id).is_ok());
+        //an unassigned audit has no auditor to give more time to
+        assert!(matches!(
+            contract.approve_additional_time(id),
+            Err(escrow::Error::WrongState)
+        ));
+        assert!(contract.assign_audit(id, accounts.eve, 100, 2000).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        let invalid = |x: escrow::Result<()>| matches!(x, Err(escrow::Error::InvalidArgument));
+        assert!(invalid(contract.request_additional_time(
+            id,
+            5000,
+            escrow::MAX_HAIRCUT + 1
+        )));
+        assert!(invalid(contract.request_additional_time(id, 0, 10)));
+        assert!(contract.request_additional_time(id, 5000, 10).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.approve_additional_time(id).is_ok());
+        //the request is used up, approving it again doesn't take another haircut
+        assert!(invalid(contract.approve_additional_time(id)));
+        let payment_info = contract.get_paymentinfo(id).unwrap();
+        assert_eq!(payment_info.value, 90);
+        assert_eq!(payment_info.due_at, Some(5000));
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), 10);
+        //a request left over when the audit is completed can't be approved
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.request_additional_time(id, 6000, 10).is_ok());
+        assert!(contract.mark_submitted(id, String::from("report")).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.assess_audit(id, true).is_ok());
+        //alice is also the treasury, credited the dust of the payout
+        let credit = contract.get_credit(accounts.alice, accounts.django);
+        let value = contract.get_paymentinfo(id).unwrap().value;
+        assert!(matches!(
+            contract.approve_additional_time(id),
+            Err(escrow::Error::WrongState)
+        ));
+        assert_eq!(contract.get_credit(accounts.alice, accounts.django), credit);
+        assert_eq!(contract.get_paymentinfo(id).unwrap().value, value);
+    }
+    #[test]
+    fn test_70_sponsored_refund_of_18_decimal_amounts() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        //20000 tokens of 18 decimals each, their product doesn't fit in a u128
+        let value: u128 = 20_000 * 1_000_000_000_000_000_000;
+        mock::set_balance(accounts.django, accounts.alice, value);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, value);
+        mock::set_balance(accounts.django, accounts.charlie, value);
+        mock::approve(accounts.django, accounts.charlie, accounts.bob, value);
+        let id = contract
+            .create_new_payment(value, accounts.frank, 1000, 1, None)
+            .unwrap();
+        assert!(contract.finalize_funding(id).is_ok());
+        //the patron is the auditor of an unassigned audit, but can't cut its value with a time request
+        assert!(matches!(
+            contract.request_additional_time(id, 5000, 10),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.contribute(id, value).is_ok());
+        assert_eq!(
+            contract.get_refund_split(id),
+            [(accounts.charlie, value), (accounts.alice, value)].to_vec()
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.expire_audit(id).is_ok());
+        assert_eq!(
+            contract.get_credit(accounts.charlie, accounts.django),
+            value
+        );
+        assert_eq!(contract.get_refunded_to_patron(id), value);
+    }
+    #[test]
+    fn test_71_expire_audit_returns_the_settlement_error() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditAssigned,
+        );
+        assert!(matches!(
+            contract.expire_audit(0),
+            Err(escrow::Error::WrongState)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.expire_audit(0),
+            Err(escrow::Error::UnAuthorisedCall)
+        ));
+        //a sponsor's contribution no refund split can pay out
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        seed_audit(
+            &mut contract,
+            1,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        contract
+            .audit_id_to_sponsors
+            .insert(1, &[accounts.charlie].to_vec());
+        contract
+            .audit_id_to_contributions
+            .insert((1, accounts.charlie), &u128::MAX);
+        assert!(matches!(
+            contract.expire_audit(1),
+            Err(escrow::Error::ArithmeticOverflow)
+        ));
+    }
+    #[test]
+    fn test_72_ids_by_status_page_across_a_status_change() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        for id in 0..5 {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.alice,
+                escrow::AuditStatus::AuditCreated,
+            );
+            assert!(contract
+                .assign_audit(id, accounts.alice, 100, 1000000)
+                .is_ok());
+        }
+        let first = contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, None, 2);
+        assert_eq!(first, vec![0, 1]);
+        //an audit of the first page and one of the next page leave the status, a new one enters it
+        assert!(contract.mark_submitted(0, String::from("QmHash")).is_ok());
+        assert!(contract.mark_submitted(3, String::from("QmHash")).is_ok());
+        seed_audit(
+            &mut contract,
+            5,
+            accounts.alice,
+            escrow::AuditStatus::AuditCreated,
+        );
+        assert!(contract
+            .assign_audit(5, accounts.alice, 100, 1000000)
+            .is_ok());
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, Some(1), 2),
+            vec![2, 4]
+        );
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditAssigned, Some(4), 2),
+            vec![5]
+        );
+        //a page can't start after an audit that has left the status
+        assert!(contract
+            .get_ids_by_status(escrow::AuditStatus::AuditAssigned, Some(0), 2)
+            .is_empty());
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditSubmitted, None, 10),
+            vec![0, 3]
+        );
+        assert_eq!(
+            contract.get_status_count(escrow::AuditStatus::AuditAssigned),
+            4
+        );
+    }
+    #[test]
+    fn test_73_failed_withdraw_leaves_the_credit() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.charlie, 50);
+        mock::approve(accounts.django, accounts.charlie, accounts.bob, 50);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.fund_paymaster(50).is_ok());
+        assert!(contract.close_paymaster().is_ok());
+        //an account with no credit has nothing to withdraw, and takes nothing from the others
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.withdraw(),
+            Err(escrow::Error::NothingToWithdraw)
+        ));
+        assert_eq!(contract.get_credit(accounts.charlie, accounts.django), 50);
+        assert_eq!(contract.get_obligations(accounts.django), 50);
+        assert_eq!(mock::balance_of(accounts.django, accounts.bob), 50);
+        //a transfer that fails withdraws nothing, the credit stays as it was
+        mock::set_unreachable(accounts.django);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(contract.withdraw().ok(), Some(Vec::new()));
+        assert_eq!(contract.get_credit(accounts.charlie, accounts.django), 50);
+        assert_eq!(
+            contract.get_credit_tokens(accounts.charlie),
+            vec![accounts.django]
+        );
+        assert_eq!(contract.get_obligations(accounts.django), 50);
+    }
+    #[test]
+    fn test_74_rejection_by_arbiter_provider_announces_the_final_info() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditDisputed,
+        );
+        assert!(contract.assess_audit(0, false).is_ok());
+        let payment_info = contract.get_paymentinfo(0);
+        assert!(matches!(
+            payment_info.as_ref().unwrap().currentstatus,
+            escrow::AuditStatus::AuditExpired
+        ));
+        let data = scale::Encode::encode(&(Some(0u32), payment_info, Some(accounts.alice)));
+        assert!(ink::env::test::recorded_events().any(|event| event.data.ends_with(&data)));
+    }
+
+    #[test]
+    fn test_75_settlement_of_a_sponsored_audit() {
+        use crate::token::mock;
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        mock::set_balance(accounts.django, accounts.alice, 100);
+        mock::approve(accounts.django, accounts.alice, accounts.bob, 100);
+        mock::set_balance(accounts.django, accounts.charlie, 100);
+        mock::approve(accounts.django, accounts.charlie, accounts.bob, 100);
+        let id = contract
+            .create_new_payment(100, accounts.frank, 1000, 1, None)
+            .unwrap();
+        assert!(contract.finalize_funding(id).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.contribute(id, 100).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.expire_audit(id).is_ok());
+        assert_eq!(contract.get_refunded_to_sponsors(id), 100);
+        assert!(contract.close_audit(id).is_ok());
+        let settlement = contract.get_settlement(id).unwrap();
+        assert_eq!(settlement.locked, 200);
+        assert_eq!(settlement.refunded_to_patron, 100);
+        assert_eq!(settlement.refunded_to_sponsors, 100);
+        assert_eq!(settlement.paid_to_auditor, 0);
+        assert_eq!(settlement.fees, 0);
+    }
+
+    #[test]
+    fn test_76_ids_by_status_page_after_an_audit_in_another_status() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        for id in 0..3 {
+            seed_audit(
+                &mut contract,
+                id,
+                accounts.alice,
+                escrow::AuditStatus::AuditCreated,
+            );
+            assert!(contract
+                .assign_audit(id, accounts.alice, 100, 1000000)
+                .is_ok());
+        }
+        for id in 0..3 {
+            assert!(contract.mark_submitted(id, String::from("QmHash")).is_ok());
         }
+        //1 isn't the head of the submitted audits, its links are those of that list
+        assert!(contract
+            .get_ids_by_status(escrow::AuditStatus::AuditAssigned, Some(1), 10)
+            .is_empty());
+        assert_eq!(
+            contract.get_ids_by_status(escrow::AuditStatus::AuditSubmitted, Some(1), 10),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_77_scorecard_waits_for_the_co_approval() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = escrow::Escrow::new(accounts.django, accounts.alice).unwrap();
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::RewardToken(Some(accounts.frank))
+        )
+        .is_ok());
+        assert!(apply_param_change(
+            &mut contract,
+            escrow::ParamChange::CoApprovalThreshold(Some(100))
+        )
+        .is_ok());
+        seed_audit(
+            &mut contract,
+            0,
+            accounts.alice,
+            escrow::AuditStatus::AuditSubmitted,
+        );
+        assert!(contract.set_co_reviewer(0, accounts.eve).is_ok());
+        let scorecard = escrow::Scorecard {
+            criticals: 0,
+            highs: 1,
+            mediums: 4,
+            quality: 75,
+        };
+        //the patron's approval alone doesn't close the audit, so it records no scorecard and mints nothing
+        assert!(contract
+            .assess_audit_with_scorecard(0, true, Some(scorecard))
+            .is_ok());
+        assert_eq!(contract.get_remaining_approvals(0), vec![accounts.eve]);
+        assert_eq!(contract.get_scorecard(0), None);
+        assert!(crate::reward::mock::records_of(accounts.frank)
+            .iter()
+            .all(|record| !matches!(
+                record,
+                crate::reward::mock::Record::Minted(..)
+                    | crate::reward::mock::Record::Scorecard(..)
+            )));
     }
 }
@@ -0,0 +1,14 @@
+// the limits on what an arbiter provider or governance can rule when extending a deadline. They are shared
+// with escrow_with_tests, so both contracts accept the same rulings.
+
+type Balance = u128;
+type Timestamp = u64;
+
+// the largest haircut, in percent of the value, an arbiter provider or governance can rule when extending a deadline.
+pub const MAX_HAIRCUT: Balance = 90;
+// the largest share of the value, in percent, the arbiter provider can take when extending a deadline,
+// together with the haircut it can never be more than the whole value.
+pub const MAX_ARBITERS_SHARE: Balance = 10;
+const _: () = assert!(MAX_HAIRCUT + MAX_ARBITERS_SHARE <= 100);
+// the least a ruling has to push the deadline out by, 1 day.
+pub const MIN_DEADLINE_EXTENSION: Timestamp = 86400000;
@@ -0,0 +1,201 @@
+// the percentage math of the escrow. Every share is floored to whoever it is paid to, and what the floors
+// leave of the value is the remainder, which goes where the RoundingPolicy of the escrow says. So the parts
+// of a split always add up to the value, whichever path of the escrow splits it.
+
+type Balance = u128;
+
+// where the rounding remainder of a split goes.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum RoundingPolicy {
+    // swept to the treasury, the default
+    Treasury,
+    // refunded to the patron of the audit
+    Patron,
+}
+
+// a value split between a payee, the auditor or the patron, and the arbiter provider. remainder is what
+// the floors of the two shares left of the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Split {
+    pub payee: Balance,
+    pub provider: Balance,
+    pub remainder: Balance,
+}
+
+// percentage (0 to 100) of the value, floored.
+pub fn percent_of(value: Balance, percentage: Balance) -> Balance {
+    fraction_of(value, percentage, 100)
+}
+
+// bps (basis points, 0 to 10000) of the value, floored.
+pub fn bps_of(value: Balance, bps: Balance) -> Balance {
+    fraction_of(value, bps, 10000)
+}
+
+// splits the value into payee_percentage of it for the payee and provider_percentage for the arbiter
+// provider, both floored, the rest is the remainder. The percentages can't add up to more than 100, every
+// caller splits by constant percentages, so more is a bug and debug builds panic on it.
+pub fn split(value: Balance, payee_percentage: Balance, provider_percentage: Balance) -> Split {
+    debug_assert!(
+        payee_percentage.saturating_add(provider_percentage) <= 100,
+        "the shares of a split add up to more than the value"
+    );
+    let payee = percent_of(value, payee_percentage);
+    let provider = percent_of(value, provider_percentage);
+    Split {
+        payee,
+        provider,
+        remainder: value.saturating_sub(payee).saturating_sub(provider),
+    }
+}
+
+// numerator / denominator of the value, floored. The value is divided first, so that the product can't
+// overflow for any value: value * n / d == (value / d) * n + (value % d) * n / d.
+fn fraction_of(value: Balance, numerator: Balance, denominator: Balance) -> Balance {
+    (value / denominator)
+        .saturating_mul(numerator)
+        .saturating_add((value % denominator).saturating_mul(numerator) / denominator)
+}
+
+// value * numerator / denominator, floored, None if the denominator is 0 or the quotient doesn't fit in a
+// Balance. The product is taken in 256 bits, so unlike fraction_of it is exact for any numerator, e.g. a
+// contribution of the same magnitude as the value.
+pub fn mul_div(value: Balance, numerator: Balance, denominator: Balance) -> Option<Balance> {
+    if denominator == 0 {
+        return None;
+    }
+    let (high, low) = widening_mul(value, numerator);
+    if high >= denominator {
+        return None;
+    }
+    //long division of the 256 bit product, one bit of the low half at a time
+    let mut remainder = high;
+    let mut quotient: Balance = 0;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+// the 256 bit product of a and b, as its high and low 128 bits.
+fn widening_mul(a: Balance, b: Balance) -> (Balance, Balance) {
+    const MASK: Balance = u64::MAX as Balance;
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let middle = (low_low >> 64) + (low_high & MASK) + (high_low & MASK);
+    let low = (low_low & MASK) | (middle << 64);
+    let high = a_high * b_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+    (high, low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the values the properties are checked on, the edges of the Balance range and pseudo-random values of
+    // every magnitude from a fixed xorshift seed, so a failure reproduces.
+    fn values() -> Vec<Balance> {
+        let mut values: Vec<Balance> = (0..=300).collect();
+        values.extend([u64::MAX as Balance, Balance::MAX - 1, Balance::MAX]);
+        let mut state: u128 = 0x2545_f491_4f6c_dd1d;
+        for shift in 0..128 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            values.push(state >> shift);
+        }
+        values
+    }
+
+    #[test]
+    fn split_parts_add_up_to_the_value() {
+        for value in values() {
+            for payee_percentage in 0..=100 {
+                for provider_percentage in 0..=100 - payee_percentage {
+                    let split = split(value, payee_percentage, provider_percentage);
+                    assert_eq!(split.payee + split.provider + split.remainder, value);
+                    assert_eq!(split.payee, percent_of(value, payee_percentage));
+                    assert_eq!(split.provider, percent_of(value, provider_percentage));
+                    //each of the two floors loses less than one unit
+                    if payee_percentage + provider_percentage == 100 {
+                        assert!(split.remainder < 2);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fractions_are_floored_and_never_exceed_the_value() {
+        for value in values() {
+            for bps in [0, 1, 50, 999, 1000, 5000, 9999, 10000] {
+                let part = bps_of(value, bps);
+                assert!(part <= value);
+                if value <= u64::MAX as Balance {
+                    assert_eq!(part, value * bps / 10000);
+                }
+            }
+            assert_eq!(percent_of(value, 100), value);
+            assert_eq!(percent_of(value, 0), 0);
+        }
+    }
+
+    #[test]
+    fn mul_div_is_exact_where_the_product_overflows() {
+        for value in values() {
+            for numerator in [0, 1, 7, u64::MAX as Balance, value] {
+                for denominator in [1, 3, 100, u64::MAX as Balance, Balance::MAX] {
+                    match value.checked_mul(numerator) {
+                        Some(product) => {
+                            assert_eq!(
+                                mul_div(value, numerator, denominator),
+                                Some(product / denominator)
+                            )
+                        }
+                        //the quotient is the largest one whose product with the denominator doesn't exceed the product
+                        None => {
+                            if let Some(quotient) = mul_div(value, numerator, denominator) {
+                                let product = widening_mul(value, numerator);
+                                assert!(widening_mul(quotient, denominator) <= product);
+                                if let Some(next) = quotient.checked_add(1) {
+                                    assert!(widening_mul(next, denominator) > product);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        //a refund of 20 tokens of 18 decimals split by a contribution of 20 of 40 tokens
+        let token: Balance = 1_000_000_000_000_000_000;
+        assert_eq!(
+            mul_div(20 * token, 20 * token, 40 * token),
+            Some(10 * token)
+        );
+        assert_eq!(
+            mul_div(Balance::MAX, Balance::MAX, Balance::MAX),
+            Some(Balance::MAX)
+        );
+        assert_eq!(mul_div(Balance::MAX, 2, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than the value")]
+    fn split_over_the_whole_value_panics() {
+        split(100, 98, 5);
+    }
+}
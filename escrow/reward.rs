@@ -0,0 +1,210 @@
+// typed calls to the reward token the escrow reports closed audits to and mints their tokens on. In tests
+// the calls are recorded by an in-memory reward token, see mock.
+
+use crate::escrow::Scorecard;
+use ink::prelude::string::String;
+use ink::primitives::AccountId;
+
+type Balance = u128;
+
+// why a call to the reward token didn't succeed.
+// the mock doesn't fail calls, it only rejects them.
+#[cfg_attr(test, allow(dead_code))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RewardError {
+    // the reward token answered the call with an error, the index of its Error variant
+    Rejected(u8),
+    // the call itself failed, e.g. the token trapped, ran out of gas or answered something undecodable
+    CallFailed,
+}
+
+pub type Result<T> = core::result::Result<T, RewardError>;
+
+// the arguments of a mint, encoded like the MintArgs of the reward token.
+#[derive(scale::Encode, Debug, Clone, PartialEq, Eq)]
+pub struct MintArgs {
+    pub recipient: AccountId,
+    pub audit_id: u32,
+    // the time the auditor took to submit, in percent of the duration of the audit
+    pub completion_time: u8,
+    pub extensions: u8,
+    pub amount: Balance,
+    pub ipfs_hash: String,
+    pub is_positive: bool,
+}
+
+// the reward token called with the gas limit the escrow uses for it, see gas_limit_for.
+#[derive(Debug, Clone, Copy)]
+pub struct RewardToken {
+    pub token: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl RewardToken {
+    pub fn new(token: AccountId, gas_limit: u64) -> Self {
+        Self { token, gas_limit }
+    }
+}
+
+#[cfg(not(test))]
+impl RewardToken {
+    // records the closed audit in the history of the patron.
+    pub fn record_patron_audit(
+        &self,
+        patron: AccountId,
+        id: u32,
+        disputed: bool,
+        dispute_won: bool,
+    ) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("record_patron_audit"),
+                ))
+                .push_arg(patron)
+                .push_arg(id)
+                .push_arg(disputed)
+                .push_arg(dispute_won),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        match result {
+            Ok(Ok(Ok(()))) => Ok(()),
+            Ok(Ok(Err(code))) => Err(RewardError::Rejected(code)),
+            _ => Err(RewardError::CallFailed),
+        }
+    }
+
+    // mints a token, returns its ID. The mint goes through mint_batch, the mint message of the reward token
+    // that answers with the IDs of the tokens it minted, and emits TokenMinted for them like mint does.
+    pub fn mint(&self, args: MintArgs) -> Result<u32> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("mint_batch"),
+                ))
+                .push_arg(ink::prelude::vec![args]),
+            )
+            .returns::<core::result::Result<ink::prelude::vec::Vec<core::result::Result<u32, u8>>, u8>>()
+            .try_invoke();
+        match result {
+            Ok(Ok(Ok(minted))) => match minted.first() {
+                Some(Ok(token_id)) => Ok(*token_id),
+                Some(Err(code)) => Err(RewardError::Rejected(*code)),
+                None => Err(RewardError::CallFailed),
+            },
+            Ok(Ok(Err(code))) => Err(RewardError::Rejected(code)),
+            _ => Err(RewardError::CallFailed),
+        }
+    }
+
+    // attaches the scorecard the audit of the token was assessed with to the token.
+    pub fn record_scorecard(&self, token_id: u32, scorecard: Scorecard) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("record_scorecard"),
+                ))
+                .push_arg(token_id)
+                .push_arg(scorecard),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        match result {
+            Ok(Ok(Ok(()))) => Ok(()),
+            Ok(Ok(Err(code))) => Err(RewardError::Rejected(code)),
+            _ => Err(RewardError::CallFailed),
+        }
+    }
+}
+
+#[cfg(test)]
+impl RewardToken {
+    pub fn record_patron_audit(
+        &self,
+        patron: AccountId,
+        id: u32,
+        disputed: bool,
+        dispute_won: bool,
+    ) -> Result<()> {
+        mock::record(
+            self.token,
+            mock::Record::PatronAudit(patron, id, disputed, dispute_won),
+        )
+    }
+
+    pub fn mint(&self, args: MintArgs) -> Result<u32> {
+        mock::mint(self.token, args)
+    }
+
+    pub fn record_scorecard(&self, token_id: u32, scorecard: Scorecard) -> Result<()> {
+        mock::record(self.token, mock::Record::Scorecard(token_id, scorecard))
+    }
+}
+
+// the reward token the escrow calls in tests, it accepts every record until a test sets it rejecting.
+#[cfg(test)]
+pub mod mock {
+    use super::{AccountId, MintArgs, Result, RewardError, Scorecard};
+    use crate::offchain::{read, write};
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    // a record the reward token accepted.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Record {
+        // patron, audit, disputed, dispute won
+        PatronAudit(AccountId, u32, bool, bool),
+        // the token minted, with the ID it was given
+        Minted(u32, MintArgs),
+        // token, scorecard
+        Scorecard(u32, Scorecard),
+    }
+
+    thread_local! {
+        static RECORDS: RefCell<BTreeMap<AccountId, Vec<Record>>> = const { RefCell::new(BTreeMap::new()) };
+        static REJECTING: RefCell<BTreeMap<AccountId, u8>> = const { RefCell::new(BTreeMap::new()) };
+    }
+
+    // every later call to the reward token is answered with the error code, as when the escrow isn't a minter.
+    pub fn set_rejecting(token: AccountId, code: u8) {
+        write(&REJECTING, |rejecting| rejecting.insert(token, code));
+    }
+
+    // the records the reward token accepted, in order.
+    pub fn records_of(token: AccountId) -> Vec<Record> {
+        read(&RECORDS, |records| {
+            records.get(&token).cloned().unwrap_or_default()
+        })
+    }
+
+    // the tokens are numbered from 0 in the order they are minted, like the reward token does.
+    pub(super) fn mint(token: AccountId, args: MintArgs) -> Result<u32> {
+        let token_id = records_of(token)
+            .iter()
+            .filter(|record| matches!(record, Record::Minted(..)))
+            .count() as u32;
+        record(token, Record::Minted(token_id, args))?;
+        Ok(token_id)
+    }
+
+    pub(super) fn record(token: AccountId, record: Record) -> Result<()> {
+        if let Some(code) = read(&REJECTING, |rejecting| rejecting.get(&token).copied()) {
+            return Err(RewardError::Rejected(code));
+        }
+        write(&RECORDS, |records| {
+            records.entry(token).or_default().push(record)
+        });
+        Ok(())
+    }
+}
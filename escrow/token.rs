@@ -0,0 +1,467 @@
+// typed calls to the PSP22 tokens the escrow holds payments in and to the DEX router it swaps them on.
+// In tests they move balances of an in-memory ledger, see mock.
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+type Balance = u128;
+
+// the error a PSP22 token answers a rejected call with, the layout of the marketplace stablecoin.
+// it is only decoded from the token's answer, which the mock doesn't produce.
+#[cfg_attr(test, allow(dead_code))]
+#[derive(scale::Decode, scale::Encode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Psp22Error {
+    InsufficientBalance,
+    InsufficientAllowance,
+}
+
+// why a call to a token didn't succeed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TokenError {
+    // the token answered the call with an error
+    Rejected,
+    // the call itself failed, e.g. the token trapped, ran out of gas or answered something undecodable
+    CallFailed,
+}
+
+// the error a DEX router answers a rejected swap with.
+#[cfg_attr(test, allow(dead_code))]
+#[derive(scale::Decode, scale::Encode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RouterError {
+    InsufficientOutputAmount,
+    InsufficientLiquidity,
+    TransferFailed,
+}
+
+pub type Result<T> = core::result::Result<T, TokenError>;
+
+// a PSP22 token called with the gas limit the escrow uses for it, see gas_limit_for.
+#[derive(Debug, Clone, Copy)]
+pub struct Psp22 {
+    pub token: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl Psp22 {
+    pub fn new(token: AccountId, gas_limit: u64) -> Self {
+        Self { token, gas_limit }
+    }
+}
+
+// a DEX router that swaps along a path of PSP22 tokens, called with a fixed gas limit.
+#[derive(Debug, Clone, Copy)]
+pub struct Router {
+    pub router: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl Router {
+    pub fn new(router: AccountId, gas_limit: u64) -> Self {
+        Self { router, gas_limit }
+    }
+}
+
+#[cfg(not(test))]
+impl Router {
+    // how much of the last token of path the router would give for amount_in of the first one.
+    pub fn get_amount_out(&self, amount_in: Balance, path: Vec<AccountId>) -> Result<Balance> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.router)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("get_amount_out"),
+                ))
+                .push_arg(amount_in)
+                .push_arg(path),
+            )
+            .returns::<Balance>()
+            .try_invoke();
+        query(result)
+    }
+
+    // swaps amount_in of the first token of path, pulled from the calling contract out of the allowance
+    // it gave the router, for at least min_amount_out of the last one sent to to. Returns the amount out.
+    pub fn swap(
+        &self,
+        amount_in: Balance,
+        min_amount_out: Balance,
+        path: Vec<AccountId>,
+        to: AccountId,
+    ) -> Result<Balance> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.router)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("swap_exact_tokens_for_tokens"),
+                ))
+                .push_arg(amount_in)
+                .push_arg(min_amount_out)
+                .push_arg(path)
+                .push_arg(to),
+            )
+            .returns::<core::result::Result<Balance, RouterError>>()
+            .try_invoke();
+        settle(result)
+    }
+}
+
+#[cfg(not(test))]
+fn settle<T, E>(
+    result: ink::env::Result<ink::MessageResult<core::result::Result<T, E>>>,
+) -> Result<T> {
+    match result {
+        Ok(Ok(Ok(value))) => Ok(value),
+        Ok(Ok(Err(_))) => Err(TokenError::Rejected),
+        _ => Err(TokenError::CallFailed),
+    }
+}
+
+#[cfg(not(test))]
+fn query<T>(result: ink::env::Result<ink::MessageResult<T>>) -> Result<T> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        _ => Err(TokenError::CallFailed),
+    }
+}
+
+#[cfg(not(test))]
+impl Psp22 {
+    // transfers value of the token from the calling contract to to.
+    pub fn transfer(&self, to: AccountId, value: Balance) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("transfer"),
+                ))
+                .push_arg(to)
+                .push_arg(value),
+            )
+            .returns::<core::result::Result<(), Psp22Error>>()
+            .try_invoke();
+        settle(result)
+    }
+
+    // transfers value of the token from from to to out of the allowance from gave the calling contract.
+    pub fn transfer_from(&self, from: AccountId, to: AccountId, value: Balance) -> Result<()> {
+        self.transfer_from_with(from, to, value, ink::env::CallFlags::default())
+    }
+
+    // transfers value of the token from from to the calling contract out of the allowance from gave it.
+    // the token may call back into the calling contract while transferring, to notify it of the receipt.
+    pub fn pull(&self, from: AccountId, value: Balance) -> Result<()> {
+        let this = ink::env::account_id::<ink::env::DefaultEnvironment>();
+        let flags = ink::env::CallFlags::default().set_allow_reentry(true);
+        self.transfer_from_with(from, this, value, flags)
+    }
+
+    fn transfer_from_with(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        flags: ink::env::CallFlags,
+    ) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .call_flags(flags)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("transfer_from"),
+                ))
+                .push_arg(from)
+                .push_arg(to)
+                .push_arg(value),
+            )
+            .returns::<core::result::Result<(), Psp22Error>>()
+            .try_invoke();
+        settle(result)
+    }
+
+    // lets spender transfer up to value of the calling contract's token, replacing the previous allowance.
+    pub fn approve(&self, spender: AccountId, value: Balance) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("approve"),
+                ))
+                .push_arg(spender)
+                .push_arg(value),
+            )
+            .returns::<core::result::Result<(), Psp22Error>>()
+            .try_invoke();
+        settle(result)
+    }
+
+    // the balance of owner.
+    pub fn balance_of(&self, owner: AccountId) -> Result<Balance> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("balance_of"),
+                ))
+                .push_arg(owner),
+            )
+            .returns::<Balance>()
+            .try_invoke();
+        query(result)
+    }
+
+    // how much of owner's token spender is still allowed to transfer.
+    pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Result<Balance> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("allowance"),
+                ))
+                .push_arg(owner)
+                .push_arg(spender),
+            )
+            .returns::<Balance>()
+            .try_invoke();
+        query(result)
+    }
+
+    // the decimals of the token.
+    pub fn decimals(&self) -> Result<u8> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(ink::env::call::ExecutionInput::new(
+                ink::env::call::Selector::new(ink::selector_bytes!("decimals")),
+            ))
+            .returns::<u8>()
+            .try_invoke();
+        query(result)
+    }
+
+    // the total supply of the token.
+    pub fn total_supply(&self) -> Result<Balance> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(ink::env::call::ExecutionInput::new(
+                ink::env::call::Selector::new(ink::selector_bytes!("total_supply")),
+            ))
+            .returns::<Balance>()
+            .try_invoke();
+        query(result)
+    }
+}
+
+#[cfg(test)]
+impl Psp22 {
+    pub fn transfer(&self, to: AccountId, value: Balance) -> Result<()> {
+        mock::reach(self.token)?;
+        mock::move_balance(self.token, mock::this(), to, value)
+    }
+
+    pub fn transfer_from(&self, from: AccountId, to: AccountId, value: Balance) -> Result<()> {
+        mock::reach(self.token)?;
+        if mock::balance_of(self.token, from) < value {
+            return Err(TokenError::Rejected);
+        }
+        mock::spend_allowance(self.token, from, mock::this(), value)?;
+        mock::move_balance(self.token, from, to, value)
+    }
+
+    pub fn pull(&self, from: AccountId, value: Balance) -> Result<()> {
+        self.transfer_from(from, mock::this(), value)
+    }
+
+    pub fn approve(&self, spender: AccountId, value: Balance) -> Result<()> {
+        mock::reach(self.token)?;
+        mock::approve(self.token, mock::this(), spender, value);
+        Ok(())
+    }
+
+    pub fn balance_of(&self, owner: AccountId) -> Result<Balance> {
+        mock::reach(self.token)?;
+        Ok(mock::balance_of(self.token, owner))
+    }
+
+    pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Result<Balance> {
+        mock::reach(self.token)?;
+        Ok(mock::allowance_of(self.token, owner, spender))
+    }
+
+    // tests deploy with 0 decimals.
+    pub fn decimals(&self) -> Result<u8> {
+        mock::reach(self.token)?;
+        Ok(0)
+    }
+
+    pub fn total_supply(&self) -> Result<Balance> {
+        mock::reach(self.token)?;
+        Ok(mock::total_supply(self.token))
+    }
+}
+
+// the mock router quotes and fills at the rates set with mock::set_rates, 1:1 by default.
+#[cfg(test)]
+impl Router {
+    pub fn get_amount_out(&self, amount_in: Balance, path: Vec<AccountId>) -> Result<Balance> {
+        mock::reach(self.router)?;
+        let (quote_bps, _) = mock::rates_of(self.router, path[path.len() - 1]);
+        Ok(amount_in * quote_bps / 10000)
+    }
+
+    pub fn swap(
+        &self,
+        amount_in: Balance,
+        min_amount_out: Balance,
+        path: Vec<AccountId>,
+        to: AccountId,
+    ) -> Result<Balance> {
+        mock::reach(self.router)?;
+        let (token_in, token_out) = (path[0], path[path.len() - 1]);
+        let (_, fill_bps) = mock::rates_of(self.router, token_out);
+        let amount_out = amount_in * fill_bps / 10000;
+        if amount_out < min_amount_out
+            || mock::balance_of(token_out, self.router) < amount_out
+            || mock::balance_of(token_in, mock::this()) < amount_in
+        {
+            return Err(TokenError::Rejected);
+        }
+        mock::spend_allowance(token_in, mock::this(), self.router, amount_in)?;
+        mock::move_balance(token_in, mock::this(), self.router, amount_in)?;
+        mock::move_balance(token_out, self.router, to, amount_out)?;
+        Ok(amount_out)
+    }
+}
+
+// the ledger of every token and router the escrow calls in tests, empty until a test sets balances.
+#[cfg(test)]
+pub mod mock {
+    use super::{AccountId, Balance, Result, TokenError};
+    use crate::offchain::{read, write};
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    pub use crate::offchain::{set_unreachable, this};
+
+    thread_local! {
+        static BALANCES: RefCell<BTreeMap<(AccountId, AccountId), Balance>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static ALLOWANCES: RefCell<BTreeMap<(AccountId, AccountId, AccountId), Balance>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static RATES: RefCell<BTreeMap<(AccountId, AccountId), (Balance, Balance)>> =
+            const { RefCell::new(BTreeMap::new()) };
+    }
+
+    // the router quotes swaps into token_out at quote_bps of the amount in and fills them at fill_bps,
+    // a fill below the quote is slippage.
+    pub fn set_rates(
+        router: AccountId,
+        token_out: AccountId,
+        quote_bps: Balance,
+        fill_bps: Balance,
+    ) {
+        write(&RATES, |rates| {
+            rates.insert((router, token_out), (quote_bps, fill_bps))
+        });
+    }
+
+    pub(super) fn rates_of(router: AccountId, token_out: AccountId) -> (Balance, Balance) {
+        read(&RATES, |rates| {
+            rates
+                .get(&(router, token_out))
+                .copied()
+                .unwrap_or((10000, 10000))
+        })
+    }
+
+    pub(super) fn reach(token: AccountId) -> Result<()> {
+        if crate::offchain::is_unreachable(token) {
+            return Err(TokenError::CallFailed);
+        }
+        Ok(())
+    }
+
+    pub fn set_balance(token: AccountId, owner: AccountId, value: Balance) {
+        write(&BALANCES, |balances| balances.insert((token, owner), value));
+    }
+
+    pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+        read(&BALANCES, |balances| {
+            balances.get(&(token, owner)).copied().unwrap_or_default()
+        })
+    }
+
+    pub fn total_supply(token: AccountId) -> Balance {
+        read(&BALANCES, |balances| {
+            balances
+                .iter()
+                .filter(|((of, _), _)| *of == token)
+                .map(|(_, value)| value)
+                .sum()
+        })
+    }
+
+    pub fn approve(token: AccountId, owner: AccountId, spender: AccountId, value: Balance) {
+        write(&ALLOWANCES, |allowances| {
+            allowances.insert((token, owner, spender), value)
+        });
+    }
+
+    pub fn allowance_of(token: AccountId, owner: AccountId, spender: AccountId) -> Balance {
+        read(&ALLOWANCES, |allowances| {
+            allowances
+                .get(&(token, owner, spender))
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    pub(super) fn spend_allowance(
+        token: AccountId,
+        owner: AccountId,
+        spender: AccountId,
+        value: Balance,
+    ) -> Result<()> {
+        let allowance = allowance_of(token, owner, spender);
+        if allowance < value {
+            return Err(TokenError::Rejected);
+        }
+        approve(token, owner, spender, allowance - value);
+        Ok(())
+    }
+
+    pub(super) fn move_balance(
+        token: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<()> {
+        let from_balance = balance_of(token, from);
+        if from_balance < value {
+            return Err(TokenError::Rejected);
+        }
+        set_balance(token, from, from_balance - value);
+        set_balance(token, to, balance_of(token, to) + value);
+        Ok(())
+    }
+}
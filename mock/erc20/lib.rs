@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use self::erc20::{Erc20, Erc20Ref};
+
 #[ink::contract]
 mod erc20 {
     use ink::storage::Mapping;
@@ -51,6 +53,9 @@ mod erc20 {
     /// The ERC-20 result type.
     pub type Result<T> = core::result::Result<T, Error>;
 
+    /// The number of decimals of the mock stablecoin.
+    pub const DECIMALS: u8 = 6;
+
     impl Erc20 {
         /// Creates a new ERC-20 contract with the specified initial supply.
         #[ink(constructor)]
@@ -76,6 +81,12 @@ mod erc20 {
             self.total_supply
         }
 
+        /// Returns the number of decimals used to display the token amounts.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            DECIMALS
+        }
+
         /// Returns the account balance for the specified `owner`.
         ///
         /// Returns `0` if the account is non-existent.
@@ -292,6 +303,13 @@ mod erc20 {
             );
         }
 
+        /// The decimals are reported.
+        #[ink::test]
+        fn decimals_works() {
+            let erc20 = Erc20::new(100);
+            assert_eq!(erc20.decimals(), DECIMALS);
+        }
+
         /// The total supply was applied.
         #[ink::test]
         fn total_supply_works() {
@@ -0,0 +1,40 @@
+// the scaffolding of the in-memory contracts that serve cross-contract calls in the unit tests of the
+// contracts, since the off-chain test environment can't call other contracts. Their state is kept per
+// thread, like the off-chain environment, so every test starts with contracts that hold nothing. Every
+// contract crate includes this file as its offchain module, for its tests only, and not every crate uses
+// every helper.
+#![allow(dead_code)]
+
+use ink::primitives::AccountId;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::thread::LocalKey;
+
+// the state of in-memory contracts, declared with thread_local! next to the calls it serves.
+pub type State<T> = LocalKey<RefCell<T>>;
+
+thread_local! {
+    static UNREACHABLE: RefCell<BTreeSet<AccountId>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+pub fn read<T, R>(state: &'static State<T>, f: impl FnOnce(&T) -> R) -> R {
+    state.with(|state| f(&state.borrow()))
+}
+
+pub fn write<T, R>(state: &'static State<T>, f: impl FnOnce(&mut T) -> R) -> R {
+    state.with(|state| f(&mut state.borrow_mut()))
+}
+
+// every later call to the contract fails like a call to a trapping or missing contract.
+pub fn set_unreachable(contract: AccountId) {
+    write(&UNREACHABLE, |unreachable| unreachable.insert(contract));
+}
+
+pub fn is_unreachable(contract: AccountId) -> bool {
+    read(&UNREACHABLE, |unreachable| unreachable.contains(&contract))
+}
+
+// the contract making the calls, the one under test.
+pub fn this() -> AccountId {
+    ink::env::account_id::<ink::env::DefaultEnvironment>()
+}
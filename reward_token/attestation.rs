@@ -0,0 +1,88 @@
+// typed queries of the attestation contracts auditors link their identity attestations from. In tests
+// they are answered by in-memory attestation contracts, see mock.
+
+use ink::primitives::AccountId;
+
+// an attestation contract called with a fixed gas limit, ATTESTATION_GAS_LIMIT.
+#[derive(Debug, Clone, Copy)]
+pub struct AttestationRegistry {
+    pub registry: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl AttestationRegistry {
+    pub fn new(registry: AccountId, gas_limit: u64) -> Self {
+        Self {
+            registry,
+            gas_limit,
+        }
+    }
+}
+
+#[cfg(not(test))]
+impl AttestationRegistry {
+    // whether the attestation is valid and about the subject, a contract that doesn't answer doesn't vouch
+    // for anyone.
+    pub fn is_attested(&self, attestation_id: [u8; 32], subject: AccountId) -> bool {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.registry)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("is_attested"),
+                ))
+                .push_arg(attestation_id)
+                .push_arg(subject),
+            )
+            .returns::<bool>()
+            .try_invoke();
+        matches!(result, Ok(Ok(true)))
+    }
+}
+
+#[cfg(test)]
+impl AttestationRegistry {
+    pub fn is_attested(&self, attestation_id: [u8; 32], subject: AccountId) -> bool {
+        mock::is_attested(self.registry, attestation_id, subject)
+    }
+}
+
+// the attestation contracts queried in tests, they vouch for no one until a test attests.
+#[cfg(test)]
+pub mod mock {
+    use super::AccountId;
+    use crate::offchain::{read, write};
+    use std::cell::RefCell;
+    use std::collections::BTreeSet;
+
+    thread_local! {
+        static ATTESTATIONS: RefCell<BTreeSet<(AccountId, [u8; 32], AccountId)>> =
+            const { RefCell::new(BTreeSet::new()) };
+    }
+
+    // the attestation contract issues the attestation about the subject.
+    pub fn attest(registry: AccountId, attestation_id: [u8; 32], subject: AccountId) {
+        write(&ATTESTATIONS, |attestations| {
+            attestations.insert((registry, attestation_id, subject))
+        });
+    }
+
+    // the attestation contract revokes the attestation, it doesn't vouch for its subject anymore.
+    pub fn revoke(registry: AccountId, attestation_id: [u8; 32], subject: AccountId) {
+        write(&ATTESTATIONS, |attestations| {
+            attestations.remove(&(registry, attestation_id, subject))
+        });
+    }
+
+    pub(super) fn is_attested(
+        registry: AccountId,
+        attestation_id: [u8; 32],
+        subject: AccountId,
+    ) -> bool {
+        read(&ATTESTATIONS, |attestations| {
+            attestations.contains(&(registry, attestation_id, subject))
+        })
+    }
+}
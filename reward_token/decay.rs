@@ -0,0 +1,132 @@
+// the decay of reputation with age, 2^(-age / half_life) in fixed point. Decaying a weight in several steps
+// gives the weight decayed once over the sum of the steps, up to the rounding of the fixed point, so a running
+// total of weights can be decayed whenever it changes instead of going through the audits it is made of.
+
+type Timestamp = u64;
+
+// 1.0 in the fixed point of the weights and the factors they are decayed by.
+pub const ONE: u128 = 1_000_000_000_000_000_000;
+
+// 2^(-1 / 2^k) in fixed point for k from 1, the factor of each bit of the fraction of a half life.
+const ROOTS: [u128; 48] = [
+    707_106_781_186_547_524,
+    840_896_415_253_714_543,
+    917_004_043_204_671_232,
+    957_603_280_698_573_647,
+    978_572_062_087_700_135,
+    989_228_013_193_975_484,
+    994_599_423_483_633_176,
+    997_296_056_085_470_126,
+    998_647_112_890_970_174,
+    999_323_327_502_650_752,
+    999_661_606_496_243_684,
+    999_830_788_931_929_063,
+    999_915_390_886_613_498,
+    999_957_694_548_431_133,
+    999_978_847_050_491_930,
+    999_989_423_469_314_464,
+    999_994_711_720_674_283,
+    999_997_355_856_841_395,
+    999_998_677_927_546_760,
+    999_999_338_963_554_895,
+    999_999_669_481_722_826,
+    999_999_834_740_847_758,
+    999_999_917_370_420_465,
+    999_999_958_685_209_379,
+    999_999_979_342_604_476,
+    999_999_989_671_302_185,
+    999_999_994_835_651_079,
+    999_999_997_417_825_536,
+    999_999_998_708_912_767,
+    999_999_999_354_456_383,
+    999_999_999_677_228_192,
+    999_999_999_838_614_096,
+    999_999_999_919_307_048,
+    999_999_999_959_653_524,
+    999_999_999_979_826_762,
+    999_999_999_989_913_381,
+    999_999_999_994_956_690,
+    999_999_999_997_478_345,
+    999_999_999_998_739_173,
+    999_999_999_999_369_586,
+    999_999_999_999_684_793,
+    999_999_999_999_842_397,
+    999_999_999_999_921_198,
+    999_999_999_999_960_599,
+    999_999_999_999_980_300,
+    999_999_999_999_990_150,
+    999_999_999_999_995_075,
+    999_999_999_999_997_537,
+];
+
+// the weight after age, halved once per full half life and by the factors of the bits of the fraction of a half
+// life that is left, the first ROOTS.len() of them.
+pub fn decay(weight: u128, age: Timestamp, half_life: Timestamp) -> u128 {
+    let halvings = age / half_life;
+    if halvings >= 128 {
+        return 0;
+    }
+    let mut weight = weight >> halvings;
+    let half_life = half_life as u128;
+    let mut remainder = (age as u128) % half_life;
+    for root in ROOTS {
+        if remainder == 0 {
+            break;
+        }
+        remainder *= 2;
+        if remainder >= half_life {
+            remainder -= half_life;
+            weight = scale(weight, root);
+        }
+    }
+    weight
+}
+
+// weight * factor / ONE, floored. The weight is divided first, so that the product can't overflow.
+fn scale(weight: u128, factor: u128) -> u128 {
+    (weight / ONE)
+        .saturating_mul(factor)
+        .saturating_add((weight % ONE) * factor / ONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the weights differ by less than a billionth of ONE, well under the precision reputation is reported with.
+    fn close(a: u128, b: u128) -> bool {
+        a.abs_diff(b) < ONE / 1_000_000_000
+    }
+
+    #[test]
+    fn decay_halves_every_half_life() {
+        assert_eq!(decay(ONE, 0, 1000), ONE);
+        assert_eq!(decay(ONE, 1000, 1000), ONE / 2);
+        assert_eq!(decay(ONE, 3000, 1000), ONE / 8);
+        assert_eq!(decay(ONE, 128000, 1000), 0);
+        //2^-0.5 and 2^-1.5
+        assert!(close(decay(ONE, 500, 1000), 707_106_781_186_547_524));
+        assert!(close(decay(ONE, 1500, 1000), 353_553_390_593_273_762));
+    }
+
+    #[test]
+    fn decay_in_steps_is_decay_over_their_sum() {
+        for half_life in [1000, 7919, 15_552_000_000] {
+            for total in [1, 333, 999, 1000, 2500, 12345, 15_552_000_000 * 3 / 2] {
+                let once = decay(5 * ONE, total, half_life);
+                for steps in [2, 3, 7, 100] {
+                    let mut weight = 5 * ONE;
+                    let step = total / steps;
+                    for _ in 1..steps {
+                        weight = decay(weight, step, half_life);
+                    }
+                    weight = decay(weight, total - step * (steps - 1), half_life);
+                    assert!(
+                        close(weight, once),
+                        "{total} in {steps} steps of a half life of {half_life}"
+                    );
+                }
+            }
+        }
+    }
+}
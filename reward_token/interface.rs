@@ -0,0 +1,68 @@
+// the interface of the reward token as other contracts and off-chain tooling see it. PROTOCOL_VERSION is bumped by
+// every release that changes its messages or what they expect, the contracts can't be upgraded in place, so
+// a new version is a new deployment. MESSAGES is the selector of every message, in the order of the source,
+// and a message added to or removed from the contract has to be added to or removed from it too.
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+pub const MESSAGES: [[u8; 4]; 49] = [
+    ink::selector_bytes!("version"),
+    ink::selector_bytes!("interface_hash"),
+    ink::selector_bytes!("mint"),
+    ink::selector_bytes!("mint_batch"),
+    ink::selector_bytes!("record_failure"),
+    ink::selector_bytes!("get_failure_history"),
+    ink::selector_bytes!("get_failure_count"),
+    ink::selector_bytes!("record_skill_tags"),
+    ink::selector_bytes!("get_skill_tags"),
+    ink::selector_bytes!("get_skill_profile"),
+    ink::selector_bytes!("set_profile"),
+    ink::selector_bytes!("get_profile"),
+    ink::selector_bytes!("resolve_name"),
+    ink::selector_bytes!("set_attestation_registry"),
+    ink::selector_bytes!("is_attestation_registry"),
+    ink::selector_bytes!("attach_attestation"),
+    ink::selector_bytes!("remove_attestation"),
+    ink::selector_bytes!("get_attestation"),
+    ink::selector_bytes!("is_verified"),
+    ink::selector_bytes!("get_monthly_stats"),
+    ink::selector_bytes!("get_minters"),
+    ink::selector_bytes!("is_minter"),
+    ink::selector_bytes!("add_minter"),
+    ink::selector_bytes!("remove_minter"),
+    ink::selector_bytes!("record_arbitration"),
+    ink::selector_bytes!("get_arbiter_stats"),
+    ink::selector_bytes!("record_patron_audit"),
+    ink::selector_bytes!("get_patron_stats"),
+    ink::selector_bytes!("get_patron_dispute_win_rate"),
+    ink::selector_bytes!("on_poll_created"),
+    ink::selector_bytes!("on_poll_finalized"),
+    ink::selector_bytes!("get_observed_poll"),
+    ink::selector_bytes!("migrate_account"),
+    ink::selector_bytes!("get_migrated_to"),
+    ink::selector_bytes!("show_auditors_record"),
+    ink::selector_bytes!("show_reward_details"),
+    ink::selector_bytes!("metadata_version"),
+    ink::selector_bytes!("issue_credential"),
+    ink::selector_bytes!("verify_credential"),
+    ink::selector_bytes!("set_credential_issuer"),
+    ink::selector_bytes!("get_credential_digest"),
+    ink::selector_bytes!("get_credential_issuer"),
+    ink::selector_bytes!("sign_credential"),
+    ink::selector_bytes!("record_scorecard"),
+    ink::selector_bytes!("revise"),
+    ink::selector_bytes!("mark_metadata_updated"),
+    ink::selector_bytes!("reputation_score"),
+    ink::selector_bytes!("get_reputation_half_life"),
+    ink::selector_bytes!("set_reputation_half_life"),
+];
+
+// the blake2x256 hash of the selectors of MESSAGES, sorted and SCALE encoded, so that it doesn't depend on
+// the order of the source.
+pub fn interface_hash() -> [u8; 32] {
+    let mut selectors = MESSAGES;
+    selectors.sort_unstable();
+    let mut hash = [0u8; 32];
+    ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&selectors, &mut hash);
+    hash
+}
@@ -1,11 +1,40 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use self::rewardtoken::{Rewardtoken, RewardtokenRef};
+
+mod attestation;
+mod decay;
+mod interface;
+#[cfg(test)]
+#[path = "../mock/offchain.rs"]
+mod offchain;
+
 #[ink::contract]
 mod rewardtoken {
+    use crate::attestation::AttestationRegistry;
+    use crate::decay;
     use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
     use scale::{Decode, Encode};
 
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// the scorecard of the assessment of the audit, as stored by the escrow.
+    pub struct Scorecard {
+        /// critical findings
+        pub criticals: u32,
+        /// high severity findings
+        pub highs: u32,
+        /// medium severity findings
+        pub mediums: u32,
+        /// quality score of the report, from 0 to 100
+        pub quality: u8,
+    }
+
     #[derive(scale::Decode, scale::Encode)]
     #[cfg_attr(
         feature = "std",
@@ -24,6 +53,29 @@ mod rewardtoken {
         pub amount: Balance,
         ///  submitted audit report ipfs_hash
         pub ipfs_hash: String,
+        /// if the audit was successful or not
+        pub is_positive: bool,
+        /// block timestamp at which the token was minted, used to decay the reputation it carries
+        pub minted_at: Timestamp,
+        /// scorecard of the assessment, if the audit was scored
+        pub scorecard: Option<Scorecard>,
+    }
+
+    /// the running reputation of an auditor, the weights of their audits decayed to updated_at, so that
+    /// reputation_score doesn't have to go through their tokens.
+    #[derive(scale::Decode, scale::Encode, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Reputation {
+        /// weight of the successful audits, decay::ONE for each audit as it was minted
+        pub gained: u128,
+        /// weight of the unsuccessful audits, decay::ONE for each audit as it was minted
+        pub lost: u128,
+        pub updated_at: Timestamp,
+        /// number of tokens minted to the auditor, they are kept in auditor_tokens under 0 to tokens - 1
+        pub tokens: u32,
     }
 
     #[derive(scale::Decode, scale::Encode, Default)]
@@ -34,6 +86,137 @@ mod rewardtoken {
     pub struct Stats {
         pub successful_audits: u32,
         pub unsuccessful_audits: u32,
+        /// sum of the amounts of the successful audits
+        pub total_value_audited: Balance,
+        /// successful audits in a row since the last unsuccessful one
+        pub current_streak: u32,
+        /// longest run of successful audits in a row
+        pub best_streak: u32,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Default, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// what an auditor achieved in a calendar month (UTC), from the successful audits minted in it.
+    pub struct MonthlyStats {
+        pub audits_completed: u32,
+        pub value_earned: Balance,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Default, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// the track record of an arbiter, badges for the finalized polls they voted on, no_shows for the ones they didn't.
+    pub struct ArbiterStats {
+        pub badges: u32,
+        pub no_shows: u32,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Default, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// the track record of a patron as reported by the escrow, so that auditors can look at it before accepting
+    /// an assignment: the closed audits they funded, how many of them they disputed and how many disputes they won.
+    pub struct PatronStats {
+        pub audits_funded: u32,
+        pub disputes_raised: u32,
+        pub disputes_won: u32,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// a proof-of-personhood or identity attestation an auditor linked to their profile, issued by an
+    /// attestation contract the owner allow-listed.
+    pub struct IdentityAttestation {
+        pub registry: AccountId,
+        pub attestation_id: [u8; 32],
+        pub attached_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// a poll of a voting contract the token observes, created_at is None if the token started observing the
+    /// voting contract after the poll was opened.
+    pub struct ObservedPoll {
+        pub audit_id: u32,
+        pub created_at: Option<Timestamp>,
+        pub finalized_at: Option<Timestamp>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// why an audit was unsuccessful, so that a missed deadline can be told apart from fraud.
+    pub enum FailureReason {
+        MissedDeadline,
+        RejectedByArbiters,
+        Plagiarism,
+        Withdrawn,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// an unsuccessful audit in the history of an auditor, token_id is the negative token minted for it.
+    pub struct FailureIncident {
+        pub token_id: u32,
+        pub audit_id: u32,
+        pub reason: FailureReason,
+        pub recorded_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// a skill tag in the profile of an auditor and how many of their successful audits it was recorded for.
+    pub struct SkillCount {
+        pub tag: String,
+        pub audits: u32,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// the display profile of an account, so the marketplace can show a name instead of an address. avatar_cid
+    /// is the IPFS CID of the avatar and contact_hash the hash of how to reach the account off-chain.
+    pub struct Profile {
+        pub display_name: String,
+        pub avatar_cid: String,
+        pub contact_hash: [u8; 32],
+        pub name_changed_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    /// the arguments of one mint of mint_batch, the same as those of mint.
+    pub struct MintArgs {
+        pub recipient: AccountId,
+        pub audit_id: u32,
+        pub completion_time: u8,
+        pub extensions: u8,
+        pub amount: Balance,
+        pub ipfs_hash: String,
+        pub is_positive: bool,
     }
 
     #[ink(event)]
@@ -43,20 +226,243 @@ mod rewardtoken {
         is_positive: bool,
     }
 
+    /// emitted once for a mint_batch after the TokenMinted of each of its tokens, the minted tokens are
+    /// from_token_id and the ones after it, failed is how many of the items weren't minted.
+    #[ink(event)]
+    pub struct BatchMinted {
+        from_token_id: u32,
+        minted: u32,
+        failed: u32,
+    }
+
+    /// emitted when the metadata of a token changes, so that indexers refresh their cached copy (ERC-4906).
+    #[ink(event)]
+    pub struct MetadataUpdate {
+        token_id: u32,
+    }
+
+    /// emitted when the metadata of every token from from_token_id to to_token_id (inclusive) changes.
+    #[ink(event)]
+    pub struct BatchMetadataUpdate {
+        from_token_id: u32,
+        to_token_id: u32,
+    }
+
+    /// emitted when the owner authorizes a new account, e.g. the escrow or voting contract, to mint.
+    #[ink(event)]
+    pub struct MinterAdded {
+        minter: AccountId,
+    }
+
+    /// emitted when the owner revokes the minting rights of an account.
+    #[ink(event)]
+    pub struct MinterRemoved {
+        minter: AccountId,
+    }
+
+    /// emitted when the history of an account is moved to a new one, proof is the hash/link of the evidence
+    /// the owner approved the migration on.
+    #[ink(event)]
+    pub struct ReputationMigrated {
+        old: AccountId,
+        new: AccountId,
+        proof: String,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    /// the portable proof of an audit returned by issue_credential, the subject is the auditor holding the token
+    /// and the issuer the reward token contract. It is signed by the credential issuer key the owner registered,
+    /// so that it can be checked against that key off-chain, or through verify_credential.
+    pub struct Credential {
+        /// CREDENTIAL_TYPE
+        pub credential_type: Vec<u8>,
+        /// CREDENTIAL_VERSION, bumped when the layout of the credential changes
+        pub version: u8,
+        pub issuer: AccountId,
+        pub subject: AccountId,
+        pub token_id: u32,
+        pub audit_id: u32,
+        /// if the audit was successful or not
+        pub is_positive: bool,
+        pub amount: Balance,
+        pub ipfs_hash: String,
+        pub scorecard: Option<Scorecard>,
+        /// block timestamp at which the token was minted
+        pub issued_at: Timestamp,
+        /// metadata version of the token, a credential stops verifying once the token is revised
+        pub metadata_version: u32,
+    }
+
+    /// emitted when the owner registers or removes the key credentials are signed with.
+    #[ink(event)]
+    pub struct CredentialIssuerChanged {
+        issuer: Option<[u8; 33]>,
+    }
+
+    /// emitted when the credential of a token is signed, for the metadata version it was signed at.
+    #[ink(event)]
+    pub struct CredentialSigned {
+        token_id: u32,
+        metadata_version: u32,
+    }
+
+    /// emitted when the reason an unsuccessful audit failed is recorded.
+    #[ink(event)]
+    pub struct FailureRecorded {
+        auditor: AccountId,
+        token_id: u32,
+        reason: FailureReason,
+    }
+
+    /// emitted when the skill tags of the audit of a token are recorded.
+    #[ink(event)]
+    pub struct SkillTagsRecorded {
+        auditor: AccountId,
+        token_id: u32,
+        skill_tags: Vec<String>,
+    }
+
+    /// emitted when the owner allows an attestation contract or stops trusting it.
+    #[ink(event)]
+    pub struct AttestationRegistryChanged {
+        registry: AccountId,
+        allowed: bool,
+    }
+
+    /// emitted when an auditor links an identity attestation to their profile.
+    #[ink(event)]
+    pub struct AttestationAttached {
+        auditor: AccountId,
+        registry: AccountId,
+        attestation_id: [u8; 32],
+    }
+
+    /// emitted when an auditor unlinks their identity attestation.
+    #[ink(event)]
+    pub struct AttestationRemoved {
+        auditor: AccountId,
+    }
+
+    /// emitted when an account sets or changes its display profile.
+    #[ink(event)]
+    pub struct ProfileUpdated {
+        account: AccountId,
+        display_name: String,
+    }
+
+    /// emitted when an arbiter who voted on a finalized poll is awarded a participation badge.
+    #[ink(event)]
+    pub struct ArbiterBadgeMinted {
+        arbiter: AccountId,
+        vote_id: u32,
+        audit_id: u32,
+    }
+
+    /// emitted when the closing of an audit is recorded in the history of its patron.
+    #[ink(event)]
+    pub struct PatronAuditRecorded {
+        patron: AccountId,
+        audit_id: u32,
+        disputed: bool,
+        dispute_won: bool,
+    }
+
+    /// emitted when a voting contract the token observes opens a poll.
+    #[ink(event)]
+    pub struct PollObserved {
+        voting: AccountId,
+        vote_id: u32,
+        audit_id: u32,
+    }
+
+    /// emitted when a voting contract the token observes finalizes a poll.
+    #[ink(event)]
+    pub struct PollFinalizationObserved {
+        voting: AccountId,
+        vote_id: u32,
+        audit_id: u32,
+    }
+
+    /// emitted when an arbiter on the panel of a finalized poll didn't vote on it.
+    #[ink(event)]
+    pub struct ArbiterNoShowRecorded {
+        arbiter: AccountId,
+        vote_id: u32,
+        audit_id: u32,
+    }
+
     #[ink(storage)]
     pub struct Rewardtoken {
         pub current_id: u32,
         pub balances: Mapping<AccountId, Stats>,
         pub owner: AccountId,
         pub rewarded_tokens: Mapping<u32, RewardInfo>,
+        pub reputations: Mapping<AccountId, Reputation>,
+        pub auditor_tokens: Mapping<(AccountId, u32), u32>,
+        pub reputation_half_life: Timestamp,
+        pub metadata_versions: Mapping<u32, u32>,
+        pub minters: Vec<AccountId>,
+        pub monthly_stats: Mapping<(AccountId, u32), MonthlyStats>,
+        pub migrated_to: Mapping<AccountId, AccountId>,
+        pub arbiter_stats: Mapping<AccountId, ArbiterStats>,
+        pub patron_stats: Mapping<AccountId, PatronStats>,
+        pub failure_history: Mapping<AccountId, Vec<FailureIncident>>,
+        pub token_skill_tags: Mapping<u32, Vec<String>>,
+        pub skill_profiles: Mapping<AccountId, Vec<SkillCount>>,
+        pub observed_polls: Mapping<(AccountId, u32), ObservedPoll>,
+        pub attestation_registries: Mapping<AccountId, bool>,
+        pub identity_attestations: Mapping<AccountId, IdentityAttestation>,
+        pub profiles: Mapping<AccountId, Profile>,
+        pub display_names: Mapping<String, AccountId>,
+        //the storage layout has no arrays over 32 bytes, the key and the signatures are kept as vectors of their
+        //33 and 65 bytes
+        pub credential_issuer: Option<Vec<u8>>,
+        pub credential_signatures: Mapping<u32, Vec<u8>>,
     }
 
     #[derive(Debug, PartialEq, Eq, Encode, Decode, Clone, Copy)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         UnAuthorisedCall,
+        InvalidArgument,
+        AccountMigrated,
+        AttestationNotValid,
+        NameTaken,
+        NameChangeTooSoon,
+        NoCredentialIssuer,
+        InvalidSignature,
     }
 
+    /// reputation score of a single audit when it is fresh, scores are fixed point with 6 decimals.
+    pub const REPUTATION_UNIT: u64 = 1_000_000;
+
+    /// the most accounts that can be authorized to mint at the same time.
+    pub const MAX_MINTERS: u32 = 10;
+    /// the most tokens a single mint_batch can mint.
+    pub const MAX_MINT_BATCH: u32 = 50;
+
+    /// the type every credential issued by the token starts with.
+    pub const CREDENTIAL_TYPE: &[u8] = b"SecurityAuditCredential";
+    /// the version of the layout of the credentials.
+    pub const CREDENTIAL_VERSION: u8 = 1;
+
+    /// the most skill tags the audit of a single token can have.
+    pub const MAX_SKILL_TAGS: u32 = 8;
+    /// the longest a skill tag can be, in bytes.
+    pub const MAX_SKILL_TAG_LENGTH: u32 = 32;
+
+    /// the gas forwarded when asking an attestation contract about an attestation, enough for a lookup.
+    pub const ATTESTATION_GAS_LIMIT: u64 = 5_000_000_000;
+    /// the most distinct skill tags the profile of an auditor can have.
+    pub const MAX_SKILL_PROFILE_TAGS: u32 = 64;
+    /// the longest a display name can be, in bytes.
+    pub const MAX_DISPLAY_NAME_LENGTH: u32 = 32;
+    /// the longest the avatar CID of a profile can be, in bytes.
+    pub const MAX_AVATAR_CID_LENGTH: u32 = 128;
+    /// how long an account has to wait to change its display name again, 30 days.
+    pub const NAME_CHANGE_COOLDOWN: Timestamp = 2_592_000_000;
+
     pub type Result<T> = core::result::Result<T, Error>;
 
     impl Rewardtoken {
@@ -66,16 +472,86 @@ mod rewardtoken {
             let owner = _owner;
             let balances = Mapping::default();
             let rewarded_tokens = Mapping::default();
+            let reputations = Mapping::default();
+            let auditor_tokens = Mapping::default();
+            //reputation of an audit halves every 180 days by default
+            let reputation_half_life = 15_552_000_000;
+            let metadata_versions = Mapping::default();
+            //the owner can mint until it hands the right to the platform contracts
+            let minters = [_owner].to_vec();
+            let monthly_stats = Mapping::default();
+            let migrated_to = Mapping::default();
+            let arbiter_stats = Mapping::default();
+            let patron_stats = Mapping::default();
+            let failure_history = Mapping::default();
+            let token_skill_tags = Mapping::default();
+            let skill_profiles = Mapping::default();
+            let observed_polls = Mapping::default();
+            let attestation_registries = Mapping::default();
+            let identity_attestations = Mapping::default();
+            let profiles = Mapping::default();
+            let display_names = Mapping::default();
+            let credential_issuer = None;
+            let credential_signatures = Mapping::default();
             Self {
                 current_id,
                 owner,
                 balances,
                 rewarded_tokens,
+                reputations,
+                auditor_tokens,
+                reputation_half_life,
+                metadata_versions,
+                minters,
+                monthly_stats,
+                migrated_to,
+                arbiter_stats,
+                patron_stats,
+                failure_history,
+                token_skill_tags,
+                skill_profiles,
+                observed_polls,
+                attestation_registries,
+                identity_attestations,
+                profiles,
+                display_names,
+                credential_issuer,
+                credential_signatures,
+            }
+        }
+
+        /// with_minters deploys the token with the platform contracts already authorized to mint next to the owner,
+        /// e.g. by the deployer, it fails if there would be more than MAX_MINTERS.
+        #[ink(constructor)]
+        pub fn with_minters(_owner: AccountId, _minters: Vec<AccountId>) -> Result<Self> {
+            let mut contract = Self::new(_owner);
+            for minter in _minters {
+                if !contract.minters.contains(&minter) {
+                    contract.minters.push(minter);
+                }
+            }
+            if contract.minters.len() as u32 > MAX_MINTERS {
+                return Err(Error::InvalidArgument);
             }
+            Ok(contract)
+        }
+
+        /// returns the protocol version of the reward token, see interface.rs
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            crate::interface::PROTOCOL_VERSION
+        }
+
+        /// returns the blake2x256 hash of the sorted selectors of every message of the reward token,
+        /// two deployments with the same hash accept the same calls
+        #[ink(message)]
+        pub fn interface_hash(&self) -> [u8; 32] {
+            crate::interface::interface_hash()
         }
 
-        /// mint function first checks that only the owner can call the contract,
+        /// mint function first checks that only an authorized minter can call the contract,
         /// then it modifies the state of both the auditors_record(if it is a successful audit or unsuccessful one)
+        /// and of the auditor's stats for the current month if it is a successful one,
         /// and mints the token with auditor as the recipient and all other details like audit_id, completion_time, if it was
         /// completed with extensions, or in what percent time, the amount, and the ipfs_hash corresponding that audit.
         #[ink(message)]
@@ -90,129 +566,1946 @@ mod rewardtoken {
             positive_or_not: bool,
         ) -> Result<()> {
             let caller = self.env().caller();
-            if self.owner != caller {
+            if !self.is_minter(caller) {
                 return Err(Error::UnAuthorisedCall);
             }
-            if positive_or_not {
-                let mut _stat = self.balances.get(&_recipient).unwrap_or_default();
-
-                _stat.successful_audits = _stat.successful_audits + 1;
-                self.balances.insert(&_recipient, &_stat);
-            } else {
-                let mut _stat = self.balances.get(_recipient).unwrap_or_default();
-                _stat.unsuccessful_audits = _stat.unsuccessful_audits + 1;
-                self.balances.insert(&_recipient, &_stat);
-            }
-            let _reward_info = RewardInfo {
+            self.mint_token(MintArgs {
                 recipient: _recipient,
                 audit_id: _audit_id,
                 completion_time: _completion_time,
                 extensions: _extensions,
                 amount: _amount,
                 ipfs_hash: _ipfs_hash,
-            };
-            self.rewarded_tokens.insert(&self.current_id, &_reward_info);
-            self.env().emit_event(TokenMinted{
-                token_id: self.current_id,
-                reward_info: Some(_reward_info),
-                is_positive: positive_or_not, 
-            });
-            self.current_id = self.current_id + 1;
+                is_positive: positive_or_not,
+            })?;
             Ok(())
         }
 
-        /// show_auditors_record returns a struct telling how many successful
-        /// and unsuccessful audits the auditor has completed.
+        /// mint_batch mints a token for each of up to MAX_MINT_BATCH items, as mint would, e.g. for migration
+        /// tooling or the settlement of several audits at once. Only an authorized minter can call it. An item
+        /// that can't be minted, e.g. for a migrated recipient, doesn't stop the others, the result of each item
+        /// is returned in the same order, with the ID of its token if it was minted. Emits TokenMinted for every
+        /// token, like mint, then BatchMinted.
         #[ink(message)]
-        pub fn show_auditors_record(&self, auditor: AccountId) -> Option<Stats> {
-            self.balances.get(&auditor)
+        pub fn mint_batch(&mut self, items: Vec<MintArgs>) -> Result<Vec<Result<u32>>> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if items.is_empty() || items.len() as u32 > MAX_MINT_BATCH {
+                return Err(Error::InvalidArgument);
+            }
+            let from_token_id = self.current_id;
+            let results: Vec<Result<u32>> = items
+                .into_iter()
+                .map(|item| self.mint_token(item))
+                .collect();
+            let minted = results.iter().filter(|result| result.is_ok()).count() as u32;
+            self.env().emit_event(BatchMinted {
+                from_token_id,
+                minted,
+                failed: results.len() as u32 - minted,
+            });
+            Ok(results)
         }
 
-        /// show_reward_details returns the RewardInfo/the metadata corresponding to the
-        /// reward token entered.
+        /// record_failure lets an authorized minter record why the audit of a token minted as unsuccessful failed,
+        /// in the failure history of its recipient, once per token. Emits FailureRecorded.
         #[ink(message)]
-        pub fn show_reward_details(&self, reward_id: u32) -> Option<RewardInfo> {
-            self.rewarded_tokens.get(&reward_id)
+        pub fn record_failure(&mut self, token_id: u32, reason: FailureReason) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let reward_info = self
+                .rewarded_tokens
+                .get(token_id)
+                .ok_or(Error::InvalidArgument)?;
+            let mut history = self.get_failure_history(reward_info.recipient);
+            if reward_info.is_positive
+                || history.iter().any(|incident| incident.token_id == token_id)
+            {
+                return Err(Error::InvalidArgument);
+            }
+            history.push(FailureIncident {
+                token_id,
+                audit_id: reward_info.audit_id,
+                reason,
+                recorded_at: self.env().block_timestamp(),
+            });
+            self.failure_history.insert(reward_info.recipient, &history);
+            self.env().emit_event(FailureRecorded {
+                auditor: reward_info.recipient,
+                token_id,
+                reason,
+            });
+            Ok(())
         }
-    }
-}
 
-#[cfg(test)]
-mod test_cases {
-    use super::*;
-    #[cfg(feature = "ink-experimental-engine")]
-    use crate::digital_certificate::digital_certificate;
+        /// get_failure_history returns the unsuccessful audits of an auditor whose reason was recorded,
+        /// oldest first.
+        #[ink(message)]
+        pub fn get_failure_history(&self, auditor: AccountId) -> Vec<FailureIncident> {
+            self.failure_history.get(auditor).unwrap_or_default()
+        }
 
-    #[test]
-    fn test_assert_owner() {
-        //testcase to validate that owner is set in the contract after deployment.
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
-        let contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        let contract_owner = contract.owner;
-        assert_eq!(contract_owner, accounts.alice);
-    }
+        /// get_failure_count returns how many of the unsuccessful audits of an auditor failed for the reason.
+        #[ink(message)]
+        pub fn get_failure_count(&self, auditor: AccountId, reason: FailureReason) -> u32 {
+            self.get_failure_history(auditor)
+                .iter()
+                .filter(|incident| incident.reason == reason)
+                .count() as u32
+        }
 
-    #[test]
-    fn test_failure_on_non_owner_call() {
-        //testcase to validate that only owner can call the contract
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
-        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-        let hash = "asdf";
-        let _res = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), false);
-        assert!(_res.is_err());
-    }
+        /// record_skill_tags lets an authorized minter tag the successful audit of a token with the domains it
+        /// covered, e.g. "ink!", "EVM" or "ZK", once per token. Every tag counts toward the skill profile of the
+        /// recipient, at most MAX_SKILL_TAGS distinct tags of up to MAX_SKILL_TAG_LENGTH bytes. Emits SkillTagsRecorded.
+        #[ink(message)]
+        pub fn record_skill_tags(&mut self, token_id: u32, skill_tags: Vec<String>) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let reward_info = self
+                .rewarded_tokens
+                .get(token_id)
+                .ok_or(Error::InvalidArgument)?;
+            let mut tags = skill_tags;
+            tags.sort();
+            tags.dedup();
+            if !reward_info.is_positive
+                || self.token_skill_tags.contains(token_id)
+                || tags.is_empty()
+                || tags.len() as u32 > MAX_SKILL_TAGS
+                || tags
+                    .iter()
+                    .any(|tag| tag.is_empty() || tag.len() as u32 > MAX_SKILL_TAG_LENGTH)
+            {
+                return Err(Error::InvalidArgument);
+            }
+            let mut profile = self.get_skill_profile(reward_info.recipient);
+            for tag in &tags {
+                match profile.binary_search_by(|skill| skill.tag.cmp(tag)) {
+                    Ok(position) => profile[position].audits += 1,
+                    Err(position) => profile.insert(
+                        position,
+                        SkillCount {
+                            tag: tag.clone(),
+                            audits: 1,
+                        },
+                    ),
+                }
+            }
+            if profile.len() as u32 > MAX_SKILL_PROFILE_TAGS {
+                return Err(Error::InvalidArgument);
+            }
+            self.skill_profiles.insert(reward_info.recipient, &profile);
+            self.token_skill_tags.insert(token_id, &tags);
+            self.env().emit_event(SkillTagsRecorded {
+                auditor: reward_info.recipient,
+                token_id,
+                skill_tags: tags,
+            });
+            Ok(())
+        }
 
-    #[test]
-    fn test_successful_audits_increment() {
-        //testcase to validate the successful increment in successful audits variable
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
-        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        let hash = "asdf";
-        let _x = contract.mint(accounts.bob, 1, 100, 5, 100, hash.to_string(), true);
-        assert_eq!(
-            contract
-                .show_auditors_record(accounts.bob)
-                .unwrap()
-                .successful_audits,
-            1
-        );
-    }
+        /// get_skill_tags returns the skill tags recorded for the audit of a token, sorted.
+        #[ink(message)]
+        pub fn get_skill_tags(&self, token_id: u32) -> Vec<String> {
+            self.token_skill_tags.get(token_id).unwrap_or_default()
+        }
 
-    #[test]
-    fn test_unsuccessful_audits_increment() {
-        //testcase to validate the successful increment in unsuccessful audits variable
-        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
-        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        /// get_skill_profile returns every skill tag of the successful audits of an auditor with how many of
+        /// them it was recorded for, sorted by tag, so that matchmaking can filter auditors by domain.
+        #[ink(message)]
+        pub fn get_skill_profile(&self, auditor: AccountId) -> Vec<SkillCount> {
+            self.skill_profiles.get(auditor).unwrap_or_default()
+        }
+
+        /// set_profile lets an account set its display name, avatar and contact hash. The display name can only
+        /// use ASCII letters, digits, '-', '_' and '.', and is unique regardless of case so that names can't be
+        /// impersonated. Changing it frees the old one and can only be done once per NAME_CHANGE_COOLDOWN,
+        /// so that names can't be cycled through, the avatar and contact hash can change any time.
+        /// Emits ProfileUpdated.
+        #[ink(message)]
+        pub fn set_profile(
+            &mut self,
+            display_name: String,
+            avatar_cid: String,
+            contact_hash: [u8; 32],
+        ) -> Result<()> {
+            let account = self.env().caller();
+            if self.get_migrated_to(account).is_some() {
+                return Err(Error::AccountMigrated);
+            }
+            if display_name.is_empty()
+                || display_name.len() as u32 > MAX_DISPLAY_NAME_LENGTH
+                || !display_name
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"-_.".contains(&byte))
+                || avatar_cid.len() as u32 > MAX_AVATAR_CID_LENGTH
+            {
+                return Err(Error::InvalidArgument);
+            }
+            let now = self.env().block_timestamp();
+            let key = display_name.to_ascii_lowercase();
+            let previous = self.profiles.get(account);
+            let name_changed_at = match &previous {
+                Some(profile) if profile.display_name.to_ascii_lowercase() == key => {
+                    profile.name_changed_at
+                }
+                Some(profile)
+                    if now < profile.name_changed_at.saturating_add(NAME_CHANGE_COOLDOWN) =>
+                {
+                    return Err(Error::NameChangeTooSoon);
+                }
+                _ => {
+                    if self.display_names.contains(&key) {
+                        return Err(Error::NameTaken);
+                    }
+                    if let Some(profile) = &previous {
+                        self.display_names
+                            .remove(profile.display_name.to_ascii_lowercase());
+                    }
+                    self.display_names.insert(&key, &account);
+                    now
+                }
+            };
+            self.profiles.insert(
+                account,
+                &Profile {
+                    display_name: display_name.clone(),
+                    avatar_cid,
+                    contact_hash,
+                    name_changed_at,
+                },
+            );
+            self.env().emit_event(ProfileUpdated {
+                account,
+                display_name,
+            });
+            Ok(())
+        }
+
+        /// get_profile returns the display profile of an account.
+        #[ink(message)]
+        pub fn get_profile(&self, account: AccountId) -> Option<Profile> {
+            self.profiles.get(account)
+        }
+
+        /// resolve_name returns the account a display name belongs to, regardless of case.
+        #[ink(message)]
+        pub fn resolve_name(&self, display_name: String) -> Option<AccountId> {
+            self.display_names.get(display_name.to_ascii_lowercase())
+        }
+
+        /// set_attestation_registry lets the owner allow an attestation contract, e.g. a proof-of-personhood or KYC
+        /// provider, to back the identity of auditors, or stop trusting it, which unverifies every auditor it backs.
+        /// It has to answer `is_attested(attestation_id, subject) -> bool`.
+        #[ink(message)]
+        pub fn set_attestation_registry(
+            &mut self,
+            registry: AccountId,
+            allowed: bool,
+        ) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if allowed {
+                self.attestation_registries.insert(registry, &true);
+            } else {
+                self.attestation_registries.remove(registry);
+            }
+            self.env()
+                .emit_event(AttestationRegistryChanged { registry, allowed });
+            Ok(())
+        }
+
+        /// is_attestation_registry returns if the attestation contract is allowed to back the identity of auditors.
+        #[ink(message)]
+        pub fn is_attestation_registry(&self, registry: AccountId) -> bool {
+            self.attestation_registries
+                .get(registry)
+                .unwrap_or_default()
+        }
+
+        /// attach_attestation lets an auditor link an attestation of an allowed attestation contract to their
+        /// profile, it is read from the contract first and refused if it isn't about the caller. It replaces the
+        /// one the auditor linked before. Emits AttestationAttached.
+        #[ink(message)]
+        pub fn attach_attestation(
+            &mut self,
+            registry: AccountId,
+            attestation_id: [u8; 32],
+        ) -> Result<()> {
+            let auditor = self.env().caller();
+            if self.get_migrated_to(auditor).is_some() {
+                return Err(Error::AccountMigrated);
+            }
+            if !self.is_attestation_registry(registry) {
+                return Err(Error::InvalidArgument);
+            }
+            if !self.is_attested_on(registry, attestation_id, auditor) {
+                return Err(Error::AttestationNotValid);
+            }
+            self.identity_attestations.insert(
+                auditor,
+                &IdentityAttestation {
+                    registry,
+                    attestation_id,
+                    attached_at: self.env().block_timestamp(),
+                },
+            );
+            self.env().emit_event(AttestationAttached {
+                auditor,
+                registry,
+                attestation_id,
+            });
+            Ok(())
+        }
+
+        /// remove_attestation lets an auditor unlink their identity attestation. Emits AttestationRemoved.
+        #[ink(message)]
+        pub fn remove_attestation(&mut self) -> Result<()> {
+            let auditor = self.env().caller();
+            if self.identity_attestations.take(auditor).is_none() {
+                return Err(Error::InvalidArgument);
+            }
+            self.env().emit_event(AttestationRemoved { auditor });
+            Ok(())
+        }
+
+        /// get_attestation returns the identity attestation an auditor linked, verified or not.
+        #[ink(message)]
+        pub fn get_attestation(&self, auditor: AccountId) -> Option<IdentityAttestation> {
+            self.identity_attestations.get(auditor)
+        }
+
+        /// is_verified tells if an auditor has an identity attestation whose contract is still allowed and still
+        /// vouches for it, it is read again on every call so that revoked attestations stop counting, e.g. for the
+        /// escrow to only let verified auditors be assigned.
+        #[ink(message)]
+        pub fn is_verified(&self, auditor: AccountId) -> bool {
+            let Some(attestation) = self.identity_attestations.get(auditor) else {
+                return false;
+            };
+            self.get_migrated_to(auditor).is_none()
+                && self.is_attestation_registry(attestation.registry)
+                && self.is_attested_on(attestation.registry, attestation.attestation_id, auditor)
+        }
+
+        /// get_monthly_stats returns the audits an auditor completed and the value they earned in a month,
+        /// year_month is the year and month as YYYYMM, e.g. 202410 for October 2024.
+        #[ink(message)]
+        pub fn get_monthly_stats(&self, auditor: AccountId, year_month: u32) -> MonthlyStats {
+            self.monthly_stats
+                .get((auditor, year_month))
+                .unwrap_or_default()
+        }
+
+        /// year_month_of converts a block timestamp to the YYYYMM of its calendar month in UTC.
+        pub fn year_month_of(timestamp: Timestamp) -> u32 {
+            // civil_from_days, proleptic Gregorian calendar with eras of 400 years starting on March 1st
+            let days = timestamp / 86_400_000 + 719_468;
+            let era = days / 146_097;
+            let day_of_era = days - era * 146_097;
+            let year_of_era =
+                (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+            let day_of_year =
+                day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+            let shifted_month = (5 * day_of_year + 2) / 153;
+            let month = if shifted_month < 10 {
+                shifted_month + 3
+            } else {
+                shifted_month - 9
+            };
+            let year = year_of_era + era * 400 + u64::from(month <= 2);
+            (year * 100 + month) as u32
+        }
+
+        /// get_minters returns the accounts that are authorized to mint.
+        #[ink(message)]
+        pub fn get_minters(&self) -> Vec<AccountId> {
+            self.minters.clone()
+        }
+
+        /// is_minter tells if the account is authorized to mint.
+        #[ink(message)]
+        pub fn is_minter(&self, account: AccountId) -> bool {
+            self.minters.contains(&account)
+        }
+
+        /// add_minter lets the owner authorize another account to mint, so that the platform contracts
+        /// (escrow, voting, a migration tool) can mint without sharing one key.
+        #[ink(message)]
+        pub fn add_minter(&mut self, minter: AccountId) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.is_minter(minter) || self.minters.len() as u32 >= MAX_MINTERS {
+                return Err(Error::InvalidArgument);
+            }
+            self.minters.push(minter);
+            self.env().emit_event(MinterAdded { minter });
+            Ok(())
+        }
+
+        /// remove_minter lets the owner revoke the minting rights of an account, the owner included.
+        #[ink(message)]
+        pub fn remove_minter(&mut self, minter: AccountId) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if !self.is_minter(minter) {
+                return Err(Error::InvalidArgument);
+            }
+            self.minters.retain(|account| *account != minter);
+            self.env().emit_event(MinterRemoved { minter });
+            Ok(())
+        }
+
+        /// record_arbitration lets an authorized minter, i.e. the voting contract when it finalizes a poll,
+        /// record the participation of an arbiter on its panel: a badge if they voted, a no-show if they didn't.
+        /// Emits ArbiterBadgeMinted or ArbiterNoShowRecorded.
+        #[ink(message)]
+        pub fn record_arbitration(
+            &mut self,
+            arbiter: AccountId,
+            vote_id: u32,
+            audit_id: u32,
+            voted: bool,
+        ) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.get_migrated_to(arbiter).is_some() {
+                return Err(Error::AccountMigrated);
+            }
+            let mut stats = self.get_arbiter_stats(arbiter);
+            if voted {
+                stats.badges += 1;
+                self.env().emit_event(ArbiterBadgeMinted {
+                    arbiter,
+                    vote_id,
+                    audit_id,
+                });
+            } else {
+                stats.no_shows += 1;
+                self.env().emit_event(ArbiterNoShowRecorded {
+                    arbiter,
+                    vote_id,
+                    audit_id,
+                });
+            }
+            self.arbiter_stats.insert(arbiter, &stats);
+            Ok(())
+        }
+
+        /// get_arbiter_stats returns how many finalized polls an arbiter voted on and how many they didn't.
+        #[ink(message)]
+        pub fn get_arbiter_stats(&self, arbiter: AccountId) -> ArbiterStats {
+            self.arbiter_stats.get(arbiter).unwrap_or_default()
+        }
+
+        /// record_patron_audit lets an authorized minter, i.e. the escrow when an audit closes, record it in the
+        /// history of its patron, whether the patron disputed it and whether they won the dispute.
+        /// Emits PatronAuditRecorded.
+        #[ink(message)]
+        pub fn record_patron_audit(
+            &mut self,
+            patron: AccountId,
+            audit_id: u32,
+            disputed: bool,
+            dispute_won: bool,
+        ) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.get_migrated_to(patron).is_some() {
+                return Err(Error::AccountMigrated);
+            }
+            if dispute_won && !disputed {
+                return Err(Error::InvalidArgument);
+            }
+            let mut stats = self.get_patron_stats(patron);
+            stats.audits_funded += 1;
+            stats.disputes_raised += u32::from(disputed);
+            stats.disputes_won += u32::from(dispute_won);
+            self.patron_stats.insert(patron, &stats);
+            self.env().emit_event(PatronAuditRecorded {
+                patron,
+                audit_id,
+                disputed,
+                dispute_won,
+            });
+            Ok(())
+        }
+
+        /// get_patron_stats returns how many closed audits a patron funded, disputed and won the dispute of.
+        #[ink(message)]
+        pub fn get_patron_stats(&self, patron: AccountId) -> PatronStats {
+            self.patron_stats.get(patron).unwrap_or_default()
+        }
+
+        /// get_patron_dispute_win_rate returns the percentage of the disputes of a patron they won, None if they
+        /// never disputed an audit.
+        #[ink(message)]
+        pub fn get_patron_dispute_win_rate(&self, patron: AccountId) -> Option<u8> {
+            let stats = self.get_patron_stats(patron);
+            if stats.disputes_raised == 0 {
+                return None;
+            }
+            Some((u64::from(stats.disputes_won) * 100 / u64::from(stats.disputes_raised)) as u8)
+        }
+
+        /// on_poll_created lets an authorized minter, i.e. a voting contract that has the token as its observer,
+        /// tell the token it opened a poll, so the token can keep its records in sync without an indexer.
+        /// A poll can only be reported once. Emits PollObserved.
+        #[ink(message)]
+        pub fn on_poll_created(&mut self, vote_id: u32, audit_id: u32) -> Result<()> {
+            let voting = self.env().caller();
+            if !self.is_minter(voting) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.observed_polls.contains((voting, vote_id)) {
+                return Err(Error::InvalidArgument);
+            }
+            self.observed_polls.insert(
+                (voting, vote_id),
+                &ObservedPoll {
+                    audit_id,
+                    created_at: Some(self.env().block_timestamp()),
+                    finalized_at: None,
+                },
+            );
+            self.env().emit_event(PollObserved {
+                voting,
+                vote_id,
+                audit_id,
+            });
+            Ok(())
+        }
+
+        /// on_poll_finalized lets an authorized minter, i.e. a voting contract that has the token as its observer,
+        /// tell the token it finalized a poll, polls opened before the token observed it are recorded too.
+        /// A poll can only be finalized once. Emits PollFinalizationObserved.
+        #[ink(message)]
+        pub fn on_poll_finalized(&mut self, vote_id: u32, audit_id: u32) -> Result<()> {
+            let voting = self.env().caller();
+            if !self.is_minter(voting) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let mut poll = self
+                .observed_polls
+                .get((voting, vote_id))
+                .unwrap_or(ObservedPoll {
+                    audit_id,
+                    created_at: None,
+                    finalized_at: None,
+                });
+            if poll.finalized_at.is_some() {
+                return Err(Error::InvalidArgument);
+            }
+            poll.finalized_at = Some(self.env().block_timestamp());
+            self.observed_polls.insert((voting, vote_id), &poll);
+            self.env().emit_event(PollFinalizationObserved {
+                voting,
+                vote_id,
+                audit_id: poll.audit_id,
+            });
+            Ok(())
+        }
+
+        /// get_observed_poll returns what the token knows about a poll of a voting contract.
+        #[ink(message)]
+        pub fn get_observed_poll(&self, voting: AccountId, vote_id: u32) -> Option<ObservedPoll> {
+            self.observed_polls.get((voting, vote_id))
+        }
+
+        /// migrate_account lets the owner move the history of an account to a new one, e.g. after its key was
+        /// compromised, with proof the hash/link of the evidence the owner approved it on. The stats, the monthly
+        /// stats, the arbiter stats, the patron stats, the failure history, the skill profile, the display profile
+        /// unless new has its own and the tokens of old are moved to new, which must have no history of its own,
+        /// and old is marked as migrated so that nothing can be minted to it anymore. Emits ReputationMigrated and
+        /// MetadataUpdate for every token that changed recipient.
+        #[ink(message)]
+        pub fn migrate_account(
+            &mut self,
+            old: AccountId,
+            new: AccountId,
+            proof: String,
+        ) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.get_migrated_to(old).is_some() || self.get_migrated_to(new).is_some() {
+                return Err(Error::AccountMigrated);
+            }
+            if old == new
+                || self.balances.contains(new)
+                || self.reputations.contains(new)
+                || self.arbiter_stats.contains(new)
+                || self.patron_stats.contains(new)
+            {
+                return Err(Error::InvalidArgument);
+            }
+            if let Some(stats) = self.balances.take(old) {
+                self.balances.insert(new, &stats);
+            }
+            if let Some(stats) = self.arbiter_stats.take(old) {
+                self.arbiter_stats.insert(new, &stats);
+            }
+            if let Some(stats) = self.patron_stats.take(old) {
+                self.patron_stats.insert(new, &stats);
+            }
+            if let Some(history) = self.failure_history.take(old) {
+                self.failure_history.insert(new, &history);
+            }
+            if let Some(profile) = self.skill_profiles.take(old) {
+                self.skill_profiles.insert(new, &profile);
+            }
+            //an attestation is about the old account, the new one has to attach its own
+            self.identity_attestations.remove(old);
+            //the display profile follows the account unless the new one already has its own
+            if let Some(profile) = self.profiles.take(old) {
+                let key = profile.display_name.to_ascii_lowercase();
+                if self.profiles.contains(new) {
+                    self.display_names.remove(key);
+                } else {
+                    self.display_names.insert(key, &new);
+                    self.profiles.insert(new, &profile);
+                }
+            }
+            if let Some(reputation) = self.reputations.take(old) {
+                for index in 0..reputation.tokens {
+                    if let Some(token_id) = self.auditor_tokens.take((old, index)) {
+                        self.migrate_token(old, new, token_id);
+                        self.auditor_tokens.insert((new, index), &token_id);
+                    }
+                }
+                self.reputations.insert(new, &reputation);
+            }
+            self.migrated_to.insert(old, &new);
+            self.env()
+                .emit_event(ReputationMigrated { old, new, proof });
+            Ok(())
+        }
+
+        /// migrate_token moves a token of a migrated account and its month of stats to the new account.
+        fn migrate_token(&mut self, old: AccountId, new: AccountId, token_id: u32) {
+            if let Some(mut reward_info) = self.rewarded_tokens.get(token_id) {
+                //monthly stats only come from successful audits, so their months are where the stats are
+                if reward_info.is_positive {
+                    let year_month = Self::year_month_of(reward_info.minted_at);
+                    if let Some(monthly) = self.monthly_stats.take((old, year_month)) {
+                        self.monthly_stats.insert((new, year_month), &monthly);
+                    }
+                }
+                reward_info.recipient = new;
+                self.rewarded_tokens.insert(token_id, &reward_info);
+                self.bump_metadata_version(token_id);
+                self.env().emit_event(MetadataUpdate { token_id });
+            }
+        }
+
+        /// get_migrated_to returns the account the history of a migrated account was moved to.
+        #[ink(message)]
+        pub fn get_migrated_to(&self, account: AccountId) -> Option<AccountId> {
+            self.migrated_to.get(account)
+        }
+
+        /// show_auditors_record returns a struct telling how many successful
+        /// and unsuccessful audits the auditor has completed, the total value of the successful ones,
+        /// and their current and best streaks of successful audits.
+        #[ink(message)]
+        pub fn show_auditors_record(&self, auditor: AccountId) -> Option<Stats> {
+            self.balances.get(&auditor)
+        }
+
+        /// show_reward_details returns the RewardInfo/the metadata corresponding to the
+        /// reward token entered.
+        #[ink(message)]
+        pub fn show_reward_details(&self, reward_id: u32) -> Option<RewardInfo> {
+            self.rewarded_tokens.get(&reward_id)
+        }
+
+        /// metadata_version returns how many times the metadata of a token has been updated since it was minted.
+        #[ink(message)]
+        pub fn metadata_version(&self, token_id: u32) -> u32 {
+            self.metadata_versions.get(token_id).unwrap_or_default()
+        }
+
+        /// issue_credential returns the SCALE encoded (Credential, digest, signature) of a token, where the digest
+        /// is the blake2x256 hash of the encoded Credential and the signature the ECDSA signature of the digest by
+        /// the credential issuer, so that the auditor can present it to third-party platforms. None until the
+        /// current credential of the token was signed with sign_credential.
+        #[ink(message)]
+        pub fn issue_credential(&self, token_id: u32) -> Option<Vec<u8>> {
+            let credential = self.credential_of(token_id)?;
+            let digest = Self::digest_of(&credential);
+            let signature: [u8; 65] = self.credential_signatures.get(token_id)?.try_into().ok()?;
+            if !self.is_issuer_signature(&digest, &signature) {
+                return None;
+            }
+            Some(scale::Encode::encode(&(credential, digest, signature)))
+        }
+
+        /// verify_credential returns whether the digest is the one of the credential the token would be issued
+        /// now and the signature is the credential issuer's signature of it, it fails for a token whose metadata
+        /// changed since the credential was issued.
+        #[ink(message)]
+        pub fn verify_credential(
+            &self,
+            token_id: u32,
+            digest: [u8; 32],
+            signature: [u8; 65],
+        ) -> bool {
+            self.credential_of(token_id)
+                .is_some_and(|credential| Self::digest_of(&credential) == digest)
+                && self.is_issuer_signature(&digest, &signature)
+        }
+
+        /// set_credential_issuer lets the owner register the compressed ECDSA public key credentials are signed
+        /// with, or remove it, which stops every credential from verifying. The key is kept off-chain by the
+        /// issuing service. Emits CredentialIssuerChanged.
+        #[ink(message)]
+        pub fn set_credential_issuer(&mut self, issuer: Option<[u8; 33]>) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.credential_issuer = issuer.map(|issuer| issuer.to_vec());
+            self.env().emit_event(CredentialIssuerChanged { issuer });
+            Ok(())
+        }
+
+        /// get_credential_digest returns the digest of the current credential of a token, the message the credential
+        /// issuer signs for sign_credential.
+        #[ink(message)]
+        pub fn get_credential_digest(&self, token_id: u32) -> Option<[u8; 32]> {
+            self.credential_of(token_id)
+                .map(|credential| Self::digest_of(&credential))
+        }
+
+        /// get_credential_issuer returns the public key credentials are signed with, if there is one.
+        #[ink(message)]
+        pub fn get_credential_issuer(&self) -> Option<[u8; 33]> {
+            self.credential_issuer
+                .as_ref()
+                .and_then(|issuer| issuer.as_slice().try_into().ok())
+        }
+
+        /// sign_credential stores the credential issuer's signature of the digest of the current credential of a
+        /// token, anyone can submit it as it is checked against the issuer key. A token revised since needs a
+        /// new signature. Emits CredentialSigned.
+        #[ink(message)]
+        pub fn sign_credential(&mut self, token_id: u32, signature: [u8; 65]) -> Result<()> {
+            if self.credential_issuer.is_none() {
+                return Err(Error::NoCredentialIssuer);
+            }
+            let credential = self.credential_of(token_id).ok_or(Error::InvalidArgument)?;
+            if !self.is_issuer_signature(&Self::digest_of(&credential), &signature) {
+                return Err(Error::InvalidSignature);
+            }
+            self.credential_signatures
+                .insert(token_id, &signature.to_vec());
+            self.env().emit_event(CredentialSigned {
+                token_id,
+                metadata_version: credential.metadata_version,
+            });
+            Ok(())
+        }
+
+        /// record_scorecard lets a minter attach the scorecard the audit was assessed with, as returned by the
+        /// escrow's get_scorecard, to its token right after minting it, so reputation isn't limited to a
+        /// success flag. A token's scorecard can't be replaced once recorded.
+        #[ink(message)]
+        pub fn record_scorecard(&mut self, token_id: u32, scorecard: Scorecard) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let mut reward_info = self
+                .rewarded_tokens
+                .get(token_id)
+                .ok_or(Error::InvalidArgument)?;
+            if scorecard.quality > 100 || reward_info.scorecard.is_some() {
+                return Err(Error::InvalidArgument);
+            }
+            reward_info.scorecard = Some(scorecard);
+            self.rewarded_tokens.insert(token_id, &reward_info);
+            self.bump_metadata_version(token_id);
+            self.env().emit_event(MetadataUpdate { token_id });
+            Ok(())
+        }
+
+        /// revise lets the owner point a token to a revised audit report, bumping its metadata version
+        /// and emitting MetadataUpdate.
+        #[ink(message)]
+        pub fn revise(&mut self, token_id: u32, ipfs_hash: String) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let mut reward_info = self
+                .rewarded_tokens
+                .get(token_id)
+                .ok_or(Error::InvalidArgument)?;
+            reward_info.ipfs_hash = ipfs_hash;
+            self.rewarded_tokens.insert(token_id, &reward_info);
+            self.bump_metadata_version(token_id);
+            self.env().emit_event(MetadataUpdate { token_id });
+            Ok(())
+        }
+
+        /// mark_metadata_updated lets the owner tell indexers that the rendered metadata of a range of tokens changed,
+        /// e.g. when the certificate template is updated, bumping the version of each and emitting BatchMetadataUpdate.
+        #[ink(message)]
+        pub fn mark_metadata_updated(
+            &mut self,
+            from_token_id: u32,
+            to_token_id: u32,
+        ) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if from_token_id > to_token_id || to_token_id >= self.current_id {
+                return Err(Error::InvalidArgument);
+            }
+            for token_id in from_token_id..=to_token_id {
+                self.bump_metadata_version(token_id);
+            }
+            self.env().emit_event(BatchMetadataUpdate {
+                from_token_id,
+                to_token_id,
+            });
+            Ok(())
+        }
+
+        /// reputation_score returns the auditor's reputation at the current block, every successful audit adds
+        /// REPUTATION_UNIT and every unsuccessful one takes it away, both decayed by their age so that the score
+        /// halves every reputation_half_life. The score never goes below zero. The weights are kept as a running
+        /// total decayed at every mint, which weighs every audit as if it was decayed from its own minted_at.
+        #[ink(message)]
+        pub fn reputation_score(&self, auditor: AccountId) -> u64 {
+            let reputation = self.reputations.get(auditor).unwrap_or_default();
+            let age = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(reputation.updated_at);
+            let weight =
+                decay::decay(reputation.gained, age, self.reputation_half_life).saturating_sub(
+                    decay::decay(reputation.lost, age, self.reputation_half_life),
+                );
+            //rounded to the nearest REPUTATION_UNIT fraction of an audit
+            let unit = decay::ONE / REPUTATION_UNIT as u128;
+            ((weight + unit / 2) / unit) as u64
+        }
+
+        /// get_reputation_half_life returns the time in milliseconds after which an audit counts half as much.
+        #[ink(message)]
+        pub fn get_reputation_half_life(&self) -> Timestamp {
+            self.reputation_half_life
+        }
+
+        /// set_reputation_half_life lets the owner change how fast reputation decays, it is applied lazily
+        /// so it changes how the reputation of every auditor decays from their last audit on.
+        #[ink(message)]
+        pub fn set_reputation_half_life(&mut self, half_life: Timestamp) -> Result<()> {
+            if self.owner != self.env().caller() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if half_life == 0 {
+                return Err(Error::InvalidArgument);
+            }
+            self.reputation_half_life = half_life;
+            Ok(())
+        }
+
+        fn credential_of(&self, token_id: u32) -> Option<Credential> {
+            let reward_info = self.rewarded_tokens.get(token_id)?;
+            Some(Credential {
+                credential_type: CREDENTIAL_TYPE.to_vec(),
+                version: CREDENTIAL_VERSION,
+                issuer: self.env().account_id(),
+                subject: reward_info.recipient,
+                token_id,
+                audit_id: reward_info.audit_id,
+                is_positive: reward_info.is_positive,
+                amount: reward_info.amount,
+                ipfs_hash: reward_info.ipfs_hash,
+                scorecard: reward_info.scorecard,
+                issued_at: reward_info.minted_at,
+                metadata_version: self.metadata_version(token_id),
+            })
+        }
+
+        /// whether the signature of the digest recovers to the credential issuer key.
+        fn is_issuer_signature(&self, digest: &[u8; 32], signature: &[u8; 65]) -> bool {
+            let Some(issuer) = self.get_credential_issuer() else {
+                return false;
+            };
+            let mut signer = [0u8; 33];
+            ink::env::ecdsa_recover(signature, digest, &mut signer).is_ok() && signer == issuer
+        }
+
+        fn digest_of(credential: &Credential) -> [u8; 32] {
+            let mut digest = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(
+                &scale::Encode::encode(credential),
+                &mut digest,
+            );
+            digest
+        }
+
+        fn bump_metadata_version(&mut self, token_id: u32) {
+            let version = self.metadata_version(token_id) + 1;
+            self.metadata_versions.insert(token_id, &version);
+        }
+
+        /// asks the attestation contract if the attestation is valid and about the subject, a contract that
+        /// doesn't answer doesn't vouch for anyone.
+        fn is_attested_on(
+            &self,
+            registry: AccountId,
+            attestation_id: [u8; 32],
+            subject: AccountId,
+        ) -> bool {
+            AttestationRegistry::new(registry, ATTESTATION_GAS_LIMIT)
+                .is_attested(attestation_id, subject)
+        }
+
+        /// mint_token updates the records of the recipient and mints the token of one audit for mint and
+        /// mint_batch, which check the caller, and returns the ID of the token. Emits TokenMinted.
+        fn mint_token(&mut self, args: MintArgs) -> Result<u32> {
+            if self.get_migrated_to(args.recipient).is_some() {
+                return Err(Error::AccountMigrated);
+            }
+            if args.is_positive {
+                let mut _stat = self.balances.get(args.recipient).unwrap_or_default();
+
+                _stat.successful_audits += 1;
+                _stat.total_value_audited = _stat.total_value_audited.saturating_add(args.amount);
+                _stat.current_streak += 1;
+                _stat.best_streak = _stat.best_streak.max(_stat.current_streak);
+                self.balances.insert(args.recipient, &_stat);
+                let year_month = Self::year_month_of(self.env().block_timestamp());
+                let mut monthly = self.get_monthly_stats(args.recipient, year_month);
+                monthly.audits_completed += 1;
+                monthly.value_earned = monthly.value_earned.saturating_add(args.amount);
+                self.monthly_stats
+                    .insert((args.recipient, year_month), &monthly);
+            } else {
+                let mut _stat = self.balances.get(args.recipient).unwrap_or_default();
+                _stat.unsuccessful_audits += 1;
+                _stat.current_streak = 0;
+                self.balances.insert(args.recipient, &_stat);
+            }
+            let _reward_info = RewardInfo {
+                recipient: args.recipient,
+                audit_id: args.audit_id,
+                completion_time: args.completion_time,
+                extensions: args.extensions,
+                amount: args.amount,
+                ipfs_hash: args.ipfs_hash,
+                is_positive: args.is_positive,
+                minted_at: self.env().block_timestamp(),
+                scorecard: None,
+            };
+            self.rewarded_tokens.insert(self.current_id, &_reward_info);
+            let now = self.env().block_timestamp();
+            let mut reputation = self.reputations.get(args.recipient).unwrap_or_default();
+            let age = now.saturating_sub(reputation.updated_at);
+            reputation.gained = decay::decay(reputation.gained, age, self.reputation_half_life);
+            reputation.lost = decay::decay(reputation.lost, age, self.reputation_half_life);
+            if args.is_positive {
+                reputation.gained = reputation.gained.saturating_add(decay::ONE);
+            } else {
+                reputation.lost = reputation.lost.saturating_add(decay::ONE);
+            }
+            reputation.updated_at = now;
+            self.auditor_tokens
+                .insert((args.recipient, reputation.tokens), &self.current_id);
+            reputation.tokens += 1;
+            self.reputations.insert(args.recipient, &reputation);
+            let token_id = self.current_id;
+            self.current_id += 1;
+            self.env().emit_event(TokenMinted {
+                token_id,
+                reward_info: Some(_reward_info),
+                is_positive: args.is_positive,
+            });
+            Ok(token_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+    #[cfg(feature = "ink-experimental-engine")]
+    use crate::digital_certificate::digital_certificate;
+
+    /// the secret key of the credential issuer in the tests.
+    fn issuer_key() -> secp256k1::SecretKey {
+        secp256k1::SecretKey::from_slice(&[1; 32]).unwrap()
+    }
+
+    fn public_key_of(key: &secp256k1::SecretKey) -> [u8; 33] {
+        secp256k1::PublicKey::from_secret_key(secp256k1::SECP256K1, key).serialize()
+    }
+
+    /// the recoverable ECDSA signature of the digest, as ecdsa_recover takes it.
+    fn sign(key: &secp256k1::SecretKey, digest: [u8; 32]) -> [u8; 65] {
+        let message = secp256k1::Message::from_slice(&digest).unwrap();
+        let (recovery_id, compact) = secp256k1::SECP256K1
+            .sign_ecdsa_recoverable(&message, key)
+            .serialize_compact();
+        let mut signature = [0u8; 65];
+        signature[..64].copy_from_slice(&compact);
+        signature[64] = recovery_id.to_i32() as u8;
+        signature
+    }
+
+    #[test]
+    fn test_assert_owner() {
+        //testcase to validate that owner is set in the contract after deployment.
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let contract_owner = contract.owner;
+        assert_eq!(contract_owner, accounts.alice);
+    }
+
+    #[test]
+    fn test_failure_on_non_owner_call() {
+        //testcase to validate that only owner can call the contract
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let hash = "asdf";
+        let _res = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), false);
+        assert!(_res.is_err());
+    }
+
+    #[test]
+    fn test_successful_audits_increment() {
+        //testcase to validate the successful increment in successful audits variable
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.mint(accounts.bob, 1, 100, 5, 100, hash.to_string(), true);
+        assert_eq!(
+            contract
+                .show_auditors_record(accounts.bob)
+                .unwrap()
+                .successful_audits,
+            1
+        );
+    }
+
+    #[test]
+    fn test_unsuccessful_audits_increment() {
+        //testcase to validate the successful increment in unsuccessful audits variable
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.mint(accounts.bob, 1, 100, 5, 100, hash.to_string(), false);
+        assert_eq!(
+            contract
+                .show_auditors_record(accounts.bob)
+                .unwrap()
+                .unsuccessful_audits,
+            1
+        );
+    }
+
+    #[test]
+    fn test_successful_entry_in_rewarded_tokens_mapping() {
+        //testcase to confirm the modification of values in the mapping of reward details
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+
+        assert_eq!(contract.show_reward_details(0).unwrap().amount, 100);
+    }
+
+    #[test]
+    fn test_reputation_halves_after_half_life() {
+        //testcase to validate that the reputation of an audit decays with its age
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.set_reputation_half_life(1000);
+        let _y = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        assert_eq!(
+            contract.reputation_score(accounts.bob),
+            rewardtoken::REPUTATION_UNIT
+        );
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        assert_eq!(
+            contract.reputation_score(accounts.bob),
+            rewardtoken::REPUTATION_UNIT / 2
+        );
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1500);
+        assert_eq!(contract.reputation_score(accounts.bob), 353_553);
+    }
+
+    #[test]
+    fn test_unsuccessful_audit_reduces_reputation() {
+        //testcase to validate that recent failures outweigh old successes
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.set_reputation_half_life(1000);
+        let _y = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+        let _z = contract.mint(accounts.bob, 2, 100, 0, 100, hash.to_string(), false);
+        assert_eq!(contract.reputation_score(accounts.bob), 0);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.set_reputation_half_life(1).is_err());
+    }
+
+    #[test]
+    fn test_reputation_keeps_a_running_total() {
+        //testcase to validate that the reputation of earlier audits keeps decaying after later mints
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.set_reputation_half_life(1000);
+        let _y = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        let _z = contract.mint(accounts.bob, 2, 100, 0, 100, hash.to_string(), true);
+        assert_eq!(
+            contract.reputation_score(accounts.bob),
+            rewardtoken::REPUTATION_UNIT * 3 / 2
+        );
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+        assert_eq!(
+            contract.reputation_score(accounts.bob),
+            rewardtoken::REPUTATION_UNIT * 3 / 4
+        );
+        let reputation = contract.reputations.get(accounts.bob).unwrap();
+        assert_eq!(reputation.tokens, 2);
+        assert_eq!(contract.auditor_tokens.get((accounts.bob, 1)), Some(1));
+    }
+
+    #[test]
+    fn test_revise_bumps_metadata_version() {
+        //testcase to validate that revising a token's report bumps its metadata version
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        assert_eq!(contract.metadata_version(0), 0);
+        assert!(contract.revise(0, "qwer".to_string()).is_ok());
+        assert_eq!(contract.metadata_version(0), 1);
+        assert_eq!(contract.show_reward_details(0).unwrap().ipfs_hash, "qwer");
+        assert!(contract.revise(1, "qwer".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_batch_metadata_update_range() {
+        //testcase to validate that only minted tokens can be marked as updated
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        let _y = contract.mint(accounts.bob, 2, 100, 0, 100, hash.to_string(), true);
+        assert!(contract.mark_metadata_updated(0, 2).is_err());
+        assert!(contract.mark_metadata_updated(0, 1).is_ok());
+        assert_eq!(contract.metadata_version(1), 1);
+    }
+
+    #[test]
+    fn test_minters_managed_by_owner() {
+        //testcase to validate that only accounts added by the owner can mint
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
         let hash = "asdf";
-        let _x = contract.mint(accounts.bob, 1, 100, 5, 100, hash.to_string(), false);
+        assert!(contract.add_minter(accounts.charlie).is_ok());
+        assert!(contract.add_minter(accounts.charlie).is_err());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.add_minter(accounts.django).is_err());
+        assert!(contract
+            .mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true)
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.remove_minter(accounts.charlie).is_ok());
+        assert_eq!(contract.get_minters(), [accounts.alice].to_vec());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract
+            .mint(accounts.bob, 2, 100, 0, 100, hash.to_string(), true)
+            .is_err());
+    }
+
+    #[test]
+    fn test_record_scorecard() {
+        //testcase to validate that a minted token gets its scorecard once
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        let mut scorecard = rewardtoken::Scorecard {
+            criticals: 1,
+            highs: 0,
+            mediums: 2,
+            quality: 101,
+        };
+        assert!(contract.record_scorecard(0, scorecard).is_err());
+        scorecard.quality = 75;
+        assert!(contract.record_scorecard(1, scorecard).is_err());
+        assert!(contract.record_scorecard(0, scorecard).is_ok());
+        assert_eq!(
+            contract.show_reward_details(0).unwrap().scorecard,
+            Some(scorecard)
+        );
+        assert_eq!(contract.metadata_version(0), 1);
+        assert!(contract.record_scorecard(0, scorecard).is_err());
+    }
+
+    #[test]
+    fn test_monthly_stats_bucketed_by_month() {
+        //testcase to validate that successful audits are added up per calendar month
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(rewardtoken::Rewardtoken::year_month_of(0), 197001);
+        assert_eq!(
+            rewardtoken::Rewardtoken::year_month_of(1709164800000),
+            202402
+        );
+        assert_eq!(
+            rewardtoken::Rewardtoken::year_month_of(1709251200000),
+            202403
+        );
+        assert_eq!(
+            rewardtoken::Rewardtoken::year_month_of(1767225599000),
+            202512
+        );
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        //29th of February 2024
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1709164800000);
+        let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        let _y = contract.mint(accounts.bob, 2, 100, 0, 50, hash.to_string(), true);
+        let _z = contract.mint(accounts.bob, 3, 100, 0, 70, hash.to_string(), false);
+        //1st of March 2024
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1709251200000);
+        let _w = contract.mint(accounts.bob, 4, 100, 0, 30, hash.to_string(), true);
+        let february = contract.get_monthly_stats(accounts.bob, 202402);
+        assert_eq!(february.audits_completed, 2);
+        assert_eq!(february.value_earned, 150);
         assert_eq!(
             contract
-                .show_auditors_record(accounts.bob)
-                .unwrap()
-                .unsuccessful_audits,
-            1
+                .get_monthly_stats(accounts.bob, 202403)
+                .value_earned,
+            30
+        );
+        assert_eq!(
+            contract.get_monthly_stats(accounts.charlie, 202402),
+            rewardtoken::MonthlyStats::default()
         );
     }
 
     #[test]
-    fn test_successful_entry_in_rewarded_tokens_mapping() {
-        //testcase to confirm the modification of values in the mapping of reward details
+    fn test_with_minters_authorizes_platform_contracts() {
+        //testcase to validate that the minters passed at deployment can mint next to the owner
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let contract = rewardtoken::Rewardtoken::with_minters(
+            accounts.alice,
+            [accounts.charlie, accounts.django, accounts.charlie].to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            contract.get_minters(),
+            [accounts.alice, accounts.charlie, accounts.django].to_vec()
+        );
+        let too_many = rewardtoken::Rewardtoken::with_minters(
+            accounts.alice,
+            (0..10u8)
+                .map(|i| ink::primitives::AccountId::from([i + 10; 32]))
+                .collect(),
+        );
+        assert!(matches!(too_many, Err(rewardtoken::Error::InvalidArgument)));
+    }
+
+    #[test]
+    fn test_migrate_account_moves_history() {
+        //testcase to validate that a migration moves the stats and tokens and closes the old account
         let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
         let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
         let hash = "asdf";
+        //29th of February 2024
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1709164800000);
         let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        let _y = contract.mint(accounts.bob, 2, 100, 0, 70, hash.to_string(), false);
+        let score = contract.reputation_score(accounts.bob);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(
+            contract.migrate_account(accounts.bob, accounts.eve, "proof".to_string()),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .migrate_account(accounts.bob, accounts.eve, "proof".to_string())
+            .is_ok());
+        assert_eq!(contract.get_migrated_to(accounts.bob), Some(accounts.eve));
+        assert!(contract.show_auditors_record(accounts.bob).is_none());
+        let stats = contract.show_auditors_record(accounts.eve).unwrap();
+        assert_eq!(stats.successful_audits, 1);
+        assert_eq!(stats.unsuccessful_audits, 1);
+        assert_eq!(contract.reputation_score(accounts.eve), score);
+        assert_eq!(
+            contract.show_reward_details(0).unwrap().recipient,
+            accounts.eve
+        );
+        assert_eq!(contract.metadata_version(1), 1);
+        assert_eq!(
+            contract
+                .get_monthly_stats(accounts.eve, 202402)
+                .value_earned,
+            100
+        );
+        assert_eq!(
+            contract.get_monthly_stats(accounts.bob, 202402),
+            rewardtoken::MonthlyStats::default()
+        );
+        assert_eq!(
+            contract.mint(accounts.bob, 3, 100, 0, 100, hash.to_string(), true),
+            Err(rewardtoken::Error::AccountMigrated)
+        );
+        assert_eq!(
+            contract.migrate_account(accounts.charlie, accounts.eve, "proof".to_string()),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+    }
+    #[test]
+    fn test_record_arbitration_by_minter() {
+        //testcase to validate that only minters record arbitrations, and that they add up per arbiter
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        assert!(contract.add_minter(accounts.django).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.record_arbitration(accounts.frank, 0, 7, true),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert!(contract
+            .record_arbitration(accounts.frank, 0, 7, true)
+            .is_ok());
+        assert!(contract
+            .record_arbitration(accounts.frank, 1, 8, true)
+            .is_ok());
+        assert!(contract
+            .record_arbitration(accounts.frank, 2, 9, false)
+            .is_ok());
+        assert_eq!(
+            contract.get_arbiter_stats(accounts.frank),
+            rewardtoken::ArbiterStats {
+                badges: 2,
+                no_shows: 1
+            }
+        );
+        assert_eq!(
+            contract.get_arbiter_stats(accounts.eve),
+            rewardtoken::ArbiterStats::default()
+        );
+    }
+    #[test]
+    fn test_streaks_and_total_value() {
+        //testcase to validate that an unsuccessful audit breaks the streak but keeps the best one
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        for (amount, positive) in [(100, true), (200, true), (50, false), (300, true)] {
+            assert!(contract
+                .mint(accounts.bob, 1, 100, 0, amount, hash.to_string(), positive)
+                .is_ok());
+        }
+        let stats = contract.show_auditors_record(accounts.bob).unwrap();
+        assert_eq!(stats.total_value_audited, 600);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 2);
+    }
+    #[test]
+    fn test_issue_credential_verifies_until_revised() {
+        //testcase to validate that an issued credential binds the token and stops verifying once it is revised
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        assert!(contract.issue_credential(0).is_none());
+        let hash = "asdf";
+        assert!(contract
+            .mint(accounts.charlie, 7, 100, 0, 100, hash.to_string(), true)
+            .is_ok());
+        //nothing is issued before the issuer signed the credential
+        assert!(contract.issue_credential(0).is_none());
+        let digest = contract.get_credential_digest(0).unwrap();
+        let signature = sign(&issuer_key(), digest);
+        assert_eq!(
+            contract.sign_credential(0, signature),
+            Err(rewardtoken::Error::NoCredentialIssuer)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.set_credential_issuer(Some(public_key_of(&issuer_key()))),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_credential_issuer(Some(public_key_of(&issuer_key())))
+            .is_ok());
+        //a signature by another key is refused
+        let forged = sign(&secp256k1::SecretKey::from_slice(&[2; 32]).unwrap(), digest);
+        assert_eq!(
+            contract.sign_credential(0, forged),
+            Err(rewardtoken::Error::InvalidSignature)
+        );
+        assert!(contract.sign_credential(0, signature).is_ok());
+        let encoded = contract.issue_credential(0).unwrap();
+        let (credential, issued_digest, issued_signature): (
+            rewardtoken::Credential,
+            [u8; 32],
+            [u8; 65],
+        ) = scale::Decode::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(credential.credential_type, rewardtoken::CREDENTIAL_TYPE);
+        assert_eq!(credential.issuer, accounts.bob);
+        assert_eq!(credential.subject, accounts.charlie);
+        assert_eq!(credential.audit_id, 7);
+        assert!(credential.is_positive);
+        assert_eq!(issued_digest, digest);
+        assert_eq!(issued_signature, signature);
+        assert!(contract.verify_credential(0, digest, signature));
+        assert!(!contract.verify_credential(0, digest, forged));
+        assert!(!contract.verify_credential(0, [0; 32], signature));
+        assert!(contract.revise(0, "revised".to_string()).is_ok());
+        assert!(!contract.verify_credential(0, digest, signature));
+        assert!(contract.issue_credential(0).is_none());
+        //removing the issuer key stops every credential from verifying
+        let revised = contract.get_credential_digest(0).unwrap();
+        let signature = sign(&issuer_key(), revised);
+        assert!(contract.sign_credential(0, signature).is_ok());
+        assert!(contract.verify_credential(0, revised, signature));
+        assert!(contract.set_credential_issuer(None).is_ok());
+        assert!(!contract.verify_credential(0, revised, signature));
+        assert!(contract.issue_credential(0).is_none());
+    }
+    #[test]
+    fn test_record_failure_reasons() {
+        //testcase to validate that the reasons of unsuccessful audits build the failure history of the auditor
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        for (audit_id, positive) in [(1, true), (2, false), (3, false)] {
+            assert!(contract
+                .mint(
+                    accounts.charlie,
+                    audit_id,
+                    100,
+                    0,
+                    100,
+                    hash.to_string(),
+                    positive
+                )
+                .is_ok());
+        }
+        //only unsuccessful audits fail for a reason
+        assert_eq!(
+            contract.record_failure(0, rewardtoken::FailureReason::Withdrawn),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert!(contract
+            .record_failure(1, rewardtoken::FailureReason::MissedDeadline)
+            .is_ok());
+        assert_eq!(
+            contract.record_failure(1, rewardtoken::FailureReason::Plagiarism),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert!(contract
+            .record_failure(2, rewardtoken::FailureReason::Plagiarism)
+            .is_ok());
+        let history = contract.get_failure_history(accounts.charlie);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].token_id, 1);
+        assert_eq!(history[0].audit_id, 2);
+        assert_eq!(history[1].reason, rewardtoken::FailureReason::Plagiarism);
+        assert_eq!(
+            contract
+                .get_failure_count(accounts.charlie, rewardtoken::FailureReason::MissedDeadline),
+            1
+        );
+        assert_eq!(
+            contract.get_failure_count(accounts.charlie, rewardtoken::FailureReason::Withdrawn),
+            0
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.record_failure(2, rewardtoken::FailureReason::Withdrawn),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+    }
 
-        assert_eq!(contract.show_reward_details(0).unwrap().amount, 100);
+    #[test]
+    fn test_skill_profile() {
+        //testcase to validate that the skill tags of successful audits add up to the skill profile of the auditor
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        for (audit_id, positive) in [(1, true), (2, true), (3, false)] {
+            assert!(contract
+                .mint(
+                    accounts.charlie,
+                    audit_id,
+                    100,
+                    0,
+                    100,
+                    hash.to_string(),
+                    positive
+                )
+                .is_ok());
+        }
+        assert!(contract
+            .record_skill_tags(
+                0,
+                ["ink!".to_string(), "ZK".to_string(), "ink!".to_string()].to_vec()
+            )
+            .is_ok());
+        assert_eq!(
+            contract.get_skill_tags(0),
+            ["ZK".to_string(), "ink!".to_string()].to_vec()
+        );
+        assert_eq!(
+            contract.record_skill_tags(0, ["EVM".to_string()].to_vec()),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        //unsuccessful audits don't demonstrate experience
+        assert_eq!(
+            contract.record_skill_tags(2, ["EVM".to_string()].to_vec()),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert_eq!(
+            contract.record_skill_tags(
+                1,
+                ["x".repeat(rewardtoken::MAX_SKILL_TAG_LENGTH as usize + 1)].to_vec()
+            ),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert!(contract
+            .record_skill_tags(1, ["EVM".to_string(), "ink!".to_string()].to_vec())
+            .is_ok());
+        assert_eq!(
+            contract.get_skill_profile(accounts.charlie),
+            [("EVM", 1), ("ZK", 1), ("ink!", 2)]
+                .map(|(tag, audits)| rewardtoken::SkillCount {
+                    tag: tag.to_string(),
+                    audits
+                })
+                .to_vec()
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.record_skill_tags(1, ["EVM".to_string()].to_vec()),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+    }
+
+    #[test]
+    fn test_poll_lifecycle_observed() {
+        //testcase to validate that only minters report polls, and that each poll is created and finalized once
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        assert!(contract.add_minter(accounts.django).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.on_poll_created(0, 7),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        assert_eq!(
+            contract.on_poll_finalized(0, 7),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert!(contract.on_poll_created(0, 7).is_ok());
+        assert_eq!(
+            contract.on_poll_created(0, 7),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert_eq!(
+            contract.get_observed_poll(accounts.django, 0),
+            Some(rewardtoken::ObservedPoll {
+                audit_id: 7,
+                created_at: Some(0),
+                finalized_at: None
+            })
+        );
+        //the same vote ID of another voting contract is another poll
+        assert!(contract.get_observed_poll(accounts.alice, 0).is_none());
+        assert!(contract.on_poll_finalized(0, 7).is_ok());
+        assert_eq!(
+            contract.on_poll_finalized(0, 7),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert_eq!(
+            contract
+                .get_observed_poll(accounts.django, 0)
+                .and_then(|poll| poll.finalized_at),
+            Some(0)
+        );
+        //a poll opened before the token observed the voting contract is only recorded as finalized
+        assert!(contract.on_poll_finalized(1, 8).is_ok());
+        assert_eq!(
+            contract.get_observed_poll(accounts.django, 1),
+            Some(rewardtoken::ObservedPoll {
+                audit_id: 8,
+                created_at: None,
+                finalized_at: Some(0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_identity_attestation() {
+        //testcase to validate that auditors are verified by attestations of allowed attestation contracts only
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(
+            contract.attach_attestation(accounts.django, [1; 32]),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert_eq!(
+            contract.set_attestation_registry(accounts.django, true),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_attestation_registry(accounts.django, true)
+            .is_ok());
+        crate::attestation::mock::attest(accounts.django, [1; 32], accounts.charlie);
+        crate::attestation::mock::attest(accounts.django, [2; 32], accounts.eve);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(
+            contract.attach_attestation(accounts.django, [0; 32]),
+            Err(rewardtoken::Error::AttestationNotValid)
+        );
+        //an attestation about someone else doesn't verify the caller
+        assert_eq!(
+            contract.attach_attestation(accounts.django, [2; 32]),
+            Err(rewardtoken::Error::AttestationNotValid)
+        );
+        assert!(!contract.is_verified(accounts.charlie));
+        assert!(contract
+            .attach_attestation(accounts.django, [1; 32])
+            .is_ok());
+        assert!(contract.is_verified(accounts.charlie));
+        assert_eq!(
+            contract.get_attestation(accounts.charlie),
+            Some(rewardtoken::IdentityAttestation {
+                registry: accounts.django,
+                attestation_id: [1; 32],
+                attached_at: 0
+            })
+        );
+        //an attestation contract that isn't trusted anymore doesn't verify anyone
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_attestation_registry(accounts.django, false)
+            .is_ok());
+        assert!(!contract.is_verified(accounts.charlie));
+        assert!(contract
+            .set_attestation_registry(accounts.django, true)
+            .is_ok());
+        assert!(contract.is_verified(accounts.charlie));
+        //nor one its contract revoked
+        crate::attestation::mock::revoke(accounts.django, [1; 32], accounts.charlie);
+        assert!(!contract.is_verified(accounts.charlie));
+        crate::attestation::mock::attest(accounts.django, [1; 32], accounts.charlie);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.remove_attestation().is_ok());
+        assert_eq!(
+            contract.remove_attestation(),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert!(!contract.is_verified(accounts.charlie));
+    }
+
+    #[test]
+    fn test_display_profile() {
+        //testcase to validate that display names are unique regardless of case and can only change after the cooldown
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(
+            contract.set_profile(String::from("Charlie Audits"), String::new(), [0; 32]),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        assert!(contract
+            .set_profile(
+                String::from("Charlie"),
+                String::from("bafy-avatar"),
+                [1; 32]
+            )
+            .is_ok());
+        assert_eq!(
+            contract.resolve_name(String::from("charlie")),
+            Some(accounts.charlie)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(
+            contract.set_profile(String::from("CHARLIE"), String::new(), [0; 32]),
+            Err(rewardtoken::Error::NameTaken)
+        );
+        //the avatar and contact can change any time, the name only after the cooldown
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract
+            .set_profile(String::from("charlie"), String::from("bafy-new"), [2; 32])
+            .is_ok());
+        assert_eq!(
+            contract.set_profile(String::from("charlie-sec"), String::new(), [2; 32]),
+            Err(rewardtoken::Error::NameChangeTooSoon)
+        );
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            rewardtoken::NAME_CHANGE_COOLDOWN,
+        );
+        assert!(contract
+            .set_profile(String::from("charlie-sec"), String::new(), [2; 32])
+            .is_ok());
+        let profile = contract.get_profile(accounts.charlie).unwrap();
+        assert_eq!(profile.display_name, String::from("charlie-sec"));
+        assert_eq!(profile.name_changed_at, rewardtoken::NAME_CHANGE_COOLDOWN);
+        assert_eq!(contract.resolve_name(String::from("Charlie")), None);
+        //the old name is free again
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert!(contract
+            .set_profile(String::from("Charlie"), String::new(), [0; 32])
+            .is_ok());
+        //the profile follows a migrated account
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .migrate_account(accounts.charlie, accounts.eve, String::from("proof"))
+            .is_ok());
+        assert_eq!(
+            contract.resolve_name(String::from("charlie-sec")),
+            Some(accounts.eve)
+        );
+        assert!(contract.get_profile(accounts.charlie).is_none());
+    }
+
+    #[test]
+    fn test_version_and_interface_hash() {
+        //testcase to validate that the interface hash covers every message of the contract
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        assert_eq!(contract.version(), 1);
+        extern "Rust" {
+            fn __ink_generate_metadata() -> ink::metadata::InkProject;
+        }
+        let metadata = unsafe { __ink_generate_metadata() };
+        let mut selectors: Vec<[u8; 4]> = metadata
+            .spec()
+            .messages()
+            .iter()
+            .map(|message| message.selector().to_bytes().try_into().unwrap())
+            .collect();
+        selectors.sort_unstable();
+        let mut interface = crate::interface::MESSAGES.to_vec();
+        interface.sort_unstable();
+        assert_eq!(interface, selectors);
+        let mut hash = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+            &<[[u8; 4]; crate::interface::MESSAGES.len()]>::try_from(selectors).unwrap(),
+            &mut hash,
+        );
+        assert_eq!(contract.interface_hash(), hash);
+    }
+
+    #[test]
+    fn test_patron_history_recorded_by_minter() {
+        //testcase to validate that only minters record the audits of a patron, and the dispute win rate they add up to
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        assert!(contract.add_minter(accounts.django).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(
+            contract.record_patron_audit(accounts.frank, 0, false, false),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(contract.get_patron_dispute_win_rate(accounts.frank), None);
+        //a dispute can't be won without being raised
+        assert_eq!(
+            contract.record_patron_audit(accounts.frank, 0, false, true),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        for (audit_id, disputed, dispute_won) in [
+            (0, false, false),
+            (1, true, true),
+            (2, true, false),
+            (3, true, true),
+        ] {
+            assert!(contract
+                .record_patron_audit(accounts.frank, audit_id, disputed, dispute_won)
+                .is_ok());
+        }
+        assert_eq!(
+            contract.get_patron_stats(accounts.frank),
+            rewardtoken::PatronStats {
+                audits_funded: 4,
+                disputes_raised: 3,
+                disputes_won: 2
+            }
+        );
+        assert_eq!(
+            contract.get_patron_dispute_win_rate(accounts.frank),
+            Some(66)
+        );
+        //the history follows a migrated patron
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .migrate_account(accounts.frank, accounts.eve, String::from("proof"))
+            .is_ok());
+        assert_eq!(contract.get_patron_stats(accounts.eve).audits_funded, 4);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(
+            contract.record_patron_audit(accounts.frank, 4, false, false),
+            Err(rewardtoken::Error::AccountMigrated)
+        );
+    }
+
+    #[test]
+    fn test_mint_batch_with_partial_failure() {
+        //testcase to validate that mint_batch mints every item it can and returns why the others weren't minted
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let item = |recipient, audit_id, is_positive| rewardtoken::MintArgs {
+            recipient,
+            audit_id,
+            completion_time: 50,
+            extensions: 0,
+            amount: 100,
+            ipfs_hash: String::from("hash"),
+            is_positive,
+        };
+        assert!(contract
+            .migrate_account(accounts.frank, accounts.eve, String::from("proof"))
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(
+            contract.mint_batch([item(accounts.charlie, 0, true)].to_vec()),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(
+            contract.mint_batch(Vec::new()),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        let oversized = (0..=rewardtoken::MAX_MINT_BATCH)
+            .map(|audit_id| item(accounts.charlie, audit_id, true))
+            .collect();
+        assert_eq!(
+            contract.mint_batch(oversized),
+            Err(rewardtoken::Error::InvalidArgument)
+        );
+        //the migrated recipient fails alone, the tokens after it keep following IDs
+        let results = contract
+            .mint_batch(
+                [
+                    item(accounts.charlie, 0, true),
+                    item(accounts.frank, 1, true),
+                    item(accounts.charlie, 2, false),
+                ]
+                .to_vec(),
+            )
+            .unwrap();
+        assert_eq!(
+            results,
+            [Ok(0), Err(rewardtoken::Error::AccountMigrated), Ok(1)].to_vec()
+        );
+        assert_eq!(contract.show_reward_details(1).unwrap().audit_id, 2);
+        assert!(contract.show_reward_details(2).is_none());
+        let stats = contract.show_auditors_record(accounts.charlie).unwrap();
+        assert_eq!(stats.successful_audits, 1);
+        assert_eq!(stats.unsuccessful_audits, 1);
+        let batched = scale::Encode::encode(&(0u32, 2u32, 1u32));
+        assert!(ink::env::test::recorded_events().any(|event| event.data.ends_with(&batched)));
+        //every minted token is announced like a single mint, so indexers of TokenMinted see them all
+        let minted_events = ink::env::test::recorded_events()
+            .filter(|event| {
+                [(0u32, true), (1u32, false)]
+                    .iter()
+                    .any(|(token_id, is_positive)| {
+                        event.data.ends_with(&scale::Encode::encode(&(
+                            token_id,
+                            contract.show_reward_details(*token_id),
+                            is_positive,
+                        )))
+                    })
+            })
+            .count();
+        assert_eq!(minted_events, 2);
+    }
+
+    #[test]
+    fn test_reputation_decays_from_each_mint() {
+        //testcase to validate that a running total weighs every audit by its own age
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let hash = "asdf";
+        let _x = contract.set_reputation_half_life(1000);
+        let _y = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        let _z = contract.mint(accounts.bob, 2, 100, 0, 100, hash.to_string(), true);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1250);
+        let _w = contract.mint(accounts.bob, 3, 100, 0, 100, hash.to_string(), true);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1500);
+        //2^-1.5 + 2^-0.5 + 2^-0.25
+        assert_eq!(contract.reputation_score(accounts.bob), 1_901_557);
     }
 }
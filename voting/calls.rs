@@ -0,0 +1,533 @@
+// typed calls from the voting contract to the escrow of a poll, the reward token, which is also the
+// reputation registry, and the observer told about polls. In tests they reach in-memory contracts that
+// record what changes their state, see mock.
+
+use crate::voting::Scorecard;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+type Balance = u128;
+type Timestamp = u64;
+
+// why a call to another contract didn't succeed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(test, allow(dead_code))]
+pub enum CallError {
+    // the contract answered the call with an error, the index of its Error variant
+    Rejected(u8),
+    // the contract couldn't dispatch the call, e.g. an unknown selector after an upgrade
+    NotDispatched,
+    // the call itself failed, e.g. the contract trapped, ran out of gas or answered something undecodable
+    CallFailed,
+}
+
+pub type Result<T> = core::result::Result<T, CallError>;
+
+// the escrow of a poll, called with the gas limit the voting contract uses for it, see gas_limit_for.
+#[derive(Debug, Clone, Copy)]
+pub struct Escrow {
+    pub escrow: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl Escrow {
+    pub fn new(escrow: AccountId, gas_limit: u64) -> Self {
+        Self { escrow, gas_limit }
+    }
+}
+
+// the reward token, or any reputation registry with its reputation_score, called with a fixed gas limit.
+#[derive(Debug, Clone, Copy)]
+pub struct RewardToken {
+    pub token: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl RewardToken {
+    pub fn new(token: AccountId, gas_limit: u64) -> Self {
+        Self { token, gas_limit }
+    }
+}
+
+// the observer told about polls, e.g. the reward token, called with a fixed gas limit.
+#[derive(Debug, Clone, Copy)]
+pub struct Observer {
+    pub observer: AccountId,
+    #[cfg_attr(test, allow(dead_code))]
+    pub gas_limit: u64,
+}
+
+impl Observer {
+    pub fn new(observer: AccountId, gas_limit: u64) -> Self {
+        Self {
+            observer,
+            gas_limit,
+        }
+    }
+}
+
+// arguments that are already SCALE encoded, pushed to a call without a length prefix.
+#[cfg(not(test))]
+struct EncodedArgs<'a>(&'a [u8]);
+
+#[cfg(not(test))]
+impl scale::Encode for EncodedArgs<'_> {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(self.0);
+    }
+}
+
+#[cfg(not(test))]
+fn settle<T>(
+    result: ink::env::Result<ink::MessageResult<core::result::Result<T, u8>>>,
+) -> Result<T> {
+    match result {
+        Ok(Ok(Ok(value))) => Ok(value),
+        Ok(Ok(Err(code))) => Err(CallError::Rejected(code)),
+        Ok(Err(_)) => Err(CallError::NotDispatched),
+        Err(_) => Err(CallError::CallFailed),
+    }
+}
+
+#[cfg(not(test))]
+fn query<T>(result: ink::env::Result<ink::MessageResult<T>>) -> Result<T> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) => Err(CallError::NotDispatched),
+        Err(_) => Err(CallError::CallFailed),
+    }
+}
+
+#[cfg(not(test))]
+impl Escrow {
+    // the protocol version the escrow reports.
+    pub fn version(&self) -> Result<u32> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(ink::env::call::ExecutionInput::new(
+                ink::env::call::Selector::new(ink::selector_bytes!("version")),
+            ))
+            .returns::<u32>()
+            .try_invoke();
+        query(result)
+    }
+
+    // the value the patron locked for the audit.
+    pub fn original_value(&self, audit_id: u32) -> Result<Balance> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("get_original_value"),
+                ))
+                .push_arg(audit_id),
+            )
+            .returns::<Balance>()
+            .try_invoke();
+        query(result)
+    }
+
+    // the bounds of arbiters_extend_deadline, the largest haircut, the largest arbiters' share and the
+    // least extension.
+    pub fn extension_bounds(&self) -> Result<(Balance, Balance, Timestamp)> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(ink::env::call::ExecutionInput::new(
+                ink::env::call::Selector::new(ink::selector_bytes!("get_extension_bounds")),
+            ))
+            .returns::<(Balance, Balance, Timestamp)>()
+            .try_invoke();
+        query(result)
+    }
+
+    // the auditors the patron of the audit assigned their latest count audits to.
+    pub fn patron_recent_auditors(&self, audit_id: u32, count: u32) -> Result<Vec<AccountId>> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("get_patron_recent_auditors"),
+                ))
+                .push_arg(audit_id)
+                .push_arg(count),
+            )
+            .returns::<Vec<AccountId>>()
+            .try_invoke();
+        query(result)
+    }
+}
+
+#[cfg(not(test))]
+impl Escrow {
+    // extends the deadline of the audit to new_deadline, with the haircut and the arbiters' share of it.
+    pub fn arbiters_extend_deadline(
+        &self,
+        audit_id: u32,
+        new_deadline: Timestamp,
+        haircut: Balance,
+        arbiters_share: Balance,
+    ) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("arbiters_extend_deadline"),
+                ))
+                .push_arg(audit_id)
+                .push_arg(new_deadline)
+                .push_arg(haircut)
+                .push_arg(arbiters_share),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        settle(result)
+    }
+
+    // approves or rejects the report of the audit, with the scorecard of the panel if it has one.
+    pub fn assess_audit(
+        &self,
+        audit_id: u32,
+        answer: bool,
+        scorecard: Option<Scorecard>,
+    ) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("assess_audit_with_scorecard"),
+                ))
+                .push_arg(audit_id)
+                .push_arg(answer)
+                .push_arg(scorecard),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        settle(result)
+    }
+
+    // calls the message of the selector with arguments that are already SCALE encoded.
+    pub fn call(&self, selector: [u8; 4], args: &[u8]) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.escrow)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(selector))
+                    .push_arg(EncodedArgs(args)),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        settle(result)
+    }
+}
+
+#[cfg(not(test))]
+impl RewardToken {
+    // the reputation of the account, fixed point with 6 decimals.
+    pub fn reputation_score(&self, account: AccountId) -> Result<u64> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("reputation_score"),
+                ))
+                .push_arg(account),
+            )
+            .returns::<u64>()
+            .try_invoke();
+        query(result)
+    }
+
+    // records whether the arbiter voted on the poll of the audit.
+    pub fn record_arbitration(
+        &self,
+        arbiter: AccountId,
+        vote_id: u32,
+        audit_id: u32,
+        voted: bool,
+    ) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.token)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                    ink::selector_bytes!("record_arbitration"),
+                ))
+                .push_arg(arbiter)
+                .push_arg(vote_id)
+                .push_arg(audit_id)
+                .push_arg(voted),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        settle(result)
+    }
+}
+
+#[cfg(not(test))]
+impl Observer {
+    // tells the observer about the poll of the audit with the message of the selector.
+    pub fn notify(&self, selector: [u8; 4], vote_id: u32, audit_id: u32) -> Result<()> {
+        let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+            .call(self.observer)
+            .gas_limit(self.gas_limit)
+            .transferred_value(0)
+            .exec_input(
+                ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(selector))
+                    .push_arg(vote_id)
+                    .push_arg(audit_id),
+            )
+            .returns::<core::result::Result<(), u8>>()
+            .try_invoke();
+        settle(result)
+    }
+}
+
+// the mock escrow answers like a deployed escrow with its default parameters unless a test sets otherwise.
+#[cfg(test)]
+impl Escrow {
+    pub fn version(&self) -> Result<u32> {
+        mock::reach(self.escrow)?;
+        Ok(mock::escrow_of(self.escrow).version)
+    }
+
+    pub fn original_value(&self, audit_id: u32) -> Result<Balance> {
+        mock::reach(self.escrow)?;
+        Ok(mock::original_value_of(self.escrow, audit_id))
+    }
+
+    pub fn extension_bounds(&self) -> Result<(Balance, Balance, Timestamp)> {
+        mock::reach(self.escrow)?;
+        Ok(mock::escrow_of(self.escrow).extension_bounds)
+    }
+
+    pub fn patron_recent_auditors(&self, audit_id: u32, count: u32) -> Result<Vec<AccountId>> {
+        mock::reach(self.escrow)?;
+        let mut auditors = mock::recent_auditors_of(self.escrow, audit_id);
+        auditors.truncate(count as usize);
+        Ok(auditors)
+    }
+}
+
+// the calls that change the state of the mock escrow are recorded, see mock::calls_to.
+#[cfg(test)]
+impl Escrow {
+    pub fn arbiters_extend_deadline(
+        &self,
+        audit_id: u32,
+        new_deadline: Timestamp,
+        haircut: Balance,
+        arbiters_share: Balance,
+    ) -> Result<()> {
+        mock::call(
+            self.escrow,
+            ink::selector_bytes!("arbiters_extend_deadline"),
+            scale::Encode::encode(&(audit_id, new_deadline, haircut, arbiters_share)),
+        )
+    }
+
+    pub fn assess_audit(
+        &self,
+        audit_id: u32,
+        answer: bool,
+        scorecard: Option<Scorecard>,
+    ) -> Result<()> {
+        mock::call(
+            self.escrow,
+            ink::selector_bytes!("assess_audit_with_scorecard"),
+            scale::Encode::encode(&(audit_id, answer, scorecard)),
+        )
+    }
+
+    pub fn call(&self, selector: [u8; 4], args: &[u8]) -> Result<()> {
+        mock::call(self.escrow, selector, args.to_vec())
+    }
+}
+
+#[cfg(test)]
+impl RewardToken {
+    pub fn reputation_score(&self, account: AccountId) -> Result<u64> {
+        mock::reach(self.token)?;
+        Ok(mock::reputation_of(self.token, account))
+    }
+
+    pub fn record_arbitration(
+        &self,
+        arbiter: AccountId,
+        vote_id: u32,
+        audit_id: u32,
+        voted: bool,
+    ) -> Result<()> {
+        mock::call(
+            self.token,
+            ink::selector_bytes!("record_arbitration"),
+            scale::Encode::encode(&(arbiter, vote_id, audit_id, voted)),
+        )
+    }
+}
+
+#[cfg(test)]
+impl Observer {
+    pub fn notify(&self, selector: [u8; 4], vote_id: u32, audit_id: u32) -> Result<()> {
+        mock::call(
+            self.observer,
+            selector,
+            scale::Encode::encode(&(vote_id, audit_id)),
+        )
+    }
+}
+
+// the contracts the voting contract calls in tests, escrows of the supported version that accept every call
+// until a test says otherwise.
+#[cfg(test)]
+pub mod mock {
+    use super::{AccountId, Balance, CallError, Result, Timestamp, Vec};
+    use crate::offchain::{read, write};
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    pub use crate::offchain::set_unreachable;
+
+    // what a mock escrow answers to the queries of the voting contract.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EscrowState {
+        pub version: u32,
+        pub extension_bounds: (Balance, Balance, Timestamp),
+    }
+
+    impl Default for EscrowState {
+        // the version the voting contract supports and the bounds a new escrow enforces.
+        fn default() -> Self {
+            Self {
+                version: crate::voting::SUPPORTED_ESCROW_VERSION,
+                extension_bounds: (90, 10, 86400000),
+            }
+        }
+    }
+
+    // the contract, selector and SCALE encoded arguments of a call.
+    type Call = (AccountId, [u8; 4], Vec<u8>);
+
+    thread_local! {
+        static ESCROWS: RefCell<BTreeMap<AccountId, EscrowState>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static ORIGINAL_VALUES: RefCell<BTreeMap<(AccountId, u32), Balance>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static RECENT_AUDITORS: RefCell<BTreeMap<(AccountId, u32), Vec<AccountId>>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static REPUTATIONS: RefCell<BTreeMap<(AccountId, AccountId), u64>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static REJECTIONS: RefCell<BTreeMap<(AccountId, [u8; 4]), u8>> =
+            const { RefCell::new(BTreeMap::new()) };
+        static CALLS: RefCell<Vec<Call>> = const { RefCell::new(Vec::new()) };
+    }
+
+    pub(super) fn reach(contract: AccountId) -> Result<()> {
+        if crate::offchain::is_unreachable(contract) {
+            return Err(CallError::CallFailed);
+        }
+        Ok(())
+    }
+
+    pub fn set_escrow(escrow: AccountId, state: EscrowState) {
+        write(&ESCROWS, |escrows| escrows.insert(escrow, state));
+    }
+
+    pub fn escrow_of(escrow: AccountId) -> EscrowState {
+        read(&ESCROWS, |escrows| {
+            escrows.get(&escrow).copied().unwrap_or_default()
+        })
+    }
+
+    // the value the patron locked for the audit on the escrow, 0 until a test sets it.
+    pub fn set_original_value(escrow: AccountId, audit_id: u32, value: Balance) {
+        write(&ORIGINAL_VALUES, |values| {
+            values.insert((escrow, audit_id), value)
+        });
+    }
+
+    pub(super) fn original_value_of(escrow: AccountId, audit_id: u32) -> Balance {
+        read(&ORIGINAL_VALUES, |values| {
+            values.get(&(escrow, audit_id)).copied().unwrap_or_default()
+        })
+    }
+
+    // the auditors the patron of the audit on the escrow assigned their latest audits to, latest first.
+    pub fn set_recent_auditors(escrow: AccountId, audit_id: u32, auditors: Vec<AccountId>) {
+        write(&RECENT_AUDITORS, |recent| {
+            recent.insert((escrow, audit_id), auditors)
+        });
+    }
+
+    pub(super) fn recent_auditors_of(escrow: AccountId, audit_id: u32) -> Vec<AccountId> {
+        read(&RECENT_AUDITORS, |recent| {
+            recent.get(&(escrow, audit_id)).cloned().unwrap_or_default()
+        })
+    }
+
+    pub fn set_reputation(registry: AccountId, account: AccountId, reputation: u64) {
+        write(&REPUTATIONS, |reputations| {
+            reputations.insert((registry, account), reputation)
+        });
+    }
+
+    pub(super) fn reputation_of(registry: AccountId, account: AccountId) -> u64 {
+        read(&REPUTATIONS, |reputations| {
+            reputations
+                .get(&(registry, account))
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    // every later call of the message of the selector on the contract is answered with the error code.
+    pub fn set_rejection(contract: AccountId, selector: [u8; 4], code: u8) {
+        write(&REJECTIONS, |rejections| {
+            rejections.insert((contract, selector), code)
+        });
+    }
+
+    // records a call that changes the state of the contract, unless it is unreachable or rejects it.
+    pub(super) fn call(contract: AccountId, selector: [u8; 4], args: Vec<u8>) -> Result<()> {
+        reach(contract)?;
+        if let Some(code) = read(&REJECTIONS, |rejections| {
+            rejections.get(&(contract, selector)).copied()
+        }) {
+            return Err(CallError::Rejected(code));
+        }
+        write(&CALLS, |calls| calls.push((contract, selector, args)));
+        Ok(())
+    }
+
+    // the selector and SCALE encoded arguments of every call the contract accepted, in order.
+    pub fn calls_to(contract: AccountId) -> Vec<([u8; 4], Vec<u8>)> {
+        read(&CALLS, |calls| {
+            calls
+                .iter()
+                .filter(|(to, _, _)| *to == contract)
+                .map(|(_, selector, args)| (*selector, args.clone()))
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,129 @@
+// the interface of the voting contract as other contracts and off-chain tooling see it. PROTOCOL_VERSION is bumped by
+// every release that changes its messages or what they expect, the contracts can't be upgraded in place, so
+// a new version is a new deployment. MESSAGES is the selector of every message, in the order of the source,
+// and a message added to or removed from the contract has to be added to or removed from it too.
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+pub const MESSAGES: [[u8; 4]; 110] = [
+    ink::selector_bytes!("version"),
+    ink::selector_bytes!("interface_hash"),
+    ink::selector_bytes!("get_current_vote_id"),
+    ink::selector_bytes!("know_your_escrow"),
+    ink::selector_bytes!("know_your_pauser"),
+    ink::selector_bytes!("is_paused"),
+    ink::selector_bytes!("is_poll_frozen"),
+    ink::selector_bytes!("get_quarantine"),
+    ink::selector_bytes!("get_quarantined_polls"),
+    ink::selector_bytes!("get_pending_escrow_address"),
+    ink::selector_bytes!("know_arbiters_share"),
+    ink::selector_bytes!("know_your_admin"),
+    ink::selector_bytes!("get_poll_info"),
+    ink::selector_bytes!("get_poll_status"),
+    ink::selector_bytes!("get_poll_scorecard"),
+    ink::selector_bytes!("get_time_extension_info"),
+    ink::selector_bytes!("get_haircut_info"),
+    ink::selector_bytes!("get_panel_size_limits"),
+    ink::selector_bytes!("get_vote_attempts"),
+    ink::selector_bytes!("get_response_deadline"),
+    ink::selector_bytes!("get_seat_status"),
+    ink::selector_bytes!("get_reputation_snapshot"),
+    ink::selector_bytes!("get_reward_token"),
+    ink::selector_bytes!("get_observer"),
+    ink::selector_bytes!("get_arbiter_fee_config"),
+    ink::selector_bytes!("get_treasury"),
+    ink::selector_bytes!("get_panel_diversity"),
+    ink::selector_bytes!("get_arbiter_organization"),
+    ink::selector_bytes!("get_reputation_registry"),
+    ink::selector_bytes!("get_dispute_class"),
+    ink::selector_bytes!("get_result_action"),
+    ink::selector_bytes!("get_dispute_class_config"),
+    ink::selector_bytes!("get_audit_outcome_count"),
+    ink::selector_bytes!("get_last_audit_outcome"),
+    ink::selector_bytes!("get_dispute_class_thresholds"),
+    ink::selector_bytes!("quote_arbitration_fee"),
+    ink::selector_bytes!("get_arbitration_fee"),
+    ink::selector_bytes!("get_my_pending_polls"),
+    ink::selector_bytes!("get_outcome"),
+    ink::selector_bytes!("get_poll_record"),
+    ink::selector_bytes!("get_execution_receipt"),
+    ink::selector_bytes!("export_poll"),
+    ink::selector_bytes!("verify_exported_poll"),
+    ink::selector_bytes!("prune_poll"),
+    ink::selector_bytes!("get_pruned_digest"),
+    ink::selector_bytes!("create_new_poll"),
+    ink::selector_bytes!("create_poll_for_escrow"),
+    ink::selector_bytes!("set_poll_scorecard"),
+    ink::selector_bytes!("set_escrow_allowed"),
+    ink::selector_bytes!("is_escrow_allowed"),
+    ink::selector_bytes!("vote"),
+    ink::selector_bytes!("release_treasury_funds"),
+    ink::selector_bytes!("get_fee_breakdown"),
+    ink::selector_bytes!("claim_arbiter_fee"),
+    ink::selector_bytes!("get_arbiter_seat"),
+    ink::selector_bytes!("get_fee_share"),
+    ink::selector_bytes!("announce_force_vote"),
+    ink::selector_bytes!("get_force_vote_announcement"),
+    ink::selector_bytes!("set_quorum_rule"),
+    ink::selector_bytes!("get_quorum_rule"),
+    ink::selector_bytes!("force_vote"),
+    ink::selector_bytes!("flush_out_tokens"),
+    ink::selector_bytes!("change_haircut_for_discrepancies"),
+    ink::selector_bytes!("change_time_extension_for_discrepancies"),
+    ink::selector_bytes!("get_escrow_extension_bounds"),
+    ink::selector_bytes!("change_arbiters_share"),
+    ink::selector_bytes!("change_panel_size_limits"),
+    ink::selector_bytes!("change_arbiter_response_window"),
+    ink::selector_bytes!("set_reputation_registry"),
+    ink::selector_bytes!("change_panel_diversity"),
+    ink::selector_bytes!("set_arbiter_organization"),
+    ink::selector_bytes!("set_reward_token"),
+    ink::selector_bytes!("set_observer"),
+    ink::selector_bytes!("change_arbiter_fee_config"),
+    ink::selector_bytes!("change_treasury"),
+    ink::selector_bytes!("change_seat_acceptance_window"),
+    ink::selector_bytes!("change_dispute_class_config"),
+    ink::selector_bytes!("set_result_action"),
+    ink::selector_bytes!("change_dispute_class_thresholds"),
+    ink::selector_bytes!("pause"),
+    ink::selector_bytes!("unpause"),
+    ink::selector_bytes!("set_poll_frozen"),
+    ink::selector_bytes!("quarantine_poll"),
+    ink::selector_bytes!("lift_quarantine"),
+    ink::selector_bytes!("change_pauser"),
+    ink::selector_bytes!("activate_alternate"),
+    ink::selector_bytes!("invite_arbiter"),
+    ink::selector_bytes!("accept_panel_seat"),
+    ink::selector_bytes!("cite_reasons"),
+    ink::selector_bytes!("get_cited_reasons"),
+    ink::selector_bytes!("get_ruling_reason_count"),
+    ink::selector_bytes!("issue_ballot_tokens"),
+    ink::selector_bytes!("commit_blinded_vote"),
+    ink::selector_bytes!("vote_blinded"),
+    ink::selector_bytes!("reveal_ballots"),
+    ink::selector_bytes!("get_ballot_tokens"),
+    ink::selector_bytes!("ballot_token_of"),
+    ink::selector_bytes!("ballot_commitment_key_of"),
+    ink::selector_bytes!("ballot_commitment_of"),
+    ink::selector_bytes!("ballot_link_of"),
+    ink::selector_bytes!("delegate_vote"),
+    ink::selector_bytes!("revoke_delegation"),
+    ink::selector_bytes!("prune_expired_delegations"),
+    ink::selector_bytes!("get_delegation"),
+    ink::selector_bytes!("get_active_delegations"),
+    ink::selector_bytes!("get_call_config"),
+    ink::selector_bytes!("set_gas_limit"),
+    ink::selector_bytes!("set_escrow_address"),
+    ink::selector_bytes!("apply_escrow_address"),
+    ink::selector_bytes!("cancel_escrow_address_change"),
+];
+
+// the blake2x256 hash of the selectors of MESSAGES, sorted and SCALE encoded, so that it doesn't depend on
+// the order of the source.
+pub fn interface_hash() -> [u8; 32] {
+    let mut selectors = MESSAGES;
+    selectors.sort_unstable();
+    let mut hash = [0u8; 32];
+    ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&selectors, &mut hash);
+    hash
+}
@@ -1,7 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use self::voting::{Voting, VotingRef};
+
+mod calls;
+mod interface;
+#[cfg(test)]
+#[path = "../mock/offchain.rs"]
+mod offchain;
+
+#[cfg(test)]
+mod testing;
+
 #[ink::contract]
 mod voting {
+    use crate::calls;
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
 
@@ -16,35 +28,467 @@ mod voting {
         pub has_voted: bool,
     }
 
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //the seat of an arbiter on a panel, index is their position in VoteInfo.arbiters,
+    //fee_claimed is set once they claimed their share of the released fee
+    pub struct ArbiterSeat {
+        pub index: u32,
+        pub fee_claimed: bool,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///PanelDiversity is what the admin requires of the people on a panel to reduce collusion, at most one arbiter
+    /// of each organization (one_per_organization), and no one the patron assigned any of their latest
+    /// patron_history_window audits to, as reported by the escrow. A window of 0 skips the escrow query.
+    pub struct PanelDiversity {
+        pub one_per_organization: bool,
+        pub patron_history_window: u32,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///ArbiterFeeConfig is how the fee released for a poll is paid to the arbiters who voted, per_vote_fee to each
+    /// of them (0 splits the released amount evenly), never less than min_payout.
+    pub struct ArbiterFeeConfig {
+        pub per_vote_fee: Balance,
+        pub min_payout: Balance,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///FeeBreakdown is how the fee released for a poll was paid out, the amount released, what each of the voters
+    /// can claim, and the dust left over that was sent to the treasury.
+    pub struct FeeBreakdown {
+        pub released: Balance,
+        pub per_arbiter: Balance,
+        pub voters: u8,
+        pub dust: Balance,
+        pub treasury: AccountId,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///BallotToken is the one-time token of a seat on a blinded poll, token_hash is ballot_token_of(secret) and
+    /// link is ballot_link_of(arbiter, secret, salt), so the token can't be told apart from the others until
+    /// the arbiter, secret and salt are revealed.
+    pub struct BallotToken {
+        pub token_hash: [u8; 32],
+        pub link: [u8; 32],
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///BallotCommitment is the vote committed for a ballot token, ballot_commitment_of its secret and the vote,
+    /// and the block it was committed in.
+    pub struct BallotCommitment {
+        pub commitment: [u8; 32],
+        pub committed_at: BlockNumber,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    ///BallotReveal opens the BallotToken of arbiter once the poll is finalized.
+    pub struct BallotReveal {
+        pub arbiter: AccountId,
+        pub secret: [u8; 32],
+        pub salt: [u8; 32],
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///Delegation lets delegate cast the vote of arbiter on a poll, it expires at expires_at, the response
+    /// deadline of the arbiter's seat, or when the poll is closed, whichever comes first.
+    pub struct Delegation {
+        pub arbiter: AccountId,
+        pub delegate: AccountId,
+        pub since: Timestamp,
+        pub expires_at: Option<Timestamp>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///Quarantine records why and by whom a poll was frozen pending an investigation, reason_hash is the hash
+    /// of the off-chain report, e.g. on suspected bribery or a sybil panel. status_before is the status the poll
+    /// goes back to when the quarantine is lifted.
+    pub struct Quarantine {
+        pub reason_hash: [u8; 32],
+        pub by: AccountId,
+        pub since: Timestamp,
+        pub status_before: PollStatus,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///PollStatus is where a poll stands. Open polls take votes, Finalized ones closed with an outcome pushed to
+    /// the escrow, Appealed ones closed with an outcome another poll for the same audit was opened to appeal,
+    /// and Quarantined ones are held pending an investigation.
+    pub enum PollStatus {
+        Open,
+        Finalized,
+        Quarantined,
+        Appealed,
+    }
+
+    impl PollStatus {
+        //whether the poll takes votes
+        pub fn is_open(self) -> bool {
+            self == PollStatus::Open
+        }
+
+        //whether the poll is over, whether or not its outcome was appealed
+        pub fn is_closed(self) -> bool {
+            matches!(self, PollStatus::Finalized | PollStatus::Appealed)
+        }
+    }
+
     #[derive(scale::Decode, scale::Encode)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     ///VoteInfo will store crucial information about the voting
-    /// like the vector of arbiters, how many arbiters/voters are there, decided deadline, and haircut will update
+    /// like the vector of arbiters, the status of the poll, how many arbiters/voters are there, decided deadline,
+    /// and haircut will update
+    /// max_haircut and max_extension cap the decided haircut and deadline extension of this poll.
+    /// response_window is how long each arbiter has to vote once seated, 0 for no limit, and alternates
+    /// are the accounts that can take the seat of an arbiter who let it lapse, in order.
+    /// dispute_class and class_config are the class of the dispute and its requirements when the poll was created.
+    /// reputation_snapshot is the reputation of every arbiter and alternate as read from the reputation registry
+    /// when the poll was created (or when they were invited to it), later changes of reputation don't affect it.
+    /// abstentions is how many of the available_votes were abstentions.
     pub struct VoteInfo {
         pub audit_id: u32,
         pub arbiters: Vec<Arbiter>,
-        pub is_active: bool,
+        pub status: PollStatus,
         pub available_votes: u8,
         pub decided_deadline: Timestamp,
         pub decided_haircut: Balance,
         pub admin_hit_time: Timestamp,
+        pub max_haircut: Balance,
+        pub max_extension: Timestamp,
+        pub response_window: Timestamp,
+        pub alternates: Vec<AccountId>,
+        pub dispute_class: DisputeClass,
+        pub class_config: DisputeClassConfig,
+        pub reputation_snapshot: Vec<(AccountId, u64)>,
+        pub abstentions: u8,
     }
     pub type Result<T> = core::result::Result<T, Error>;
 
-    #[derive(scale::Decode, scale::Encode)]
+    #[derive(scale::Decode, scale::Encode, Clone, Copy)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     //AuditArbitrationResult enum is there to convey what the decided deadline should be extended by along with the haircut.
+    //Abstain counts toward the quorum but not toward the averaged outcome, for arbiters with minor conflicts.
     pub enum AuditArbitrationResult {
         NoDiscrepancies,
         MinorDiscrepancies,
         ModerateDiscrepancies,
         Reject,
+        Abstain,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //a vote that was accepted on a poll, and when it was cast
+    pub struct Ballot {
+        pub voter: AccountId,
+        pub result: AuditArbitrationResult,
+        pub cast_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //the call that closed a poll on the escrow, with the arguments it was made with
+    pub enum EscrowCall {
+        ArbitersExtendDeadline(Timestamp, Balance, Balance),
+        AssessAudit(bool, Option<Scorecard>),
+        Custom([u8; 4], Vec<u8>),
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //the findings of a disputed report as judged by the panel, counts per severity and a quality score from 0
+    //to 100, encoded like the Scorecard of the escrow, which stores it and mints the token of the audit with it
+    pub struct Scorecard {
+        pub criticals: u32,
+        pub highs: u32,
+        pub mediums: u32,
+        pub quality: u8,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //an argument of a custom escrow action, filled in from the finalized poll when the call is made.
+    //NewDeadline is the averaged extension from now, Constant is pushed as is and has to be SCALE encoded.
+    pub enum ActionArg {
+        AuditId,
+        NewDeadline,
+        Haircut,
+        ArbitersShare,
+        Constant(Vec<u8>),
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///EscrowAction is the escrow message a poll finalized by a given result calls instead of the built-in one,
+    /// selector is the selector of the message and args the layout of its arguments, in order.
+    pub struct EscrowAction {
+        pub selector: [u8; 4],
+        pub args: Vec<ActionArg>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    ///ExecutionReceipt is the call a finalized poll made on the escrow, the escrow and audit it was made for, the
+    /// selector of the message, the blake2x256 hash of its SCALE encoded arguments, what the escrow returned and when.
+    pub struct ExecutionReceipt {
+        pub escrow: AccountId,
+        pub audit_id: u32,
+        pub selector: [u8; 4],
+        pub args_hash: [u8; 32],
+        pub result: core::result::Result<(), u8>,
+        pub executed_at: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //what a poll decided: the audit is paid out, the patron is refunded, or the deadline is extended with a haircut,
+    //Custom when the poll was closed by an escrow action the admin registered for the deciding result
+    pub enum OutcomeKind {
+        Approved,
+        Rejected,
+        Extended,
+        Custom,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //the stable record of what a poll decided, stored when it is finalized. haircut_bps and extension_ms are
+    //only set for Extended. escrow_result is what the escrow answered to the closing call, Ok or the
+    //EscrowCallFailed code, a call the escrow refuses fails the finalization so stored outcomes are Ok.
+    //ruling_reason is every finding the panel cited with cite_reasons, each once, in the order first cited.
+    pub struct PollOutcome {
+        pub kind: OutcomeKind,
+        pub haircut_bps: u32,
+        pub extension_ms: Timestamp,
+        pub executed_at: Timestamp,
+        pub escrow_result: core::result::Result<(), u8>,
+        pub ruling_reason: Vec<ReasonCode>,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //a common finding a ruling is based on, so that both parties can read why the poll decided what it did
+    //in their own language, and rulings can be counted by their reasons on-chain
+    pub enum ReasonCode {
+        LateDelivery,
+        QualityBelowBar,
+        ScopeMismatch,
+        IncompleteReport,
+        MissedFindings,
+        UnresponsiveParty,
+        Other,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    //DisputeClass is chosen from the value escrowed for the audit, bigger disputes get bigger panels.
+    pub enum DisputeClass {
+        Small,
+        Standard,
+        HighValue,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///DisputeClassConfig is what a dispute class requires of its polls, the panel size range, how many ballots
+    /// the admin needs before forcing the vote (quorum), the most that can be released to the arbiters (fee_budget)
+    /// and how many times the outcome can be appealed (max_appeals), no poll is opened for an audit whose polls on
+    /// the escrow already reached max_appeals + 1 outcomes.
+    /// fee_bps is the arbitration fee of its polls in basis points of the escrowed value, capped at the fee_budget.
+    pub struct DisputeClassConfig {
+        pub min_panel_size: u8,
+        pub max_panel_size: u8,
+        pub quorum: u8,
+        pub fee_budget: Balance,
+        pub max_appeals: u8,
+        pub fee_bps: u16,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///QuorumRule is what the admin needs of a poll before forcing its vote. HeadCount is the quorum of the
+    /// dispute class, StakeWeight is a fraction of the stake of the panel, as in the reputation snapshot of
+    /// the poll, so that the votes of senior arbiters weigh more than those of junior ones.
+    pub enum QuorumRule {
+        HeadCount,
+        StakeWeight { numerator: u32, denominator: u32 },
+    }
+
+    #[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///SeatStatus is where an arbiter stands on a poll's panel, invited until they accept the seat (and with it
+    /// the fee terms), after accept_by the admin can invite someone else to an unaccepted seat.
+    pub enum SeatStatus {
+        Invited { accept_by: Timestamp },
+        Accepted,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    ///PollRecord keeps the history of a poll that VoteInfo doesn't, the escrow the poll was opened for,
+    /// the ballots in the order they were cast, when the poll was opened and closed and the escrow call that closed it.
+    /// The ballots of a blinded poll are cast by the pseudonym of their token, revealed_voters maps every pseudonym
+    /// to its arbiter once the tokens are revealed.
+    pub struct PollRecord {
+        pub escrow_address: Option<AccountId>,
+        pub created_at: Timestamp,
+        pub ballots: Vec<Ballot>,
+        pub closed_at: Option<Timestamp>,
+        pub escrow_call: Option<EscrowCall>,
+        pub revealed_voters: Vec<(AccountId, AccountId)>,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    ///NewPoll is the panel and the caps of a poll opened with create_poll_for_escrow, see create_new_poll.
+    pub struct NewPoll {
+        pub buffer_for_admin: Timestamp,
+        pub arbiters: Vec<Arbiter>,
+        pub max_haircut: Balance,
+        pub max_extension: Timestamp,
+        pub alternates: Vec<AccountId>,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    ///PollExport is the self-contained record of a poll returned SCALE encoded by export_poll,
+    /// followed by its blake2x256 digest, so that it can be archived and checked outside the chain.
+    pub struct PollExport {
+        pub vote_id: u32,
+        pub escrow_address: AccountId,
+        pub vote_info: VoteInfo,
+        pub record: PollRecord,
+    }
+
+    #[ink(event)]
+    pub struct EscrowAddressChangeProposed {
+        new_escrow: AccountId,
+        executable_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct EscrowAddressChanged {
+        old_escrow: AccountId,
+        new_escrow: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct PollFreezeChanged {
+        id: u32,
+        frozen: bool,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AlternateActivated {
+        id: u32,
+        replaced: AccountId,
+        alternate: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ArbiterInvited {
+        id: u32,
+        replaced: AccountId,
+        arbiter: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct PanelSeatAccepted {
+        id: u32,
+        arbiter: AccountId,
     }
 
     #[ink(event)]
@@ -60,79 +504,463 @@ mod voting {
         vote_type: Option<AuditArbitrationResult>,
     }
 
+    //emitted after every vote with the progress of the tally, running_haircut and running_extension are the
+    //average over the ballots that weren't abstentions so far, and once the poll is final, what was pushed to the escrow.
+    #[ink(event)]
+    pub struct TallyCheckpoint {
+        vote_id: u32,
+        votes_cast: u8,
+        running_haircut: Balance,
+        running_extension: Timestamp,
+        is_final: bool,
+    }
+
+    //emitted when a poll is finalized and the participation of its panel was recorded on the reward token,
+    //failed counts the arbiters the reward token refused to record, e.g. because the voting contract isn't a minter
+    #[ink(event)]
+    pub struct ParticipationRecorded {
+        id: u32,
+        badges: u8,
+        no_shows: u8,
+        failed: u8,
+    }
+
     #[ink(event)]
     pub struct NoOneVotedTransferredToAdmin {
         id: u32,
         amount: Balance,
     }
 
+    #[ink(event)]
+    pub struct ArbiterFeesReleased {
+        id: u32,
+        per_arbiter: Balance,
+        dust: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ArbiterFeeClaimed {
+        id: u32,
+        arbiter: AccountId,
+        amount: Balance,
+    }
+
     #[ink(event)]
     pub struct FinalVotePushed {
         id: u32,
         pusher: AccountId,
     }
 
-    #[derive(scale::Decode, scale::Encode)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    pub enum Error {
-        UnAuthorisedCall,
-        AssessmentFailed,
-        ResultAlreadyPublished,
-        VotingFailed,
-        RightsNotActivatedYet,
-        TransferFailed,
-        TreasuryEmpty,
-        ValueTooLow,
-        ValueTooHigh,
+    //emitted when the admin announces they will force the vote of a poll, arbiters can vote until executable_at
+    #[ink(event)]
+    pub struct ForceVoteAnnounced {
+        id: u32,
+        executable_at: Timestamp,
     }
 
-    /// Defines the storage of your contract.
-    /// Add new fields to the below struct in order
-    /// to add new static storage fields to your contract.
-    #[ink(storage)]
-    pub struct Voting {
-        pub current_vote_id: u32,
-        pub escrow_address: AccountId,
-        pub stablecoin_address: AccountId,
-        pub admin: AccountId,
-        pub vote_id_to_info: Mapping<u32, VoteInfo>,
-        pub haircut_for_minor_discreapancies: Balance,
-        pub haircut_for_moderate_discrepancies: Balance,
-        pub time_extension_for_minor_discrepancies: Timestamp,
-        pub time_extension_for_moderate_discrepancies: Timestamp,
-        pub arbiters_share: Balance,
+    //emitted when the admin changes the quorum rule of a poll
+    #[ink(event)]
+    pub struct QuorumRuleSet {
+        id: u32,
+        rule: QuorumRule,
     }
 
-    impl Voting {
-        /// Constructor that initializes the escrow that our contract will be voting for,
-        /// the stablecoin that the contract will use and
-        /// the admin's address
-        #[ink(constructor)]
-        pub fn new(
-            _escrow_address: AccountId,
-            _stablecoin_address: AccountId,
-            _admin: AccountId,
-        ) -> Self {
-            let current_vote_id = u32::default();
-            let vote_id_to_info = Mapping::default();
-            let escrow_address = _escrow_address;
-            let stablecoin_address = _stablecoin_address;
-            let admin = _admin;
-            let haircut_for_minor_discreapancies = 5;
-            let haircut_for_moderate_discrepancies = 15;
-            let time_extension_for_minor_discrepancies = 604800000;
-            //time extension for minor discrepancies is 7 days
-            let time_extension_for_moderate_discrepancies = 1296000000;
-            //time extension for moderate discrepancies is 15 days
-            let arbiters_share = 5;
+    //emitted when the admin forces the vote of a poll after the announcement delay
+    #[ink(event)]
+    pub struct ForceVoteExecuted {
+        id: u32,
+    }
+
+    //emitted when the panel and ballots of a finalized poll are pruned, digest is its export digest before pruning
+    #[ink(event)]
+    pub struct PollPruned {
+        id: u32,
+        digest: [u8; 32],
+    }
+
+    //emitted when the admin adds an escrow to the allow-list or removes it
+    #[ink(event)]
+    pub struct EscrowAllowListChanged {
+        escrow: AccountId,
+        allowed: bool,
+    }
+
+    //emitted when the admin changes the contract notified of the creation and finalization of polls
+    #[ink(event)]
+    pub struct ObserverChanged {
+        observer: Option<AccountId>,
+    }
+
+    //emitted when the admin or the PAUSER quarantines a poll
+    #[ink(event)]
+    pub struct PollQuarantined {
+        id: u32,
+        reason_hash: [u8; 32],
+        by: AccountId,
+    }
+
+    //emitted when an arbiter delegates their vote on a poll
+    #[ink(event)]
+    pub struct VoteDelegated {
+        id: u32,
+        arbiter: AccountId,
+        delegate: AccountId,
+        expires_at: Option<Timestamp>,
+    }
+
+    //emitted when an arbiter revokes the delegation of their vote before it was used
+    #[ink(event)]
+    pub struct DelegationRevoked {
+        id: u32,
+        arbiter: AccountId,
+        delegate: AccountId,
+    }
+
+    //emitted when a delegation that can't be used anymore is pruned
+    #[ink(event)]
+    pub struct DelegationExpired {
+        id: u32,
+        arbiter: AccountId,
+        delegate: AccountId,
+    }
+
+    //emitted when the admin issues the one-time ballot tokens of a blinded poll
+    #[ink(event)]
+    pub struct BallotTokensIssued {
+        id: u32,
+        tokens: u8,
+    }
+
+    //emitted when the vote of a ballot token is committed, key is its ballot_commitment_key_of
+    #[ink(event)]
+    pub struct BallotCommitted {
+        id: u32,
+        key: [u8; 32],
+    }
+
+    //emitted when ballot tokens of a finalized blinded poll are revealed, revealed_voters are the used ones of
+    //those tokens, complete once every token was revealed or REVEAL_WINDOW has passed
+    #[ink(event)]
+    pub struct BallotsRevealed {
+        id: u32,
+        revealed_voters: Vec<(AccountId, AccountId)>,
+        complete: bool,
+    }
+
+    //emitted when an arbiter cites the findings behind their vote
+    #[ink(event)]
+    pub struct ReasonsCited {
+        id: u32,
+        arbiter: AccountId,
+        reasons: Vec<ReasonCode>,
+    }
+
+    //emitted with the outcome of a poll, the findings its ruling is based on
+    #[ink(event)]
+    pub struct RulingReasoned {
+        id: u32,
+        ruling_reason: Vec<ReasonCode>,
+    }
+
+    //emitted when a delegate casts the vote of an arbiter, next to the ArbiterVoted of the arbiter
+    #[ink(event)]
+    pub struct DelegatedVoteCast {
+        id: u32,
+        arbiter: AccountId,
+        delegate: AccountId,
+    }
+
+    //emitted when a poll moves from one status to another
+    #[ink(event)]
+    pub struct PollStatusChanged {
+        id: u32,
+        from: PollStatus,
+        to: PollStatus,
+    }
+
+    //emitted when the quarantine of a poll is lifted
+    #[ink(event)]
+    pub struct QuarantineLifted {
+        id: u32,
+        by: AccountId,
+    }
+
+    //emitted when the admin registers or removes the escrow action of a result
+    #[ink(event)]
+    pub struct ResultActionChanged {
+        result: AuditArbitrationResult,
+        action: Option<EscrowAction>,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Error {
+        UnAuthorisedCall,
+        AssessmentFailed,
+        ResultAlreadyPublished,
+        VotingFailed,
+        RightsNotActivatedYet,
+        TransferFailed,
+        TreasuryEmpty,
+        ValueTooLow,
+        ValueTooHigh,
+        PanelTooSmall,
+        PanelTooLarge,
+        PanelSizeNotOdd,
+        StaleVoteNonce,
+        NoPendingChange,
+        TimelockNotElapsed,
+        ContractPaused,
+        PollIsFrozen,
+        AlternateAlreadyOnPanel,
+        ResponseDeadlineNotPassed,
+        NoAlternateLeft,
+        QuorumNotReached,
+        EscrowQueryFailed,
+        EscrowCallFailed { code: u8 },
+        SeatNotAccepted,
+        NoLapsedSeat,
+        AlreadyOnPanel,
+        ReputationQueryFailed,
+        OnlyAbstentions,
+        FeeAlreadyReleased,
+        NothingToClaim,
+        ForceVoteNotAnnounced,
+        ForceVoteAlreadyAnnounced,
+        SameOrganizationOnPanel,
+        ConflictOfInterest,
+        PollNotPrunable,
+        RetentionPeriodNotPassed,
+        FeesNotSettled,
+        PollNotFound,
+        PollQuarantined,
+        PollNotQuarantined,
+        TooManyQuarantinedPolls,
+        EscrowNotAllowed,
+        InvalidDelegate,
+        NoDelegation,
+        DelegationAlreadyUsed,
+        BlindedPoll,
+        InvalidBallotToken,
+        BallotsNotRevealed,
+        ArbitersShareAboveEscrowMax,
+        IncompatibleEscrow,
+        InvalidQuorumRule,
+        BallotNotCommitted,
+        BallotAlreadyCommitted,
+        PollNotClosed,
+        BallotAlreadyRevealed,
+        AppealsExhausted,
+    }
+
+    //EscrowCallFailed code when the call to the escrow itself failed, e.g. it trapped or ran out of gas
+    pub const ESCROW_CALL_FAILED: u8 = 255;
+    //EscrowCallFailed code when the escrow couldn't dispatch the call, e.g. an unknown selector after an upgrade
+    pub const ESCROW_DISPATCH_FAILED: u8 = 254;
+
+    //how long a proposed escrow address has to wait before the admin can apply it, 2 days
+    pub const ESCROW_CHANGE_DELAY: Timestamp = 172800000;
+    //gas limit of calls to the stablecoin without a configured one, enough for a transfer
+    pub const TOKEN_GAS_LIMIT: u64 = 5_000_000_000;
+    //gas limit of calls to other contracts without a configured one, the escrow pays out and notifies its own
+    //listeners and reward token when a poll settles an audit
+    pub const DEFAULT_GAS_LIMIT: u64 = 50_000_000_000;
+    //hard cap on the size of a panel (and of its alternates), whatever the configured limits,
+    //so that the loops over a panel stay within the block limits
+    pub const MAX_PANEL_SIZE: u8 = 15;
+    //how long after announce_force_vote the admin can force the vote, 1 day
+    pub const FORCE_VOTE_DELAY: Timestamp = 86400000;
+    //the arbitration fee of a poll can't be more than the whole escrowed value
+    pub const MAX_FEE_BPS: u16 = 10000;
+    //the most of a patron's latest audits the escrow remembers the auditors of
+    pub const MAX_PATRON_HISTORY_WINDOW: u32 = 20;
+    //how long a finalized poll keeps its panel and ballots before anyone can prune it, 90 days
+    pub const POLL_RETENTION_PERIOD: Timestamp = 7776000000;
+    //the most arguments a custom escrow action can have
+    pub const MAX_ACTION_ARGS: u32 = 8;
+    //the most polls that can be quarantined at the same time, so that the list of them stays readable
+    pub const MAX_QUARANTINED_POLLS: u32 = 50;
+    //how long after a blinded poll is closed its ballot tokens can be revealed, the ballots not revealed by
+    //then are skipped, 7 days
+    pub const REVEAL_WINDOW: Timestamp = 604800000;
+    //the protocol version an escrow has to report for this voting contract to resolve its disputes
+    pub const SUPPORTED_ESCROW_VERSION: u32 = 1;
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    //the gas limit the voting contract uses when calling a contract, and whether it is the default one
+    pub struct CallConfig {
+        pub callee: AccountId,
+        pub gas_limit: u64,
+        pub is_default: bool,
+    }
+
+    /// Defines the storage of your contract.
+    /// Add new fields to the below struct in order
+    /// to add new static storage fields to your contract.
+    #[ink(storage)]
+    pub struct Voting {
+        pub current_vote_id: u32,
+        pub escrow_address: AccountId,
+        pub stablecoin_address: AccountId,
+        pub admin: AccountId,
+        pub vote_id_to_info: Mapping<u32, VoteInfo>,
+        pub poll_scorecards: Mapping<u32, Scorecard>,
+        pub haircut_for_minor_discreapancies: Balance,
+        pub haircut_for_moderate_discrepancies: Balance,
+        pub time_extension_for_minor_discrepancies: Timestamp,
+        pub time_extension_for_moderate_discrepancies: Timestamp,
+        pub arbiters_share: Balance,
+        pub min_panel_size: u8,
+        pub max_panel_size: u8,
+        pub odd_panel_required: bool,
+        pub vote_attempts: Mapping<(u32, AccountId), u32>,
+        pub vote_id_to_record: Mapping<u32, PollRecord>,
+        pub pending_escrow_address: Option<(AccountId, Timestamp)>,
+        pub pauser: AccountId,
+        pub paused: bool,
+        pub frozen_polls: Mapping<u32, bool>,
+        pub callee_gas_limits: Mapping<AccountId, u64>,
+        pub response_deadlines: Mapping<(u32, AccountId), Timestamp>,
+        pub arbiter_response_window: Timestamp,
+        pub dispute_class_thresholds: (Balance, Balance),
+        pub dispute_class_configs: Mapping<DisputeClass, DisputeClassConfig>,
+        pub arbiter_to_pending_polls: Mapping<AccountId, Vec<u32>>,
+        pub panel_seats: Mapping<(u32, AccountId), SeatStatus>,
+        pub seat_acceptance_window: Timestamp,
+        pub reputation_registry: Option<AccountId>,
+        pub arbiter_seats: Mapping<(u32, AccountId), ArbiterSeat>,
+        pub poll_fee_share: Mapping<u32, Balance>,
+        pub reward_token: Option<AccountId>,
+        pub vote_id_to_outcome: Mapping<u32, PollOutcome>,
+        pub force_vote_announcements: Mapping<u32, Timestamp>,
+        pub poll_arbitration_fee: Mapping<u32, Balance>,
+        pub panel_diversity: PanelDiversity,
+        pub arbiter_organizations: Mapping<AccountId, u32>,
+        pub pruned_poll_digests: Mapping<u32, [u8; 32]>,
+        pub result_actions: Mapping<AuditArbitrationResult, EscrowAction>,
+        pub arbiter_fee_config: ArbiterFeeConfig,
+        pub treasury: AccountId,
+        pub poll_fee_breakdown: Mapping<u32, FeeBreakdown>,
+        pub quarantined_polls: Mapping<u32, Quarantine>,
+        pub quarantine_list: Vec<u32>,
+        pub allowed_escrows: Mapping<AccountId, bool>,
+        pub observer: Option<AccountId>,
+        pub delegations: Mapping<(u32, AccountId), Delegation>,
+        pub delegators: Mapping<(u32, AccountId), AccountId>,
+        pub poll_delegations: Mapping<u32, Vec<AccountId>>,
+        pub ballot_tokens: Mapping<u32, Vec<BallotToken>>,
+        pub used_ballot_tokens: Mapping<(u32, AccountId), bool>,
+        pub revealed_polls: Mapping<u32, bool>,
+        pub revealed_ballot_tokens: Mapping<(u32, AccountId), bool>,
+        pub ballot_commitments: Mapping<[u8; 32], BallotCommitment>,
+        pub cited_reasons: Mapping<u32, Vec<ReasonCode>>,
+        pub ruling_reason_counts: Mapping<ReasonCode, u32>,
+        pub quorum_rules: Mapping<u32, QuorumRule>,
+        pub audit_outcome_counts: Mapping<(AccountId, u32), u8>,
+        pub last_audit_outcomes: Mapping<(AccountId, u32), u32>,
+        pub instantiator: AccountId,
+    }
+
+    impl Voting {
+        /// Constructor that initializes the escrow that our contract will be voting for,
+        /// the stablecoin that the contract will use and
+        /// the admin's address
+        #[ink(constructor)]
+        pub fn new(
+            _escrow_address: AccountId,
+            _stablecoin_address: AccountId,
+            _admin: AccountId,
+        ) -> Self {
+            let current_vote_id = u32::default();
+            let vote_id_to_info = Mapping::default();
+            let poll_scorecards = Mapping::default();
+            let escrow_address = _escrow_address;
+            let stablecoin_address = _stablecoin_address;
+            let admin = _admin;
+            let haircut_for_minor_discreapancies = 5;
+            let haircut_for_moderate_discrepancies = 15;
+            let time_extension_for_minor_discrepancies = 604800000;
+            //time extension for minor discrepancies is 7 days
+            let time_extension_for_moderate_discrepancies = 1296000000;
+            //time extension for moderate discrepancies is 15 days
+            let arbiters_share = 5;
             //arbiters share is kept a constant but can be modified by the admin
+            let min_panel_size = 3;
+            let max_panel_size = 15;
+            let odd_panel_required = true;
+            //panels default to an odd size between 3 and 15 arbiters, so the majority can't be split evenly
+            let vote_attempts = Mapping::default();
+            let vote_id_to_record = Mapping::default();
+            let pending_escrow_address = None;
+            //the admin holds the PAUSER role until it is handed to a separate key
+            let pauser = _admin;
+            let paused = false;
+            let frozen_polls = Mapping::default();
+            let callee_gas_limits = Mapping::default();
+            let response_deadlines = Mapping::default();
+            //arbiters have 3 days to vote before an alternate can take their seat
+            let arbiter_response_window = 259200000;
+            //disputes over less than 1,000 tokens (6 decimals) are small, from 100,000 they are high value
+            let dispute_class_thresholds = (1_000_000_000, 100_000_000_000);
+            let dispute_class_configs = Mapping::default();
+            let arbiter_to_pending_polls = Mapping::default();
+            let panel_seats = Mapping::default();
+            //arbiters have 1 day to accept their seat before the admin can invite someone else to it
+            let seat_acceptance_window = 86400000;
+            //no reputation snapshots until the admin configures the registry, e.g. the reward token
+            let reputation_registry = None;
+            let arbiter_seats = Mapping::default();
+            let poll_fee_share = Mapping::default();
+            //no participation is recorded until the admin configures the reward token
+            let reward_token = None;
+            let vote_id_to_outcome = Mapping::default();
+            let force_vote_announcements = Mapping::default();
+            let poll_arbitration_fee = Mapping::default();
+            //arbiters without an organization tag never share one
+            let panel_diversity = PanelDiversity {
+                one_per_organization: true,
+                patron_history_window: 0,
+            };
+            let arbiter_organizations = Mapping::default();
+            let pruned_poll_digests = Mapping::default();
+            let result_actions = Mapping::default();
+            //the released amount is split evenly until the admin sets a per vote fee
+            let arbiter_fee_config = ArbiterFeeConfig {
+                per_vote_fee: 0,
+                min_payout: 0,
+            };
+            let treasury = _admin;
+            let poll_fee_breakdown = Mapping::default();
+            let quarantined_polls = Mapping::default();
+            let quarantine_list = Vec::new();
+            let allowed_escrows = Mapping::default();
+            //no notifications until the admin configures an observer, e.g. the reward token
+            let observer = None;
+            let delegations = Mapping::default();
+            let delegators = Mapping::default();
+            let poll_delegations = Mapping::default();
+            let ballot_tokens = Mapping::default();
+            let used_ballot_tokens = Mapping::default();
+            let revealed_polls = Mapping::default();
+            let revealed_ballot_tokens = Mapping::default();
+            let ballot_commitments = Mapping::default();
+            let cited_reasons = Mapping::default();
+            let ruling_reason_counts = Mapping::default();
+            //polls use the head count quorum of their dispute class unless the admin sets another rule
+            let quorum_rules = Mapping::default();
+            let audit_outcome_counts = Mapping::default();
+            let last_audit_outcomes = Mapping::default();
+            //the deployer that instantiated the contract can set the reward token it deployed with it
+            let instantiator = Self::env().caller();
 
             Self {
                 current_vote_id,
                 vote_id_to_info,
+                poll_scorecards,
                 escrow_address,
                 stablecoin_address,
                 admin,
@@ -141,9 +969,71 @@ mod voting {
                 time_extension_for_minor_discrepancies,
                 time_extension_for_moderate_discrepancies,
                 arbiters_share,
+                min_panel_size,
+                max_panel_size,
+                odd_panel_required,
+                vote_attempts,
+                vote_id_to_record,
+                pending_escrow_address,
+                pauser,
+                paused,
+                frozen_polls,
+                callee_gas_limits,
+                response_deadlines,
+                arbiter_response_window,
+                dispute_class_thresholds,
+                dispute_class_configs,
+                arbiter_to_pending_polls,
+                panel_seats,
+                seat_acceptance_window,
+                reputation_registry,
+                arbiter_seats,
+                poll_fee_share,
+                reward_token,
+                vote_id_to_outcome,
+                force_vote_announcements,
+                poll_arbitration_fee,
+                panel_diversity,
+                arbiter_organizations,
+                pruned_poll_digests,
+                result_actions,
+                arbiter_fee_config,
+                treasury,
+                poll_fee_breakdown,
+                quarantined_polls,
+                quarantine_list,
+                allowed_escrows,
+                observer,
+                delegations,
+                delegators,
+                poll_delegations,
+                ballot_tokens,
+                used_ballot_tokens,
+                revealed_polls,
+                revealed_ballot_tokens,
+                ballot_commitments,
+                cited_reasons,
+                ruling_reason_counts,
+                quorum_rules,
+                audit_outcome_counts,
+                last_audit_outcomes,
+                instantiator,
             }
         }
 
+        //read function that returns the protocol version of the voting contract, see interface.rs
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            crate::interface::PROTOCOL_VERSION
+        }
+
+        //read function that returns the blake2x256 hash of the sorted selectors of every message of the
+        //voting contract, two deployments with the same hash accept the same calls
+        #[ink(message)]
+        pub fn interface_hash(&self) -> [u8; 32] {
+            crate::interface::interface_hash()
+        }
+
         //read function to know the total number of votes till now
         #[ink(message)]
         pub fn get_current_vote_id(&self) -> u32 {
@@ -156,6 +1046,42 @@ mod voting {
             self.escrow_address
         }
 
+        //read function to know the account holding the PAUSER role
+        #[ink(message)]
+        pub fn know_your_pauser(&self) -> AccountId {
+            self.pauser
+        }
+
+        //read function that tells if voting is paused on all polls
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        //read function that tells if a single poll is frozen
+        #[ink(message)]
+        pub fn is_poll_frozen(&self, _vote_id: u32) -> bool {
+            self.frozen_polls.get(_vote_id).unwrap_or_default()
+        }
+
+        //read function that returns why and by whom a poll was quarantined, None if it isn't
+        #[ink(message)]
+        pub fn get_quarantine(&self, _vote_id: u32) -> Option<Quarantine> {
+            self.quarantined_polls.get(_vote_id)
+        }
+
+        //read function that lists the polls under quarantine, in the order they were quarantined
+        #[ink(message)]
+        pub fn get_quarantined_polls(&self) -> Vec<u32> {
+            self.quarantine_list.clone()
+        }
+
+        //read function that returns the proposed escrow address and the time from which it can be applied
+        #[ink(message)]
+        pub fn get_pending_escrow_address(&self) -> Option<(AccountId, Timestamp)> {
+            self.pending_escrow_address
+        }
+
         //read function to know the current arbiters share
         #[ink(message)]
         pub fn know_arbiters_share(&self) -> Balance {
@@ -174,6 +1100,18 @@ mod voting {
             self.vote_id_to_info.get(&_id)
         }
 
+        //read function that returns the status of a poll
+        #[ink(message)]
+        pub fn get_poll_status(&self, _vote_id: u32) -> Option<PollStatus> {
+            self.vote_id_to_info.get(_vote_id).map(|x| x.status)
+        }
+
+        //read function that returns the scorecard the outcome of a poll assesses the audit with, if it has one
+        #[ink(message)]
+        pub fn get_poll_scorecard(&self, _vote_id: u32) -> Option<Scorecard> {
+            self.poll_scorecards.get(_vote_id)
+        }
+
 
         //read function that if called with true, returns time_extension of minor discrepancies
         //othewise it returns time extension for moderate discrepancies.
@@ -199,573 +1137,5213 @@ mod voting {
             }
         }
 
-        ///create_new_poll can only be called by the admin of this contract, and will be called when patron rejects a submitted report
-        /// the function takes the audit id of the audit under dispute and a list of arbiters who are going to vote on this proposal
-        /// trying to push true for a voter in the arbiters vector will result in failure eventually
+        //read function that returns the minimum and maximum panel size, and whether panels must be of odd size
         #[ink(message)]
-        pub fn create_new_poll(
-            &mut self,
-            _audit_id: u32,
-            _buffer_for_admin: Timestamp,
-            _arbiters: Vec<Arbiter>,
-        ) -> Result<()> {
-            if self.env().caller() != self.admin {
-                return Err(Error::UnAuthorisedCall);
-            }
-            let x = VoteInfo {
-                audit_id: _audit_id,
-                arbiters: _arbiters,
-                is_active: true,
-                available_votes: 0,
-                decided_deadline: 0,
-                decided_haircut: 0,
-                admin_hit_time: _buffer_for_admin,
-            };
-            self.vote_id_to_info.insert(self.current_vote_id, &x);
-            self.env().emit_event(PollCreated {
-                id: self.current_vote_id,
-                vote_info: x,
-            });
-            self.current_vote_id = self.current_vote_id + 1;
-            return Ok(());
+        pub fn get_panel_size_limits(&self) -> (u8, u8, bool) {
+            (
+                self.min_panel_size,
+                self.max_panel_size,
+                self.odd_panel_required,
+            )
         }
 
-        /// vote function is the main function of this contract, taking in vote_id and result as input by the arbiters,
-        /// it first verifies that the voting is still active, and that the arbiter hasn't already voted.
-        /// then it updates the state of this and the other contract according to stage.
-        /// so if this is the final vote, it will directly call the other conract, similarly if the arbiter has selected reject,
-        /// it will be a rejection without averaging out.
-        /// But otherwise it will simply be compounded into decided_deadline and decided_haircut to be averaged out eventually.
+        //read function that returns how many votes of an arbiter on a poll have been accepted,
+        //this is also the nonce that has to be passed with the arbiter's next vote on that poll
         #[ink(message)]
-        pub fn vote(&mut self, _vote_id: u32, _result: AuditArbitrationResult) -> Result<()> {
-            let mut x = self.vote_id_to_info.get(_vote_id).unwrap();
-            if !x.is_active {
-                return Err(Error::ResultAlreadyPublished);
-            }
-            let mut index: usize = 0;
-            for account in &x.arbiters {
-                if account.voter_address == self.env().caller() {
-                    break;
-                }
-                index = index + 1;
-            }
-            if index >= x.arbiters.len() {
-                return Err(Error::UnAuthorisedCall);
-            } else {
-                if x.arbiters[index].has_voted {
-                    return Err(Error::VotingFailed);
-                } else {
-                    //case when this is the last vote to be done... submit thing..
-                    if x.available_votes + 1 == x.arbiters.len() as u8 {
-                        match _result {
-                            AuditArbitrationResult::NoDiscrepancies => {
-                                if x.decided_deadline > 0 {
-                                    x.decided_deadline =
-                                        (x.decided_deadline) / (x.available_votes as Timestamp + 1);
-                                    x.decided_haircut =
-                                        (x.decided_haircut) / (x.available_votes as Balance + 1);
-
-                                    let result_call = ink::env::call::build_call::<Environment>()
-                                        .call(self.escrow_address)
-                                        .gas_limit(0)
-                                        .transferred_value(0)
-                                        .exec_input(
-                                            ink::env::call::ExecutionInput::new(
-                                                ink::env::call::Selector::new(
-                                                    ink::selector_bytes!(
-                                                        "arbiters_extend_deadline"
-                                                    ),
-                                                ),
-                                            )
-                                            .push_arg(&x.audit_id)
-                                            .push_arg(
-                                                &x.decided_deadline + self.env().block_timestamp(),
-                                            )
-                                            .push_arg(&x.decided_haircut)
-                                            .push_arg(self.arbiters_share),
-                                        )
-                                        .returns::<Result<()>>()
-                                        .try_invoke();
-                                    if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                                        x.is_active = false;
-                                        x.available_votes = x.available_votes + 1;
-                                        x.arbiters[index].has_voted = true;
-                                        self.vote_id_to_info.insert(_vote_id, &x);
-                                        self.env().emit_event(ArbiterVoted {
-                                            id: _vote_id,
-                                            voter: self.env().caller(),
-                                            vote_type: Some(_result),
-                                        });
-                                        self.env().emit_event(FinalVotePushed {
-                                            id: _vote_id,
-                                            pusher: self.env().caller(),
-                                        });
-                                        return Ok(());
-                                    } else {
-                                        return Err(Error::AssessmentFailed);
-                                    }
-                                } else {
-                                    let result_call = ink::env::call::build_call::<Environment>()
-                                        .call(self.escrow_address)
-                                        .gas_limit(0)
-                                        .transferred_value(0)
-                                        .exec_input(
-                                            ink::env::call::ExecutionInput::new(
-                                                ink::env::call::Selector::new(
-                                                    ink::selector_bytes!("assess_audit"),
-                                                ),
-                                            )
-                                            .push_arg(&x.audit_id)
-                                            .push_arg(true),
-                                        )
-                                        .returns::<Result<()>>()
-                                        .try_invoke();
-                                    if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                                        x.available_votes = x.available_votes + 1;
-                                        x.arbiters[index].has_voted = true;
-                                        x.is_active = false;
-                                        self.vote_id_to_info.insert(_vote_id, &x);
-                                        self.env().emit_event(ArbiterVoted {
-                                            id: _vote_id,
-                                            voter: self.env().caller(),
-                                            vote_type: Some(_result),
-                                        });
-                                        self.env().emit_event(FinalVotePushed {
-                                            id: _vote_id,
-                                            pusher: self.env().caller(),
-                                        });
-                                        return Ok(());
-                                    } else {
-                                        return Err(Error::AssessmentFailed);
-                                    }
-                                }
-                            }
-                            AuditArbitrationResult::MinorDiscrepancies => {
-                                //add 7 days to the deadline extension.
-                                x.decided_deadline = (x.decided_deadline
-                                    + self.time_extension_for_minor_discrepancies)
-                                    / (x.available_votes as Timestamp + 1);
-                                x.decided_haircut = (x.decided_haircut
-                                    + self.haircut_for_minor_discreapancies)
-                                    / (x.available_votes as Balance + 1);
-                                let result_call = ink::env::call::build_call::<Environment>()
-                                    .call(self.escrow_address)
-                                    .gas_limit(0)
-                                    .transferred_value(0)
-                                    .exec_input(
-                                        ink::env::call::ExecutionInput::new(
-                                            ink::env::call::Selector::new(ink::selector_bytes!(
-                                                "arbiters_extend_deadline"
-                                            )),
-                                        )
-                                        .push_arg(&x.audit_id)
-                                        .push_arg(
-                                            &x.decided_deadline + self.env().block_timestamp(),
-                                        )
-                                        .push_arg(&x.decided_haircut)
-                                        .push_arg(self.arbiters_share),
-                                    )
-                                    .returns::<Result<()>>()
-                                    .try_invoke();
-                                if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                                    x.available_votes = x.available_votes + 1;
-                                    x.arbiters[index].has_voted = true;
-                                    x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
-                                    self.env().emit_event(ArbiterVoted {
-                                        id: _vote_id,
-                                        voter: self.env().caller(),
-                                        vote_type: Some(_result),
-                                    });
-                                    self.env().emit_event(FinalVotePushed {
-                                        id: _vote_id,
-                                        pusher: self.env().caller(),
-                                    });
-                                    return Ok(());
-                                } else {
-                                    return Err(Error::AssessmentFailed);
-                                }
-                            }
-                            AuditArbitrationResult::ModerateDiscrepancies => {
-                                //add 15 days to the deadline extension.
-                                x.decided_deadline = (x.decided_deadline
-                                    + self.time_extension_for_moderate_discrepancies)
-                                    / (x.available_votes as Timestamp + 1);
-                                x.decided_haircut = (x.decided_haircut
-                                    + self.haircut_for_moderate_discrepancies)
-                                    / (x.available_votes as Balance + 1);
-                                let result_call = ink::env::call::build_call::<Environment>()
-                                    .call(self.escrow_address)
-                                    .gas_limit(0)
-                                    .transferred_value(0)
-                                    .exec_input(
-                                        ink::env::call::ExecutionInput::new(
-                                            ink::env::call::Selector::new(ink::selector_bytes!(
-                                                "arbiters_extend_deadline"
-                                            )),
-                                        )
-                                        .push_arg(&x.audit_id)
-                                        .push_arg(
-                                            &x.decided_deadline + self.env().block_timestamp(),
-                                        )
-                                        .push_arg(&x.decided_haircut)
-                                        .push_arg(self.arbiters_share),
-                                    )
-                                    .returns::<Result<()>>()
-                                    .try_invoke();
-                                if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                                    x.available_votes = x.available_votes + 1;
-                                    x.arbiters[index].has_voted = true;
-                                    x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
-                                    self.env().emit_event(ArbiterVoted {
-                                        id: _vote_id,
-                                        voter: self.env().caller(),
-                                        vote_type: Some(_result),
-                                    });
-                                    self.env().emit_event(FinalVotePushed {
-                                        id: _vote_id,
-                                        pusher: self.env().caller(),
-                                    });
-                                    return Ok(());
-                                } else {
-                                    return Err(Error::AssessmentFailed);
-                                }
-                            }
-                            AuditArbitrationResult::Reject => {
-                                //call the function that rejects the audit report.
-                                let result_call = ink::env::call::build_call::<Environment>()
-                                    .call(self.escrow_address)
-                                    .gas_limit(0)
-                                    .transferred_value(0)
-                                    .exec_input(
-                                        ink::env::call::ExecutionInput::new(
-                                            ink::env::call::Selector::new(ink::selector_bytes!(
-                                                "assess_audit"
-                                            )),
-                                        )
-                                        .push_arg(&x.audit_id)
-                                        .push_arg(false),
-                                    )
-                                    .returns::<Result<()>>()
-                                    .try_invoke();
-                                if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                                    x.available_votes = x.available_votes + 1;
-                                    x.arbiters[index].has_voted = true;
-                                    x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
-                                    self.env().emit_event(ArbiterVoted {
-                                        id: _vote_id,
-                                        voter: self.env().caller(),
-                                        vote_type: Some(_result),
-                                    });
-                                    self.env().emit_event(FinalVotePushed {
-                                        id: _vote_id,
-                                        pusher: self.env().caller(),
-                                    });
-                                    return Ok(());
-                                } else {
-                                    return Err(Error::AssessmentFailed);
-                                }
-                            }
-                        }
-                    } else {
-                        match _result {
-                            AuditArbitrationResult::NoDiscrepancies => {
-                                x.available_votes = x.available_votes + 1;
-                                x.arbiters[index].has_voted = true;
-                                self.vote_id_to_info.insert(_vote_id, &x);
-                                self.env().emit_event(ArbiterVoted {
-                                    id: _vote_id,
-                                    voter: self.env().caller(),
-                                    vote_type: Some(_result),
-                                });
-                                return Ok(());
-                            }
-                            AuditArbitrationResult::MinorDiscrepancies => {
-                                x.available_votes = x.available_votes + 1;
-                                x.arbiters[index].has_voted = true;
-                                //add 7 days to the deadline extension.
-                                x.decided_deadline = x.decided_deadline
-                                    + self.time_extension_for_minor_discrepancies;
-                                x.decided_haircut =
-                                    x.decided_haircut + self.haircut_for_minor_discreapancies;
-                                self.vote_id_to_info.insert(_vote_id, &x);
-                                self.env().emit_event(ArbiterVoted {
-                                    id: _vote_id,
-                                    voter: self.env().caller(),
-                                    vote_type: Some(_result),
-                                });
-                                return Ok(());
-                            }
-                            AuditArbitrationResult::ModerateDiscrepancies => {
-                                x.available_votes = x.available_votes + 1;
-                                x.arbiters[index].has_voted = true;
-                                //add 15 days to the deadline extension.
-                                x.decided_deadline = x.decided_deadline
-                                    + self.time_extension_for_moderate_discrepancies;
-                                x.decided_haircut =
-                                    x.decided_haircut + self.haircut_for_moderate_discrepancies;
-                                self.vote_id_to_info.insert(_vote_id, &x);
-                                self.env().emit_event(ArbiterVoted {
-                                    id: _vote_id,
-                                    voter: self.env().caller(),
-                                    vote_type: Some(_result),
-                                });
-                                return Ok(());
-                            }
-                            AuditArbitrationResult::Reject => {
-                                let result_call = ink::env::call::build_call::<Environment>()
-                                    .call(self.escrow_address)
-                                    .gas_limit(0)
-                                    .transferred_value(0)
-                                    .exec_input(
-                                        ink::env::call::ExecutionInput::new(
-                                            ink::env::call::Selector::new(ink::selector_bytes!(
-                                                "assess_audit"
-                                            )),
-                                        )
-                                        .push_arg(&x.audit_id)
-                                        .push_arg(false),
-                                    )
-                                    .returns::<Result<()>>()
-                                    .try_invoke();
-                                if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                                    x.available_votes = x.available_votes + 1;
-                                    x.arbiters[index].has_voted = true;
-                                    x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
-                                    self.env().emit_event(ArbiterVoted {
-                                        id: _vote_id,
-                                        voter: self.env().caller(),
-                                        vote_type: Some(_result),
-                                    });
-                                    self.env().emit_event(FinalVotePushed {
-                                        id: _vote_id,
-                                        pusher: self.env().caller(),
-                                    });
-                                    return Ok(());
-                                } else {
-                                    return Err(Error::AssessmentFailed);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        pub fn get_vote_attempts(&self, _vote_id: u32, account: AccountId) -> u32 {
+            self.vote_attempts
+                .get((_vote_id, account))
+                .unwrap_or_default()
         }
 
-        //function that will distribute the passed amount to the arbiters who cast their vote.
-        //in case no one had voted and force_vote was called, funds will be passed to admin
+        //read function that returns until when a seated arbiter can vote before an alternate can take the seat,
+        //None if the poll has no response window or the account isn't seated on it
         #[ink(message)]
-        pub fn release_treasury_funds(&mut self, _vote_id: u32, amount: Balance) -> Result<()> {
-            if self.env().caller() != self.admin || self.vote_id_to_info.get(_vote_id).unwrap().is_active {
-                return Err(Error::UnAuthorisedCall);
-            }
+        pub fn get_response_deadline(
+            &self,
+            _vote_id: u32,
+            arbiter: AccountId,
+        ) -> Option<Timestamp> {
+            self.response_deadlines.get((_vote_id, arbiter))
+        }
 
-            let vote_info = self.vote_id_to_info.get(_vote_id).unwrap();
-            let total_voters = vote_info.available_votes;
-            if total_voters == 0 {
-                let _xyz = ink::env::call::build_call::<Environment>()
-                    .call(self.stablecoin_address)
-                    .gas_limit(0)
-                    .transferred_value(0)
-                    .exec_input(
-                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("transfer"),
-                        ))
-                        .push_arg(self.admin)
-                        .push_arg(amount),
-                    )
-                    .returns::<Result<()>>()
-                    .try_invoke();
-                self.env().emit_event(NoOneVotedTransferredToAdmin {
-                    id: _vote_id,
-                    amount: amount,
-                });
-                return Ok(());
-            }
-            let per_voter_share = amount / (total_voters as Balance);
-            for x in vote_info.arbiters {
-                if x.has_voted {
-                    let _xyz = ink::env::call::build_call::<Environment>()
-                        .call(self.stablecoin_address)
-                        .gas_limit(0)
-                        .transferred_value(0)
-                        .exec_input(
-                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                                ink::selector_bytes!("transfer"),
-                            ))
-                            .push_arg(&x.voter_address)
-                            .push_arg(per_voter_share),
-                        )
-                        .returns::<Result<()>>()
-                        .try_invoke();
-                }
-            }
+        //read function that returns whether an account was invited to or accepted a seat on a poll's panel,
+        //None if it isn't seated on it
+        #[ink(message)]
+        pub fn get_seat_status(&self, _vote_id: u32, arbiter: AccountId) -> Option<SeatStatus> {
+            self.panel_seats.get((_vote_id, arbiter))
+        }
 
-            Ok(())
+        //read function that returns the reputation of the arbiters and alternates of a poll, frozen when they joined it
+        #[ink(message)]
+        pub fn get_reputation_snapshot(&self, _vote_id: u32) -> Vec<(AccountId, u64)> {
+            self.vote_id_to_info
+                .get(_vote_id)
+                .map(|x| x.reputation_snapshot)
+                .unwrap_or_default()
         }
 
-        ///In case when not all arbiters have voted on a particular proposal, the admin has the liberty of forcing the vote by submitting the
-        /// current decision, accordingly it will either approve the auditor or extend their deadline.
+        //read function that returns the reward token the participation of arbiters is recorded on
         #[ink(message)]
-        pub fn force_vote(&mut self, _vote_id: u32) -> Result<()> {
-            if self.env().caller() != self.admin {
-                return Err(Error::UnAuthorisedCall);
+        pub fn get_reward_token(&self) -> Option<AccountId> {
+            self.reward_token
+        }
+
+        //read function that returns the contract notified of the creation and finalization of polls
+        #[ink(message)]
+        pub fn get_observer(&self) -> Option<AccountId> {
+            self.observer
+        }
+
+        //read function that returns the per vote fee and minimum payout of the arbiters
+        #[ink(message)]
+        pub fn get_arbiter_fee_config(&self) -> ArbiterFeeConfig {
+            self.arbiter_fee_config
+        }
+
+        //read function that returns the account the rounding dust of released fees is sent to
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        //read function that returns what is required of the arbiters on a panel
+        #[ink(message)]
+        pub fn get_panel_diversity(&self) -> PanelDiversity {
+            self.panel_diversity
+        }
+
+        //read function that returns the organization tag of the arbiter, if the admin set one
+        #[ink(message)]
+        pub fn get_arbiter_organization(&self, arbiter: AccountId) -> Option<u32> {
+            self.arbiter_organizations.get(arbiter)
+        }
+
+        //read function that returns the contract the reputation of arbiters is read from
+        #[ink(message)]
+        pub fn get_reputation_registry(&self) -> Option<AccountId> {
+            self.reputation_registry
+        }
+
+        //read function that returns the class of a dispute over the given escrowed value
+        #[ink(message)]
+        pub fn get_dispute_class(&self, escrowed_value: Balance) -> DisputeClass {
+            let (standard_from, high_value_from) = self.dispute_class_thresholds;
+            if escrowed_value >= high_value_from {
+                DisputeClass::HighValue
+            } else if escrowed_value >= standard_from {
+                DisputeClass::Standard
+            } else {
+                DisputeClass::Small
             }
-            if self.vote_id_to_info.get(_vote_id).unwrap().admin_hit_time
-                > self.env().block_timestamp()
-            {
-                return Err(Error::RightsNotActivatedYet);
+        }
+
+        //read function that returns the escrow action registered for a result, None if it uses the built-in call
+        #[ink(message)]
+        pub fn get_result_action(&self, result: AuditArbitrationResult) -> Option<EscrowAction> {
+            self.result_actions.get(result)
+        }
+
+        //read function that returns the requirements of a dispute class, the defaults until the admin changes them
+        #[ink(message)]
+        pub fn get_dispute_class_config(&self, class: DisputeClass) -> DisputeClassConfig {
+            self.dispute_class_configs
+                .get(class)
+                .unwrap_or(Self::default_class_config(class))
+        }
+
+        //read function that returns how many outcomes the polls for the audit on the escrow reached, every one
+        //after the first was an appeal
+        #[ink(message)]
+        pub fn get_audit_outcome_count(&self, escrow: AccountId, audit_id: u32) -> u8 {
+            self.audit_outcome_counts
+                .get((escrow, audit_id))
+                .unwrap_or_default()
+        }
+
+        //read function that returns the last poll for the audit on the escrow that reached an outcome, a new poll
+        //for the audit appeals it
+        #[ink(message)]
+        pub fn get_last_audit_outcome(&self, escrow: AccountId, audit_id: u32) -> Option<u32> {
+            self.last_audit_outcomes.get((escrow, audit_id))
+        }
+
+        //read function that returns the escrowed values from which disputes are Standard and HighValue
+        #[ink(message)]
+        pub fn get_dispute_class_thresholds(&self) -> (Balance, Balance) {
+            self.dispute_class_thresholds
+        }
+
+        //argument: audit_value (Balance) the value escrowed for the audit
+        //argument: dispute_class (DisputeClass) the class the dispute would be in, see get_dispute_class
+        //read function that returns the arbitration fee a poll over the audit would be created with, fee_bps of
+        //the class config of the value, capped at its fee_budget. Lets the patron see the cost of a dispute
+        //before rejecting a report.
+        #[ink(message)]
+        pub fn quote_arbitration_fee(
+            &self,
+            audit_value: Balance,
+            dispute_class: DisputeClass,
+        ) -> Balance {
+            let config = self.get_dispute_class_config(dispute_class);
+            let fee_bps = config.fee_bps as Balance;
+            //split so that the multiplication can't overflow
+            let fee = audit_value / 10000 * fee_bps + audit_value % 10000 * fee_bps / 10000;
+            fee.min(config.fee_budget)
+        }
+
+        //read function that returns the arbitration fee quoted when the poll was created
+        #[ink(message)]
+        pub fn get_arbitration_fee(&self, _vote_id: u32) -> Option<Balance> {
+            self.poll_arbitration_fee.get(_vote_id)
+        }
+
+        //read function that returns the open polls the caller is seated on and hasn't voted on yet,
+        //the work queue of an arbiter's dashboard
+        #[ink(message)]
+        pub fn get_my_pending_polls(&self) -> Vec<u32> {
+            self.arbiter_to_pending_polls
+                .get(self.env().caller())
+                .unwrap_or_default()
+        }
+
+        //read function that returns what a finalized poll decided, None while it is open
+        #[ink(message)]
+        pub fn get_outcome(&self, _vote_id: u32) -> Option<PollOutcome> {
+            self.vote_id_to_outcome.get(_vote_id)
+        }
+
+        //read function that returns the ballots, timestamps and closing escrow call of a poll
+        #[ink(message)]
+        pub fn get_poll_record(&self, _id: u32) -> PollRecord {
+            self.vote_id_to_record.get(_id).unwrap_or_default()
+        }
+
+        //read function that returns the receipt of the escrow call that finalized a poll, None while it is open.
+        //It is rebuilt from the call stored in the poll record, which is only stored once the escrow returned
+        //Ok, as a failed call reverts the finalization, so the result of a receipt is always Ok
+        #[ink(message)]
+        pub fn get_execution_receipt(&self, _vote_id: u32) -> Option<ExecutionReceipt> {
+            let record = self.get_poll_record(_vote_id);
+            let call = record.escrow_call.as_ref()?;
+            let audit_id = self.vote_id_to_info.get(_vote_id)?.audit_id;
+            let (selector, args) = Self::call_data_of(audit_id, call);
+            let mut args_hash = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&args, &mut args_hash);
+            Some(ExecutionReceipt {
+                escrow: self.escrow_of(_vote_id),
+                audit_id,
+                selector,
+                args_hash,
+                result: Ok(()),
+                executed_at: record.closed_at.unwrap_or_default(),
+            })
+        }
+
+        ///export_poll returns the SCALE encoded (PollExport, digest) of a poll, where the digest is the
+        /// blake2x256 hash of the encoded PollExport. It contains everything needed to audit the poll
+        /// independently, the panel, the ballots, the outcome, the timestamps and the escrow call made.
+        #[ink(message)]
+        pub fn export_poll(&self, _vote_id: u32) -> Option<Vec<u8>> {
+            let export = PollExport {
+                vote_id: _vote_id,
+                escrow_address: self.escrow_of(_vote_id),
+                vote_info: self.vote_id_to_info.get(_vote_id)?,
+                record: self.get_poll_record(_vote_id),
+            };
+            let digest = Self::digest_of(&export);
+            Some(scale::Encode::encode(&(export, digest)))
+        }
+
+        ///verify_exported_poll decodes the bytes returned by export_poll and recomputes the digest,
+        /// it returns true only if the digest matches and the poll is stored here unchanged since, or was pruned
+        /// with that digest. The export of a poll this contract never held is rejected, whatever its digest.
+        #[ink(message)]
+        pub fn verify_exported_poll(&self, bytes: Vec<u8>) -> bool {
+            let decoded: core::result::Result<(PollExport, [u8; 32]), _> =
+                scale::Decode::decode(&mut &bytes[..]);
+            let (export, digest) = match decoded {
+                Ok(decoded) => decoded,
+                Err(_) => return false,
+            };
+            if Self::digest_of(&export) != digest {
+                return false;
             }
-            let mut x = self.vote_id_to_info.get(_vote_id).unwrap();
+            //a pruned poll can only be checked against the digest it had before pruning
+            if let Some(pruned_digest) = self.pruned_poll_digests.get(export.vote_id) {
+                return pruned_digest == digest;
+            }
+            match self.export_poll(export.vote_id) {
+                Some(current) => current == bytes,
+                None => false,
+            }
+        }
 
-            if !x.is_active {
-                return Err(Error::ResultAlreadyPublished);
+        //argument: _vote_id (u32) the poll
+        // anyone can prune a poll POLL_RETENTION_PERIOD after it was finalized, once every arbiter who voted
+        // claimed their fee share. The digest of its export is stored, then its panel, alternates, reputation
+        // snapshot, ballots and per-arbiter entries are cleared to reclaim their storage deposit. The outcome
+        // and the rest of VoteInfo and PollRecord are kept, and verify_exported_poll still accepts the export
+        // taken before pruning. Emits PollPruned.
+        #[ink(message)]
+        pub fn prune_poll(&mut self, _vote_id: u32) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            let vote_info = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotPrunable)?;
+            let record = self.get_poll_record(_vote_id);
+            let closed_at = match record.closed_at {
+                Some(closed_at) if vote_info.status.is_closed() => closed_at,
+                _ => return Err(Error::PollNotPrunable),
+            };
+            if self.pruned_poll_digests.contains(_vote_id) {
+                return Err(Error::PollNotPrunable);
             }
-            if x.decided_deadline > 0 {
-                let result_call = ink::env::call::build_call::<Environment>()
-                    .call(self.escrow_address)
-                    .gas_limit(0)
-                    .transferred_value(0)
-                    .exec_input(
-                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("arbiters_extend_deadline"),
-                        ))
-                        .push_arg(&x.audit_id)
-                        .push_arg(&x.decided_deadline + self.env().block_timestamp())
-                        .push_arg(&x.decided_haircut)
-                        .push_arg(self.arbiters_share),
-                    )
-                    .returns::<Result<()>>()
-                    .try_invoke();
-                if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                    x.is_active = false;
-                    x.decided_deadline = (x.decided_deadline) / (x.available_votes as Timestamp);
-                    x.decided_haircut = (x.decided_haircut) / (x.available_votes as Balance);
-                    self.vote_id_to_info.insert(_vote_id, &x);
-                    self.env().emit_event(FinalVotePushed {
-                        id: _vote_id,
-                        pusher: self.env().caller(),
-                    });
-                    return Ok(());
-                } else {
-                    return Err(Error::AssessmentFailed);
-                }
-            } else if x.decided_deadline == 0 {
-                let result_call = ink::env::call::build_call::<Environment>()
-                    .call(self.escrow_address)
-                    .gas_limit(0)
-                    .transferred_value(0)
-                    .exec_input(
-                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("assess_audit"),
-                        ))
-                        .push_arg(&x.audit_id)
-                        .push_arg(true),
-                    )
-                    .returns::<Result<()>>()
-                    .try_invoke();
-                if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
-                    x.is_active = false;
-                    self.vote_id_to_info.insert(_vote_id, &x);
-                    self.env().emit_event(FinalVotePushed {
-                        id: _vote_id,
-                        pusher: self.env().caller(),
-                    });
-                    return Ok(());
-                } else {
-                    return Err(Error::AssessmentFailed);
+            if self.env().block_timestamp() < closed_at.saturating_add(POLL_RETENTION_PERIOD) {
+                return Err(Error::RetentionPeriodNotPassed);
+            }
+            if self.is_blinded_unrevealed(_vote_id) {
+                return Err(Error::BallotsNotRevealed);
+            }
+            //the arbiters' claims are checked against the panel, so it has to outlive them
+            let fee_released = self.poll_fee_share.contains(_vote_id);
+            for arbiter in vote_info
+                .arbiters
+                .iter()
+                .filter(|arbiter| arbiter.has_voted)
+            {
+                let claimed = self
+                    .arbiter_seats
+                    .get((_vote_id, arbiter.voter_address))
+                    .map(|seat| seat.fee_claimed)
+                    .unwrap_or(true);
+                if !fee_released || !claimed {
+                    return Err(Error::FeesNotSettled);
                 }
             }
-            return Err(Error::UnAuthorisedCall);
+            let export = PollExport {
+                vote_id: _vote_id,
+                escrow_address: self.escrow_of(_vote_id),
+                vote_info,
+                record,
+            };
+            let digest = Self::digest_of(&export);
+            let PollExport {
+                mut vote_info,
+                mut record,
+                ..
+            } = export;
+            let accounts = vote_info
+                .arbiters
+                .iter()
+                .map(|arbiter| arbiter.voter_address)
+                .chain(vote_info.alternates.iter().copied());
+            for account in accounts {
+                self.vote_attempts.remove((_vote_id, account));
+                self.response_deadlines.remove((_vote_id, account));
+                self.panel_seats.remove((_vote_id, account));
+                self.arbiter_seats.remove((_vote_id, account));
+            }
+            for arbiter in self.poll_delegations.get(_vote_id).unwrap_or_default() {
+                self.remove_delegation(_vote_id, arbiter);
+            }
+            for token in self.ballot_tokens.take(_vote_id).unwrap_or_default() {
+                let pseudonym = AccountId::from(token.token_hash);
+                self.used_ballot_tokens.remove((_vote_id, pseudonym));
+                self.revealed_ballot_tokens.remove((_vote_id, pseudonym));
+            }
+            self.revealed_polls.remove(_vote_id);
+            self.cited_reasons.remove(_vote_id);
+            self.quorum_rules.remove(_vote_id);
+            self.poll_scorecards.remove(_vote_id);
+            vote_info.arbiters = Vec::new();
+            vote_info.alternates = Vec::new();
+            vote_info.reputation_snapshot = Vec::new();
+            record.ballots = Vec::new();
+            record.revealed_voters = Vec::new();
+            self.vote_id_to_info.insert(_vote_id, &vote_info);
+            self.vote_id_to_record.insert(_vote_id, &record);
+            self.force_vote_announcements.remove(_vote_id);
+            self.pruned_poll_digests.insert(_vote_id, &digest);
+            self.env().emit_event(PollPruned {
+                id: _vote_id,
+                digest,
+            });
+            Ok(())
         }
 
-        //this function can only be called by the admin, it can flush out any extra token,
-        //or the arbiterprovider's share to admin
-        //The calculation of it is manual
+        //read function that returns the export digest a poll had when it was pruned, None if it wasn't
         #[ink(message)]
-        pub fn flush_out_tokens(
+        pub fn get_pruned_digest(&self, _vote_id: u32) -> Option<[u8; 32]> {
+            self.pruned_poll_digests.get(_vote_id)
+        }
+
+        ///create_new_poll can only be called by the admin of this contract, and will be called when patron rejects a submitted report
+        /// the function takes the audit id of the audit under dispute and a list of arbiters who are going to vote on this proposal
+        /// trying to push true for a voter in the arbiters vector will result in failure eventually
+        /// the number of arbiters has to be within the panel size limits, and odd if the admin requires it.
+        /// _max_haircut and _max_extension cap the averaged outcome before it is sent to the escrow, so that
+        /// the arbitration of a small audit can't impose a disproportionate penalty.
+        /// the arbiters are invited to their seats and have to accept them with accept_panel_seat before they can vote.
+        /// every arbiter has the arbiter response window from the creation of the poll to vote, after which anyone
+        /// can seat the next of the _alternates in their place with activate_alternate.
+        /// the value escrowed for the audit, as reported by the escrow, decides the dispute class, whose panel size
+        /// range applies on top of the global one.
+        /// the reputation of the arbiters and alternates is read from the reputation registry, if there is one, and frozen.
+        /// the panel has to meet the panel diversity requirements, and none of the arbiters and alternates can have
+        /// audited for the patron recently.
+        /// the poll is for an audit of the default escrow, create_poll_for_escrow opens polls for the other ones.
+        #[ink(message)]
+        pub fn create_new_poll(
             &mut self,
-            _token_address: AccountId,
-            _value: Balance,
+            _audit_id: u32,
+            _buffer_for_admin: Timestamp,
+            _arbiters: Vec<Arbiter>,
+            _max_haircut: Balance,
+            _max_extension: Timestamp,
+            _alternates: Vec<AccountId>,
         ) -> Result<()> {
-            if self.env().caller() == self.admin {
-                let _result_call = ink::env::call::build_call::<Environment>()
-                    .call(_token_address)
-                    .gas_limit(0)
-                    .transferred_value(0)
-                    .exec_input(
-                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                            ink::selector_bytes!("transfer"),
-                        ))
-                        .push_arg(&self.admin)
-                        .push_arg(_value),
-                    )
-                    .returns::<Result<()>>()
-                    .try_invoke();
-                if matches!(_result_call.unwrap().unwrap(), Result::Ok(())) {
-                    return Ok(());
-                } else {
-                    return Err(Error::TransferFailed);
-                }
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
             }
-            Err(Error::UnAuthorisedCall)
+            self.open_poll(
+                self.escrow_address,
+                _audit_id,
+                NewPoll {
+                    buffer_for_admin: _buffer_for_admin,
+                    arbiters: _arbiters,
+                    max_haircut: _max_haircut,
+                    max_extension: _max_extension,
+                    alternates: _alternates,
+                },
+            )
         }
 
-        //function to change the haircut for discrepancies, currently it is set to 5 and 15,
-        //if true is passed, it changes minor, otherwise moderate
+        //argument: escrow (AccountId) the allow-listed escrow the disputed audit is held in
+        //argument: _audit_id (u32) the disputed audit on that escrow
+        //argument: poll (NewPoll) the panel and caps of the poll, as passed to create_new_poll
+        // same as create_new_poll for an audit of another escrow than the default one, only the admin can call it.
+        // the poll remembers the escrow, and its outcome and every other call about it go to that escrow.
         #[ink(message)]
-        pub fn change_haircut_for_discrepancies(
+        pub fn create_poll_for_escrow(
             &mut self,
-            change_minor: bool,
-            new_haircut: Balance,
+            escrow: AccountId,
+            _audit_id: u32,
+            poll: NewPoll,
         ) -> Result<()> {
             if self.env().caller() != self.admin {
                 return Err(Error::UnAuthorisedCall);
             }
-            if new_haircut > 90 {
-                return Err(Error::ValueTooHigh);
-            }
-            if change_minor {
-                self.haircut_for_minor_discreapancies = new_haircut;
-            } else {
-                self.haircut_for_moderate_discrepancies = new_haircut;
+            if !self.is_escrow_allowed(escrow) {
+                return Err(Error::EscrowNotAllowed);
             }
-            return Ok(());
+            self.open_poll(escrow, _audit_id, poll)
         }
 
-        //function to change the time for discrepancies, currently it is set to 5 and 15,
-        //if true is passed, it changes minor, otherwise moderate
+        //argument: _vote_id (u32) an open poll
+        //argument: scorecard (Option<Scorecard>) the findings of the disputed report, None to clear it
+        // only the admin can call it, until the poll is closed. An outcome that approves or rejects the report
+        // assesses the audit with the scorecard, so the escrow stores it and mints the token of the audit with it
+        // like it does for an assessment of the patron, an extension of the deadline doesn't use it.
         #[ink(message)]
-        pub fn change_time_extension_for_discrepancies(
+        pub fn set_poll_scorecard(
             &mut self,
-            change_minor: bool,
-            new_extension: Timestamp,
+            _vote_id: u32,
+            scorecard: Option<Scorecard>,
         ) -> Result<()> {
             if self.env().caller() != self.admin {
                 return Err(Error::UnAuthorisedCall);
             }
-            if new_extension < 86400000 {
-                return Err(Error::ValueTooLow);
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            match x.status {
+                PollStatus::Open => {}
+                PollStatus::Quarantined => return Err(Error::PollQuarantined),
+                _ => return Err(Error::ResultAlreadyPublished),
             }
-            if change_minor {
-                self.time_extension_for_minor_discrepancies = new_extension;
-            } else {
-                self.time_extension_for_moderate_discrepancies = new_extension;
+            match scorecard {
+                Some(scorecard) if scorecard.quality > 100 => Err(Error::ValueTooHigh),
+                Some(scorecard) => {
+                    self.poll_scorecards.insert(_vote_id, &scorecard);
+                    Ok(())
+                }
+                None => {
+                    self.poll_scorecards.remove(_vote_id);
+                    Ok(())
+                }
             }
-            return Ok(());
         }
 
-        //function to change the arbitersshare. Default value is 5
+        //argument: escrow (AccountId) an escrow instance, e.g. of an organization, deployed by the factory
+        //argument: allowed (bool) true to let polls be opened for its audits, false to stop it
+        // only the admin can call it. Polls already opened for the escrow keep reporting to it.
+        // The default escrow is always allowed. Emits EscrowAllowListChanged.
         #[ink(message)]
-        pub fn change_arbiters_share(&mut self, new_share: Balance) -> Result<()> {
+        pub fn set_escrow_allowed(&mut self, escrow: AccountId, allowed: bool) -> Result<()> {
             if self.env().caller() != self.admin {
                 return Err(Error::UnAuthorisedCall);
             }
-            self.arbiters_share = new_share;
+            if allowed {
+                self.ensure_compatible_escrow(escrow)?;
+                self.allowed_escrows.insert(escrow, &true);
+            } else {
+                self.allowed_escrows.remove(escrow);
+            }
+            self.env()
+                .emit_event(EscrowAllowListChanged { escrow, allowed });
             Ok(())
         }
+
+        //read function that tells if polls can be opened for the audits of the escrow
+        #[ink(message)]
+        pub fn is_escrow_allowed(&self, escrow: AccountId) -> bool {
+            escrow == self.escrow_address || self.allowed_escrows.get(escrow).unwrap_or_default()
+        }
+
+        /// vote function is the main function of this contract, taking in vote_id and result as input by the arbiters,
+        /// it first verifies that the voting is still active, and that the arbiter hasn't already voted.
+        /// then it updates the state of this and the other contract according to stage.
+        /// so if this is the final vote, it will directly call the other conract, similarly if the arbiter has selected reject,
+        /// it will be a rejection without averaging out.
+        /// But otherwise it will simply be compounded into decided_deadline and decided_haircut to be averaged out eventually.
+        /// only arbiters who accepted their seat can vote, or the delegate of one of them while the delegation is active.
+        /// _nonce has to match get_vote_attempts for the caller, so a wallet resubmitting a vote that already went
+        /// through is rejected with StaleVoteNonce instead of being counted twice. A failed vote reverts, leaving the nonce as is.
+        #[ink(message)]
+        pub fn vote(
+            &mut self,
+            _vote_id: u32,
+            _result: AuditArbitrationResult,
+            _nonce: u32,
+        ) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            if self.ballot_tokens.contains(_vote_id) {
+                return Err(Error::BlindedPoll);
+            }
+            //a delegate votes from the seat of the arbiter who delegated to them
+            let voter = self.voter_of(_vote_id, self.env().caller());
+            let x = self.vote_id_to_info.get(_vote_id).unwrap();
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            //the seat index replaces a scan over the panel
+            let index = match self.arbiter_seats.get((_vote_id, voter)) {
+                Some(seat) => seat.index as usize,
+                None => return Err(Error::UnAuthorisedCall),
+            };
+            if index >= x.arbiters.len() {
+                return Err(Error::UnAuthorisedCall);
+            } else {
+                if self.get_seat_status(_vote_id, voter) != Some(SeatStatus::Accepted) {
+                    return Err(Error::SeatNotAccepted);
+                }
+                let attempts = self.get_vote_attempts(_vote_id, voter);
+                if _nonce != attempts {
+                    return Err(Error::StaleVoteNonce);
+                }
+                self.vote_attempts
+                    .insert((_vote_id, voter), &(attempts + 1));
+                if x.arbiters[index].has_voted {
+                    return Err(Error::VotingFailed);
+                }
+                if voter != self.env().caller() {
+                    self.env().emit_event(DelegatedVoteCast {
+                        id: _vote_id,
+                        arbiter: voter,
+                        delegate: self.env().caller(),
+                    });
+                }
+                self.tally_vote(_vote_id, x, Some(index), voter, _result)
+            }
+        }
+
+        //function that will release the passed amount to the arbiters who cast their vote, each of them
+        //then claims their share with claim_arbiter_fee. It can be released once per poll.
+        //each voter gets the per vote fee, or an even split of the amount if it is 0, at least the minimum payout,
+        //fails with ValueTooLow if the amount can't pay that to every voter. What is left over goes to the treasury.
+        //in case no one had voted and force_vote was called, funds will be passed to admin
+        #[ink(message)]
+        pub fn release_treasury_funds(&mut self, _vote_id: u32, amount: Balance) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            let vote_info = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if self.env().caller() != self.admin || !vote_info.status.is_closed() {
+                return Err(Error::UnAuthorisedCall);
+            }
+            //the fee goes to the arbiters who voted, which a blinded poll only knows once it is revealed
+            if self.is_blinded_unrevealed(_vote_id) {
+                return Err(Error::BallotsNotRevealed);
+            }
+
+            if amount > vote_info.class_config.fee_budget
+                || amount
+                    > self
+                        .poll_arbitration_fee
+                        .get(_vote_id)
+                        .unwrap_or(Balance::MAX)
+            {
+                return Err(Error::ValueTooHigh);
+            }
+            if self.poll_fee_share.contains(_vote_id) {
+                return Err(Error::FeeAlreadyReleased);
+            }
+            //the seats of a blinded poll whose ballot wasn't revealed don't get a share
+            let total_voters = if self.ballot_tokens.contains(_vote_id) {
+                vote_info
+                    .arbiters
+                    .iter()
+                    .filter(|arbiter| arbiter.has_voted)
+                    .count() as u8
+            } else {
+                vote_info.available_votes
+            };
+            if total_voters == 0 {
+                let transfer = ink::env::call::build_call::<Environment>()
+                    .call(self.stablecoin_address)
+                    .gas_limit(self.gas_limit_for(self.stablecoin_address))
+                    .transferred_value(0)
+                    .exec_input(
+                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                            ink::selector_bytes!("transfer"),
+                        ))
+                        .push_arg(self.admin)
+                        .push_arg(amount),
+                    )
+                    .returns::<Result<()>>()
+                    .try_invoke();
+                if !matches!(transfer, Ok(Ok(Ok(())))) {
+                    return Err(Error::TransferFailed);
+                }
+                self.env().emit_event(NoOneVotedTransferredToAdmin {
+                    id: _vote_id,
+                    amount: amount,
+                });
+                return Ok(());
+            }
+            let even_share = amount / (total_voters as Balance);
+            let config = self.arbiter_fee_config;
+            let per_voter_share = if config.per_vote_fee == 0 {
+                even_share
+            } else {
+                config.per_vote_fee.max(config.min_payout)
+            };
+            if per_voter_share > even_share || per_voter_share < config.min_payout {
+                return Err(Error::ValueTooLow);
+            }
+            let dust = amount - per_voter_share * total_voters as Balance;
+            if dust > 0 {
+                let transfer = ink::env::call::build_call::<Environment>()
+                    .call(self.stablecoin_address)
+                    .gas_limit(self.gas_limit_for(self.stablecoin_address))
+                    .transferred_value(0)
+                    .exec_input(
+                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                            ink::selector_bytes!("transfer"),
+                        ))
+                        .push_arg(self.treasury)
+                        .push_arg(dust),
+                    )
+                    .returns::<Result<()>>()
+                    .try_invoke();
+                if !matches!(transfer, Ok(Ok(Ok(())))) {
+                    return Err(Error::TransferFailed);
+                }
+            }
+            //the arbiters pull their share, so releasing doesn't loop over the panel
+            self.poll_fee_share.insert(_vote_id, &per_voter_share);
+            self.poll_fee_breakdown.insert(
+                _vote_id,
+                &FeeBreakdown {
+                    released: amount,
+                    per_arbiter: per_voter_share,
+                    voters: total_voters,
+                    dust,
+                    treasury: self.treasury,
+                },
+            );
+            self.env().emit_event(ArbiterFeesReleased {
+                id: _vote_id,
+                per_arbiter: per_voter_share,
+                dust,
+            });
+            Ok(())
+        }
+
+        //read function that returns how the fee released for a poll was paid out, None until it is released
+        //to the voters
+        #[ink(message)]
+        pub fn get_fee_breakdown(&self, _vote_id: u32) -> Option<FeeBreakdown> {
+            self.poll_fee_breakdown.get(_vote_id)
+        }
+
+        //argument: _vote_id (u32) the poll
+        // an arbiter who voted on the poll claims their share of the fee released with release_treasury_funds,
+        // once. Fails with NothingToClaim if the fee isn't released yet, the caller didn't vote or already claimed.
+        #[ink(message)]
+        pub fn claim_arbiter_fee(&mut self, _vote_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            let share = self
+                .poll_fee_share
+                .get(_vote_id)
+                .ok_or(Error::NothingToClaim)?;
+            let mut seat = self
+                .arbiter_seats
+                .get((_vote_id, caller))
+                .ok_or(Error::NothingToClaim)?;
+            let has_voted = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .and_then(|x| {
+                    x.arbiters
+                        .get(seat.index as usize)
+                        .map(|arbiter| arbiter.has_voted)
+                })
+                .unwrap_or(false);
+            if !has_voted || seat.fee_claimed {
+                return Err(Error::NothingToClaim);
+            }
+            seat.fee_claimed = true;
+            self.arbiter_seats.insert((_vote_id, caller), &seat);
+            let transfer = ink::env::call::build_call::<Environment>()
+                .call(self.stablecoin_address)
+                .gas_limit(self.gas_limit_for(self.stablecoin_address))
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("transfer"),
+                    ))
+                    .push_arg(caller)
+                    .push_arg(share),
+                )
+                .returns::<Result<()>>()
+                .try_invoke();
+            if !matches!(transfer, Ok(Ok(Ok(())))) {
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(ArbiterFeeClaimed {
+                id: _vote_id,
+                arbiter: caller,
+                amount: share,
+            });
+            Ok(())
+        }
+
+        //read function that returns the seat of an arbiter on the panel of a poll
+        #[ink(message)]
+        pub fn get_arbiter_seat(&self, _vote_id: u32, arbiter: AccountId) -> Option<ArbiterSeat> {
+            self.arbiter_seats.get((_vote_id, arbiter))
+        }
+
+        //read function that returns the fee share each voter of a poll can claim, once released
+        #[ink(message)]
+        pub fn get_fee_share(&self, _vote_id: u32) -> Option<Balance> {
+            self.poll_fee_share.get(_vote_id)
+        }
+
+        //the account taking over a seat gets the seat index of the account it replaces
+        fn move_arbiter_seat(
+            &mut self,
+            _vote_id: u32,
+            replaced: AccountId,
+            account: AccountId,
+            index: u32,
+        ) {
+            self.arbiter_seats.remove((_vote_id, replaced));
+            self.arbiter_seats.insert(
+                (_vote_id, account),
+                &ArbiterSeat {
+                    index,
+                    fee_claimed: false,
+                },
+            );
+        }
+
+        ///announce_force_vote starts the FORCE_VOTE_DELAY the admin has to wait before forcing the vote of a poll,
+        /// once their rights on it are activated. Arbiters can still vote meanwhile, so an override never comes
+        /// as a surprise to a panel that is deliberating.
+        #[ink(message)]
+        pub fn announce_force_vote(&mut self, _vote_id: u32) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_poll_open(_vote_id)?;
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if x.admin_hit_time > self.env().block_timestamp() {
+                return Err(Error::RightsNotActivatedYet);
+            }
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            if self.force_vote_announcements.contains(_vote_id) {
+                return Err(Error::ForceVoteAlreadyAnnounced);
+            }
+            let executable_at = self.env().block_timestamp() + FORCE_VOTE_DELAY;
+            self.force_vote_announcements
+                .insert(_vote_id, &executable_at);
+            self.env().emit_event(ForceVoteAnnounced {
+                id: _vote_id,
+                executable_at,
+            });
+            Ok(())
+        }
+
+        //read function that returns when the announced forced vote of a poll can be executed
+        #[ink(message)]
+        pub fn get_force_vote_announcement(&self, _vote_id: u32) -> Option<Timestamp> {
+            self.force_vote_announcements.get(_vote_id)
+        }
+
+        ///set_quorum_rule changes what the admin needs of a poll before forcing its vote, before anyone voted on it.
+        /// A StakeWeight rule is a fraction from 1/denominator up to the whole stake of the panel, and needs a
+        /// reputation snapshot with some stake in it. Blinded polls can't be stake weighted, their ballots don't
+        /// name the arbiters until the poll is finalized.
+        #[ink(message)]
+        pub fn set_quorum_rule(&mut self, _vote_id: u32, rule: QuorumRule) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_poll_open(_vote_id)?;
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if !x.status.is_open() || x.available_votes > 0 {
+                return Err(Error::VotingFailed);
+            }
+            if let QuorumRule::StakeWeight {
+                numerator,
+                denominator,
+            } = rule
+            {
+                if self.ballot_tokens.contains(_vote_id) {
+                    return Err(Error::BlindedPoll);
+                }
+                if numerator == 0 || numerator > denominator || Self::panel_stake(&x, false) == 0 {
+                    return Err(Error::InvalidQuorumRule);
+                }
+            }
+            self.quorum_rules.insert(_vote_id, &rule);
+            self.env().emit_event(QuorumRuleSet { id: _vote_id, rule });
+            Ok(())
+        }
+
+        //read function that returns the quorum rule of a poll
+        #[ink(message)]
+        pub fn get_quorum_rule(&self, _vote_id: u32) -> QuorumRule {
+            self.quorum_rules
+                .get(_vote_id)
+                .unwrap_or(QuorumRule::HeadCount)
+        }
+
+        ///In case when not all arbiters have voted on a particular proposal, the admin has the liberty of forcing the vote by submitting the
+        /// current decision, accordingly it will either approve the auditor or extend their deadline.
+        /// The forced vote has to be announced with announce_force_vote FORCE_VOTE_DELAY before.
+        #[ink(message)]
+        pub fn force_vote(&mut self, _vote_id: u32) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_poll_open(_vote_id)?;
+            if self.vote_id_to_info.get(_vote_id).unwrap().admin_hit_time
+                > self.env().block_timestamp()
+            {
+                return Err(Error::RightsNotActivatedYet);
+            }
+            let mut x = self.vote_id_to_info.get(_vote_id).unwrap();
+            let escrow = self.escrow_of(_vote_id);
+
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            if !self.quorum_reached(_vote_id, &x) {
+                return Err(Error::QuorumNotReached);
+            }
+            if Self::counted_votes(&x) == 0 {
+                return Err(Error::OnlyAbstentions);
+            }
+            match self.force_vote_announcements.get(_vote_id) {
+                None => return Err(Error::ForceVoteNotAnnounced),
+                Some(executable_at) if executable_at > self.env().block_timestamp() => {
+                    return Err(Error::TimelockNotElapsed)
+                }
+                Some(_) => self.force_vote_announcements.remove(_vote_id),
+            }
+            self.env().emit_event(ForceVoteExecuted { id: _vote_id });
+            if x.decided_deadline > 0 {
+                x.decided_deadline /= Self::counted_votes(&x) as Timestamp;
+                x.decided_haircut /= Self::counted_votes(&x) as Balance;
+                self.clamp_to_caps(escrow, &mut x)?;
+                let result_call = self.escrow_at(escrow).arbiters_extend_deadline(
+                    x.audit_id,
+                    x.decided_deadline + self.env().block_timestamp(),
+                    x.decided_haircut,
+                    self.arbiters_share,
+                );
+                let checked = Self::check_escrow_call(result_call);
+                if checked.is_ok() {
+                    self.record_escrow_call(
+                        _vote_id,
+                        EscrowCall::ArbitersExtendDeadline(
+                            x.decided_deadline + self.env().block_timestamp(),
+                            x.decided_haircut,
+                            self.arbiters_share,
+                        ),
+                    );
+                    self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                    self.vote_id_to_info.insert(_vote_id, &x);
+                    self.env().emit_event(FinalVotePushed {
+                        id: _vote_id,
+                        pusher: self.env().caller(),
+                    });
+                    self.on_poll_finalized(_vote_id, &x);
+                    return Ok(());
+                } else {
+                    return checked;
+                }
+            } else if x.decided_deadline == 0 {
+                let scorecard = self.poll_scorecards.get(_vote_id);
+                let result_call = self
+                    .escrow_at(escrow)
+                    .assess_audit(x.audit_id, true, scorecard);
+                let checked = Self::check_escrow_call(result_call);
+                if checked.is_ok() {
+                    self.record_escrow_call(_vote_id, EscrowCall::AssessAudit(true, scorecard));
+                    self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                    self.vote_id_to_info.insert(_vote_id, &x);
+                    self.env().emit_event(FinalVotePushed {
+                        id: _vote_id,
+                        pusher: self.env().caller(),
+                    });
+                    self.on_poll_finalized(_vote_id, &x);
+                    return Ok(());
+                } else {
+                    return checked;
+                }
+            }
+            return Err(Error::UnAuthorisedCall);
+        }
+
+        //this function can only be called by the admin, it can flush out any extra token,
+        //or the arbiterprovider's share to admin
+        //The calculation of it is manual
+        #[ink(message)]
+        pub fn flush_out_tokens(
+            &mut self,
+            _token_address: AccountId,
+            _value: Balance,
+        ) -> Result<()> {
+            if self.env().caller() == self.admin {
+                let _result_call = ink::env::call::build_call::<Environment>()
+                    .call(_token_address)
+                    .gas_limit(self.gas_limit_for(_token_address))
+                    .transferred_value(0)
+                    .exec_input(
+                        ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                            ink::selector_bytes!("transfer"),
+                        ))
+                        .push_arg(&self.admin)
+                        .push_arg(_value),
+                    )
+                    .returns::<Result<()>>()
+                    .try_invoke();
+                if matches!(_result_call.unwrap().unwrap(), Result::Ok(())) {
+                    return Ok(());
+                } else {
+                    return Err(Error::TransferFailed);
+                }
+            }
+            Err(Error::UnAuthorisedCall)
+        }
+
+        //function to change the haircut for discrepancies, currently it is set to 5 and 15,
+        //if true is passed, it changes minor, otherwise moderate
+        #[ink(message)]
+        pub fn change_haircut_for_discrepancies(
+            &mut self,
+            change_minor: bool,
+            new_haircut: Balance,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if new_haircut > 90 {
+                return Err(Error::ValueTooHigh);
+            }
+            if change_minor {
+                self.haircut_for_minor_discreapancies = new_haircut;
+            } else {
+                self.haircut_for_moderate_discrepancies = new_haircut;
+            }
+            return Ok(());
+        }
+
+        //function to change the time for discrepancies, currently it is set to 5 and 15,
+        //if true is passed, it changes minor, otherwise moderate
+        #[ink(message)]
+        pub fn change_time_extension_for_discrepancies(
+            &mut self,
+            change_minor: bool,
+            new_extension: Timestamp,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if new_extension < 86400000 {
+                return Err(Error::ValueTooLow);
+            }
+            if change_minor {
+                self.time_extension_for_minor_discrepancies = new_extension;
+            } else {
+                self.time_extension_for_moderate_discrepancies = new_extension;
+            }
+            return Ok(());
+        }
+
+        //read function that returns the bounds the escrow of a poll enforces on arbiters_extend_deadline, as
+        //(largest haircut, largest arbiters' share, least extension), None if the escrow doesn't answer
+        #[ink(message)]
+        pub fn get_escrow_extension_bounds(
+            &self,
+            _vote_id: u32,
+        ) -> Option<(Balance, Balance, Timestamp)> {
+            self.extension_bounds_of(self.escrow_of(_vote_id))
+        }
+
+        //function to change the arbitersshare. Default value is 5
+        #[ink(message)]
+        pub fn change_arbiters_share(&mut self, new_share: Balance) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.arbiters_share = new_share;
+            Ok(())
+        }
+
+        //function to change the limits on the number of arbiters in a poll. Default values are 3 and 15,
+        //with odd sized panels required.
+        #[ink(message)]
+        pub fn change_panel_size_limits(
+            &mut self,
+            new_min: u8,
+            new_max: u8,
+            odd_required: bool,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if new_min == 0 {
+                return Err(Error::ValueTooLow);
+            }
+            if new_min > new_max || new_max > MAX_PANEL_SIZE {
+                return Err(Error::ValueTooHigh);
+            }
+            self.min_panel_size = new_min;
+            self.max_panel_size = new_max;
+            self.odd_panel_required = odd_required;
+            Ok(())
+        }
+
+        //function to change how long a seated arbiter has to vote before an alternate can take the seat,
+        //applies to polls created afterwards. Default value is 3 days, 0 disables the substitution.
+        #[ink(message)]
+        pub fn change_arbiter_response_window(&mut self, new_window: Timestamp) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.arbiter_response_window = new_window;
+            Ok(())
+        }
+
+        //function to change the contract that answers `reputation_score(account) -> u64` for the reputation
+        //snapshots of new polls, e.g. the reward token. None stops taking snapshots.
+        #[ink(message)]
+        pub fn set_reputation_registry(&mut self, new_registry: Option<AccountId>) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.reputation_registry = new_registry;
+            Ok(())
+        }
+
+        //function to change what is required of the arbiters on the panels of new polls, and of the ones
+        //activated or invited onto open polls
+        #[ink(message)]
+        pub fn change_panel_diversity(&mut self, new_diversity: PanelDiversity) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if new_diversity.patron_history_window > MAX_PATRON_HISTORY_WINDOW {
+                return Err(Error::ValueTooHigh);
+            }
+            self.panel_diversity = new_diversity;
+            Ok(())
+        }
+
+        //argument: arbiter (AccountId) the arbiter
+        //argument: organization (Option<u32>) the tag of the organization the arbiter belongs to, None removes it
+        // the admin keeps the organization of every arbiter, for the one arbiter per organization requirement.
+        #[ink(message)]
+        pub fn set_arbiter_organization(
+            &mut self,
+            arbiter: AccountId,
+            organization: Option<u32>,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if let Some(tag) = organization {
+                self.arbiter_organizations.insert(arbiter, &tag);
+            } else {
+                self.arbiter_organizations.remove(arbiter);
+            }
+            Ok(())
+        }
+
+        //function to change the reward token that records a badge for every arbiter who voted on a poll, and a
+        //no-show for every one who didn't, when the poll is finalized. The voting contract has to be one of its
+        //minters. None stops recording participation. The account that instantiated the contract, e.g. the
+        //deployer, can set it too while no reward token is set, so that a deployment is wired in one transaction.
+        #[ink(message)]
+        pub fn set_reward_token(&mut self, new_reward_token: Option<AccountId>) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.admin && (caller != self.instantiator || self.reward_token.is_some())
+            {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.reward_token = new_reward_token;
+            Ok(())
+        }
+
+        //argument: new_observer (Option<AccountId>) the contract to notify, None stops the notifications
+        // only the admin can call it. The observer is called with on_poll_created(vote_id, audit_id) when a poll
+        // is opened and on_poll_finalized(vote_id, audit_id) when it is finalized, e.g. the reward token keeps
+        // its reputation records in sync this way. The notifications are best-effort, a failing observer never
+        // blocks a poll. Emits ObserverChanged.
+        #[ink(message)]
+        pub fn set_observer(&mut self, new_observer: Option<AccountId>) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.observer = new_observer;
+            self.env().emit_event(ObserverChanged {
+                observer: new_observer,
+            });
+            Ok(())
+        }
+
+        //function to change the per vote fee and minimum payout of the arbiters, applies to fees released afterwards
+        #[ink(message)]
+        pub fn change_arbiter_fee_config(&mut self, config: ArbiterFeeConfig) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.arbiter_fee_config = config;
+            Ok(())
+        }
+
+        //function to change the account the rounding dust of released fees is sent to, the admin by default
+        #[ink(message)]
+        pub fn change_treasury(&mut self, treasury: AccountId) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.treasury = treasury;
+            Ok(())
+        }
+
+        //function to change how long an invited arbiter has to accept their seat before the admin can invite
+        //someone else to it, applies to invitations made afterwards. Default value is 1 day.
+        #[ink(message)]
+        pub fn change_seat_acceptance_window(&mut self, new_window: Timestamp) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.seat_acceptance_window = new_window;
+            Ok(())
+        }
+
+        //function to change the requirements of a dispute class, they apply to polls created afterwards.
+        //the panel size range can't be empty and the quorum can't be more than the largest panel.
+        #[ink(message)]
+        pub fn change_dispute_class_config(
+            &mut self,
+            class: DisputeClass,
+            config: DisputeClassConfig,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if config.min_panel_size == 0 {
+                return Err(Error::ValueTooLow);
+            }
+            if config.min_panel_size > config.max_panel_size
+                || config.quorum > config.max_panel_size
+                || config.max_panel_size > MAX_PANEL_SIZE
+                || config.fee_bps > MAX_FEE_BPS
+            {
+                return Err(Error::ValueTooHigh);
+            }
+            self.dispute_class_configs.insert(class, &config);
+            Ok(())
+        }
+
+        //argument: result (AuditArbitrationResult) the result deciding the poll with its final vote
+        //argument: action (Option<EscrowAction>) the escrow message to call for it, None for the built-in one
+        // only the admin can call it. A poll whose final vote has the result calls the action on the escrow instead
+        // of the built-in call, with the arguments filled in as laid out, so that the escrow can offer new resolutions
+        // without changing this contract. At most MAX_ACTION_ARGS arguments. Emits ResultActionChanged.
+        #[ink(message)]
+        pub fn set_result_action(
+            &mut self,
+            result: AuditArbitrationResult,
+            action: Option<EscrowAction>,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            match &action {
+                Some(action) if action.args.len() as u32 > MAX_ACTION_ARGS => {
+                    return Err(Error::ValueTooHigh);
+                }
+                Some(action) => {
+                    self.result_actions.insert(result, action);
+                }
+                None => self.result_actions.remove(result),
+            }
+            self.env()
+                .emit_event(ResultActionChanged { result, action });
+            Ok(())
+        }
+
+        //function to change the escrowed values from which disputes are Standard and HighValue
+        #[ink(message)]
+        pub fn change_dispute_class_thresholds(
+            &mut self,
+            standard_from: Balance,
+            high_value_from: Balance,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if standard_from > high_value_from {
+                return Err(Error::ValueTooHigh);
+            }
+            self.dispute_class_thresholds = (standard_from, high_value_from);
+            Ok(())
+        }
+
+        //emergency stop, only the PAUSER can call it. While paused no one can vote, force a vote
+        //or release treasury funds on any poll, reads keep working.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            if self.env().caller() != self.pauser {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.paused = true;
+            self.env().emit_event(Paused {
+                by: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        //lifts the emergency stop, only the PAUSER can call it.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            if self.env().caller() != self.pauser {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.paused = false;
+            self.env().emit_event(Unpaused {
+                by: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll whose panel is suspected to be compromised
+        //argument: frozen (bool) true to freeze the poll, false to unfreeze it
+        // same as pause but for a single poll, only the PAUSER can call it.
+        #[ink(message)]
+        pub fn set_poll_frozen(&mut self, _vote_id: u32, frozen: bool) -> Result<()> {
+            if self.env().caller() != self.pauser {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if frozen {
+                self.frozen_polls.insert(_vote_id, &true);
+            } else {
+                self.frozen_polls.remove(_vote_id);
+            }
+            self.env().emit_event(PollFreezeChanged {
+                id: _vote_id,
+                frozen,
+                by: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll suspected of bribery or of being decided by a sybil panel
+        //argument: reason_hash ([u8; 32]) the hash of the report the quarantine is based on
+        // only the admin or the PAUSER can call it. The poll is Quarantined, it can't be voted on, forced,
+        // finalized, revealed or have its fee released until the quarantine is lifted, unfreezing the poll doesn't
+        // lift it. Emits PollQuarantined and PollStatusChanged.
+        #[ink(message)]
+        pub fn quarantine_poll(&mut self, _vote_id: u32, reason_hash: [u8; 32]) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.admin && caller != self.pauser {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let mut x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if self.quarantined_polls.contains(_vote_id) {
+                return Err(Error::PollQuarantined);
+            }
+            if self.quarantine_list.len() >= MAX_QUARANTINED_POLLS as usize {
+                return Err(Error::TooManyQuarantinedPolls);
+            }
+            self.quarantined_polls.insert(
+                _vote_id,
+                &Quarantine {
+                    reason_hash,
+                    by: caller,
+                    since: self.env().block_timestamp(),
+                    status_before: x.status,
+                },
+            );
+            self.quarantine_list.push(_vote_id);
+            self.set_poll_status(_vote_id, &mut x, PollStatus::Quarantined);
+            self.vote_id_to_info.insert(_vote_id, &x);
+            self.env().emit_event(PollQuarantined {
+                id: _vote_id,
+                reason_hash,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the quarantined poll
+        // only the admin or the PAUSER can call it, the poll goes back to the status it had before the quarantine.
+        // Emits QuarantineLifted and PollStatusChanged.
+        #[ink(message)]
+        pub fn lift_quarantine(&mut self, _vote_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.admin && caller != self.pauser {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let quarantine = self
+                .quarantined_polls
+                .take(_vote_id)
+                .ok_or(Error::PollNotQuarantined)?;
+            self.quarantine_list.retain(|id| *id != _vote_id);
+            if let Some(mut x) = self.vote_id_to_info.get(_vote_id) {
+                self.set_poll_status(_vote_id, &mut x, quarantine.status_before);
+                self.vote_id_to_info.insert(_vote_id, &x);
+            }
+            self.env().emit_event(QuarantineLifted {
+                id: _vote_id,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        //function to hand the PAUSER role to another account, only the admin can call it
+        #[ink(message)]
+        pub fn change_pauser(&mut self, new_pauser: AccountId) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.pauser = new_pauser;
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll
+        //argument: index (u32) the position on the panel of the arbiter whose response deadline lapsed
+        // anyone can call it once the arbiter at index has let their response deadline pass without voting,
+        // the next alternate of the poll takes the seat with a fresh response window. An alternate who lets
+        // it lapse in turn can be replaced the same way, as long as alternates are left.
+        #[ink(message)]
+        pub fn activate_alternate(&mut self, _vote_id: u32, index: u32) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            //the seats of a blinded poll are fixed by its tokens, and whether they voted is hidden
+            if self.ballot_tokens.contains(_vote_id) {
+                return Err(Error::BlindedPoll);
+            }
+            let mut x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            let seat = x
+                .arbiters
+                .get(index as usize)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if seat.has_voted {
+                return Err(Error::VotingFailed);
+            }
+            let replaced = seat.voter_address;
+            let now = self.env().block_timestamp();
+            match self.get_response_deadline(_vote_id, replaced) {
+                Some(deadline) if deadline < now => {}
+                _ => return Err(Error::ResponseDeadlineNotPassed),
+            }
+            if x.alternates.is_empty() {
+                return Err(Error::NoAlternateLeft);
+            }
+            let alternate = x.alternates.remove(0);
+            self.ensure_no_shared_organization(
+                alternate,
+                x.arbiters
+                    .iter()
+                    .filter(|arbiter| arbiter.voter_address != replaced)
+                    .map(|arbiter| arbiter.voter_address),
+            )?;
+            x.arbiters[index as usize] = Arbiter {
+                voter_address: alternate,
+                has_voted: false,
+            };
+            self.remove_pending_poll(replaced, _vote_id);
+            self.add_pending_poll(alternate, _vote_id);
+            self.panel_seats.remove((_vote_id, replaced));
+            self.invite_to_seat(_vote_id, alternate);
+            self.move_arbiter_seat(_vote_id, replaced, alternate, index);
+            self.response_deadlines.remove((_vote_id, replaced));
+            self.response_deadlines
+                .insert((_vote_id, alternate), &(now + x.response_window));
+            self.vote_id_to_info.insert(_vote_id, &x);
+            self.env().emit_event(AlternateActivated {
+                id: _vote_id,
+                replaced,
+                alternate,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll
+        //argument: account (AccountId) the arbiter to invite
+        // only the admin can call it, the account takes the first seat of the panel whose invitation lapsed
+        // without being accepted, and is invited to it with a fresh acceptance window (and response window).
+        // The account can't already be on the panel or among its alternates, its reputation is snapshotted now.
+        #[ink(message)]
+        pub fn invite_arbiter(&mut self, _vote_id: u32, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_poll_open(_vote_id)?;
+            if self.ballot_tokens.contains(_vote_id) {
+                return Err(Error::BlindedPoll);
+            }
+            let mut x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            if x.alternates.contains(&account) || self.arbiter_seats.contains((_vote_id, account)) {
+                return Err(Error::AlreadyOnPanel);
+            }
+            let now = self.env().block_timestamp();
+            let index = x
+                .arbiters
+                .iter()
+                .position(|arbiter| {
+                    matches!(
+                        self.get_seat_status(_vote_id, arbiter.voter_address),
+                        Some(SeatStatus::Invited { accept_by }) if accept_by < now
+                    )
+                })
+                .ok_or(Error::NoLapsedSeat)?;
+            let replaced = x.arbiters[index].voter_address;
+            self.ensure_no_shared_organization(
+                account,
+                x.arbiters
+                    .iter()
+                    .filter(|arbiter| arbiter.voter_address != replaced)
+                    .map(|arbiter| arbiter.voter_address),
+            )?;
+            self.ensure_no_conflict_of_interest(
+                self.escrow_of(_vote_id),
+                x.audit_id,
+                core::iter::once(account),
+            )?;
+            x.arbiters[index] = Arbiter {
+                voter_address: account,
+                has_voted: false,
+            };
+            x.reputation_snapshot
+                .extend(self.snapshot_reputations(core::iter::once(account))?);
+            self.remove_pending_poll(replaced, _vote_id);
+            self.add_pending_poll(account, _vote_id);
+            self.panel_seats.remove((_vote_id, replaced));
+            self.invite_to_seat(_vote_id, account);
+            self.move_arbiter_seat(_vote_id, replaced, account, index as u32);
+            self.response_deadlines.remove((_vote_id, replaced));
+            if x.response_window > 0 {
+                self.response_deadlines
+                    .insert((_vote_id, account), &(now + x.response_window));
+            }
+            self.vote_id_to_info.insert(_vote_id, &x);
+            self.env().emit_event(ArbiterInvited {
+                id: _vote_id,
+                replaced,
+                arbiter: account,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll
+        // an arbiter invited to the panel of the poll accepts the seat, and with it the fee terms of the poll,
+        // only then can they vote. An invitation that lapsed can still be accepted until someone else is invited.
+        #[ink(message)]
+        pub fn accept_panel_seat(&mut self, _vote_id: u32) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            let arbiter = self.env().caller();
+            match self.get_seat_status(_vote_id, arbiter) {
+                Some(SeatStatus::Invited { .. }) => {}
+                Some(SeatStatus::Accepted) => return Err(Error::VotingFailed),
+                None => return Err(Error::UnAuthorisedCall),
+            }
+            self.panel_seats
+                .insert((_vote_id, arbiter), &SeatStatus::Accepted);
+            self.env().emit_event(PanelSeatAccepted {
+                id: _vote_id,
+                arbiter,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll
+        //argument: reasons (Vec<ReasonCode>) the findings the arbiter's vote is based on
+        // an arbiter who accepted their seat, or their active delegate, cites the findings behind their vote while
+        // the poll is active, not on a blinded poll where it would name them. What the panel cited becomes the
+        // ruling_reason of the outcome, each code once. Emits ReasonsCited.
+        #[ink(message)]
+        pub fn cite_reasons(&mut self, _vote_id: u32, reasons: Vec<ReasonCode>) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            if self.ballot_tokens.contains(_vote_id) {
+                return Err(Error::BlindedPoll);
+            }
+            let voter = self.voter_of(_vote_id, self.env().caller());
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            match self.get_seat_status(_vote_id, voter) {
+                Some(SeatStatus::Accepted) => {}
+                Some(SeatStatus::Invited { .. }) => return Err(Error::SeatNotAccepted),
+                None => return Err(Error::UnAuthorisedCall),
+            }
+            let mut cited = self.get_cited_reasons(_vote_id);
+            for reason in &reasons {
+                if !cited.contains(reason) {
+                    cited.push(*reason);
+                }
+            }
+            self.cited_reasons.insert(_vote_id, &cited);
+            self.env().emit_event(ReasonsCited {
+                id: _vote_id,
+                arbiter: voter,
+                reasons,
+            });
+            Ok(())
+        }
+
+        //read function that returns the findings the panel of an active poll cited so far
+        #[ink(message)]
+        pub fn get_cited_reasons(&self, _vote_id: u32) -> Vec<ReasonCode> {
+            self.cited_reasons.get(_vote_id).unwrap_or_default()
+        }
+
+        //read function that returns how many finalized rulings were based on the finding
+        #[ink(message)]
+        pub fn get_ruling_reason_count(&self, reason: ReasonCode) -> u32 {
+            self.ruling_reason_counts.get(reason).unwrap_or_default()
+        }
+
+        //argument: _vote_id (u32) the poll, before any vote was cast on it
+        //argument: tokens (Vec<BallotToken>) one token per seat of the panel, in an order unrelated to the panel
+        // only the admin can call it, it makes the poll blinded: its votes are cast with vote_blinded by whoever
+        // holds the secret of a token, e.g. through a relayer, and the ballots name the pseudonym of the token
+        // instead of the arbiter. The admin hands every arbiter the secret of their token off-chain.
+        // The panel can't change afterwards, and a poll with a StakeWeight quorum rule can't be blinded. Emits
+        // BallotTokensIssued.
+        #[ink(message)]
+        pub fn issue_ballot_tokens(
+            &mut self,
+            _vote_id: u32,
+            tokens: Vec<BallotToken>,
+        ) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_poll_open(_vote_id)?;
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if !x.status.is_open() || x.available_votes > 0 || self.ballot_tokens.contains(_vote_id)
+            {
+                return Err(Error::VotingFailed);
+            }
+            if self.get_quorum_rule(_vote_id) != QuorumRule::HeadCount {
+                return Err(Error::InvalidQuorumRule);
+            }
+            if tokens.len() != x.arbiters.len() {
+                return Err(Error::InvalidBallotToken);
+            }
+            for (i, token) in tokens.iter().enumerate() {
+                if tokens[..i]
+                    .iter()
+                    .any(|other| other.token_hash == token.token_hash)
+                {
+                    return Err(Error::InvalidBallotToken);
+                }
+            }
+            self.ballot_tokens.insert(_vote_id, &tokens);
+            self.env().emit_event(BallotTokensIssued {
+                id: _vote_id,
+                tokens: tokens.len() as u8,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the blinded poll
+        //argument: key ([u8; 32]) ballot_commitment_key_of the poll and the secret of the ballot token
+        //argument: commitment ([u8; 32]) ballot_commitment_of the secret and the vote
+        // anyone can call it, e.g. a relayer. It binds the vote of a ballot token before its secret is sent with
+        // vote_blinded, which only counts the committed vote, so that whoever sees the secret on its way can't
+        // cast another vote with it. The key can only be computed with the secret, and can be committed once.
+        // Emits BallotCommitted.
+        #[ink(message)]
+        pub fn commit_blinded_vote(
+            &mut self,
+            _vote_id: u32,
+            key: [u8; 32],
+            commitment: [u8; 32],
+        ) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            if !self.ballot_tokens.contains(_vote_id) {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            if self.ballot_commitments.contains(key) {
+                return Err(Error::BallotAlreadyCommitted);
+            }
+            self.ballot_commitments.insert(
+                key,
+                &BallotCommitment {
+                    commitment,
+                    committed_at: self.env().block_number(),
+                },
+            );
+            self.env().emit_event(BallotCommitted { id: _vote_id, key });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the blinded poll
+        //argument: secret ([u8; 32]) the secret of an unused ballot token of the poll
+        //argument: _result (AuditArbitrationResult) the vote
+        // counts the vote like vote does, for the seat of the token, which stays hidden until the poll is revealed.
+        // The vote has to be committed with commit_blinded_vote in an earlier block, else BallotNotCommitted.
+        // The ballot and ArbiterVoted name the pseudonym of the token, the token can be used once.
+        #[ink(message)]
+        pub fn vote_blinded(
+            &mut self,
+            _vote_id: u32,
+            secret: [u8; 32],
+            _result: AuditArbitrationResult,
+        ) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            let tokens = self
+                .ballot_tokens
+                .get(_vote_id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if !x.status.is_open() {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            let token_hash = self.ballot_token_of(secret);
+            if !tokens.iter().any(|token| token.token_hash == token_hash) {
+                return Err(Error::InvalidBallotToken);
+            }
+            //the pseudonym of the token votes in place of the arbiter
+            let pseudonym = AccountId::from(token_hash);
+            if self.used_ballot_tokens.contains((_vote_id, pseudonym)) {
+                return Err(Error::VotingFailed);
+            }
+            let key = self.ballot_commitment_key_of(_vote_id, secret);
+            match self.ballot_commitments.get(key) {
+                Some(committed)
+                    if committed.commitment == self.ballot_commitment_of(secret, _result)
+                        && committed.committed_at < self.env().block_number() => {}
+                _ => return Err(Error::BallotNotCommitted),
+            }
+            self.ballot_commitments.remove(key);
+            self.used_ballot_tokens.insert((_vote_id, pseudonym), &true);
+            self.tally_vote(_vote_id, x, None, pseudonym, _result)
+        }
+
+        //argument: _vote_id (u32) the finalized blinded poll
+        //argument: reveals (Vec<BallotReveal>) the arbiter, secret and salt of some of the tokens of the poll
+        // anyone can reveal the tokens of a finalized blinded poll, any number of them per call. The seats whose
+        // token was used are marked as voted, which releases their fee, and the pseudonym to arbiter mapping is
+        // added to the record of the poll. The reveal is complete once every token was revealed, or once
+        // REVEAL_WINDOW has passed since the poll was closed, the tokens not revealed by then are skipped and their
+        // seats don't get a fee. Participation is recorded when it completes, a call after the window with no
+        // reveals completes it. Emits BallotsRevealed.
+        #[ink(message)]
+        pub fn reveal_ballots(&mut self, _vote_id: u32, reveals: Vec<BallotReveal>) -> Result<()> {
+            let tokens = self
+                .ballot_tokens
+                .get(_vote_id)
+                .ok_or(Error::UnAuthorisedCall)?;
+            let mut x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            if !x.status.is_closed() {
+                return Err(Error::PollNotClosed);
+            }
+            if self.revealed_polls.contains(_vote_id) {
+                return Err(Error::BallotAlreadyRevealed);
+            }
+            let window_passed = self.is_reveal_window_passed(_vote_id);
+            if reveals.is_empty() && !window_passed {
+                return Err(Error::InvalidBallotToken);
+            }
+            let mut revealed_voters = Vec::new();
+            for (i, reveal) in reveals.iter().enumerate() {
+                let token_hash = self.ballot_token_of(reveal.secret);
+                let link = self.ballot_link_of(reveal.arbiter, reveal.secret, reveal.salt);
+                let index = self
+                    .arbiter_seats
+                    .get((_vote_id, reveal.arbiter))
+                    .map(|seat| seat.index as usize)
+                    .ok_or(Error::InvalidBallotToken)?;
+                if !tokens
+                    .iter()
+                    .any(|token| token.token_hash == token_hash && token.link == link)
+                {
+                    return Err(Error::InvalidBallotToken);
+                }
+                let pseudonym = AccountId::from(token_hash);
+                if self.revealed_ballot_tokens.contains((_vote_id, pseudonym))
+                    || reveals[..i]
+                        .iter()
+                        .any(|other| other.arbiter == reveal.arbiter)
+                {
+                    return Err(Error::BallotAlreadyRevealed);
+                }
+                if self.used_ballot_tokens.contains((_vote_id, pseudonym)) {
+                    x.arbiters[index].has_voted = true;
+                    revealed_voters.push((pseudonym, reveal.arbiter));
+                }
+            }
+            for reveal in &reveals {
+                let pseudonym = AccountId::from(self.ballot_token_of(reveal.secret));
+                self.revealed_ballot_tokens
+                    .insert((_vote_id, pseudonym), &true);
+            }
+            self.vote_id_to_info.insert(_vote_id, &x);
+            let mut record = self.get_poll_record(_vote_id);
+            record
+                .revealed_voters
+                .extend(revealed_voters.iter().copied());
+            self.vote_id_to_record.insert(_vote_id, &record);
+            let complete = window_passed
+                || tokens.iter().all(|token| {
+                    self.revealed_ballot_tokens
+                        .contains((_vote_id, AccountId::from(token.token_hash)))
+                });
+            if complete {
+                self.revealed_polls.insert(_vote_id, &true);
+            }
+            self.env().emit_event(BallotsRevealed {
+                id: _vote_id,
+                revealed_voters,
+                complete,
+            });
+            if complete {
+                self.record_participation(_vote_id, &x);
+            }
+            Ok(())
+        }
+
+        //read function that returns the ballot tokens of a blinded poll, None if the poll isn't blinded
+        #[ink(message)]
+        pub fn get_ballot_tokens(&self, _vote_id: u32) -> Option<Vec<BallotToken>> {
+            self.ballot_tokens.get(_vote_id)
+        }
+
+        //read function that returns the token_hash of a BallotToken for its secret, the blake2x256 hash of it
+        #[ink(message)]
+        pub fn ballot_token_of(&self, secret: [u8; 32]) -> [u8; 32] {
+            Self::hash_of(&secret)
+        }
+
+        //read function that returns the key a vote of the ballot token with the secret is committed under on the
+        //poll, the blake2x256 hash of the SCALE encoded vote ID and secret, so keys of different polls differ
+        #[ink(message)]
+        pub fn ballot_commitment_key_of(&self, _vote_id: u32, secret: [u8; 32]) -> [u8; 32] {
+            Self::hash_of(&(_vote_id, secret))
+        }
+
+        //read function that returns the commitment of a vote of the ballot token with the secret, the blake2x256
+        //hash of the SCALE encoded secret and vote
+        #[ink(message)]
+        pub fn ballot_commitment_of(
+            &self,
+            secret: [u8; 32],
+            _result: AuditArbitrationResult,
+        ) -> [u8; 32] {
+            Self::hash_of(&(secret, _result))
+        }
+
+        //read function that returns the link of a BallotToken, the blake2x256 hash of the SCALE encoded
+        //arbiter, secret and salt
+        #[ink(message)]
+        pub fn ballot_link_of(
+            &self,
+            arbiter: AccountId,
+            secret: [u8; 32],
+            salt: [u8; 32],
+        ) -> [u8; 32] {
+            Self::hash_of(&(arbiter, secret, salt))
+        }
+
+        //argument: _vote_id (u32) the poll
+        //argument: delegate (AccountId) the account that may cast the caller's vote, it can't be on the panel
+        // an arbiter who accepted their seat and hasn't voted lets someone else vote for them, e.g. while they
+        // are unavailable. The delegation expires at the response deadline of the seat or when the poll is closed,
+        // and the arbiter can still vote themselves. It replaces the previous delegation. Emits VoteDelegated.
+        #[ink(message)]
+        pub fn delegate_vote(&mut self, _vote_id: u32, delegate: AccountId) -> Result<()> {
+            self.ensure_poll_open(_vote_id)?;
+            let arbiter = self.env().caller();
+            if self.get_seat_status(_vote_id, arbiter) != Some(SeatStatus::Accepted) {
+                return Err(Error::SeatNotAccepted);
+            }
+            let x = self
+                .vote_id_to_info
+                .get(_vote_id)
+                .ok_or(Error::PollNotFound)?;
+            let seat = self
+                .arbiter_seats
+                .get((_vote_id, arbiter))
+                .ok_or(Error::UnAuthorisedCall)?;
+            if !x.status.is_open() || x.arbiters[seat.index as usize].has_voted {
+                return Err(Error::VotingFailed);
+            }
+            if delegate == arbiter
+                || self.arbiter_seats.contains((_vote_id, delegate))
+                || self
+                    .delegators
+                    .get((_vote_id, delegate))
+                    .is_some_and(|other| other != arbiter)
+            {
+                return Err(Error::InvalidDelegate);
+            }
+            self.remove_delegation(_vote_id, arbiter);
+            let mut delegated = self.poll_delegations.get(_vote_id).unwrap_or_default();
+            delegated.push(arbiter);
+            self.poll_delegations.insert(_vote_id, &delegated);
+            let expires_at = self.get_response_deadline(_vote_id, arbiter);
+            self.delegations.insert(
+                (_vote_id, arbiter),
+                &Delegation {
+                    arbiter,
+                    delegate,
+                    since: self.env().block_timestamp(),
+                    expires_at,
+                },
+            );
+            self.delegators.insert((_vote_id, delegate), &arbiter);
+            self.env().emit_event(VoteDelegated {
+                id: _vote_id,
+                arbiter,
+                delegate,
+                expires_at,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll
+        // the arbiter takes back the delegation of their vote, only before the delegate voted with it.
+        // Emits DelegationRevoked.
+        #[ink(message)]
+        pub fn revoke_delegation(&mut self, _vote_id: u32) -> Result<()> {
+            let arbiter = self.env().caller();
+            if !self.delegations.contains((_vote_id, arbiter)) {
+                return Err(Error::NoDelegation);
+            }
+            if !self.is_delegation_active(_vote_id, arbiter) {
+                return Err(Error::DelegationAlreadyUsed);
+            }
+            let delegation = self
+                .remove_delegation(_vote_id, arbiter)
+                .ok_or(Error::NoDelegation)?;
+            self.env().emit_event(DelegationRevoked {
+                id: _vote_id,
+                arbiter,
+                delegate: delegation.delegate,
+            });
+            Ok(())
+        }
+
+        //argument: _vote_id (u32) the poll
+        // anyone can remove the delegations of the poll that can't be used anymore, because they expired, the
+        // poll was closed or the arbiter voted or lost their seat. Emits DelegationExpired for every one of them
+        // and returns how many were removed.
+        #[ink(message)]
+        pub fn prune_expired_delegations(&mut self, _vote_id: u32) -> Result<u32> {
+            let mut pruned = 0;
+            for arbiter in self.poll_delegations.get(_vote_id).unwrap_or_default() {
+                if self.is_delegation_active(_vote_id, arbiter) {
+                    continue;
+                }
+                if let Some(delegation) = self.remove_delegation(_vote_id, arbiter) {
+                    self.env().emit_event(DelegationExpired {
+                        id: _vote_id,
+                        arbiter,
+                        delegate: delegation.delegate,
+                    });
+                    pruned += 1;
+                }
+            }
+            Ok(pruned)
+        }
+
+        //read function that returns the delegation of the arbiter on the poll, active or not
+        #[ink(message)]
+        pub fn get_delegation(&self, _vote_id: u32, arbiter: AccountId) -> Option<Delegation> {
+            self.delegations.get((_vote_id, arbiter))
+        }
+
+        //read function that returns the delegations of the poll that can still be used, in the order they were made
+        #[ink(message)]
+        pub fn get_active_delegations(&self, _vote_id: u32) -> Vec<Delegation> {
+            self.poll_delegations
+                .get(_vote_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|arbiter| self.is_delegation_active(_vote_id, *arbiter))
+                .filter_map(|arbiter| self.delegations.get((_vote_id, arbiter)))
+                .collect()
+        }
+
+        //argument: callee (AccountId) the contract the voting contract calls, e.g. the escrow or the stablecoin
+        #[ink(message)]
+        pub fn get_call_config(&self, callee: AccountId) -> CallConfig {
+            let configured = self.callee_gas_limits.get(callee);
+            CallConfig {
+                callee,
+                gas_limit: configured.unwrap_or(self.default_gas_limit_for(callee)),
+                is_default: configured.is_none(),
+            }
+        }
+
+        //argument: callee (AccountId) the contract the limit applies to
+        //argument: gas_limit (Option<u64>) the gas forwarded on every call to it, None to go back to the default
+        // only the admin can tune the limits, e.g. when an upgraded escrow becomes more expensive to call.
+        #[ink(message)]
+        pub fn set_gas_limit(&mut self, callee: AccountId, gas_limit: Option<u64>) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if let Some(gas_limit) = gas_limit {
+                self.callee_gas_limits.insert(callee, &gas_limit);
+            } else {
+                self.callee_gas_limits.remove(callee);
+            }
+            Ok(())
+        }
+
+        //argument: new_escrow (AccountId) the escrow the voting contract should follow after an upgrade
+        // first step of changing the escrow, only the admin can propose it, and it can be applied
+        // with apply_escrow_address after ESCROW_CHANGE_DELAY. Proposing again replaces the pending one.
+        #[ink(message)]
+        pub fn set_escrow_address(&mut self, new_escrow: AccountId) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.ensure_compatible_escrow(new_escrow)?;
+            let executable_at = self.env().block_timestamp() + ESCROW_CHANGE_DELAY;
+            self.pending_escrow_address = Some((new_escrow, executable_at));
+            self.env().emit_event(EscrowAddressChangeProposed {
+                new_escrow,
+                executable_at,
+            });
+            Ok(())
+        }
+
+        // second step of changing the escrow, polls that were already open keep resolving
+        // on the escrow they were created for.
+        #[ink(message)]
+        pub fn apply_escrow_address(&mut self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let (new_escrow, executable_at) = match self.pending_escrow_address {
+                Some(pending) => pending,
+                None => return Err(Error::NoPendingChange),
+            };
+            if self.env().block_timestamp() < executable_at {
+                return Err(Error::TimelockNotElapsed);
+            }
+            let old_escrow = self.escrow_address;
+            self.escrow_address = new_escrow;
+            self.pending_escrow_address = None;
+            self.env().emit_event(EscrowAddressChanged {
+                old_escrow,
+                new_escrow,
+            });
+            Ok(())
+        }
+
+        //drops the pending escrow address change, if there is one
+        #[ink(message)]
+        pub fn cancel_escrow_address_change(&mut self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::UnAuthorisedCall);
+            }
+            self.pending_escrow_address = None;
+            Ok(())
+        }
+
+        //requirements of the dispute classes until the admin configures them, small disputes can be settled by
+        //the admin without ballots, bigger ones need larger panels and some of it to have voted.
+        //the fee rate goes down as the value goes up, 5%, 3% and 2%
+        fn default_class_config(class: DisputeClass) -> DisputeClassConfig {
+            let (min_panel_size, max_panel_size, quorum, max_appeals, fee_bps) = match class {
+                DisputeClass::Small => (3, 5, 0, 1, 500),
+                DisputeClass::Standard => (3, 9, 1, 2, 300),
+                DisputeClass::HighValue => (5, 15, 3, 2, 200),
+            };
+            DisputeClassConfig {
+                min_panel_size,
+                max_panel_size,
+                quorum,
+                fee_budget: Balance::MAX,
+                max_appeals,
+                fee_bps,
+            }
+        }
+
+        //queries the escrow for the value that was locked for the audit
+        fn escrowed_value_of(&self, escrow: AccountId, audit_id: u32) -> Result<Balance> {
+            self.escrow_at(escrow)
+                .original_value(audit_id)
+                .map_err(|_| Error::EscrowQueryFailed)
+        }
+
+        //fails with IncompatibleEscrow unless the escrow reports the SUPPORTED_ESCROW_VERSION, so that no poll
+        //is opened for or pushed to an escrow whose messages this contract doesn't know
+        fn ensure_compatible_escrow(&self, escrow: AccountId) -> Result<()> {
+            if self.escrow_version_of(escrow) != Some(SUPPORTED_ESCROW_VERSION) {
+                return Err(Error::IncompatibleEscrow);
+            }
+            Ok(())
+        }
+
+        //queries the protocol version of the escrow, None if it doesn't answer
+        fn escrow_version_of(&self, escrow: AccountId) -> Option<u32> {
+            self.escrow_at(escrow).version().ok()
+        }
+
+        //queries the escrow for the bounds of arbiters_extend_deadline, None if it doesn't answer
+        fn extension_bounds_of(&self, escrow: AccountId) -> Option<(Balance, Balance, Timestamp)> {
+            self.escrow_at(escrow).extension_bounds().ok()
+        }
+
+        //fails if one_per_organization is required and the arbiter shares an organization with one of the others
+        fn ensure_no_shared_organization(
+            &self,
+            arbiter: AccountId,
+            mut others: impl Iterator<Item = AccountId>,
+        ) -> Result<()> {
+            if !self.panel_diversity.one_per_organization {
+                return Ok(());
+            }
+            let Some(organization) = self.arbiter_organizations.get(arbiter) else {
+                return Ok(());
+            };
+            if others.any(|other| self.arbiter_organizations.get(other) == Some(organization)) {
+                return Err(Error::SameOrganizationOnPanel);
+            }
+            Ok(())
+        }
+
+        //fails if one of the accounts audited one of the latest audits of the patron of the audit
+        fn ensure_no_conflict_of_interest(
+            &self,
+            escrow: AccountId,
+            audit_id: u32,
+            mut accounts: impl Iterator<Item = AccountId>,
+        ) -> Result<()> {
+            let window = self.panel_diversity.patron_history_window;
+            if window == 0 {
+                return Ok(());
+            }
+            let recent_auditors = self.recent_auditors_of(escrow, audit_id, window)?;
+            if accounts.any(|account| recent_auditors.contains(&account)) {
+                return Err(Error::ConflictOfInterest);
+            }
+            Ok(())
+        }
+
+        //queries the escrow for the auditors the patron of the audit assigned their latest audits to
+        fn recent_auditors_of(
+            &self,
+            escrow: AccountId,
+            audit_id: u32,
+            count: u32,
+        ) -> Result<Vec<AccountId>> {
+            self.escrow_at(escrow)
+                .patron_recent_auditors(audit_id, count)
+                .map_err(|_| Error::EscrowQueryFailed)
+        }
+
+        //reads the reputation of every account from the reputation registry, empty if there is none
+        fn snapshot_reputations(
+            &self,
+            accounts: impl Iterator<Item = AccountId>,
+        ) -> Result<Vec<(AccountId, u64)>> {
+            let Some(registry) = self.reputation_registry else {
+                return Ok(Vec::new());
+            };
+            accounts
+                .map(|account| Ok((account, self.reputation_of(registry, account)?)))
+                .collect()
+        }
+
+        //queries the registry for the reputation of the account
+        fn reputation_of(&self, registry: AccountId, account: AccountId) -> Result<u64> {
+            calls::RewardToken::new(registry, self.gas_limit_for(registry))
+                .reputation_score(account)
+                .map_err(|_| Error::ReputationQueryFailed)
+        }
+
+        //the escrow at the account, called with the gas limit configured for it
+        fn escrow_at(&self, escrow: AccountId) -> calls::Escrow {
+            calls::Escrow::new(escrow, self.gas_limit_for(escrow))
+        }
+
+        //turns the outcome of a call to the escrow into EscrowCallFailed, whose code is the index of the escrow's
+        //Error variant if the escrow returned an error, or ESCROW_DISPATCH_FAILED / ESCROW_CALL_FAILED
+        fn check_escrow_call(result: calls::Result<()>) -> Result<()> {
+            match result {
+                Ok(()) => Ok(()),
+                Err(calls::CallError::Rejected(code)) => Err(Error::EscrowCallFailed { code }),
+                Err(calls::CallError::NotDispatched) => Err(Error::EscrowCallFailed {
+                    code: ESCROW_DISPATCH_FAILED,
+                }),
+                Err(calls::CallError::CallFailed) => Err(Error::EscrowCallFailed {
+                    code: ESCROW_CALL_FAILED,
+                }),
+            }
+        }
+
+        //stores the outcome of a poll from the escrow call that closed it, emits its final TallyCheckpoint and
+        //records the participation of its panel on the reward token
+        fn on_poll_finalized(&mut self, vote_id: u32, x: &VoteInfo) {
+            let kind = match self.get_poll_record(vote_id).escrow_call {
+                Some(EscrowCall::AssessAudit(false, _)) => OutcomeKind::Rejected,
+                Some(EscrowCall::ArbitersExtendDeadline(..)) => OutcomeKind::Extended,
+                Some(EscrowCall::Custom(..)) => OutcomeKind::Custom,
+                _ => OutcomeKind::Approved,
+            };
+            let (haircut_bps, extension_ms) = if kind == OutcomeKind::Extended {
+                ((x.decided_haircut * 100) as u32, x.decided_deadline)
+            } else {
+                (0, 0)
+            };
+            let audit = (self.escrow_of(vote_id), x.audit_id);
+            let outcomes = self.get_audit_outcome_count(audit.0, audit.1);
+            self.audit_outcome_counts
+                .insert(audit, &outcomes.saturating_add(1));
+            self.last_audit_outcomes.insert(audit, &vote_id);
+            let ruling_reason = self.cited_reasons.take(vote_id).unwrap_or_default();
+            for reason in &ruling_reason {
+                let count = self.get_ruling_reason_count(*reason);
+                self.ruling_reason_counts.insert(reason, &(count + 1));
+            }
+            self.vote_id_to_outcome.insert(
+                vote_id,
+                &PollOutcome {
+                    kind,
+                    haircut_bps,
+                    extension_ms,
+                    executed_at: self.env().block_timestamp(),
+                    escrow_result: Ok(()),
+                    ruling_reason: ruling_reason.clone(),
+                },
+            );
+            self.env().emit_event(RulingReasoned {
+                id: vote_id,
+                ruling_reason,
+            });
+            self.emit_tally_checkpoint(vote_id, x, true);
+            self.notify_observer(
+                ink::selector_bytes!("on_poll_finalized"),
+                vote_id,
+                x.audit_id,
+            );
+            //who voted on a blinded poll is only known once it is revealed, see reveal_ballots
+            if !self.ballot_tokens.contains(vote_id) {
+                self.record_participation(vote_id, x);
+            }
+        }
+
+        //records the participation of the panel of a finalized poll on the reward token, if there is one
+        fn record_participation(&mut self, vote_id: u32, x: &VoteInfo) {
+            let Some(reward_token) = self.reward_token else {
+                return;
+            };
+            let (mut badges, mut no_shows, mut failed) = (0u8, 0u8, 0u8);
+            //a refused record doesn't undo the outcome of the poll, it is only counted in the event
+            for arbiter in &x.arbiters {
+                if !self.record_arbitration_on(reward_token, arbiter, vote_id, x.audit_id) {
+                    failed += 1;
+                } else if arbiter.has_voted {
+                    badges += 1;
+                } else {
+                    no_shows += 1;
+                }
+            }
+            self.env().emit_event(ParticipationRecorded {
+                id: vote_id,
+                badges,
+                no_shows,
+                failed,
+            });
+        }
+
+        //asks the reward token to record whether the arbiter voted on the poll, returns whether it did
+        fn record_arbitration_on(
+            &self,
+            reward_token: AccountId,
+            arbiter: &Arbiter,
+            vote_id: u32,
+            audit_id: u32,
+        ) -> bool {
+            calls::RewardToken::new(reward_token, self.gas_limit_for(reward_token))
+                .record_arbitration(arbiter.voter_address, vote_id, audit_id, arbiter.has_voted)
+                .is_ok()
+        }
+
+        //calls the observer with the poll, if one is configured. Its result is ignored, the observer is only told
+        //about polls and can't refuse them
+        fn notify_observer(&self, selector: [u8; 4], vote_id: u32, audit_id: u32) {
+            let Some(observer) = self.observer else {
+                return;
+            };
+            let _ = calls::Observer::new(observer, self.gas_limit_for(observer))
+                .notify(selector, vote_id, audit_id);
+        }
+
+        //emits the TallyCheckpoint of a poll, the decided haircut and deadline are running sums until the poll is final
+        fn emit_tally_checkpoint(&self, vote_id: u32, x: &VoteInfo, is_final: bool) {
+            let counted = Self::counted_votes(x).max(1);
+            let (running_haircut, running_extension) = if is_final {
+                (x.decided_haircut, x.decided_deadline)
+            } else {
+                (
+                    x.decided_haircut / counted as Balance,
+                    x.decided_deadline / counted as Timestamp,
+                )
+            };
+            self.env().emit_event(TallyCheckpoint {
+                vote_id,
+                votes_cast: x.available_votes,
+                running_haircut,
+                running_extension,
+                is_final,
+            });
+        }
+
+        //the ballots of a poll that count toward its averaged outcome, abstentions only count toward the quorum
+        fn counted_votes(x: &VoteInfo) -> u8 {
+            x.available_votes - x.abstentions
+        }
+
+        //whether the ballots cast on a poll meet its quorum rule, abstentions included
+        fn quorum_reached(&self, vote_id: u32, x: &VoteInfo) -> bool {
+            match self.get_quorum_rule(vote_id) {
+                QuorumRule::HeadCount => x.available_votes >= x.class_config.quorum,
+                QuorumRule::StakeWeight {
+                    numerator,
+                    denominator,
+                } => {
+                    Self::panel_stake(x, true).saturating_mul(denominator as u128)
+                        >= Self::panel_stake(x, false).saturating_mul(numerator as u128)
+                }
+            }
+        }
+
+        //the snapshotted reputation of the panel of a poll, or of the arbiters who voted on it, an arbiter
+        //without a snapshot weighs nothing
+        fn panel_stake(x: &VoteInfo, voted_only: bool) -> u128 {
+            x.arbiters
+                .iter()
+                .filter(|arbiter| !voted_only || arbiter.has_voted)
+                .filter_map(|arbiter| {
+                    x.reputation_snapshot
+                        .iter()
+                        .find(|(account, _)| *account == arbiter.voter_address)
+                })
+                .map(|(_, reputation)| *reputation as u128)
+                .sum()
+        }
+
+        //makes the given call on the escrow for the audit of a poll
+        fn push_escrow_call(
+            &self,
+            escrow: AccountId,
+            audit_id: u32,
+            call: &EscrowCall,
+        ) -> Result<()> {
+            let (selector, args) = Self::call_data_of(audit_id, call);
+            Self::check_escrow_call(self.escrow_at(escrow).call(selector, &args))
+        }
+
+        //the selector and SCALE encoded arguments of an escrow call for the audit of a poll, as they are sent
+        fn call_data_of(audit_id: u32, call: &EscrowCall) -> ([u8; 4], Vec<u8>) {
+            match call {
+                EscrowCall::ArbitersExtendDeadline(new_deadline, haircut, arbiters_share) => (
+                    ink::selector_bytes!("arbiters_extend_deadline"),
+                    scale::Encode::encode(&(audit_id, new_deadline, haircut, arbiters_share)),
+                ),
+                EscrowCall::AssessAudit(answer, scorecard) => (
+                    ink::selector_bytes!("assess_audit_with_scorecard"),
+                    scale::Encode::encode(&(audit_id, answer, scorecard)),
+                ),
+                EscrowCall::Custom(selector, args) => (*selector, args.clone()),
+            }
+        }
+
+        //counts the ballot of voter, from the seat at index of the panel or from a blinded ballot token, and
+        //finalizes the poll through the escrow if it is the last one
+        fn tally_vote(
+            &mut self,
+            _vote_id: u32,
+            mut x: VoteInfo,
+            index: Option<usize>,
+            voter: AccountId,
+            _result: AuditArbitrationResult,
+        ) -> Result<()> {
+            let escrow = self.escrow_of(_vote_id);
+            self.record_ballot(_vote_id, voter, _result);
+            //case when this is the last vote to be done... submit thing..
+            if x.available_votes + 1 == x.arbiters.len() as u8 {
+                //a result the admin registered an escrow action for skips the built-in calls
+                if let Some(action) = self.result_actions.get(_result) {
+                    return self.finalize_with_action(_vote_id, index, voter, x, _result, &action);
+                }
+                match _result {
+                    AuditArbitrationResult::NoDiscrepancies => {
+                        if x.decided_deadline > 0 {
+                            x.decided_deadline /= Self::counted_votes(&x) as Timestamp + 1;
+                            x.decided_haircut /= Self::counted_votes(&x) as Balance + 1;
+                            self.clamp_to_caps(escrow, &mut x)?;
+
+                            let result_call = self.escrow_at(escrow).arbiters_extend_deadline(
+                                x.audit_id,
+                                x.decided_deadline + self.env().block_timestamp(),
+                                x.decided_haircut,
+                                self.arbiters_share,
+                            );
+                            let checked = Self::check_escrow_call(result_call);
+                            if checked.is_ok() {
+                                self.record_escrow_call(
+                                    _vote_id,
+                                    EscrowCall::ArbitersExtendDeadline(
+                                        x.decided_deadline + self.env().block_timestamp(),
+                                        x.decided_haircut,
+                                        self.arbiters_share,
+                                    ),
+                                );
+                                self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                                x.available_votes += 1;
+                                Self::mark_voted(&mut x, index);
+                                self.vote_id_to_info.insert(_vote_id, &x);
+                                self.env().emit_event(ArbiterVoted {
+                                    id: _vote_id,
+                                    voter,
+                                    vote_type: Some(_result),
+                                });
+                                self.env().emit_event(FinalVotePushed {
+                                    id: _vote_id,
+                                    pusher: self.env().caller(),
+                                });
+                                self.on_poll_finalized(_vote_id, &x);
+                                Ok(())
+                            } else {
+                                checked
+                            }
+                        } else {
+                            let scorecard = self.poll_scorecards.get(_vote_id);
+                            let result_call = self
+                                .escrow_at(escrow)
+                                .assess_audit(x.audit_id, true, scorecard);
+                            let checked = Self::check_escrow_call(result_call);
+                            if checked.is_ok() {
+                                self.record_escrow_call(
+                                    _vote_id,
+                                    EscrowCall::AssessAudit(true, scorecard),
+                                );
+                                x.available_votes += 1;
+                                Self::mark_voted(&mut x, index);
+                                self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                                self.vote_id_to_info.insert(_vote_id, &x);
+                                self.env().emit_event(ArbiterVoted {
+                                    id: _vote_id,
+                                    voter,
+                                    vote_type: Some(_result),
+                                });
+                                self.env().emit_event(FinalVotePushed {
+                                    id: _vote_id,
+                                    pusher: self.env().caller(),
+                                });
+                                self.on_poll_finalized(_vote_id, &x);
+                                Ok(())
+                            } else {
+                                checked
+                            }
+                        }
+                    }
+                    AuditArbitrationResult::MinorDiscrepancies => {
+                        //add 7 days to the deadline extension.
+                        x.decided_deadline = (x.decided_deadline
+                            + self.time_extension_for_minor_discrepancies)
+                            / (Self::counted_votes(&x) as Timestamp + 1);
+                        x.decided_haircut = (x.decided_haircut
+                            + self.haircut_for_minor_discreapancies)
+                            / (Self::counted_votes(&x) as Balance + 1);
+                        self.clamp_to_caps(escrow, &mut x)?;
+                        let result_call = self.escrow_at(escrow).arbiters_extend_deadline(
+                            x.audit_id,
+                            x.decided_deadline + self.env().block_timestamp(),
+                            x.decided_haircut,
+                            self.arbiters_share,
+                        );
+                        let checked = Self::check_escrow_call(result_call);
+                        if checked.is_ok() {
+                            self.record_escrow_call(
+                                _vote_id,
+                                EscrowCall::ArbitersExtendDeadline(
+                                    x.decided_deadline + self.env().block_timestamp(),
+                                    x.decided_haircut,
+                                    self.arbiters_share,
+                                ),
+                            );
+                            x.available_votes += 1;
+                            Self::mark_voted(&mut x, index);
+                            self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                            self.vote_id_to_info.insert(_vote_id, &x);
+                            self.env().emit_event(ArbiterVoted {
+                                id: _vote_id,
+                                voter,
+                                vote_type: Some(_result),
+                            });
+                            self.env().emit_event(FinalVotePushed {
+                                id: _vote_id,
+                                pusher: self.env().caller(),
+                            });
+                            self.on_poll_finalized(_vote_id, &x);
+                            Ok(())
+                        } else {
+                            checked
+                        }
+                    }
+                    AuditArbitrationResult::ModerateDiscrepancies => {
+                        //add 15 days to the deadline extension.
+                        x.decided_deadline = (x.decided_deadline
+                            + self.time_extension_for_moderate_discrepancies)
+                            / (Self::counted_votes(&x) as Timestamp + 1);
+                        x.decided_haircut = (x.decided_haircut
+                            + self.haircut_for_moderate_discrepancies)
+                            / (Self::counted_votes(&x) as Balance + 1);
+                        self.clamp_to_caps(escrow, &mut x)?;
+                        let result_call = self.escrow_at(escrow).arbiters_extend_deadline(
+                            x.audit_id,
+                            x.decided_deadline + self.env().block_timestamp(),
+                            x.decided_haircut,
+                            self.arbiters_share,
+                        );
+                        let checked = Self::check_escrow_call(result_call);
+                        if checked.is_ok() {
+                            self.record_escrow_call(
+                                _vote_id,
+                                EscrowCall::ArbitersExtendDeadline(
+                                    x.decided_deadline + self.env().block_timestamp(),
+                                    x.decided_haircut,
+                                    self.arbiters_share,
+                                ),
+                            );
+                            x.available_votes += 1;
+                            Self::mark_voted(&mut x, index);
+                            self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                            self.vote_id_to_info.insert(_vote_id, &x);
+                            self.env().emit_event(ArbiterVoted {
+                                id: _vote_id,
+                                voter,
+                                vote_type: Some(_result),
+                            });
+                            self.env().emit_event(FinalVotePushed {
+                                id: _vote_id,
+                                pusher: self.env().caller(),
+                            });
+                            self.on_poll_finalized(_vote_id, &x);
+                            Ok(())
+                        } else {
+                            checked
+                        }
+                    }
+                    AuditArbitrationResult::Reject => {
+                        //call the function that rejects the audit report.
+                        let scorecard = self.poll_scorecards.get(_vote_id);
+                        let result_call = self
+                            .escrow_at(escrow)
+                            .assess_audit(x.audit_id, false, scorecard);
+                        let checked = Self::check_escrow_call(result_call);
+                        if checked.is_ok() {
+                            self.record_escrow_call(
+                                _vote_id,
+                                EscrowCall::AssessAudit(false, scorecard),
+                            );
+                            x.available_votes += 1;
+                            Self::mark_voted(&mut x, index);
+                            self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                            self.vote_id_to_info.insert(_vote_id, &x);
+                            self.env().emit_event(ArbiterVoted {
+                                id: _vote_id,
+                                voter,
+                                vote_type: Some(_result),
+                            });
+                            self.env().emit_event(FinalVotePushed {
+                                id: _vote_id,
+                                pusher: self.env().caller(),
+                            });
+                            self.on_poll_finalized(_vote_id, &x);
+                            Ok(())
+                        } else {
+                            checked
+                        }
+                    }
+                    AuditArbitrationResult::Abstain => {
+                        //the outcome is averaged over the arbiters who didn't abstain
+                        if Self::counted_votes(&x) == 0 {
+                            return Err(Error::OnlyAbstentions);
+                        }
+                        x.decided_deadline /= Self::counted_votes(&x) as Timestamp;
+                        x.decided_haircut /= Self::counted_votes(&x) as Balance;
+                        self.clamp_to_caps(escrow, &mut x)?;
+                        let call = if x.decided_deadline > 0 {
+                            EscrowCall::ArbitersExtendDeadline(
+                                x.decided_deadline + self.env().block_timestamp(),
+                                x.decided_haircut,
+                                self.arbiters_share,
+                            )
+                        } else {
+                            EscrowCall::AssessAudit(true, self.poll_scorecards.get(_vote_id))
+                        };
+                        self.push_escrow_call(escrow, x.audit_id, &call)?;
+                        self.record_escrow_call(_vote_id, call);
+                        x.available_votes += 1;
+                        x.abstentions += 1;
+                        Self::mark_voted(&mut x, index);
+                        self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                        self.vote_id_to_info.insert(_vote_id, &x);
+                        self.env().emit_event(ArbiterVoted {
+                            id: _vote_id,
+                            voter,
+                            vote_type: Some(_result),
+                        });
+                        self.env().emit_event(FinalVotePushed {
+                            id: _vote_id,
+                            pusher: self.env().caller(),
+                        });
+                        self.on_poll_finalized(_vote_id, &x);
+                        Ok(())
+                    }
+                }
+            } else {
+                match _result {
+                    AuditArbitrationResult::NoDiscrepancies => {
+                        x.available_votes += 1;
+                        Self::mark_voted(&mut x, index);
+                        self.vote_id_to_info.insert(_vote_id, &x);
+                        self.env().emit_event(ArbiterVoted {
+                            id: _vote_id,
+                            voter,
+                            vote_type: Some(_result),
+                        });
+                        self.emit_tally_checkpoint(_vote_id, &x, false);
+                        Ok(())
+                    }
+                    AuditArbitrationResult::MinorDiscrepancies => {
+                        x.available_votes += 1;
+                        Self::mark_voted(&mut x, index);
+                        //add 7 days to the deadline extension.
+                        x.decided_deadline += self.time_extension_for_minor_discrepancies;
+                        x.decided_haircut += self.haircut_for_minor_discreapancies;
+                        self.vote_id_to_info.insert(_vote_id, &x);
+                        self.env().emit_event(ArbiterVoted {
+                            id: _vote_id,
+                            voter,
+                            vote_type: Some(_result),
+                        });
+                        self.emit_tally_checkpoint(_vote_id, &x, false);
+                        Ok(())
+                    }
+                    AuditArbitrationResult::ModerateDiscrepancies => {
+                        x.available_votes += 1;
+                        Self::mark_voted(&mut x, index);
+                        //add 15 days to the deadline extension.
+                        x.decided_deadline += self.time_extension_for_moderate_discrepancies;
+                        x.decided_haircut += self.haircut_for_moderate_discrepancies;
+                        self.vote_id_to_info.insert(_vote_id, &x);
+                        self.env().emit_event(ArbiterVoted {
+                            id: _vote_id,
+                            voter,
+                            vote_type: Some(_result),
+                        });
+                        self.emit_tally_checkpoint(_vote_id, &x, false);
+                        Ok(())
+                    }
+                    AuditArbitrationResult::Reject => {
+                        let scorecard = self.poll_scorecards.get(_vote_id);
+                        let result_call = self
+                            .escrow_at(escrow)
+                            .assess_audit(x.audit_id, false, scorecard);
+                        let checked = Self::check_escrow_call(result_call);
+                        if checked.is_ok() {
+                            self.record_escrow_call(
+                                _vote_id,
+                                EscrowCall::AssessAudit(false, scorecard),
+                            );
+                            x.available_votes += 1;
+                            Self::mark_voted(&mut x, index);
+                            self.set_poll_status(_vote_id, &mut x, PollStatus::Finalized);
+                            self.vote_id_to_info.insert(_vote_id, &x);
+                            self.env().emit_event(ArbiterVoted {
+                                id: _vote_id,
+                                voter,
+                                vote_type: Some(_result),
+                            });
+                            self.env().emit_event(FinalVotePushed {
+                                id: _vote_id,
+                                pusher: self.env().caller(),
+                            });
+                            self.on_poll_finalized(_vote_id, &x);
+                            Ok(())
+                        } else {
+                            checked
+                        }
+                    }
+                    AuditArbitrationResult::Abstain => {
+                        x.available_votes += 1;
+                        x.abstentions += 1;
+                        Self::mark_voted(&mut x, index);
+                        self.vote_id_to_info.insert(_vote_id, &x);
+                        self.env().emit_event(ArbiterVoted {
+                            id: _vote_id,
+                            voter,
+                            vote_type: Some(_result),
+                        });
+                        self.emit_tally_checkpoint(_vote_id, &x, false);
+                        Ok(())
+                    }
+                }
+            }
+        }
+
+        //marks the seat at index of the panel as voted, blinded ballots only mark their seat once revealed
+        fn mark_voted(x: &mut VoteInfo, index: Option<usize>) {
+            if let Some(index) = index {
+                x.arbiters[index].has_voted = true;
+            }
+        }
+
+        //closes a poll whose final vote has a result with a registered escrow action. The final result adds its
+        //extension and haircut and the outcome is averaged over the counted votes like the built-in calls do, then
+        //the action is called with its arguments filled in from the poll.
+        fn finalize_with_action(
+            &mut self,
+            vote_id: u32,
+            index: Option<usize>,
+            voter: AccountId,
+            mut x: VoteInfo,
+            result: AuditArbitrationResult,
+            action: &EscrowAction,
+        ) -> Result<()> {
+            let is_abstention = matches!(result, AuditArbitrationResult::Abstain);
+            let counted = Self::counted_votes(&x) + u8::from(!is_abstention);
+            if counted == 0 {
+                return Err(Error::OnlyAbstentions);
+            }
+            match result {
+                AuditArbitrationResult::MinorDiscrepancies => {
+                    x.decided_deadline += self.time_extension_for_minor_discrepancies;
+                    x.decided_haircut += self.haircut_for_minor_discreapancies;
+                }
+                AuditArbitrationResult::ModerateDiscrepancies => {
+                    x.decided_deadline += self.time_extension_for_moderate_discrepancies;
+                    x.decided_haircut += self.haircut_for_moderate_discrepancies;
+                }
+                _ => {}
+            }
+            x.decided_deadline /= counted as Timestamp;
+            x.decided_haircut /= counted as Balance;
+            let escrow = self.escrow_of(vote_id);
+            self.clamp_to_caps(escrow, &mut x)?;
+            let mut args = Vec::new();
+            for arg in &action.args {
+                match arg {
+                    ActionArg::AuditId => scale::Encode::encode_to(&x.audit_id, &mut args),
+                    ActionArg::NewDeadline => scale::Encode::encode_to(
+                        &(x.decided_deadline + self.env().block_timestamp()),
+                        &mut args,
+                    ),
+                    ActionArg::Haircut => scale::Encode::encode_to(&x.decided_haircut, &mut args),
+                    ActionArg::ArbitersShare => {
+                        scale::Encode::encode_to(&self.arbiters_share, &mut args)
+                    }
+                    ActionArg::Constant(bytes) => args.extend_from_slice(bytes),
+                }
+            }
+            let call = EscrowCall::Custom(action.selector, args);
+            self.push_escrow_call(escrow, x.audit_id, &call)?;
+            self.record_escrow_call(vote_id, call);
+            x.available_votes += 1;
+            if is_abstention {
+                x.abstentions += 1;
+            }
+            Self::mark_voted(&mut x, index);
+            self.set_poll_status(vote_id, &mut x, PollStatus::Finalized);
+            self.vote_id_to_info.insert(vote_id, &x);
+            self.env().emit_event(ArbiterVoted {
+                id: vote_id,
+                voter,
+                vote_type: Some(result),
+            });
+            self.env().emit_event(FinalVotePushed {
+                id: vote_id,
+                pusher: self.env().caller(),
+            });
+            self.on_poll_finalized(vote_id, &x);
+            Ok(())
+        }
+
+        //caps the averaged outcome by the caps of the poll, then fits it into the bounds the escrow enforces on
+        //arbiters_extend_deadline, so the final vote doesn't fail on the escrow's side. The haircut is lowered
+        //and an extension raised to the least the escrow accepts, only an arbiters' share above the escrow's
+        //largest can't be fitted. An escrow that can't be queried is left to enforce its bounds itself.
+        fn clamp_to_caps(&self, escrow: AccountId, x: &mut VoteInfo) -> Result<()> {
+            x.decided_haircut = x.decided_haircut.min(x.max_haircut);
+            x.decided_deadline = x.decided_deadline.min(x.max_extension);
+            let Some((max_haircut, max_arbiters_share, min_extension)) =
+                self.extension_bounds_of(escrow)
+            else {
+                return Ok(());
+            };
+            if self.arbiters_share > max_arbiters_share {
+                return Err(Error::ArbitersShareAboveEscrowMax);
+            }
+            x.decided_haircut = x
+                .decided_haircut
+                .min(max_haircut)
+                .min(100u128.saturating_sub(self.arbiters_share));
+            if x.decided_deadline > 0 {
+                x.decided_deadline = x.decided_deadline.max(min_extension);
+            }
+            Ok(())
+        }
+
+        //opens a poll for the audit of the escrow, see create_new_poll
+        fn open_poll(&mut self, escrow: AccountId, _audit_id: u32, poll: NewPoll) -> Result<()> {
+            let NewPoll {
+                buffer_for_admin: _buffer_for_admin,
+                arbiters: _arbiters,
+                max_haircut: _max_haircut,
+                max_extension: _max_extension,
+                alternates: _alternates,
+            } = poll;
+            if _max_haircut > 90 {
+                return Err(Error::ValueTooHigh);
+            }
+            if _max_extension < 86400000 {
+                return Err(Error::ValueTooLow);
+            }
+            if _arbiters.len() < self.min_panel_size as usize {
+                return Err(Error::PanelTooSmall);
+            }
+            if _arbiters.len() > self.max_panel_size as usize
+                || _arbiters.len() > MAX_PANEL_SIZE as usize
+            {
+                return Err(Error::PanelTooLarge);
+            }
+            if self.odd_panel_required && _arbiters.len().is_multiple_of(2) {
+                return Err(Error::PanelSizeNotOdd);
+            }
+            let escrowed_value = self.escrowed_value_of(escrow, _audit_id)?;
+            let dispute_class = self.get_dispute_class(escrowed_value);
+            let class_config = self.get_dispute_class_config(dispute_class);
+            if _arbiters.len() < class_config.min_panel_size as usize {
+                return Err(Error::PanelTooSmall);
+            }
+            if _arbiters.len() > class_config.max_panel_size as usize {
+                return Err(Error::PanelTooLarge);
+            }
+            //every outcome after the first was an appeal
+            if self.get_audit_outcome_count(escrow, _audit_id) > class_config.max_appeals {
+                return Err(Error::AppealsExhausted);
+            }
+            if _alternates.len() > self.max_panel_size as usize
+                || _alternates.len() > MAX_PANEL_SIZE as usize
+            {
+                return Err(Error::PanelTooLarge);
+            }
+            if _arbiters.iter().enumerate().any(|(i, arbiter)| {
+                _arbiters[..i]
+                    .iter()
+                    .any(|other| other.voter_address == arbiter.voter_address)
+            }) {
+                return Err(Error::AlreadyOnPanel);
+            }
+            if _alternates.iter().any(|alternate| {
+                _arbiters
+                    .iter()
+                    .any(|arbiter| arbiter.voter_address == *alternate)
+            }) {
+                return Err(Error::AlternateAlreadyOnPanel);
+            }
+            for (i, arbiter) in _arbiters.iter().enumerate() {
+                self.ensure_no_shared_organization(
+                    arbiter.voter_address,
+                    _arbiters[..i].iter().map(|other| other.voter_address),
+                )?;
+            }
+            self.ensure_no_conflict_of_interest(
+                escrow,
+                _audit_id,
+                _arbiters
+                    .iter()
+                    .map(|arbiter| arbiter.voter_address)
+                    .chain(_alternates.iter().copied()),
+            )?;
+            let reputation_snapshot = self.snapshot_reputations(
+                _arbiters
+                    .iter()
+                    .map(|arbiter| arbiter.voter_address)
+                    .chain(_alternates.iter().copied()),
+            )?;
+            for (index, arbiter) in _arbiters.iter().enumerate() {
+                self.add_pending_poll(arbiter.voter_address, self.current_vote_id);
+                self.invite_to_seat(self.current_vote_id, arbiter.voter_address);
+                self.arbiter_seats.insert(
+                    (self.current_vote_id, arbiter.voter_address),
+                    &ArbiterSeat {
+                        index: index as u32,
+                        fee_claimed: false,
+                    },
+                );
+            }
+            let response_window = self.arbiter_response_window;
+            if response_window > 0 {
+                let deadline = self.env().block_timestamp() + response_window;
+                for arbiter in &_arbiters {
+                    self.response_deadlines
+                        .insert((self.current_vote_id, arbiter.voter_address), &deadline);
+                }
+            }
+            if let Some(appealed) = self.last_audit_outcomes.get((escrow, _audit_id)) {
+                self.mark_appealed(appealed);
+            }
+            let x = VoteInfo {
+                audit_id: _audit_id,
+                arbiters: _arbiters,
+                status: PollStatus::Open,
+                available_votes: 0,
+                decided_deadline: 0,
+                decided_haircut: 0,
+                admin_hit_time: _buffer_for_admin,
+                max_haircut: _max_haircut,
+                max_extension: _max_extension,
+                response_window,
+                alternates: _alternates,
+                dispute_class,
+                class_config,
+                reputation_snapshot,
+                abstentions: 0,
+            };
+            self.vote_id_to_info.insert(self.current_vote_id, &x);
+            self.poll_arbitration_fee.insert(
+                self.current_vote_id,
+                &self.quote_arbitration_fee(escrowed_value, dispute_class),
+            );
+            let record = PollRecord {
+                escrow_address: Some(escrow),
+                created_at: self.env().block_timestamp(),
+                ..Default::default()
+            };
+            self.vote_id_to_record.insert(self.current_vote_id, &record);
+            self.env().emit_event(PollCreated {
+                id: self.current_vote_id,
+                vote_info: x,
+            });
+            self.notify_observer(
+                ink::selector_bytes!("on_poll_created"),
+                self.current_vote_id,
+                _audit_id,
+            );
+            self.current_vote_id += 1;
+            Ok(())
+        }
+
+        //moves the poll to the status and emits PollStatusChanged, the caller stores the poll
+        fn set_poll_status(&self, vote_id: u32, x: &mut VoteInfo, status: PollStatus) {
+            let from = x.status;
+            x.status = status;
+            self.env().emit_event(PollStatusChanged {
+                id: vote_id,
+                from,
+                to: status,
+            });
+        }
+
+        //moves a finalized poll whose outcome a new poll appeals to Appealed, a quarantined one goes back to
+        //Appealed once its quarantine is lifted
+        fn mark_appealed(&mut self, vote_id: u32) {
+            if let Some(mut quarantine) = self.quarantined_polls.get(vote_id) {
+                if quarantine.status_before == PollStatus::Finalized {
+                    quarantine.status_before = PollStatus::Appealed;
+                    self.quarantined_polls.insert(vote_id, &quarantine);
+                }
+                return;
+            }
+            if let Some(mut x) = self.vote_id_to_info.get(vote_id) {
+                if x.status == PollStatus::Finalized {
+                    self.set_poll_status(vote_id, &mut x, PollStatus::Appealed);
+                    self.vote_id_to_info.insert(vote_id, &x);
+                }
+            }
+        }
+
+        //whether the poll is open, or will be again once its quarantine is lifted
+        fn resumes_open(&self, vote_id: u32, x: &VoteInfo) -> bool {
+            match x.status {
+                PollStatus::Quarantined => self
+                    .quarantined_polls
+                    .get(vote_id)
+                    .is_some_and(|quarantine| quarantine.status_before.is_open()),
+                status => status.is_open(),
+            }
+        }
+
+        //fails if the contract is paused or the poll is frozen or quarantined
+        fn ensure_poll_open(&self, vote_id: u32) -> Result<()> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+            if self.is_poll_frozen(vote_id) {
+                return Err(Error::PollIsFrozen);
+            }
+            if self.quarantined_polls.contains(vote_id) {
+                return Err(Error::PollQuarantined);
+            }
+            Ok(())
+        }
+
+        //the gas limit of calls to callee, the default for the callee unless the admin configured one
+        fn gas_limit_for(&self, callee: AccountId) -> u64 {
+            self.callee_gas_limits
+                .get(callee)
+                .unwrap_or(self.default_gas_limit_for(callee))
+        }
+
+        //TOKEN_GAS_LIMIT for the stablecoin, DEFAULT_GAS_LIMIT for every other contract, including other tokens
+        //flushed out by the admin, which can only get more gas than they need
+        fn default_gas_limit_for(&self, callee: AccountId) -> u64 {
+            if callee == self.stablecoin_address {
+                TOKEN_GAS_LIMIT
+            } else {
+                DEFAULT_GAS_LIMIT
+            }
+        }
+
+        //the escrow a poll was opened for, polls from before it was recorded use the current one
+        fn escrow_of(&self, vote_id: u32) -> AccountId {
+            self.get_poll_record(vote_id)
+                .escrow_address
+                .unwrap_or(self.escrow_address)
+        }
+
+        //appends the caller's ballot to the record of the poll
+        fn record_ballot(
+            &mut self,
+            vote_id: u32,
+            voter: AccountId,
+            result: AuditArbitrationResult,
+        ) {
+            let mut record = self.get_poll_record(vote_id);
+            record.ballots.push(Ballot {
+                voter,
+                result,
+                cast_at: self.env().block_timestamp(),
+            });
+            self.vote_id_to_record.insert(vote_id, &record);
+            self.remove_pending_poll(voter, vote_id);
+        }
+
+        //the arbiter whose seat the caller votes from, the caller itself unless it is an active delegate
+        fn voter_of(&self, vote_id: u32, caller: AccountId) -> AccountId {
+            if self.arbiter_seats.contains((vote_id, caller)) {
+                return caller;
+            }
+            match self.delegators.get((vote_id, caller)) {
+                Some(arbiter) if self.is_delegation_active(vote_id, arbiter) => arbiter,
+                _ => caller,
+            }
+        }
+
+        //a blinded poll whose ballot tokens weren't revealed yet
+        fn is_blinded_unrevealed(&self, vote_id: u32) -> bool {
+            self.ballot_tokens.contains(vote_id)
+                && !self.revealed_polls.contains(vote_id)
+                && !self.is_reveal_window_passed(vote_id)
+        }
+
+        //whether REVEAL_WINDOW has passed since the poll was closed
+        fn is_reveal_window_passed(&self, vote_id: u32) -> bool {
+            self.get_poll_record(vote_id)
+                .closed_at
+                .is_some_and(|closed_at| {
+                    self.env().block_timestamp() >= closed_at.saturating_add(REVEAL_WINDOW)
+                })
+        }
+
+        //a delegation is active until the arbiter's seat is used or lost, the poll is closed or the response
+        //deadline of the seat has passed
+        fn is_delegation_active(&self, vote_id: u32, arbiter: AccountId) -> bool {
+            let Some(delegation) = self.delegations.get((vote_id, arbiter)) else {
+                return false;
+            };
+            let Some(x) = self.vote_id_to_info.get(vote_id) else {
+                return false;
+            };
+            let seat_unused = self
+                .arbiter_seats
+                .get((vote_id, arbiter))
+                .and_then(|seat| x.arbiters.get(seat.index as usize))
+                .is_some_and(|seated| seated.voter_address == arbiter && !seated.has_voted);
+            self.resumes_open(vote_id, &x)
+                && seat_unused
+                && delegation
+                    .expires_at
+                    .is_none_or(|expires_at| self.env().block_timestamp() < expires_at)
+        }
+
+        //forgets the delegation of the arbiter on the poll
+        fn remove_delegation(&mut self, vote_id: u32, arbiter: AccountId) -> Option<Delegation> {
+            let delegation = self.delegations.take((vote_id, arbiter))?;
+            self.delegators.remove((vote_id, delegation.delegate));
+            let mut delegated = self.poll_delegations.get(vote_id).unwrap_or_default();
+            delegated.retain(|account| *account != arbiter);
+            if delegated.is_empty() {
+                self.poll_delegations.remove(vote_id);
+            } else {
+                self.poll_delegations.insert(vote_id, &delegated);
+            }
+            Some(delegation)
+        }
+
+        //marks the poll as closed now by the given call on the escrow
+        fn record_escrow_call(&mut self, vote_id: u32, call: EscrowCall) {
+            let mut record = self.get_poll_record(vote_id);
+            record.closed_at = Some(self.env().block_timestamp());
+            record.escrow_call = Some(call);
+            self.vote_id_to_record.insert(vote_id, &record);
+            if let Some(vote_info) = self.vote_id_to_info.get(vote_id) {
+                for arbiter in &vote_info.arbiters {
+                    self.remove_pending_poll(arbiter.voter_address, vote_id);
+                }
+            }
+        }
+
+        fn invite_to_seat(&mut self, vote_id: u32, arbiter: AccountId) {
+            let accept_by = self.env().block_timestamp() + self.seat_acceptance_window;
+            self.panel_seats
+                .insert((vote_id, arbiter), &SeatStatus::Invited { accept_by });
+        }
+
+        fn add_pending_poll(&mut self, arbiter: AccountId, vote_id: u32) {
+            let mut pending = self
+                .arbiter_to_pending_polls
+                .get(arbiter)
+                .unwrap_or_default();
+            pending.push(vote_id);
+            self.arbiter_to_pending_polls.insert(arbiter, &pending);
+        }
+
+        fn remove_pending_poll(&mut self, arbiter: AccountId, vote_id: u32) {
+            let mut pending = self
+                .arbiter_to_pending_polls
+                .get(arbiter)
+                .unwrap_or_default();
+            pending.retain(|id| *id != vote_id);
+            if pending.is_empty() {
+                self.arbiter_to_pending_polls.remove(arbiter);
+            } else {
+                self.arbiter_to_pending_polls.insert(arbiter, &pending);
+            }
+        }
+
+        fn hash_of<T: scale::Encode>(value: &T) -> [u8; 32] {
+            let mut hash = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(value, &mut hash);
+            hash
+        }
+
+        fn digest_of(export: &PollExport) -> [u8; 32] {
+            let mut digest = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(
+                &scale::Encode::encode(export),
+                &mut digest,
+            );
+            digest
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+    use crate::testing::{all_results, arbiter, panel_of, PollBuilder};
+
+    #[test]
+    fn test_1_successful_creation_of_odd_panel() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(x.is_ok());
+        assert_eq!(contract.get_poll_status(0), Some(voting::PollStatus::Open));
+    }
+
+    #[test]
+    fn test_2_failure_on_panel_too_small() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob]);
+        let x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(matches!(x, Err(voting::Error::PanelTooSmall)));
+    }
+
+    #[test]
+    fn test_3_failure_on_panel_too_large_or_even() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let _x = contract.change_panel_size_limits(3, 3, true);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve, accounts.frank]);
+        let y = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(matches!(y, Err(voting::Error::PanelTooLarge)));
+        let _x = contract.change_panel_size_limits(2, 4, true);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve, accounts.frank]);
+        let z = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(matches!(z, Err(voting::Error::PanelSizeNotOdd)));
+        assert_eq!(contract.get_current_vote_id(), 0);
+    }
+
+    #[test]
+    fn test_4_failure_on_invalid_panel_limits() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(matches!(
+            contract.change_panel_size_limits(0, 5, false),
+            Err(voting::Error::ValueTooLow)
+        ));
+        assert!(matches!(
+            contract.change_panel_size_limits(6, 5, false),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.change_panel_size_limits(1, 5, false),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+    }
+
+    #[test]
+    fn test_5_vote_nonce_blocks_resubmission() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert_eq!(contract.get_vote_attempts(0, accounts.bob), 0);
+        let y = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(y.is_ok());
+        assert_eq!(contract.get_vote_attempts(0, accounts.bob), 1);
+        let z = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(matches!(z, Err(voting::Error::StaleVoteNonce)));
+        assert_eq!(contract.get_poll_info(0).unwrap().available_votes, 1);
+        assert_eq!(contract.get_vote_attempts(0, accounts.charlie), 0);
+    }
+
+    #[test]
+    fn test_6_exported_poll_verifies_until_changed() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(contract.export_poll(1).is_none());
+        let exported = contract.export_poll(0).unwrap();
+        assert!(contract.verify_exported_poll(exported.clone()));
+        let mut tampered = exported.clone();
+        tampered[0] ^= 1;
+        assert!(!contract.verify_exported_poll(tampered));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        let _y = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(!contract.verify_exported_poll(exported));
+        let record = contract.get_poll_record(0);
+        assert_eq!(record.ballots.len(), 1);
+        assert_eq!(record.ballots[0].voter, accounts.bob);
+        assert!(record.closed_at.is_none());
+        assert!(contract.verify_exported_poll(contract.export_poll(0).unwrap()));
+        //a self-consistent export of a poll the contract doesn't hold
+        let exported = contract.export_poll(0).unwrap();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.frank);
+        let other = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(other.export_poll(0).is_none());
+        assert!(!other.verify_exported_poll(exported));
+    }
+
+    #[test]
+    fn test_7_escrow_change_is_timelocked() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(matches!(
+            contract.apply_escrow_address(),
+            Err(voting::Error::NoPendingChange)
+        ));
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(contract.set_escrow_address(accounts.frank).is_ok());
+        assert!(matches!(
+            contract.apply_escrow_address(),
+            Err(voting::Error::TimelockNotElapsed)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            voting::ESCROW_CHANGE_DELAY,
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.apply_escrow_address(),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.apply_escrow_address().is_ok());
+        assert_eq!(contract.know_your_escrow(), accounts.frank);
+        assert!(contract.get_pending_escrow_address().is_none());
+        //the poll opened before the change still belongs to the old escrow
+        assert_eq!(
+            contract.get_poll_record(0).escrow_address,
+            Some(accounts.charlie)
+        );
+    }
+
+    #[test]
+    fn test_8_pause_blocks_voting() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let _y = contract.change_pauser(accounts.frank);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.pause().is_ok());
+        assert!(contract.is_paused());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let z = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(matches!(z, Err(voting::Error::ContractPaused)));
+        assert!(matches!(
+            contract.unpause(),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let w = contract.force_vote(0);
+        assert!(matches!(w, Err(voting::Error::ContractPaused)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.unpause().is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let v = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(v.is_ok());
+    }
+
+    #[test]
+    fn test_9_frozen_poll_blocks_only_that_poll() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _y = contract.create_new_poll(2, 0, arbiters, 90, 1296000000, Vec::new());
+        assert!(contract.set_poll_frozen(0, true).is_ok());
+        assert!(contract.is_poll_frozen(0));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(1).is_ok());
+        let z = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(matches!(z, Err(voting::Error::PollIsFrozen)));
+        let w = contract.vote(1, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(w.is_ok());
+        assert!(matches!(
+            contract.set_poll_frozen(0, false),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+    }
+
+    #[test]
+    fn test_10_poll_caps_are_stored_and_validated() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let x = contract.create_new_poll(1, 0, arbiters, 91, 1296000000, Vec::new());
+        assert!(matches!(x, Err(voting::Error::ValueTooHigh)));
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let y = contract.create_new_poll(1, 0, arbiters, 10, 1000, Vec::new());
+        assert!(matches!(y, Err(voting::Error::ValueTooLow)));
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        assert!(contract
+            .create_new_poll(1, 0, arbiters, 10, 604800000, Vec::new())
+            .is_ok());
+        let poll = contract.get_poll_info(0).unwrap();
+        assert_eq!(poll.max_haircut, 10);
+        assert_eq!(poll.max_extension, 604800000);
+    }
+
+    #[test]
+    fn test_11_gas_limits_configured_by_admin_only() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let config = contract.get_call_config(accounts.charlie);
+        assert!(config.is_default);
+        assert_eq!(config.gas_limit, voting::DEFAULT_GAS_LIMIT);
+        //the stablecoin gets the limit of a token
+        assert_eq!(
+            contract.get_call_config(accounts.django).gas_limit,
+            voting::TOKEN_GAS_LIMIT
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_gas_limit(accounts.charlie, Some(1_000_000)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_gas_limit(accounts.charlie, Some(1_000_000))
+            .is_ok());
+        assert_eq!(
+            contract.get_call_config(accounts.charlie).gas_limit,
+            1_000_000
+        );
+        assert!(contract.set_gas_limit(accounts.charlie, None).is_ok());
+        assert!(contract.get_call_config(accounts.charlie).is_default);
+    }
+
+    #[test]
+    fn test_12_alternate_takes_lapsed_seat() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        let alternates = [accounts.eve, accounts.frank].to_vec();
+        assert!(contract.change_arbiter_response_window(100).is_ok());
+        let x = contract.create_new_poll(
+            1,
+            0,
+            panel_of(&[accounts.bob, accounts.charlie, accounts.django]),
+            90,
+            1296000000,
+            [accounts.bob].to_vec(),
+        );
+        assert!(matches!(x, Err(voting::Error::AlternateAlreadyOnPanel)));
+        assert!(contract
+            .create_new_poll(1, 0, arbiters, 90, 1296000000, alternates)
+            .is_ok());
+        assert_eq!(
+            contract.get_response_deadline(0, accounts.charlie),
+            Some(100)
+        );
+        //anyone can call it, but only once the arbiter's deadline has passed
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.activate_alternate(0, 1),
+            Err(voting::Error::ResponseDeadlineNotPassed)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(101);
+        assert!(contract.activate_alternate(0, 1).is_ok());
+        let poll = contract.get_poll_info(0).unwrap();
+        assert!(poll.arbiters[1].voter_address == accounts.eve);
+        assert!(poll.alternates == [accounts.frank].to_vec());
+        assert_eq!(contract.get_response_deadline(0, accounts.charlie), None);
+        assert_eq!(contract.get_response_deadline(0, accounts.eve), Some(201));
+        //the substitution cascades once the alternate lets the seat lapse too
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(202);
+        assert!(contract.activate_alternate(0, 1).is_ok());
+        assert!(contract.get_poll_info(0).unwrap().arbiters[1].voter_address == accounts.frank);
+        assert!(matches!(
+            contract.activate_alternate(0, 0),
+            Err(voting::Error::NoAlternateLeft)
+        ));
+    }
+
+    #[test]
+    fn test_13_dispute_class_sets_panel_requirements() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        crate::calls::mock::set_original_value(accounts.charlie, 7, 1000);
+        assert!(contract.change_dispute_class_thresholds(100, 1000).is_ok());
+        assert_eq!(contract.get_dispute_class(99), voting::DisputeClass::Small);
+        assert_eq!(
+            contract.get_dispute_class(100),
+            voting::DisputeClass::Standard
+        );
+        assert_eq!(
+            contract.get_dispute_class(1000),
+            voting::DisputeClass::HighValue
+        );
+        //high value disputes need at least 5 arbiters by default
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let x = contract.create_new_poll(7, 0, arbiters, 90, 1296000000, Vec::new());
+        assert!(matches!(x, Err(voting::Error::PanelTooSmall)));
+        let mut config = contract.get_dispute_class_config(voting::DisputeClass::HighValue);
+        config.min_panel_size = 3;
+        config.quorum = 16;
+        assert!(matches!(
+            contract.change_dispute_class_config(voting::DisputeClass::HighValue, config),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        config.quorum = 2;
+        assert!(contract
+            .change_dispute_class_config(voting::DisputeClass::HighValue, config)
+            .is_ok());
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        assert!(contract
+            .create_new_poll(7, 0, arbiters, 90, 1296000000, Vec::new())
+            .is_ok());
+        let poll = contract.get_poll_info(0).unwrap();
+        assert_eq!(poll.dispute_class, voting::DisputeClass::HighValue);
+        assert_eq!(poll.class_config, config);
+        //the admin can't force the vote before the quorum has voted
+        assert!(matches!(
+            contract.force_vote(0),
+            Err(voting::Error::QuorumNotReached)
+        ));
+    }
+
+    #[test]
+    fn test_14_pending_polls_follow_the_panel() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        let _y =
+            contract.create_new_poll(2, 0, arbiters, 90, 1296000000, [accounts.frank].to_vec());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.get_my_pending_polls(), [0, 1].to_vec());
+        assert!(contract.accept_panel_seat(0).is_ok());
+        let z = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(z.is_ok());
+        assert_eq!(contract.get_my_pending_polls(), [1].to_vec());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(contract.get_my_pending_polls(), [0].to_vec());
+        //a seat taken over by an alternate moves to the alternate's queue
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(259200001);
+        assert!(contract.activate_alternate(1, 2).is_ok());
+        assert_eq!(contract.get_my_pending_polls(), [0].to_vec());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert_eq!(contract.get_my_pending_polls(), [1].to_vec());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert!(contract.get_my_pending_polls().is_empty());
+    }
+
+    #[test]
+    fn test_15_arbiters_accept_seats_before_voting() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        assert_eq!(
+            contract.get_seat_status(0, accounts.bob),
+            Some(voting::SeatStatus::Invited {
+                accept_by: 86400000
+            })
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let y = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(matches!(y, Err(voting::Error::SeatNotAccepted)));
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert_eq!(
+            contract.get_seat_status(0, accounts.bob),
+            Some(voting::SeatStatus::Accepted)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.accept_panel_seat(0),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        //seats can only be reassigned once their invitation lapsed
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.invite_arbiter(0, accounts.frank),
+            Err(voting::Error::NoLapsedSeat)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400001);
+        assert!(matches!(
+            contract.invite_arbiter(0, accounts.eve),
+            Err(voting::Error::AlreadyOnPanel)
+        ));
+        assert!(contract.invite_arbiter(0, accounts.frank).is_ok());
+        let panel = contract.get_poll_info(0).unwrap().arbiters;
+        assert_eq!(panel[0].voter_address, accounts.bob);
+        assert_eq!(panel[1].voter_address, accounts.frank);
+        assert_eq!(contract.get_seat_status(0, accounts.charlie), None);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        let z = contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0);
+        assert!(z.is_ok());
+    }
+
+    #[test]
+    fn test_16_reputations_frozen_at_poll_creation() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        assert!(contract.get_reputation_snapshot(0).is_empty());
+        assert!(contract
+            .set_reputation_registry(Some(accounts.frank))
+            .is_ok());
+        crate::calls::mock::set_reputation(accounts.frank, accounts.bob, 2);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _y =
+            contract.create_new_poll(2, 0, arbiters, 90, 1296000000, [accounts.frank].to_vec());
+        let snapshot = contract.get_reputation_snapshot(1);
+        assert_eq!(snapshot.len(), 4);
+        assert_eq!(snapshot[0], (accounts.bob, 2));
+        assert_eq!(snapshot[1], (accounts.charlie, 0));
+        assert_eq!(snapshot[3].0, accounts.frank);
+        //a later change of reputation doesn't touch the snapshot either
+        crate::calls::mock::set_reputation(accounts.frank, accounts.bob, 9);
+        assert_eq!(contract.get_reputation_snapshot(1), snapshot);
+        //a registry change doesn't touch the snapshot of an open poll
+        assert!(contract.set_reputation_registry(None).is_ok());
+        assert_eq!(contract.get_reputation_snapshot(1), snapshot);
+        //no poll is opened without the reputations of its panel
+        assert!(contract
+            .set_reputation_registry(Some(accounts.frank))
+            .is_ok());
+        crate::calls::mock::set_unreachable(accounts.frank);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        assert!(matches!(
+            contract.create_new_poll(3, 0, arbiters, 90, 1296000000, Vec::new()),
+            Err(voting::Error::ReputationQueryFailed)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_reputation_registry(Some(accounts.eve)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+    }
+
+    #[test]
+    fn test_17_abstentions_alone_cannot_finalize() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        for arbiter in [accounts.bob, accounts.charlie] {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(arbiter);
+            assert!(contract.accept_panel_seat(0).is_ok());
+            assert!(contract
+                .vote(0, voting::AuditArbitrationResult::Abstain, 0)
+                .is_ok());
+        }
+        let poll = contract.get_poll_info(0).unwrap();
+        assert_eq!(poll.available_votes, 2);
+        assert_eq!(poll.abstentions, 2);
+        assert!(poll.status.is_open());
+        //the abstentions reach the quorum, but there is no outcome to push
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.force_vote(0),
+            Err(voting::Error::OnlyAbstentions)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::Abstain, 0),
+            Err(voting::Error::OnlyAbstentions)
+        ));
+        assert_eq!(contract.get_poll_status(0), Some(voting::PollStatus::Open));
+    }
+
+    #[test]
+    fn test_18_tally_checkpoint_after_every_vote() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+        let checkpoint = ink::env::test::recorded_events().last().unwrap();
+        assert!(checkpoint.data.ends_with(&scale::Encode::encode(&(
+            0u32,
+            1u8,
+            5u128,
+            604800000u64,
+            false
+        ))));
+        //the abstention is counted as a vote cast but doesn't change the running average
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::Abstain, 0)
+            .is_ok());
+        let checkpoint = ink::env::test::recorded_events().last().unwrap();
+        assert!(checkpoint.data.ends_with(&scale::Encode::encode(&(
+            0u32,
+            2u8,
+            5u128,
+            604800000u64,
+            false
+        ))));
+    }
+    #[test]
+    fn test_19_arbiter_seats_and_panel_cap() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        //the configured limits can't go over the hard cap
+        assert!(matches!(
+            contract.change_panel_size_limits(1, voting::MAX_PANEL_SIZE + 1, true),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        let duplicated = panel_of(&[accounts.bob, accounts.charlie, accounts.bob]);
+        assert!(matches!(
+            contract.create_new_poll(1, 0, duplicated, 90, 1296000000, Vec::new()),
+            Err(voting::Error::AlreadyOnPanel)
+        ));
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        assert_eq!(
+            contract.get_arbiter_seat(0, accounts.eve),
+            Some(voting::ArbiterSeat {
+                index: 2,
+                fee_claimed: false
+            })
+        );
+        assert!(contract.get_arbiter_seat(0, accounts.frank).is_none());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::NoDiscrepancies, 0),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+        assert!(contract.get_poll_info(0).unwrap().arbiters[2].has_voted);
+        //nothing to claim until the fee is released
+        assert!(contract.get_fee_share(0).is_none());
+        assert!(matches!(
+            contract.claim_arbiter_fee(0),
+            Err(voting::Error::NothingToClaim)
+        ));
+    }
+    #[test]
+    fn test_20_reward_token_set_by_admin() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(contract.get_reward_token().is_none());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_reward_token(Some(accounts.eve)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_reward_token(Some(accounts.frank)).is_ok());
+        assert_eq!(contract.get_reward_token(), Some(accounts.frank));
+        assert!(contract.set_reward_token(None).is_ok());
+        assert!(contract.get_reward_token().is_none());
+    }
+    #[test]
+    fn test_21_no_outcome_while_poll_is_open() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+        assert!(contract.get_outcome(0).is_none());
+        assert!(contract.get_outcome(1).is_none());
+    }
+    #[test]
+    fn test_22_force_vote_needs_announcement() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+        assert!(matches!(
+            contract.announce_force_vote(0),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.force_vote(0),
+            Err(voting::Error::ForceVoteNotAnnounced)
+        ));
+        assert!(contract.announce_force_vote(0).is_ok());
+        assert_eq!(
+            contract.get_force_vote_announcement(0),
+            Some(voting::FORCE_VOTE_DELAY)
+        );
+        assert!(matches!(
+            contract.announce_force_vote(0),
+            Err(voting::Error::ForceVoteAlreadyAnnounced)
+        ));
+        assert!(matches!(
+            contract.force_vote(0),
+            Err(voting::Error::TimelockNotElapsed)
+        ));
+        //the panel can still vote during the delay
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_23_quote_arbitration_fee() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert_eq!(
+            contract.quote_arbitration_fee(99, voting::DisputeClass::Small),
+            4
+        );
+        assert_eq!(
+            contract.quote_arbitration_fee(1000, voting::DisputeClass::HighValue),
+            20
+        );
+        //no overflow on the largest values
+        assert_eq!(
+            contract.quote_arbitration_fee(u128::MAX, voting::DisputeClass::Small),
+            u128::MAX / 20
+        );
+        let mut config = contract.get_dispute_class_config(voting::DisputeClass::Small);
+        config.fee_bps = voting::MAX_FEE_BPS + 1;
+        assert!(matches!(
+            contract.change_dispute_class_config(voting::DisputeClass::Small, config),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        //the fee is capped at the budget of the class
+        config.fee_bps = 1000;
+        config.fee_budget = 30;
+        assert!(contract
+            .change_dispute_class_config(voting::DisputeClass::Small, config)
+            .is_ok());
+        assert_eq!(
+            contract.quote_arbitration_fee(200, voting::DisputeClass::Small),
+            20
+        );
+        assert_eq!(
+            contract.quote_arbitration_fee(500, voting::DisputeClass::Small),
+            30
+        );
+        crate::calls::mock::set_original_value(accounts.charlie, 3, 200);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        assert!(contract
+            .create_new_poll(3, 0, arbiters, 90, 1296000000, Vec::new())
+            .is_ok());
+        assert_eq!(contract.get_arbitration_fee(0), Some(20));
+        assert_eq!(contract.get_arbitration_fee(1), None);
+    }
+
+    #[test]
+    fn test_24_panel_diversity() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(contract
+            .set_arbiter_organization(accounts.bob, Some(7))
+            .is_ok());
+        assert!(contract
+            .set_arbiter_organization(accounts.charlie, Some(7))
+            .is_ok());
+        assert_eq!(contract.get_arbiter_organization(accounts.bob), Some(7));
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let x = contract.create_new_poll(0, 0, arbiters, 90, 1296000000, Vec::new());
+        assert!(matches!(x, Err(voting::Error::SameOrganizationOnPanel)));
+        assert!(contract
+            .set_arbiter_organization(accounts.charlie, None)
+            .is_ok());
+        //eve audited for the patron of audit 5 recently
+        crate::calls::mock::set_recent_auditors(
+            accounts.charlie,
+            5,
+            [arbiter(9), accounts.eve].to_vec(),
+        );
+        let diversity = voting::PanelDiversity {
+            one_per_organization: true,
+            patron_history_window: voting::MAX_PATRON_HISTORY_WINDOW + 1,
+        };
+        assert!(matches!(
+            contract.change_panel_diversity(diversity),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        let diversity = voting::PanelDiversity {
+            one_per_organization: true,
+            patron_history_window: 3,
+        };
+        assert!(contract.change_panel_diversity(diversity).is_ok());
+        assert_eq!(contract.get_panel_diversity(), diversity);
+        //frank's audit for the patron is older than the window
+        crate::calls::mock::set_recent_auditors(
+            accounts.charlie,
+            6,
+            [arbiter(9), arbiter(9), arbiter(9), accounts.frank].to_vec(),
+        );
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        assert!(contract
+            .create_new_poll(6, 0, arbiters, 90, 1296000000, [accounts.frank].to_vec())
+            .is_ok());
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        let x = contract.create_new_poll(5, 0, arbiters, 90, 1296000000, [accounts.eve].to_vec());
+        assert!(matches!(x, Err(voting::Error::ConflictOfInterest)));
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        assert!(contract
+            .create_new_poll(5, 0, arbiters, 90, 1296000000, [accounts.frank].to_vec())
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_arbiter_organization(accounts.eve, Some(1)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+    }
+
+    #[test]
+    fn test_25_prune_poll() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        assert!(contract
+            .create_new_poll(100, 0, arbiters, 90, 1296000000, [accounts.eve].to_vec())
+            .is_ok());
+        assert!(matches!(
+            contract.prune_poll(0),
+            Err(voting::Error::PollNotPrunable)
+        ));
+        //finalization calls the escrow, so the finalized poll is seeded
+        let mut vote_info = contract.vote_id_to_info.get(0).unwrap();
+        vote_info.status = voting::PollStatus::Finalized;
+        vote_info.arbiters[0].has_voted = true;
+        contract.vote_id_to_info.insert(0, &vote_info);
+        let mut record = contract.get_poll_record(0);
+        record.closed_at = Some(1000);
+        record.ballots.push(voting::Ballot {
+            voter: accounts.bob,
+            result: voting::AuditArbitrationResult::MinorDiscrepancies,
+            cast_at: 500,
+        });
+        contract.vote_id_to_record.insert(0, &record);
+        let exported = contract.export_poll(0).unwrap();
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            1000 + voting::POLL_RETENTION_PERIOD - 1,
+        );
+        assert!(matches!(
+            contract.prune_poll(0),
+            Err(voting::Error::RetentionPeriodNotPassed)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            1000 + voting::POLL_RETENTION_PERIOD,
+        );
+        //bob voted and hasn't claimed a fee share yet
+        assert!(matches!(
+            contract.prune_poll(0),
+            Err(voting::Error::FeesNotSettled)
+        ));
+        contract.poll_fee_share.insert(0, &0);
+        let mut seat = contract.get_arbiter_seat(0, accounts.bob).unwrap();
+        seat.fee_claimed = true;
+        contract.arbiter_seats.insert((0, accounts.bob), &seat);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.prune_poll(0).is_ok());
+        assert!(contract.get_pruned_digest(0).is_some());
+        let vote_info = contract.vote_id_to_info.get(0).unwrap();
+        assert!(vote_info.arbiters.is_empty());
+        assert!(vote_info.alternates.is_empty());
+        assert_eq!(vote_info.audit_id, 100);
+        let record = contract.get_poll_record(0);
+        assert!(record.ballots.is_empty());
+        assert_eq!(record.closed_at, Some(1000));
+        assert!(contract.get_arbiter_seat(0, accounts.bob).is_none());
+        assert!(contract.verify_exported_poll(exported));
+        assert!(matches!(
+            contract.prune_poll(0),
+            Err(voting::Error::PollNotPrunable)
+        ));
+    }
+
+    #[test]
+    fn test_26_result_actions() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(contract
+            .get_result_action(voting::AuditArbitrationResult::Reject)
+            .is_none());
+        let action = voting::EscrowAction {
+            selector: ink::selector_bytes!("settle_partially"),
+            args: [
+                voting::ActionArg::AuditId,
+                voting::ActionArg::Haircut,
+                voting::ActionArg::Constant(scale::Encode::encode(&true)),
+            ]
+            .to_vec(),
+        };
+        assert!(contract
+            .set_result_action(voting::AuditArbitrationResult::Reject, Some(action.clone()))
+            .is_ok());
+        assert_eq!(
+            contract.get_result_action(voting::AuditArbitrationResult::Reject),
+            Some(action.clone())
+        );
+        let too_long = voting::EscrowAction {
+            selector: action.selector,
+            args: vec![voting::ActionArg::AuditId; voting::MAX_ACTION_ARGS as usize + 1],
+        };
+        assert!(matches!(
+            contract.set_result_action(voting::AuditArbitrationResult::Reject, Some(too_long)),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_result_action(voting::AuditArbitrationResult::Reject, None),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract
+            .set_result_action(voting::AuditArbitrationResult::Reject, None)
+            .is_ok());
+        assert!(contract
+            .get_result_action(voting::AuditArbitrationResult::Reject)
+            .is_none());
+    }
+
+    #[test]
+    fn test_27_per_vote_fee_with_floor() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert_eq!(contract.get_treasury(), accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.django]);
+        assert!(contract
+            .create_new_poll(100000, 0, arbiters, 90, 1296000000, Vec::new())
+            .is_ok());
+        //finalization calls the escrow, so the finalized poll is seeded
+        let mut vote_info = contract.vote_id_to_info.get(0).unwrap();
+        vote_info.status = voting::PollStatus::Finalized;
+        vote_info.available_votes = 3;
+        contract.vote_id_to_info.insert(0, &vote_info);
+        contract.poll_arbitration_fee.insert(0, &1000);
+        let config = voting::ArbiterFeeConfig {
+            per_vote_fee: 50,
+            min_payout: 120,
+        };
+        assert!(contract.change_arbiter_fee_config(config).is_ok());
+        assert_eq!(contract.get_arbiter_fee_config(), config);
+        //the floor of 120 per voter is more than 300 can pay
+        assert!(matches!(
+            contract.release_treasury_funds(0, 300),
+            Err(voting::Error::ValueTooLow)
+        ));
+        let config = voting::ArbiterFeeConfig {
+            per_vote_fee: 100,
+            min_payout: 50,
+        };
+        assert!(contract.change_arbiter_fee_config(config).is_ok());
+        assert!(contract.release_treasury_funds(0, 300).is_ok());
+        assert_eq!(contract.get_fee_share(0), Some(100));
+        assert_eq!(
+            contract.get_fee_breakdown(0),
+            Some(voting::FeeBreakdown {
+                released: 300,
+                per_arbiter: 100,
+                voters: 3,
+                dust: 0,
+                treasury: accounts.alice,
+            })
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.change_arbiter_fee_config(config),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(
+            contract.change_treasury(accounts.eve),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+    }
+
+    #[test]
+    fn test_28_quarantined_poll_is_frozen_until_lifted() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 0, arbiters, 90, 1296000000, Vec::new());
+        assert!(matches!(
+            contract.quarantine_poll(1, [7; 32]),
+            Err(voting::Error::PollNotFound)
+        ));
+        let _y = contract.change_pauser(accounts.frank);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.quarantine_poll(0, [7; 32]),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract.quarantine_poll(0, [7; 32]).is_ok());
+        assert!(matches!(
+            contract.quarantine_poll(0, [8; 32]),
+            Err(voting::Error::PollQuarantined)
+        ));
+        let quarantine = contract.get_quarantine(0).unwrap();
+        assert_eq!(quarantine.reason_hash, [7; 32]);
+        assert_eq!(quarantine.by, accounts.frank);
+        assert_eq!(contract.get_quarantined_polls(), [0].to_vec());
+        //unfreezing the poll doesn't lift the quarantine
+        assert!(contract.set_poll_frozen(0, false).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.accept_panel_seat(0),
+            Err(voting::Error::PollQuarantined)
+        ));
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0),
+            Err(voting::Error::PollQuarantined)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.lift_quarantine(0).is_ok());
+        assert!(matches!(
+            contract.lift_quarantine(0),
+            Err(voting::Error::PollNotQuarantined)
+        ));
+        assert!(contract.get_quarantine(0).is_none());
+        assert!(contract.get_quarantined_polls().is_empty());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_29_polls_for_allow_listed_escrows() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let new_poll = || voting::NewPoll {
+            buffer_for_admin: 0,
+            arbiters: panel_of(&[accounts.bob, accounts.charlie, accounts.eve]),
+            max_haircut: 90,
+            max_extension: 1296000000,
+            alternates: Vec::new(),
+        };
+        assert!(contract.is_escrow_allowed(accounts.charlie));
+        assert!(!contract.is_escrow_allowed(accounts.frank));
+        assert!(matches!(
+            contract.create_poll_for_escrow(accounts.frank, 1, new_poll()),
+            Err(voting::Error::EscrowNotAllowed)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.set_escrow_allowed(accounts.frank, true),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_escrow_allowed(accounts.frank, true).is_ok());
+        assert!(contract
+            .create_poll_for_escrow(accounts.frank, 1, new_poll())
+            .is_ok());
+        assert!(contract
+            .create_poll_for_escrow(accounts.charlie, 1, new_poll())
+            .is_ok());
+        assert_eq!(
+            contract.get_poll_record(0).escrow_address,
+            Some(accounts.frank)
+        );
+        assert_eq!(
+            contract.get_poll_record(1).escrow_address,
+            Some(accounts.charlie)
+        );
+        //removing the escrow stops new polls, the open one keeps its escrow
+        assert!(contract.set_escrow_allowed(accounts.frank, false).is_ok());
+        assert!(matches!(
+            contract.create_poll_for_escrow(accounts.frank, 2, new_poll()),
+            Err(voting::Error::EscrowNotAllowed)
+        ));
+        assert_eq!(
+            contract.get_poll_record(0).escrow_address,
+            Some(accounts.frank)
+        );
+        assert_eq!(contract.get_current_vote_id(), 2);
+    }
+
+    #[test]
+    fn test_30_observer_set_by_admin() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(contract.get_observer().is_none());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.set_observer(Some(accounts.eve)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_observer(Some(accounts.frank)).is_ok());
+        assert_eq!(contract.get_observer(), Some(accounts.frank));
+        //the notification is best-effort, opening a poll doesn't depend on it
+        assert!(contract
+            .create_new_poll(
+                1,
+                0,
+                panel_of(&[accounts.bob, accounts.charlie, accounts.eve]),
+                90,
+                1296000000,
+                Vec::new()
+            )
+            .is_ok());
+        assert_eq!(contract.get_current_vote_id(), 1);
+        assert!(contract.set_observer(None).is_ok());
+        assert!(contract.get_observer().is_none());
+    }
+
+    #[test]
+    fn test_31_vote_delegation_revocation_and_expiry() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.delegate_vote(0, accounts.frank),
+            Err(voting::Error::SeatNotAccepted)
+        ));
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(matches!(
+            contract.delegate_vote(0, accounts.charlie),
+            Err(voting::Error::InvalidDelegate)
+        ));
+        assert!(matches!(
+            contract.revoke_delegation(0),
+            Err(voting::Error::NoDelegation)
+        ));
+        assert!(contract.delegate_vote(0, accounts.frank).is_ok());
+        let deadline = contract.get_response_deadline(0, accounts.bob);
+        assert!(deadline.is_some());
+        assert_eq!(
+            contract.get_active_delegations(0),
+            [voting::Delegation {
+                arbiter: accounts.bob,
+                delegate: accounts.frank,
+                since: 0,
+                expires_at: deadline
+            }]
+            .to_vec()
+        );
+        assert!(contract.revoke_delegation(0).is_ok());
+        assert!(contract.get_active_delegations(0).is_empty());
+        //a revoked delegate can't vote
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.delegate_vote(0, accounts.frank).is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(contract
+            .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+            .is_ok());
+        assert_eq!(contract.get_poll_record(0).ballots[0].voter, accounts.bob);
+        assert_eq!(contract.get_vote_attempts(0, accounts.bob), 1);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.revoke_delegation(0),
+            Err(voting::Error::DelegationAlreadyUsed)
+        ));
+        //the delegation of charlie expires at the response deadline of their seat
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract.delegate_vote(0, accounts.django).is_ok());
+        assert_eq!(contract.get_active_delegations(0).len(), 1);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(deadline.unwrap());
+        assert!(contract.get_active_delegations(0).is_empty());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        assert!(matches!(contract.prune_expired_delegations(0), Ok(2)));
+        assert!(contract.get_delegation(0, accounts.bob).is_none());
+        assert!(contract.get_delegation(0, accounts.charlie).is_none());
+    }
+
+    #[test]
+    fn test_32_blinded_ballots_are_revealed_after_finalization() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        let panel = [accounts.bob, accounts.charlie, accounts.eve];
+        let reveals: Vec<voting::BallotReveal> = panel
+            .iter()
+            .enumerate()
+            .map(|(i, arbiter)| voting::BallotReveal {
+                arbiter: *arbiter,
+                secret: [i as u8 + 1; 32],
+                salt: [i as u8 + 7; 32],
+            })
+            .collect();
+        //the tokens are issued in an order unrelated to the panel
+        let tokens: Vec<voting::BallotToken> = reveals
+            .iter()
+            .rev()
+            .map(|reveal| voting::BallotToken {
+                token_hash: contract.ballot_token_of(reveal.secret),
+                link: contract.ballot_link_of(reveal.arbiter, reveal.secret, reveal.salt),
+            })
+            .collect();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.issue_ballot_tokens(0, tokens.clone()),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.issue_ballot_tokens(0, tokens[..2].to_vec()),
+            Err(voting::Error::InvalidBallotToken)
+        ));
+        assert!(contract.issue_ballot_tokens(0, tokens.clone()).is_ok());
+        assert_eq!(contract.get_ballot_tokens(0), Some(tokens.clone()));
+        assert!(matches!(
+            contract.issue_ballot_tokens(0, tokens),
+            Err(voting::Error::VotingFailed)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0),
+            Err(voting::Error::BlindedPoll)
+        ));
+        assert!(matches!(
+            contract.vote_blinded(
+                0,
+                [9; 32],
+                voting::AuditArbitrationResult::MinorDiscrepancies
+            ),
+            Err(voting::Error::InvalidBallotToken)
+        ));
+        //a relayer casts the ballots of bob and eve, the ballots only name the pseudonyms
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        for secret in [[1; 32], [3; 32]] {
+            let key = contract.ballot_commitment_key_of(0, secret);
+            let commitment = contract
+                .ballot_commitment_of(secret, voting::AuditArbitrationResult::MinorDiscrepancies);
+            assert!(contract.commit_blinded_vote(0, key, commitment).is_ok());
+        }
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        assert!(contract
+            .vote_blinded(
+                0,
+                [1; 32],
+                voting::AuditArbitrationResult::MinorDiscrepancies
+            )
+            .is_ok());
+        assert!(matches!(
+            contract.vote_blinded(
+                0,
+                [1; 32],
+                voting::AuditArbitrationResult::MinorDiscrepancies
+            ),
+            Err(voting::Error::VotingFailed)
+        ));
+        assert!(contract
+            .vote_blinded(
+                0,
+                [3; 32],
+                voting::AuditArbitrationResult::MinorDiscrepancies
+            )
+            .is_ok());
+        let bob_pseudonym = ink::primitives::AccountId::from(contract.ballot_token_of([1; 32]));
+        let eve_pseudonym = ink::primitives::AccountId::from(contract.ballot_token_of([3; 32]));
+        let record = contract.get_poll_record(0);
+        assert_eq!(record.ballots[0].voter, bob_pseudonym);
+        assert_eq!(record.ballots[1].voter, eve_pseudonym);
+        assert!(contract
+            .get_poll_info(0)
+            .unwrap()
+            .arbiters
+            .iter()
+            .all(|arbiter| !arbiter.has_voted));
+        assert!(matches!(
+            contract.reveal_ballots(0, reveals.clone()),
+            Err(voting::Error::PollNotClosed)
+        ));
+        //finalization calls the escrow, so the finalized poll is seeded
+        let mut vote_info = contract.vote_id_to_info.get(0).unwrap();
+        vote_info.status = voting::PollStatus::Finalized;
+        contract.vote_id_to_info.insert(0, &vote_info);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.release_treasury_funds(0, 0),
+            Err(voting::Error::BallotsNotRevealed)
+        ));
+        let mut wrong_salt = reveals.clone();
+        wrong_salt[2].salt = [0; 32];
+        assert!(matches!(
+            contract.reveal_ballots(0, wrong_salt),
+            Err(voting::Error::InvalidBallotToken)
+        ));
+        //the tokens can be revealed a few at a time, the fee waits for all of them
+        assert!(contract.reveal_ballots(0, reveals[..1].to_vec()).is_ok());
+        assert!(matches!(
+            contract.release_treasury_funds(0, 0),
+            Err(voting::Error::BallotsNotRevealed)
+        ));
+        assert!(matches!(
+            contract.reveal_ballots(0, reveals.clone()),
+            Err(voting::Error::BallotAlreadyRevealed)
+        ));
+        assert!(contract.reveal_ballots(0, reveals[1..].to_vec()).is_ok());
+        assert_eq!(
+            contract.get_poll_record(0).revealed_voters,
+            [(bob_pseudonym, accounts.bob), (eve_pseudonym, accounts.eve)].to_vec()
+        );
+        let voted: Vec<bool> = contract
+            .get_poll_info(0)
+            .unwrap()
+            .arbiters
+            .iter()
+            .map(|arbiter| arbiter.has_voted)
+            .collect();
+        assert_eq!(voted, [true, false, true].to_vec());
+        assert!(matches!(
+            contract.reveal_ballots(0, reveals),
+            Err(voting::Error::BallotAlreadyRevealed)
+        ));
+    }
+
+    #[test]
+    fn test_33_outcome_fits_escrow_extension_bounds() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert_eq!(
+            contract.get_escrow_extension_bounds(0),
+            Some((90, 10, 86400000))
+        );
+        //a share the escrow would refuse fails the final vote before the escrow is called
+        assert!(contract.change_arbiters_share(11).is_ok());
+        for arbiter in [accounts.bob, accounts.charlie, accounts.eve] {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(arbiter);
+            assert!(contract.accept_panel_seat(0).is_ok());
+        }
+        for arbiter in [accounts.bob, accounts.charlie] {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(arbiter);
+            assert!(contract
+                .vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+                .is_ok());
+        }
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            contract.vote(0, voting::AuditArbitrationResult::MinorDiscrepancies, 0),
+            Err(voting::Error::ArbitersShareAboveEscrowMax)
+        ));
+        assert_eq!(contract.get_poll_status(0), Some(voting::PollStatus::Open));
+    }
+
+    #[test]
+    fn test_34_execution_receipt_of_finalized_poll() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        assert!(contract.get_execution_receipt(0).is_none());
+        //finalization calls the escrow, so the finalized poll is seeded
+        let mut record = contract.get_poll_record(0);
+        record.closed_at = Some(1000);
+        record.escrow_call = Some(voting::EscrowCall::ArbitersExtendDeadline(90000000, 10, 5));
+        contract.vote_id_to_record.insert(0, &record);
+        let receipt = contract.get_execution_receipt(0).unwrap();
+        let mut args_hash = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+            &(1u32, 90000000u64, 10u128, 5u128),
+            &mut args_hash,
+        );
+        assert_eq!(
+            receipt,
+            voting::ExecutionReceipt {
+                escrow: accounts.charlie,
+                audit_id: 1,
+                selector: ink::selector_bytes!("arbiters_extend_deadline"),
+                args_hash,
+                result: Ok(()),
+                executed_at: 1000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_35_panel_cites_the_reasons_of_the_ruling() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = panel_of(&[accounts.bob, accounts.charlie, accounts.eve]);
+        let _x = contract.create_new_poll(1, 100000000000, arbiters, 90, 1296000000, Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.cite_reasons(0, [voting::ReasonCode::LateDelivery].to_vec()),
+            Err(voting::Error::SeatNotAccepted)
+        ));
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .cite_reasons(
+                0,
+                [
+                    voting::ReasonCode::LateDelivery,
+                    voting::ReasonCode::ScopeMismatch
+                ]
+                .to_vec()
+            )
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(contract.accept_panel_seat(0).is_ok());
+        assert!(contract
+            .cite_reasons(
+                0,
+                [
+                    voting::ReasonCode::ScopeMismatch,
+                    voting::ReasonCode::QualityBelowBar
+                ]
+                .to_vec()
+            )
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.cite_reasons(0, [voting::ReasonCode::Other].to_vec()),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        assert_eq!(
+            contract.get_cited_reasons(0),
+            [
+                voting::ReasonCode::LateDelivery,
+                voting::ReasonCode::ScopeMismatch,
+                voting::ReasonCode::QualityBelowBar
+            ]
+            .to_vec()
+        );
+        assert_eq!(
+            contract.get_ruling_reason_count(voting::ReasonCode::LateDelivery),
+            0
+        );
+        //a finalized poll takes no more reasons
+        let mut x = contract.get_poll_info(0).unwrap();
+        x.status = voting::PollStatus::Finalized;
+        contract.vote_id_to_info.insert(0, &x);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(
+            contract.cite_reasons(0, [voting::ReasonCode::Other].to_vec()),
+            Err(voting::Error::ResultAlreadyPublished)
+        ));
+    }
+
+    #[test]
+    fn test_36_every_result_from_every_position() {
+        //the result under test is cast first, in the middle or last, the rest of the panel finds no discrepancies
+        for result in all_results() {
+            let mut extension = None;
+            for position in 0..3 {
+                let mut votes = [voting::AuditArbitrationResult::NoDiscrepancies; 3];
+                votes[position] = result;
+                let scenario = PollBuilder::new()
+                    .with_arbiters(3)
+                    .with_votes(votes)
+                    .build();
+                let rejected = matches!(result, voting::AuditArbitrationResult::Reject);
+                //a rejection closes the poll at once, the votes after it come too late
+                for (seat, cast) in scenario.results.iter().enumerate() {
+                    if rejected && seat > position {
+                        assert!(matches!(cast, Err(voting::Error::ResultAlreadyPublished)));
+                    } else {
+                        assert!(cast.is_ok());
+                    }
+                }
+                assert_eq!(scenario.status(), Some(voting::PollStatus::Finalized));
+                let ballots = scenario.contract.get_poll_record(scenario.vote_id).ballots;
+                assert_eq!(ballots.len(), if rejected { position + 1 } else { 3 });
+                let outcome = scenario.outcome().unwrap();
+                let kind = match result {
+                    voting::AuditArbitrationResult::MinorDiscrepancies
+                    | voting::AuditArbitrationResult::ModerateDiscrepancies => {
+                        voting::OutcomeKind::Extended
+                    }
+                    voting::AuditArbitrationResult::Reject => voting::OutcomeKind::Rejected,
+                    _ => voting::OutcomeKind::Approved,
+                };
+                assert_eq!(outcome.kind, kind);
+                assert_eq!(
+                    outcome.extension_ms > 0,
+                    kind == voting::OutcomeKind::Extended
+                );
+                //the averaged outcome doesn't depend on the order of the votes
+                assert_eq!(
+                    *extension.get_or_insert(outcome.extension_ms),
+                    outcome.extension_ms
+                );
+                let abstentions = scenario
+                    .contract
+                    .get_poll_info(scenario.vote_id)
+                    .unwrap()
+                    .abstentions;
+                assert_eq!(
+                    abstentions,
+                    u8::from(matches!(result, voting::AuditArbitrationResult::Abstain))
+                );
+                //the final vote made the escrow call of the outcome
+                let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+                let (selector, args) = crate::calls::mock::calls_to(accounts.charlie)
+                    .pop()
+                    .unwrap();
+                match kind {
+                    voting::OutcomeKind::Extended => {
+                        assert_eq!(selector, ink::selector_bytes!("arbiters_extend_deadline"));
+                    }
+                    _ => {
+                        assert_eq!(
+                            selector,
+                            ink::selector_bytes!("assess_audit_with_scorecard")
+                        );
+                        assert_eq!(
+                            args,
+                            scale::Encode::encode(&(
+                                1u32,
+                                kind == voting::OutcomeKind::Approved,
+                                None::<voting::Scorecard>
+                            ))
+                        );
+                    }
+                }
+            }
+        }
+        //an escrow that refuses the call fails the final vote and leaves the poll open
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        crate::calls::mock::set_rejection(
+            accounts.charlie,
+            ink::selector_bytes!("assess_audit_with_scorecard"),
+            7,
+        );
+        let scenario = PollBuilder::new()
+            .with_arbiters(3)
+            .with_votes([voting::AuditArbitrationResult::NoDiscrepancies; 3])
+            .build();
+        assert!(matches!(
+            scenario.results[2],
+            Err(voting::Error::EscrowCallFailed { code: 7 })
+        ));
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Open));
+    }
+
+    #[test]
+    fn test_37_version_and_interface_hash() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert_eq!(contract.version(), 1);
+        //every message of the contract is listed in the interface, and nothing else
+        extern "Rust" {
+            fn __ink_generate_metadata() -> ink::metadata::InkProject;
+        }
+        let metadata = unsafe { __ink_generate_metadata() };
+        let mut selectors: Vec<[u8; 4]> = metadata
+            .spec()
+            .messages()
+            .iter()
+            .map(|message| message.selector().to_bytes().try_into().unwrap())
+            .collect();
+        selectors.sort_unstable();
+        let mut interface = crate::interface::MESSAGES.to_vec();
+        interface.sort_unstable();
+        assert_eq!(interface, selectors);
+        let mut hash = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+            &<[[u8; 4]; crate::interface::MESSAGES.len()]>::try_from(selectors).unwrap(),
+            &mut hash,
+        );
+        assert_eq!(contract.interface_hash(), hash);
+        //an escrow of another version, or one that doesn't answer, can't be allow-listed or switched to
+        let other = ink::primitives::AccountId::from([0xff; 32]);
+        crate::calls::mock::set_escrow(
+            other,
+            crate::calls::mock::EscrowState {
+                version: voting::SUPPORTED_ESCROW_VERSION + 1,
+                ..Default::default()
+            },
+        );
+        crate::calls::mock::set_unreachable(arbiter(9));
+        assert!(matches!(
+            contract.set_escrow_allowed(arbiter(9), true),
+            Err(voting::Error::IncompatibleEscrow)
+        ));
+        assert!(matches!(
+            contract.set_escrow_allowed(other, true),
+            Err(voting::Error::IncompatibleEscrow)
+        ));
+        assert!(matches!(
+            contract.set_escrow_address(other),
+            Err(voting::Error::IncompatibleEscrow)
+        ));
+        assert!(contract.set_escrow_allowed(accounts.eve, true).is_ok());
+        assert!(contract.set_escrow_allowed(other, false).is_ok());
+    }
+
+    #[test]
+    fn test_38_stake_weighted_quorum_weighs_senior_arbiters() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        //without a reputation registry there is no stake to weigh
+        let _x =
+            contract.create_new_poll(1, 0, PollBuilder::new().panel(), 90, 1296000000, Vec::new());
+        let rule = voting::QuorumRule::StakeWeight {
+            numerator: 2,
+            denominator: 3,
+        };
+        assert!(matches!(
+            contract.set_quorum_rule(0, rule),
+            Err(voting::Error::InvalidQuorumRule)
+        ));
+        assert!(contract
+            .set_reputation_registry(Some(accounts.frank))
+            .is_ok());
+        //the senior arbiter's reputation is 200, the juniors' 64 to 67, 462 in all
+        let senior = ink::primitives::AccountId::from([200; 32]);
+        let panel = [senior, arbiter(0), arbiter(1), arbiter(2), arbiter(3)];
+        crate::calls::mock::set_reputation(accounts.frank, senior, 200);
+        for (i, seat) in panel[1..].iter().enumerate() {
+            crate::calls::mock::set_reputation(accounts.frank, *seat, 64 + i as u64);
+        }
+        assert!(contract
+            .create_new_poll(1, 0, panel_of(&panel), 90, 1296000000, Vec::new())
+            .is_ok());
+        assert_eq!(contract.get_quorum_rule(1), voting::QuorumRule::HeadCount);
+        assert!(matches!(
+            contract.set_quorum_rule(
+                1,
+                voting::QuorumRule::StakeWeight {
+                    numerator: 4,
+                    denominator: 3,
+                }
+            ),
+            Err(voting::Error::InvalidQuorumRule)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(senior);
+        assert!(matches!(
+            contract.set_quorum_rule(1, rule),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_quorum_rule(1, rule).is_ok());
+        assert_eq!(contract.get_quorum_rule(1), rule);
+        for seat in &panel {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(*seat);
+            assert!(contract.accept_panel_seat(1).is_ok());
+        }
+        //three of the five arbiters is enough heads for the class, but only 195 of the 462 stake
+        for seat in &panel[1..4] {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(*seat);
+            assert!(contract
+                .vote(1, voting::AuditArbitrationResult::MinorDiscrepancies, 0)
+                .is_ok());
+        }
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.set_quorum_rule(1, voting::QuorumRule::HeadCount),
+            Err(voting::Error::VotingFailed)
+        ));
+        assert!(contract.announce_force_vote(1).is_ok());
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            voting::FORCE_VOTE_DELAY,
+        );
+        assert!(matches!(
+            contract.force_vote(1),
+            Err(voting::Error::QuorumNotReached)
+        ));
+        //the senior vote brings it to 395, more than 2/3 of the stake
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(senior);
+        assert!(contract
+            .vote(1, voting::AuditArbitrationResult::NoDiscrepancies, 0)
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        //the panel's participation is recorded on the reward token, an observer that refuses the notification
+        //doesn't hold up the outcome
+        assert!(contract.set_reward_token(Some(accounts.eve)).is_ok());
+        assert!(contract.set_observer(Some(accounts.django)).is_ok());
+        crate::calls::mock::set_rejection(
+            accounts.django,
+            ink::selector_bytes!("on_poll_finalized"),
+            0,
+        );
+        assert!(contract.force_vote(1).is_ok());
+        assert!(contract.get_outcome(1).is_some());
+        assert!(crate::calls::mock::calls_to(accounts.django).is_empty());
+        let records = crate::calls::mock::calls_to(accounts.eve);
+        assert_eq!(records.len(), panel.len());
+        assert_eq!(
+            records[0],
+            (
+                ink::selector_bytes!("record_arbitration"),
+                scale::Encode::encode(&(senior, 1u32, 1u32, true))
+            )
+        );
+        assert_eq!(
+            records[4].1,
+            scale::Encode::encode(&(arbiter(3), 1u32, 1u32, false))
+        );
+    }
+
+    #[test]
+    fn test_39_poll_status_follows_the_poll() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut scenario = PollBuilder::new()
+            .with_votes([voting::AuditArbitrationResult::NoDiscrepancies])
+            .build();
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Open));
+        assert_eq!(scenario.contract.get_poll_status(7), None);
+        //a quarantined poll goes back to the status it had when the quarantine is lifted
+        assert!(scenario.contract.quarantine_poll(0, [1; 32]).is_ok());
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Quarantined));
+        assert_eq!(
+            scenario.contract.get_quarantine(0).unwrap().status_before,
+            voting::PollStatus::Open
+        );
+        assert!(matches!(
+            scenario.cast(1, voting::AuditArbitrationResult::NoDiscrepancies),
+            Err(voting::Error::PollQuarantined)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(scenario.contract.lift_quarantine(0).is_ok());
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Open));
+        for seat in 1..3 {
+            assert!(scenario
+                .cast(seat, voting::AuditArbitrationResult::NoDiscrepancies)
+                .is_ok());
+        }
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Finalized));
+        assert!(scenario
+            .contract
+            .get_poll_status(0)
+            .is_some_and(voting::PollStatus::is_closed));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(scenario.contract.quarantine_poll(0, [1; 32]).is_ok());
+        assert!(!scenario.status().unwrap().is_closed());
+        assert!(scenario.contract.lift_quarantine(0).is_ok());
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Finalized));
+    }
+
+    #[test]
+    fn test_40_blinded_votes_are_committed_and_reveals_time_out() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut scenario = PollBuilder::new().build();
+        let reveals: Vec<voting::BallotReveal> = scenario
+            .arbiters
+            .iter()
+            .enumerate()
+            .map(|(i, arbiter)| voting::BallotReveal {
+                arbiter: *arbiter,
+                secret: [i as u8 + 1; 32],
+                salt: [0; 32],
+            })
+            .collect();
+        let contract = &mut scenario.contract;
+        let tokens: Vec<voting::BallotToken> = reveals
+            .iter()
+            .map(|reveal| voting::BallotToken {
+                token_hash: contract.ballot_token_of(reveal.secret),
+                link: contract.ballot_link_of(reveal.arbiter, reveal.secret, reveal.salt),
+            })
+            .collect();
+        assert!(contract.issue_ballot_tokens(0, tokens).is_ok());
+        let key = contract.ballot_commitment_key_of(0, [1; 32]);
+        let commitment =
+            contract.ballot_commitment_of([1; 32], voting::AuditArbitrationResult::Reject);
+        assert!(contract.commit_blinded_vote(0, key, commitment).is_ok());
+        //the vote can't be cast in the block it was committed in
+        assert!(matches!(
+            contract.vote_blinded(0, [1; 32], voting::AuditArbitrationResult::Reject),
+            Err(voting::Error::BallotNotCommitted)
+        ));
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        //whoever sees the secret can neither cast another vote with it nor commit one for it
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert!(matches!(
+            contract.vote_blinded(0, [1; 32], voting::AuditArbitrationResult::NoDiscrepancies),
+            Err(voting::Error::BallotNotCommitted)
+        ));
+        assert!(matches!(
+            contract.commit_blinded_vote(
+                0,
+                key,
+                contract
+                    .ballot_commitment_of([1; 32], voting::AuditArbitrationResult::NoDiscrepancies)
+            ),
+            Err(voting::Error::BallotAlreadyCommitted)
+        ));
+        assert!(contract
+            .vote_blinded(0, [1; 32], voting::AuditArbitrationResult::Reject)
+            .is_ok());
+        //finalization calls the escrow, so the finalized poll is seeded
+        let mut vote_info = contract.vote_id_to_info.get(0).unwrap();
+        vote_info.status = voting::PollStatus::Finalized;
+        contract.vote_id_to_info.insert(0, &vote_info);
+        let mut record = contract.get_poll_record(0);
+        record.closed_at = Some(1000);
+        contract.vote_id_to_record.insert(0, &record);
+        //the voter keeps their token back, the reveal completes without it once the window has passed
+        assert!(contract.reveal_ballots(0, reveals[1..].to_vec()).is_ok());
+        assert!(matches!(
+            contract.reveal_ballots(0, Vec::new()),
+            Err(voting::Error::InvalidBallotToken)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            contract.release_treasury_funds(0, 0),
+            Err(voting::Error::BallotsNotRevealed)
+        ));
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            1000 + voting::REVEAL_WINDOW,
+        );
+        assert!(contract.reveal_ballots(0, Vec::new()).is_ok());
+        assert!(matches!(
+            contract.reveal_ballots(0, reveals[..1].to_vec()),
+            Err(voting::Error::BallotAlreadyRevealed)
+        ));
+        assert!(contract
+            .get_poll_info(0)
+            .unwrap()
+            .arbiters
+            .iter()
+            .all(|arbiter| !arbiter.has_voted));
+        assert!(contract.get_poll_record(0).revealed_voters.is_empty());
+    }
+
+    #[test]
+    fn test_41_appeals_capped_by_dispute_class() {
+        let mut scenario = PollBuilder::new()
+            .with_votes([voting::AuditArbitrationResult::NoDiscrepancies; 3])
+            .build();
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Finalized));
+        assert_eq!(
+            scenario
+                .contract
+                .get_audit_outcome_count(accounts.charlie, 1),
+            1
+        );
+        //a small dispute can be appealed once
+        let appeal = scenario.vote_id + 1;
+        assert!(scenario
+            .contract
+            .create_new_poll(1, 0, PollBuilder::new().panel(), 90, 1296000000, Vec::new())
+            .is_ok());
+        //the poll whose outcome is appealed stays closed
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Appealed));
+        assert!(scenario.status().unwrap().is_closed());
+        assert_eq!(
+            scenario
+                .contract
+                .get_last_audit_outcome(accounts.charlie, 1),
+            Some(scenario.vote_id)
+        );
+        for seat in &scenario.arbiters {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(*seat);
+            assert!(scenario.contract.accept_panel_seat(appeal).is_ok());
+        }
+        assert!(scenario
+            .contract
+            .vote(appeal, voting::AuditArbitrationResult::Reject, 0)
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(
+            scenario
+                .contract
+                .get_audit_outcome_count(accounts.charlie, 1),
+            2
+        );
+        assert_eq!(
+            scenario
+                .contract
+                .get_last_audit_outcome(accounts.charlie, 1),
+            Some(appeal)
+        );
+        assert!(matches!(
+            scenario.contract.create_new_poll(
+                1,
+                0,
+                PollBuilder::new().panel(),
+                90,
+                1296000000,
+                Vec::new()
+            ),
+            Err(voting::Error::AppealsExhausted)
+        ));
+        //other audits aren't affected, and the admin can allow more appeals
+        assert!(scenario
+            .contract
+            .create_new_poll(2, 0, PollBuilder::new().panel(), 90, 1296000000, Vec::new())
+            .is_ok());
+        let mut config = scenario
+            .contract
+            .get_dispute_class_config(voting::DisputeClass::Small);
+        config.max_appeals = 2;
+        assert!(scenario
+            .contract
+            .change_dispute_class_config(voting::DisputeClass::Small, config)
+            .is_ok());
+        //a quarantined poll whose outcome is appealed goes back to Appealed
+        assert!(scenario.contract.quarantine_poll(appeal, [1; 32]).is_ok());
+        assert!(scenario
+            .contract
+            .create_new_poll(1, 0, PollBuilder::new().panel(), 90, 1296000000, Vec::new())
+            .is_ok());
+        assert_eq!(
+            scenario.contract.get_poll_status(appeal),
+            Some(voting::PollStatus::Quarantined)
+        );
+        assert!(scenario.contract.lift_quarantine(appeal).is_ok());
+        assert_eq!(
+            scenario.contract.get_poll_status(appeal),
+            Some(voting::PollStatus::Appealed)
+        );
+    }
+
+    #[test]
+    fn test_42_registered_action_averages_the_final_vote() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut scenario = PollBuilder::new()
+            .with_votes([
+                voting::AuditArbitrationResult::MinorDiscrepancies,
+                voting::AuditArbitrationResult::NoDiscrepancies,
+            ])
+            .build();
+        let action = voting::EscrowAction {
+            selector: ink::selector_bytes!("settle_partially"),
+            args: [
+                voting::ActionArg::AuditId,
+                voting::ActionArg::NewDeadline,
+                voting::ActionArg::Haircut,
+                voting::ActionArg::ArbitersShare,
+            ]
+            .to_vec(),
+        };
+        assert!(scenario
+            .contract
+            .set_result_action(
+                voting::AuditArbitrationResult::MinorDiscrepancies,
+                Some(action)
+            )
+            .is_ok());
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        assert!(scenario
+            .cast(2, voting::AuditArbitrationResult::MinorDiscrepancies)
+            .is_ok());
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Finalized));
+        assert_eq!(
+            scenario.outcome().unwrap().kind,
+            voting::OutcomeKind::Custom
+        );
+        //two minor discrepancies of 7 days and 5% over three votes, the final one included
+        let poll = scenario.contract.get_poll_info(scenario.vote_id).unwrap();
+        assert_eq!(poll.decided_deadline, 2 * 604800000 / 3);
+        assert_eq!(poll.decided_haircut, 10 / 3);
+        let (selector, args) = crate::calls::mock::calls_to(accounts.charlie)
+            .pop()
+            .unwrap();
+        assert_eq!(selector, ink::selector_bytes!("settle_partially"));
+        assert_eq!(
+            args,
+            scale::Encode::encode(&(1u32, 2 * 604800000 / 3 + 1000u64, 3u128, 5u128))
+        );
+    }
+
+    #[test]
+    fn test_43_release_treasury_funds_of_unknown_or_open_poll() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut scenario = PollBuilder::new()
+            .with_votes([voting::AuditArbitrationResult::NoDiscrepancies])
+            .build();
+        //a poll that doesn't exist is an error instead of a trap
+        assert!(matches!(
+            scenario
+                .contract
+                .release_treasury_funds(scenario.vote_id + 1, 10),
+            Err(voting::Error::PollNotFound)
+        ));
+        assert!(matches!(
+            scenario
+                .contract
+                .release_treasury_funds(scenario.vote_id, 10),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert!(matches!(
+            scenario
+                .contract
+                .release_treasury_funds(scenario.vote_id, 10),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+    }
+    #[test]
+    fn test_44_reward_token_set_once_by_instantiator() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(contract.set_reward_token(Some(accounts.eve)).is_ok());
+        assert_eq!(contract.get_reward_token(), Some(accounts.eve));
+        //once it is set only the admin can change it
+        assert!(matches!(
+            contract.set_reward_token(Some(accounts.frank)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_reward_token(Some(accounts.frank)).is_ok());
+        assert_eq!(contract.get_reward_token(), Some(accounts.frank));
+    }
+
+    #[test]
+    fn test_45_poll_scorecard_sent_with_the_assessment() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut scenario = PollBuilder::new().with_arbiters(3).build();
+        let scorecard = voting::Scorecard {
+            criticals: 1,
+            highs: 2,
+            mediums: 3,
+            quality: 60,
+        };
+        //only the admin sets the scorecard of an open poll, with a quality of at most 100
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(scenario.arbiters[0]);
+        assert!(matches!(
+            scenario
+                .contract
+                .set_poll_scorecard(scenario.vote_id, Some(scorecard)),
+            Err(voting::Error::UnAuthorisedCall)
+        ));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            scenario.contract.set_poll_scorecard(
+                scenario.vote_id,
+                Some(voting::Scorecard {
+                    quality: 101,
+                    ..scorecard
+                })
+            ),
+            Err(voting::Error::ValueTooHigh)
+        ));
+        assert!(matches!(
+            scenario.contract.set_poll_scorecard(99, Some(scorecard)),
+            Err(voting::Error::PollNotFound)
+        ));
+        assert!(scenario
+            .contract
+            .set_poll_scorecard(scenario.vote_id, Some(scorecard))
+            .is_ok());
+        assert_eq!(
+            scenario.contract.get_poll_scorecard(scenario.vote_id),
+            Some(scorecard)
+        );
+        //the outcome assesses the audit with it, so the escrow mints the token of the audit with it
+        assert!(scenario
+            .cast(0, voting::AuditArbitrationResult::Reject)
+            .is_ok());
+        assert_eq!(scenario.status(), Some(voting::PollStatus::Finalized));
+        let (selector, args) = crate::calls::mock::calls_to(accounts.charlie)
+            .pop()
+            .unwrap();
+        assert_eq!(
+            selector,
+            ink::selector_bytes!("assess_audit_with_scorecard")
+        );
+        assert_eq!(args, scale::Encode::encode(&(1u32, false, Some(scorecard))));
+        //it can't change once the poll is closed
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(
+            scenario.contract.set_poll_scorecard(scenario.vote_id, None),
+            Err(voting::Error::ResultAlreadyPublished)
+        ));
+    }
+
+    #[test]
+    fn test_46_force_vote_sends_the_averaged_outcome() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let mut scenario = PollBuilder::new()
+            .with_arbiters(5)
+            .with_votes([
+                voting::AuditArbitrationResult::MinorDiscrepancies,
+                voting::AuditArbitrationResult::ModerateDiscrepancies,
+                voting::AuditArbitrationResult::MinorDiscrepancies,
+            ])
+            .build();
+        assert!(scenario
+            .contract
+            .announce_force_vote(scenario.vote_id)
+            .is_ok());
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+            voting::FORCE_VOTE_DELAY,
+        );
+        assert!(scenario.contract.force_vote(scenario.vote_id).is_ok());
+        //7, 15 and 7 days with haircuts of 5, 15 and 5 average to 29/3 days and 8, below the caps
+        let outcome = scenario.outcome().unwrap();
+        assert_eq!(outcome.extension_ms, 835200000);
+        assert_eq!(outcome.haircut_bps, 800);
+        //the escrow is called with the averaged outcome and the receipt matches the call
+        let args =
+            scale::Encode::encode(&(1u32, 835200000 + voting::FORCE_VOTE_DELAY, 8u128, 5u128));
+        assert_eq!(
+            crate::calls::mock::calls_to(accounts.charlie).pop(),
+            Some((
+                ink::selector_bytes!("arbiters_extend_deadline"),
+                args.clone()
+            ))
+        );
+        let mut args_hash = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&args, &mut args_hash);
+        let receipt = scenario
+            .contract
+            .get_execution_receipt(scenario.vote_id)
+            .unwrap();
+        assert_eq!(
+            receipt.selector,
+            ink::selector_bytes!("arbiters_extend_deadline")
+        );
+        assert_eq!(receipt.args_hash, args_hash);
     }
 }
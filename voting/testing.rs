@@ -0,0 +1,150 @@
+// fixtures for the tests of the voting contract. PollBuilder opens a poll on a fresh contract, seats its
+// panel and casts the given votes in order, and the Scenario it builds is the contract with what every
+// vote returned. The admin is alice and the escrow charlie of the default test accounts, the arbiters are
+// arbiter(0), arbiter(1), ... so that a panel isn't limited by the number of default accounts.
+
+use crate::voting::{self, Arbiter, AuditArbitrationResult, PollOutcome, PollStatus, Voting};
+use ink::primitives::AccountId;
+
+type Environment = ink::env::DefaultEnvironment;
+type Balance = u128;
+type Timestamp = u64;
+
+// the account in seat i of a panel built here, it shares no bytes with the default test accounts.
+pub fn arbiter(i: u8) -> AccountId {
+    AccountId::from([0x40u8.wrapping_add(i); 32])
+}
+
+// a panel of the given accounts, none of whom has voted yet.
+pub fn panel_of(addresses: &[AccountId]) -> Vec<Arbiter> {
+    addresses
+        .iter()
+        .map(|address| Arbiter {
+            voter_address: *address,
+            has_voted: false,
+        })
+        .collect()
+}
+
+// every result an arbiter can vote, in the order of the enum.
+pub fn all_results() -> [AuditArbitrationResult; 5] {
+    [
+        AuditArbitrationResult::NoDiscrepancies,
+        AuditArbitrationResult::MinorDiscrepancies,
+        AuditArbitrationResult::ModerateDiscrepancies,
+        AuditArbitrationResult::Reject,
+        AuditArbitrationResult::Abstain,
+    ]
+}
+
+// the poll a scenario starts from, a panel of 3 on audit 1 with the caps the tests use unless changed.
+pub struct PollBuilder {
+    audit_id: u32,
+    arbiters: u8,
+    votes: Vec<AuditArbitrationResult>,
+    max_haircut: Balance,
+    max_extension: Timestamp,
+}
+
+impl Default for PollBuilder {
+    fn default() -> Self {
+        Self {
+            audit_id: 1,
+            arbiters: 3,
+            votes: Vec::new(),
+            max_haircut: 90,
+            max_extension: 1296000000,
+        }
+    }
+}
+
+impl PollBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the size of the panel, seated as arbiter(0) to arbiter(count - 1).
+    pub fn with_arbiters(mut self, count: u8) -> Self {
+        self.arbiters = count;
+        self
+    }
+
+    // the votes cast once the panel is seated, the first by arbiter(0), the next by arbiter(1) and so on.
+    pub fn with_votes(mut self, votes: impl IntoIterator<Item = AuditArbitrationResult>) -> Self {
+        self.votes = votes.into_iter().collect();
+        self
+    }
+
+    // the panel the poll is created with, for tests that open the poll themselves.
+    pub fn panel(&self) -> Vec<Arbiter> {
+        let seats: Vec<AccountId> = (0..self.arbiters).map(arbiter).collect();
+        panel_of(&seats)
+    }
+
+    // deploys the contract, opens the poll as the admin, has every arbiter accept their seat and casts the
+    // votes. The caller is the admin again afterwards.
+    pub fn build(self) -> Scenario {
+        let accounts = ink::env::test::default_accounts::<Environment>();
+        ink::env::test::set_caller::<Environment>(accounts.alice);
+        ink::env::test::set_callee::<Environment>(accounts.bob);
+        let mut contract = Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        //the off-chain environment keeps the mappings of the callee between deployments, an earlier scenario's
+        //outcome for the same audit would count as an appeal
+        contract
+            .audit_outcome_counts
+            .remove((accounts.charlie, self.audit_id));
+        let vote_id = contract.get_current_vote_id();
+        let opened = contract.create_new_poll(
+            self.audit_id,
+            0,
+            self.panel(),
+            self.max_haircut,
+            self.max_extension,
+            Vec::new(),
+        );
+        assert!(opened.is_ok(), "the poll of the scenario can't be opened");
+        let arbiters: Vec<AccountId> = (0..self.arbiters).map(arbiter).collect();
+        for seat in &arbiters {
+            ink::env::test::set_caller::<Environment>(*seat);
+            assert!(contract.accept_panel_seat(vote_id).is_ok());
+        }
+        let mut scenario = Scenario {
+            contract,
+            vote_id,
+            arbiters,
+            results: Vec::new(),
+        };
+        for (seat, result) in self.votes.into_iter().enumerate() {
+            let cast = scenario.cast(seat, result);
+            scenario.results.push(cast);
+        }
+        ink::env::test::set_caller::<Environment>(accounts.alice);
+        scenario
+    }
+}
+
+// a poll built by PollBuilder, results holds what each of its votes returned, in order.
+pub struct Scenario {
+    pub contract: Voting,
+    pub vote_id: u32,
+    pub arbiters: Vec<AccountId>,
+    pub results: Vec<voting::Result<()>>,
+}
+
+impl Scenario {
+    // casts the vote of the arbiter in the seat, with the nonce the contract expects of them.
+    pub fn cast(&mut self, seat: usize, result: AuditArbitrationResult) -> voting::Result<()> {
+        let arbiter = self.arbiters[seat];
+        ink::env::test::set_caller::<Environment>(arbiter);
+        let nonce = self.contract.get_vote_attempts(self.vote_id, arbiter);
+        self.contract.vote(self.vote_id, result, nonce)
+    }
+
+    pub fn outcome(&self) -> Option<PollOutcome> {
+        self.contract.get_outcome(self.vote_id)
+    }
+
+    pub fn status(&self) -> Option<PollStatus> {
+        self.contract.get_poll_status(self.vote_id)
+    }
+}